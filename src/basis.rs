@@ -0,0 +1,236 @@
+use std::ops;
+
+use crate::field::Field;
+use crate::univariate::UPoly;
+
+// coefs c_0, c_1, ... of sum c_k T_k(x), lowest first as is usual for
+// orthogonal bases; T_0 = 1, T_1 = x, T_(k + 1) = 2x T_k - T_(k - 1)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Chebyshev<T: Field>(pub Vec<T>);
+
+// coefs c_0, c_1, ... of sum c_k P_k(x); P_0 = 1, P_1 = x,
+// (k + 1) P_(k + 1) = (2k + 1) x P_k - k P_(k - 1)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Legendre<T: Field>(pub Vec<T>);
+
+fn x<T: Field>() -> UPoly<T> {
+    UPoly(vec![T::one(), T::zero()])
+}
+
+pub fn chebyshev_polys<T: Field>(n: usize) -> Vec<UPoly<T>> {
+    let mut polys = vec![UPoly(vec![T::one()]), x()];
+
+    for k in 1..n {
+        let next = (x() * polys[k].clone()).scale(&T::from(2)) - polys[k - 1].clone();
+        polys.push(next);
+    }
+
+    polys.truncate(n + 1);
+    polys
+}
+
+pub fn legendre_polys<T: Field>(n: usize) -> Vec<UPoly<T>> {
+    let mut polys = vec![UPoly(vec![T::one()]), x()];
+
+    for k in 1..n {
+        let next = (x() * polys[k].clone()).scale(&T::from(2 * k as i64 + 1))
+            - polys[k - 1].scale(&T::from(k as i64));
+        polys.push(next.scale(&(T::one() / T::from(k as i64 + 1))));
+    }
+
+    polys.truncate(n + 1);
+    polys
+}
+
+// coefs of p in a basis with basis[k] of degree k, by cancelling leading
+// terms from the top
+fn to_basis<T: Field>(p: &UPoly<T>, basis: impl Fn(usize) -> Vec<UPoly<T>>) -> Vec<T> {
+    let mut p = p.clone().trim();
+
+    if p.is_zero() {
+        return vec![];
+    }
+
+    let basis = basis(p.deg());
+    let mut coefs = vec![T::zero(); basis.len()];
+
+    while !p.is_zero() {
+        let k = p.deg();
+        coefs[k] = p.lc() / basis[k].lc();
+        p = p - basis[k].scale(&coefs[k]);
+    }
+
+    coefs
+}
+
+fn from_basis<T: Field>(coefs: &[T], basis: impl Fn(usize) -> Vec<UPoly<T>>) -> UPoly<T> {
+    if coefs.is_empty() {
+        return UPoly(vec![]);
+    }
+
+    basis(coefs.len() - 1)
+        .iter()
+        .zip(coefs)
+        .fold(UPoly(vec![]), |acc, (b, c)| acc + b.scale(c))
+}
+
+impl<T: Field> Chebyshev<T> {
+    pub fn from_upoly(p: &UPoly<T>) -> Self {
+        Self(to_basis(p, chebyshev_polys))
+    }
+
+    pub fn to_upoly(&self) -> UPoly<T> {
+        from_basis(&self.0, chebyshev_polys)
+    }
+
+    // drop trailing zero coefs
+    pub fn trim(mut self) -> Self {
+        while self.0.last().is_some_and(|c| c.is_zero()) {
+            self.0.pop();
+        }
+        self
+    }
+
+    pub fn scale(&self, by: &T) -> Self {
+        Self(self.0.iter().map(|c| c.clone() * by.clone()).collect()).trim()
+    }
+
+    // Clenshaw's recurrence
+    pub fn eval(&self, x: &T) -> T {
+        let mut b1 = T::zero();
+        let mut b2 = T::zero();
+
+        for c in self.0.iter().skip(1).rev() {
+            let b = c.clone() + x.clone() * b1.clone() * T::from(2) - b2;
+            b2 = b1;
+            b1 = b;
+        }
+
+        match self.0.first() {
+            Some(c0) => c0.clone() + x.clone() * b1 - b2,
+            None => T::zero(),
+        }
+    }
+}
+
+impl<T: Field> Legendre<T> {
+    pub fn from_upoly(p: &UPoly<T>) -> Self {
+        Self(to_basis(p, legendre_polys))
+    }
+
+    pub fn to_upoly(&self) -> UPoly<T> {
+        from_basis(&self.0, legendre_polys)
+    }
+}
+
+impl<T: Field> ops::Add<Chebyshev<T>> for Chebyshev<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let (mut long, short) = if self.0.len() >= rhs.0.len() {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
+
+        for (i, c) in short.0.into_iter().enumerate() {
+            long.0[i] = long.0[i].clone() + c;
+        }
+
+        long.trim()
+    }
+}
+
+impl<T: Field> ops::Sub<Chebyshev<T>> for Chebyshev<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + rhs.scale(&T::from(-1))
+    }
+}
+
+// T_m T_n = (T_(m + n) + T_|m - n|) / 2
+impl<T: Field> ops::Mul<Chebyshev<T>> for Chebyshev<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        if self.0.is_empty() || rhs.0.is_empty() {
+            return Self(vec![]);
+        }
+
+        let mut new = vec![T::zero(); self.0.len() + rhs.0.len() - 1];
+        let half = T::one() / T::from(2);
+
+        for (m, a) in self.0.iter().enumerate() {
+            for (n, b) in rhs.0.iter().enumerate() {
+                let prod = a.clone() * b.clone() * half.clone();
+                new[m + n] = new[m + n].clone() + prod.clone();
+                new[m.abs_diff(n)] = new[m.abs_diff(n)].clone() + prod;
+            }
+        }
+
+        Self(new).trim()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{chebyshev_polys, legendre_polys, Chebyshev, Legendre};
+    use crate::rational::Rat;
+    use crate::univariate;
+    use crate::univariate::UPoly;
+
+    fn rats(coefs: &[i64]) -> Vec<Rat> {
+        coefs.iter().map(|c| Rat::from(*c)).collect()
+    }
+
+    #[test]
+    fn polys() {
+        let t = chebyshev_polys::<Rat>(4);
+        assert_eq!(t[4], UPoly(rats(&[8, 0, -8, 0, 1])));
+
+        // P_3 = (5x^3 - 3x) / 2
+        let p = legendre_polys::<Rat>(3);
+        assert_eq!(
+            p[3],
+            UPoly(rats(&[5, 0, -3, 0])).scale(&(Rat::from(1) / Rat::from(2)))
+        );
+
+        assert_eq!(1, chebyshev_polys::<Rat>(0).len());
+    }
+
+    #[test]
+    fn conversions() {
+        let p = univariate! { 4*x^3 + 2*x^2 - 1 };
+
+        // x^3 = (3T_1 + T_3) / 4, x^2 = (T_0 + T_2) / 2
+        let cheb = Chebyshev::from_upoly(&p);
+        assert_eq!(cheb, Chebyshev(rats(&[0, 3, 1, 1])));
+        assert_eq!(p, cheb.to_upoly());
+
+        let leg = Legendre::from_upoly(&p);
+        assert_eq!(p, leg.to_upoly());
+        assert_eq!(leg.0[3], Rat::from(8) / Rat::from(5));
+
+        assert_eq!(
+            Chebyshev::<Rat>(vec![]),
+            Chebyshev::from_upoly(&UPoly(vec![]))
+        );
+    }
+
+    #[test]
+    fn arithmetic() {
+        let p = univariate! { 4*x^3 + 2*x^2 - 1 };
+        let q = univariate! { x^2 - 3*x + 5 };
+        let (cp, cq) = (Chebyshev::from_upoly(&p), Chebyshev::from_upoly(&q));
+
+        assert_eq!((cp.clone() * cq.clone()).to_upoly(), p.clone() * q.clone());
+        assert_eq!((cp.clone() + cq.clone()).to_upoly(), p.clone() + q.clone());
+        assert_eq!((cp.clone() - cp.clone()), Chebyshev(vec![]));
+
+        for x in [-2, 0, 1, 3] {
+            let x = Rat::from(x);
+            assert_eq!(cp.eval(&x), p.eval(&x));
+        }
+    }
+}
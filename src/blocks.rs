@@ -0,0 +1,557 @@
+// structure of large sparse systems from which vars each member involves.
+// independent subsystems are solved separately and their solutions combined,
+// which keeps the boxes of one from being bisected along the vars of another.
+// a square system is also split into blocks which can be solved in order, each
+// once the vars of the blocks before it are known (block triangular form, from
+// a matching of members to vars and the strong components of what is left).
+// for elimination, a tree decomposition of the interaction graph bounds how
+// many vars can meet in one eliminant by its width
+
+use std::rc::Rc;
+use std::sync::atomic::AtomicBool;
+
+use crate::branch_and_prune::{Budget, RealSolutions};
+use crate::field::Field;
+use crate::interval::Interval;
+use crate::poly::system::System;
+use crate::poly::var_store::reindex;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub members: Vec<usize>,
+    pub vars: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeDecomposition {
+    // vars in the order they are eliminated
+    pub order: Vec<usize>,
+    // one per var of order: the var and its neighbors when it is eliminated
+    pub bags: Vec<Vec<usize>>,
+    // the bag of the first of those neighbors to be eliminated; None at roots
+    pub parents: Vec<Option<usize>>,
+}
+
+impl TreeDecomposition {
+    pub fn width(&self) -> usize {
+        self.bags.iter().map(|bag| bag.len()).max().unwrap_or(1) - 1
+    }
+}
+
+fn vars_of<T: Field>(p: &Poly<T>) -> Vec<usize> {
+    let mut vars = p
+        .terms
+        .iter()
+        .flat_map(|t| t.vars.iter().map(|(v, _)| *v))
+        .collect::<Vec<_>>();
+    vars.sort();
+    vars.dedup();
+    vars
+}
+
+// augmenting path from member i, for a maximum matching
+fn augment(
+    i: usize,
+    involves: &[Vec<usize>],
+    matched: &mut [Option<usize>],
+    seen: &mut [bool],
+) -> bool {
+    for v in &involves[i] {
+        if !seen[*v] {
+            seen[*v] = true;
+
+            if matched[*v].is_none_or(|j| augment(j, involves, matched, seen)) {
+                matched[*v] = Some(i);
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+// Tarjan's strong components of the member graph, each after the ones it
+// depends on
+struct Tarjan<'a> {
+    depends: &'a [Vec<usize>],
+    index: Vec<Option<usize>>,
+    low: Vec<usize>,
+    stack: Vec<usize>,
+    on_stack: Vec<bool>,
+    visited: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl Tarjan<'_> {
+    fn visit(&mut self, i: usize) {
+        let index = self.visited;
+        self.visited += 1;
+        self.index[i] = Some(index);
+        self.low[i] = index;
+        self.stack.push(i);
+        self.on_stack[i] = true;
+
+        for j in self.depends[i].clone() {
+            match self.index[j] {
+                None => {
+                    self.visit(j);
+                    self.low[i] = self.low[i].min(self.low[j]);
+                }
+                Some(index) if self.on_stack[j] => self.low[i] = self.low[i].min(index),
+                _ => (),
+            }
+        }
+
+        if self.low[i] == index {
+            let mut component = vec![];
+            while let Some(j) = self.stack.pop() {
+                self.on_stack[j] = false;
+                component.push(j);
+                if j == i {
+                    break;
+                }
+            }
+            component.sort();
+            self.components.push(component);
+        }
+    }
+}
+
+impl<T: Field> System<T> {
+    // for each var, the other vars sharing a member with it
+    pub fn interaction_graph(&self) -> Vec<Vec<usize>> {
+        let mut graph = vec![vec![]; self.var_dict.len()];
+
+        for p in &self.members {
+            let vars = vars_of(p);
+            for v in &vars {
+                graph[*v].extend(vars.iter().filter(|w| *w != v));
+            }
+        }
+
+        for adjacent in &mut graph {
+            adjacent.sort();
+            adjacent.dedup();
+        }
+
+        graph
+    }
+
+    // subsystems sharing no vars, ordered by their first var; members without
+    // vars are left out
+    pub fn components(&self) -> Vec<Block> {
+        let graph = self.interaction_graph();
+        let mut component = vec![None; graph.len()];
+        let mut blocks: Vec<Block> = vec![];
+
+        for start in 0..graph.len() {
+            if component[start].is_some() {
+                continue;
+            }
+
+            let mut vars = vec![];
+            let mut work = vec![start];
+            component[start] = Some(blocks.len());
+
+            while let Some(v) = work.pop() {
+                vars.push(v);
+                for w in &graph[v] {
+                    if component[*w].is_none() {
+                        component[*w] = Some(blocks.len());
+                        work.push(*w);
+                    }
+                }
+            }
+
+            vars.sort();
+            blocks.push(Block {
+                members: vec![],
+                vars,
+            });
+        }
+
+        for (i, p) in self.members.iter().enumerate() {
+            if let Some(v) = vars_of(p).first() {
+                blocks[component[*v].unwrap()].members.push(i);
+            }
+        }
+
+        blocks
+    }
+
+    // blocks in the order they can be solved, where the members of each
+    // involve only its vars and those of earlier blocks. None unless the
+    // system is square and every member can be matched to a distinct var it
+    // involves
+    pub fn block_triangular(&self) -> Option<Vec<Block>> {
+        let n = self.var_dict.len();
+
+        if self.members.len() != n {
+            return None;
+        }
+
+        let involves = self.members.iter().map(vars_of).collect::<Vec<_>>();
+        let mut matched = vec![None; n];
+
+        for i in 0..n {
+            if !augment(i, &involves, &mut matched, &mut vec![false; n]) {
+                return None;
+            }
+        }
+
+        // member i depends on the members matched to its vars
+        let depends = involves
+            .iter()
+            .enumerate()
+            .map(|(i, vars)| {
+                vars.iter()
+                    .map(|v| matched[*v].unwrap())
+                    .filter(|j| *j != i)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut tarjan = Tarjan {
+            depends: &depends,
+            index: vec![None; n],
+            low: vec![0; n],
+            stack: vec![],
+            on_stack: vec![false; n],
+            visited: 0,
+            components: vec![],
+        };
+
+        for i in 0..n {
+            if tarjan.index[i].is_none() {
+                tarjan.visit(i);
+            }
+        }
+
+        let var_of = |i: usize| matched.iter().position(|m| *m == Some(i)).unwrap();
+
+        Some(
+            tarjan
+                .components
+                .into_iter()
+                .map(|members| {
+                    let mut vars = members.iter().map(|i| var_of(*i)).collect::<Vec<_>>();
+                    vars.sort();
+                    Block { members, vars }
+                })
+                .collect(),
+        )
+    }
+
+    // by min fill: eliminate the var whose neighbors miss the fewest edges
+    // between them, then the one with fewest neighbors, then the first, and
+    // join its neighbors
+    pub fn tree_decomposition(&self) -> TreeDecomposition {
+        let mut graph = self.interaction_graph();
+        let mut eliminated = vec![false; graph.len()];
+        let mut order = vec![];
+        let mut bags = vec![];
+
+        let fill = |graph: &[Vec<usize>], v: usize| {
+            let adjacent = &graph[v];
+            adjacent
+                .iter()
+                .enumerate()
+                .flat_map(|(i, a)| adjacent[i + 1..].iter().map(move |b| (*a, *b)))
+                .filter(|(a, b)| !graph[*a].contains(b))
+                .count()
+        };
+
+        while order.len() < graph.len() {
+            let v = (0..graph.len())
+                .filter(|v| !eliminated[*v])
+                .min_by_key(|v| (fill(&graph, *v), graph[*v].len(), *v))
+                .unwrap();
+
+            let adjacent = std::mem::take(&mut graph[v]);
+            for a in &adjacent {
+                graph[*a].retain(|w| *w != v);
+                graph[*a].extend(adjacent.iter().filter(|b| *b != a));
+                graph[*a].sort();
+                graph[*a].dedup();
+            }
+
+            let mut bag = vec![v];
+            bag.extend(&adjacent);
+            bags.push(bag);
+            eliminated[v] = true;
+            order.push(v);
+        }
+
+        let position = |v: usize| order.iter().position(|w| *w == v).unwrap();
+        let parents = bags
+            .iter()
+            .map(|bag| bag[1..].iter().map(|v| position(*v)).min())
+            .collect();
+
+        TreeDecomposition {
+            order,
+            bags,
+            parents,
+        }
+    }
+
+    // the members of block, with var block.vars[i] renamed to var i
+    pub fn subsystem(&self, block: &Block) -> System<T> {
+        // vars outside the block do not occur, so their index is never read
+        let mut map = vec![0; self.var_dict.len()];
+        for (i, v) in block.vars.iter().enumerate() {
+            map[*v] = i;
+        }
+
+        System {
+            var_dict: Rc::new(
+                block
+                    .vars
+                    .iter()
+                    .map(|v| self.var_dict[*v].clone())
+                    .collect(),
+            ),
+            members: block
+                .members
+                .iter()
+                .map(|i| reindex(&self.members[*i], &map))
+                .collect(),
+        }
+    }
+
+    fn split_by_vars(&self, vars: &[usize]) -> (Vec<Poly<T>>, Vec<Poly<T>>) {
+        self.members
+            .iter()
+            .cloned()
+            .partition(|p| vars_of(p).iter().all(|v| vars.contains(v)))
+    }
+
+    // the members involving no var outside vars, over the same var dict; what
+    // a partial solution in vars can be checked against
+    pub fn restrict_to_vars(&self, vars: &[usize]) -> System<T> {
+        System {
+            var_dict: self.var_dict.clone(),
+            members: self.split_by_vars(vars).0,
+        }
+    }
+
+    // the other members, each involving some var outside vars
+    pub fn complement_of_vars(&self, vars: &[usize]) -> System<T> {
+        System {
+            var_dict: self.var_dict.clone(),
+            members: self.split_by_vars(vars).1,
+        }
+    }
+}
+
+impl System<Rat> {
+    // solve_real_in_box on each component, with the solution boxes of the
+    // components combined. a combined box is as good as the worst of its parts
+    pub fn solve_real_in_box_by_components(&self, bx: &[Interval], tol: f64) -> RealSolutions {
+        if self.members.iter().any(|p| p.is_constant() && !p.is_zero()) {
+            return RealSolutions {
+                excluded: 1,
+                ..RealSolutions::default()
+            };
+        }
+
+        // 0 for verified boxes, 1 for candidates and 2 for undecided ones
+        let mut boxes = vec![(bx.to_vec(), 0)];
+        let mut excluded = 0;
+
+        for block in self.components() {
+            let sub_bx = block.vars.iter().map(|v| bx[*v]).collect::<Vec<_>>();
+            let sub = self.subsystem(&block).solve_real_in_box_until(
+                &sub_bx,
+                tol,
+                Budget::default(),
+                &AtomicBool::new(false),
+            );
+            excluded += sub.excluded;
+
+            let parts = sub.verified.iter().map(|part| (part, 0));
+            let parts = parts.chain(sub.candidates.iter().map(|part| (part, 1)));
+            let parts = parts
+                .chain(sub.undecided.iter().map(|part| (part, 2)))
+                .collect::<Vec<_>>();
+
+            boxes = boxes
+                .iter()
+                .flat_map(|(bx, rank)| {
+                    parts.iter().map(|(part, part_rank)| {
+                        let mut bx = bx.clone();
+                        for (v, i) in block.vars.iter().zip(*part) {
+                            bx[*v] = *i;
+                        }
+                        (bx, *rank.max(part_rank))
+                    })
+                })
+                .collect();
+        }
+
+        let mut res = RealSolutions {
+            excluded,
+            ..RealSolutions::default()
+        };
+
+        for (bx, rank) in boxes {
+            match rank {
+                0 => res.verified.push(bx),
+                1 => res.candidates.push(bx),
+                _ => res.undecided.push(bx),
+            }
+        }
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Block;
+    use crate::interval::Interval;
+    use crate::system;
+
+    #[test]
+    fn blocks() {
+        let sys = system! {
+            x^2 - 1,
+            y^2 - 4,
+            y*z - 2
+        };
+
+        assert_eq!(
+            vec![Vec::<usize>::new(), vec![2], vec![1]],
+            sys.interaction_graph()
+        );
+
+        let components = sys.components();
+        assert_eq!(
+            vec![
+                Block {
+                    members: vec![0],
+                    vars: vec![0]
+                },
+                Block {
+                    members: vec![1, 2],
+                    vars: vec![1, 2]
+                }
+            ],
+            components
+        );
+        assert_eq!(
+            "[y^2 - 4, yz - 2]",
+            format!("{:?}", sys.subsystem(&components[1]))
+        );
+
+        let bx = vec![Interval::new(-10., 10.); 3];
+        let split = sys.solve_real_in_box_by_components(&bx, 1e-6);
+        let whole = sys.solve_real_in_box(&bx, 1e-6);
+        assert_eq!(4, split.verified.len());
+        assert_eq!(whole.verified.len(), split.verified.len());
+        assert!(split.candidates.is_empty() && split.undecided.is_empty());
+        assert!(split
+            .verified
+            .iter()
+            .all(|bx| (bx[1].mid() * bx[2].mid() - 2.).abs() < 1e-5));
+
+        // x, then y, then z
+        let sys = system! {
+            x*y + z - 1,
+            x - 1,
+            y + x^2 - 3
+        };
+
+        let blocks = sys.block_triangular().unwrap();
+        let order = blocks
+            .iter()
+            .map(|b| (b.members.clone(), b.vars.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![(vec![1], vec![0]), (vec![2], vec![1]), (vec![0], vec![2])],
+            order
+        );
+
+        // a coupled pair, then z
+        let sys = system! {
+            z - x,
+            x + y - 3,
+            x - y - 1
+        };
+
+        let blocks = sys.block_triangular().unwrap();
+        assert_eq!(2, blocks.len());
+        assert_eq!(
+            (vec![1, 2], vec![0, 1]),
+            (blocks[0].members.clone(), blocks[0].vars.clone())
+        );
+
+        // x - 1 and x + 2 cannot both be matched to x
+        assert!(system! { x - 1, x + 2, y*z - 1 }
+            .block_triangular()
+            .is_none());
+        assert!(system! { x - y }.block_triangular().is_none());
+        assert_eq!(
+            1,
+            system! { x - 1, 3 }
+                .solve_real_in_box_by_components(&bx[..1], 1e-6)
+                .excluded
+        );
+    }
+
+    #[test]
+    fn restrict_to_vars() {
+        let sys = system! {
+            x - 1,
+            y - x,
+            y*z - 2,
+            3
+        };
+
+        // constants involve no var, so every restriction keeps them
+        assert_eq!("[x - 1, 3]", format!("{:?}", sys.restrict_to_vars(&[0])));
+        assert_eq!(
+            "[x - 1, -x + y, 3]",
+            format!("{:?}", sys.restrict_to_vars(&[0, 1]))
+        );
+        assert_eq!("[yz - 2]", format!("{:?}", sys.complement_of_vars(&[0, 1])));
+        assert_eq!(3, sys.restrict_to_vars(&[0, 1]).var_dict.len());
+        assert_eq!(
+            "[x - 1, -x + y, yz - 2]",
+            format!("{:?}", sys.complement_of_vars(&[]))
+        );
+    }
+
+    #[test]
+    fn tree_decomposition() {
+        // a path a - b - c - d
+        let sys = system! {
+            a - b,
+            b*c - 1,
+            c + d^2
+        };
+
+        let td = sys.tree_decomposition();
+        assert_eq!(vec![0, 1, 2, 3], td.order);
+        assert_eq!(vec![vec![0, 1], vec![1, 2], vec![2, 3], vec![3]], td.bags);
+        assert_eq!(vec![Some(1), Some(2), Some(3), None], td.parents);
+        assert_eq!(1, td.width());
+
+        // a cycle a - b - c - d - a needs one fill edge
+        let sys = system! {
+            a*b - 1,
+            b + c,
+            c*d - 2,
+            d - a
+        };
+
+        let td = sys.tree_decomposition();
+        assert_eq!(
+            vec![vec![0, 1, 3], vec![1, 2, 3], vec![2, 3], vec![3]],
+            td.bags
+        );
+        assert_eq!(2, td.width());
+    }
+}
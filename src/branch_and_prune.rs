@@ -0,0 +1,370 @@
+// real solutions of a polynomial system inside a box, without Groebner bases:
+// contract boxes by HC4, throw away those some equation cannot vanish on,
+// prove a unique solution with the Krawczyk test where the system is square,
+// and bisect whatever is left
+
+use std::sync::atomic::{self, AtomicBool};
+
+use crate::cad::tarski::{Cmp, Constraint};
+use crate::interval::{eval, hc4, Interval};
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+// Krawczyk iterations once a box is known to hold a unique solution
+const MAX_REFINEMENTS: usize = 50;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Budget {
+    // boxes taken off the work list, including ones excluded right away
+    pub max_boxes: usize,
+    // bisections along any one branch
+    pub max_depth: usize,
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Budget {
+            max_boxes: 10_000,
+            max_depth: 64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RealSolutions {
+    // boxes no wider than tol, each proven to hold exactly one solution
+    pub verified: Vec<Vec<Interval>>,
+    // boxes no wider than tol which could neither be proven to hold a
+    // solution nor excluded, e.g. around singular solutions
+    pub candidates: Vec<Vec<Interval>>,
+    // boxes left over when the budget ran out
+    pub undecided: Vec<Vec<Interval>>,
+    // boxes some member was proven not to vanish on
+    pub excluded: usize,
+}
+
+enum Krawczyk {
+    Excluded,
+    Unique(Vec<Interval>),
+    Unknown(Vec<Interval>),
+}
+
+fn max_width(bx: &[Interval]) -> f64 {
+    bx.iter().map(|i| i.width()).fold(0., f64::max)
+}
+
+fn point(x: f64) -> Interval {
+    Interval::new(x, x)
+}
+
+// inverse of a dense f64 matrix by Gauss-Jordan elimination
+pub(crate) fn inverse(mut a: Vec<Vec<f64>>) -> Option<Vec<Vec<f64>>> {
+    let n = a.len();
+    let mut inv = (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| if i == j { 1. } else { 0. })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    for k in 0..n {
+        let pivot = (k..n).max_by(|i, j| a[*i][k].abs().total_cmp(&a[*j][k].abs()))?;
+        if a[pivot][k].abs() < 1e-300 || !a[pivot][k].is_finite() {
+            return None;
+        }
+
+        a.swap(k, pivot);
+        inv.swap(k, pivot);
+
+        let scale = a[k][k];
+        a[k].iter_mut().for_each(|x| *x /= scale);
+        inv[k].iter_mut().for_each(|x| *x /= scale);
+
+        for i in (0..n).filter(|i| *i != k) {
+            let factor = a[i][k];
+            for j in 0..n {
+                a[i][j] -= factor * a[k][j];
+                inv[i][j] -= factor * inv[k][j];
+            }
+        }
+    }
+
+    Some(inv)
+}
+
+// K(X) = y - Y f(y) + (I - Y J(X))(X - y) for y the midpoint of X and Y an
+// approximate inverse of J(y); every solution in X lies in K(X), and K(X)
+// inside the interior of X proves there is exactly one
+fn krawczyk(fs: &[Poly<Rat>], jac: &[Vec<Poly<Rat>>], bx: &[Interval]) -> Krawczyk {
+    let n = bx.len();
+
+    if bx.iter().any(|i| !i.lo.is_finite() || !i.hi.is_finite()) {
+        return Krawczyk::Unknown(bx.to_vec());
+    }
+
+    let y = bx.iter().map(|i| i.mid()).collect::<Vec<_>>();
+    let y_box = y.iter().map(|y| point(*y)).collect::<Vec<_>>();
+
+    let jac_y = jac
+        .iter()
+        .map(|row| row.iter().map(|d| eval(d, &y_box).mid()).collect())
+        .collect();
+
+    let inv = match inverse(jac_y) {
+        Some(inv) => inv,
+        None => return Krawczyk::Unknown(bx.to_vec()),
+    };
+
+    let f_y = fs.iter().map(|f| eval(f, &y_box)).collect::<Vec<_>>();
+    let jac_x = jac
+        .iter()
+        .map(|row| row.iter().map(|d| eval(d, bx)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let offsets = bx
+        .iter()
+        .zip(&y_box)
+        .map(|(x, y)| x.sub(y))
+        .collect::<Vec<_>>();
+
+    let mut contracted = vec![];
+    let mut interior = true;
+
+    for i in 0..n {
+        let mut k = y_box[i];
+
+        for j in 0..n {
+            k = k.sub(&point(inv[i][j]).mul(&f_y[j]));
+
+            // (I - Y J(X))_ij
+            let mut entry = point(if i == j { 1. } else { 0. });
+            for (l, row) in jac_x.iter().enumerate() {
+                entry = entry.sub(&point(inv[i][l]).mul(&row[j]));
+            }
+
+            k = k.add(&entry.mul(&offsets[j]));
+        }
+
+        interior &= bx[i].lo < k.lo && k.hi < bx[i].hi;
+
+        match k.intersect(&bx[i]) {
+            Some(k) => contracted.push(k),
+            None => return Krawczyk::Excluded,
+        }
+    }
+
+    if interior {
+        Krawczyk::Unique(contracted)
+    } else {
+        Krawczyk::Unknown(contracted)
+    }
+}
+
+// split the widest side in half
+pub(crate) fn bisect(bx: &[Interval]) -> (Vec<Interval>, Vec<Interval>) {
+    let (i, widest) = bx
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.width().total_cmp(&b.width()))
+        .unwrap();
+
+    let mid = widest.mid();
+    let (mut left, mut right) = (bx.to_vec(), bx.to_vec());
+    left[i].hi = mid;
+    right[i].lo = mid;
+
+    (left, right)
+}
+
+impl System<Rat> {
+    pub fn solve_real_in_box(&self, bx: &[Interval], tol: f64) -> RealSolutions {
+        self.solve_real_in_box_with_budget(bx, tol, Budget::default())
+    }
+
+    pub fn solve_real_in_box_with_budget(
+        &self,
+        bx: &[Interval],
+        tol: f64,
+        budget: Budget,
+    ) -> RealSolutions {
+        self.solve_real_in_box_until(bx, tol, budget, &AtomicBool::new(false))
+    }
+
+    // stops early once stop is set, leaving the remaining boxes undecided
+    pub fn solve_real_in_box_until(
+        &self,
+        bx: &[Interval],
+        tol: f64,
+        budget: Budget,
+        stop: &AtomicBool,
+    ) -> RealSolutions {
+        let constraints = self
+            .members
+            .iter()
+            .map(|p| Constraint {
+                value: p.clone(),
+                cmp_zero: Cmp::Eq,
+            })
+            .collect::<Vec<_>>();
+
+        // the Krawczyk test needs as many equations as unknowns
+        let jac = (self.members.len() == bx.len()).then(|| {
+            self.members
+                .iter()
+                .map(|f| {
+                    (0..bx.len())
+                        .map(|var| f.derivative(var))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let mut res = RealSolutions::default();
+        let mut work = vec![(bx.to_vec(), 0)];
+        let mut processed = 0;
+
+        while let Some((bx, depth)) = work.pop() {
+            if processed == budget.max_boxes || stop.load(atomic::Ordering::Relaxed) {
+                res.undecided.push(bx);
+                continue;
+            }
+
+            processed += 1;
+
+            let mut bx = match hc4(&constraints, &bx) {
+                Some(bx) => bx,
+                None => {
+                    res.excluded += 1;
+                    continue;
+                }
+            };
+
+            if let Some(jac) = &jac {
+                match krawczyk(&self.members, jac, &bx) {
+                    Krawczyk::Excluded => {
+                        res.excluded += 1;
+                        continue;
+                    }
+                    Krawczyk::Unique(mut unique) => {
+                        for _ in 0..MAX_REFINEMENTS {
+                            if max_width(&unique) <= tol {
+                                break;
+                            }
+
+                            // the solution stays in every contraction
+                            match krawczyk(&self.members, jac, &unique) {
+                                Krawczyk::Unique(next) | Krawczyk::Unknown(next) => unique = next,
+                                Krawczyk::Excluded => break,
+                            }
+                        }
+
+                        if max_width(&unique) <= tol {
+                            res.verified.push(unique);
+                            continue;
+                        }
+
+                        bx = unique;
+                    }
+                    Krawczyk::Unknown(contracted) => bx = contracted,
+                }
+            }
+
+            if max_width(&bx) <= tol {
+                res.candidates.push(bx);
+            } else if depth == budget.max_depth {
+                res.undecided.push(bx);
+            } else {
+                let (left, right) = bisect(&bx);
+                work.push((right, depth + 1));
+                work.push((left, depth + 1));
+            }
+        }
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Budget;
+    use crate::interval::Interval;
+    use crate::system;
+
+    #[test]
+    fn square() {
+        // circle and parabola meet twice
+        let sys = system! {
+            x^2 + y^2 - 1,
+            x^2 - y
+        };
+
+        let sols = sys.solve_real_in_box(&[Interval::new(-2., 2.), Interval::new(-2., 2.)], 1e-9);
+        assert_eq!(2, sols.verified.len());
+        assert!(sols.candidates.is_empty() && sols.undecided.is_empty());
+
+        let y = (5f64.sqrt() - 1.) / 2.;
+        let x = y.sqrt();
+
+        let mut xs = sols
+            .verified
+            .iter()
+            .map(|bx| {
+                assert!(bx[1].contains(y) && bx[0].width() <= 1e-9);
+                bx[0]
+            })
+            .collect::<Vec<_>>();
+        xs.sort_by(|a, b| a.lo.total_cmp(&b.lo));
+        assert!(xs[0].contains(-x) && xs[1].contains(x));
+
+        // nothing real
+        let sys = system! {
+            x^2 + y^2 + 1,
+            x - y
+        };
+
+        let sols = sys.solve_real_in_box(&[Interval::new(-2., 2.), Interval::new(-2., 2.)], 1e-9);
+        assert!(sols.verified.is_empty() && sols.candidates.is_empty());
+        assert!(sols.excluded > 0);
+    }
+
+    #[test]
+    fn singular() {
+        // a double root can't be verified, but is kept as a candidate
+        let sys = system! {
+            x^2 - 2*x + 1
+        };
+
+        let sols = sys.solve_real_in_box(&[Interval::new(-3., 3.)], 1e-6);
+        assert!(sols.verified.is_empty() && !sols.candidates.is_empty());
+        assert!(sols
+            .candidates
+            .iter()
+            .all(|bx| (bx[0].mid() - 1.).abs() < 1e-5));
+        assert!(sols.candidates.iter().any(|bx| bx[0].contains(1.)));
+    }
+
+    #[test]
+    fn budget() {
+        let sys = system! {
+            x^2 + y^2 - 1,
+            x - y
+        };
+
+        let bx = [Interval::new(-2., 2.), Interval::new(-2., 2.)];
+        let sols = sys.solve_real_in_box_with_budget(
+            &bx,
+            1e-9,
+            Budget {
+                max_boxes: 1,
+                max_depth: 64,
+            },
+        );
+
+        assert!(sols.verified.is_empty());
+        assert_eq!(2, sols.undecided.len());
+
+        let sols = sys.solve_real_in_box(&bx, 1e-9);
+        assert_eq!(2, sols.verified.len());
+    }
+}
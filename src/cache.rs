@@ -0,0 +1,412 @@
+// an on disk cache of Groebner bases and real solutions, so that rerunning a
+// pipeline with unchanged subproblems is instant. entries are keyed by a
+// canonical form of the system which forgets the var names, the order and
+// scaling of the members, and repeated members: all of these leave the reduced
+// basis and the solutions unchanged. var indices are kept, since monomial
+// orders depend on them. an unreadable or unwritable cache only costs the
+// recomputation
+
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::branch_and_prune::RealSolutions;
+use crate::interval::Interval;
+use crate::poly::mono::Mono;
+use crate::poly::order::MonomialOrder;
+use crate::poly::system::System;
+use crate::poly::var_order::VarOrder;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+#[derive(Debug, Clone)]
+pub struct Cache {
+    pub dir: PathBuf,
+}
+
+// 64 bit FNV-1a, which unlike the std hashers is stable across releases
+fn fnv1a(s: &str) -> u64 {
+    s.bytes().fold(0xcbf2_9ce4_8422_2325, |h, b| {
+        (h ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+// terms as "num/den v^pow v^pow", separated by commas
+fn write_poly(p: &Poly<Rat>) -> String {
+    let mut s = String::new();
+
+    for (i, term) in p.terms.iter().enumerate() {
+        if i > 0 {
+            s.push(',');
+        }
+        write!(s, "{}/{}", term.val.num, term.val.den).unwrap();
+        for (v, pow) in &term.vars {
+            write!(s, " {v}^{pow}").unwrap();
+        }
+    }
+
+    s
+}
+
+fn read_poly(s: &str) -> Option<Poly<Rat>> {
+    let mut terms = vec![];
+
+    for term in s.split(',').filter(|t| !t.is_empty()) {
+        let mut parts = term.split(' ');
+        let (num, den) = parts.next()?.split_once('/')?;
+
+        let vars = parts
+            .map(|var| {
+                let (v, pow) = var.split_once('^')?;
+                Some((v.parse().ok()?, pow.parse().ok()?))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        terms.push(Mono {
+            val: Rat::from(num.parse::<i64>().ok()?) / Rat::from(den.parse::<i64>().ok()?),
+            vars,
+        });
+    }
+
+    Some(Poly { terms })
+}
+
+// exact, as the bits of the bounds
+fn write_box(bx: &[Interval]) -> String {
+    bx.iter()
+        .map(|x| format!("{:x}:{:x}", x.lo.to_bits(), x.hi.to_bits()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn read_box(s: &str) -> Option<Vec<Interval>> {
+    s.split(' ')
+        .filter(|x| !x.is_empty())
+        .map(|x| {
+            let (lo, hi) = x.split_once(':')?;
+            let bits = |b| u64::from_str_radix(b, 16).ok().map(f64::from_bits);
+            Some(Interval::new(bits(lo)?, bits(hi)?))
+        })
+        .collect()
+}
+
+fn write_order(order: &MonomialOrder) -> String {
+    match order {
+        MonomialOrder::Grlex => "grlex".to_string(),
+        MonomialOrder::Grevlex => "grevlex".to_string(),
+        MonomialOrder::Lex => "lex".to_string(),
+        MonomialOrder::Block(blocks) => {
+            let blocks = blocks.iter().map(|block| {
+                let mut block = block.clone();
+                block.sort();
+                format!("{block:?}")
+            });
+            format!("block{}", blocks.collect::<String>())
+        }
+    }
+}
+
+impl System<Rat> {
+    // one line, equal for systems which differ only in var names and in the
+    // order, scaling and repetition of their members
+    pub fn canonical_form(&self) -> String {
+        let mut members = self
+            .members
+            .iter()
+            .filter(|p| !p.is_zero())
+            .map(|p| write_poly(&p.monic()))
+            .collect::<Vec<_>>();
+
+        members.sort();
+        members.dedup();
+
+        format!("{};{}", self.var_dict.len(), members.join(";"))
+    }
+
+    // a stable hash for telling problems apart across runs: FNV-1a of the
+    // var names and the canonical form, with the vars sorted by name first.
+    // unlike the canonical form, it keeps the names but not the order of the
+    // var dict, and unlike the derived Hash, it does not depend on how the
+    // terms are stored
+    pub fn canonical_hash(&self) -> u64 {
+        let mut order = (0..self.var_dict.len()).collect::<Vec<_>>();
+        order.sort_by_key(|v| &self.var_dict[*v]);
+        let sorted = self.with_var_order(&VarOrder { order });
+
+        fnv1a(&format!(
+            "{};{}",
+            sorted.var_dict.join(","),
+            sorted.canonical_form()
+        ))
+    }
+}
+
+impl Poly<Rat> {
+    // FNV-1a of the terms with their vars by name, each sorted, so neither
+    // the order of the terms nor the indices of the vars matter
+    pub fn canonical_hash(&self, var_dict: &[String]) -> u64 {
+        let mut terms = self
+            .terms
+            .iter()
+            .filter(|t| !t.val.is_zero())
+            .map(|t| {
+                let mut vars = t
+                    .vars
+                    .iter()
+                    .map(|(v, pow)| format!("{}^{pow}", var_dict[*v]))
+                    .collect::<Vec<_>>();
+                vars.sort();
+                format!("{}/{} {}", t.val.num, t.val.den, vars.join(" "))
+            })
+            .collect::<Vec<_>>();
+
+        terms.sort();
+        fnv1a(&terms.join(","))
+    }
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Cache {
+        Cache { dir: dir.into() }
+    }
+
+    // the lines stored under key, checking the key itself against hash
+    // collisions
+    fn load(&self, key: &str) -> Option<Vec<String>> {
+        let contents = fs::read_to_string(self.dir.join(format!("{:016x}", fnv1a(key)))).ok()?;
+        let mut lines = contents.lines();
+
+        if lines.next()? != key {
+            return None;
+        }
+
+        Some(lines.map(|l| l.to_string()).collect())
+    }
+
+    fn store(&self, key: &str, lines: &[String]) {
+        let path = self.dir.join(format!("{:016x}", fnv1a(key)));
+        let contents = format!("{key}\n{}", lines.join("\n"));
+
+        // a failed write only means recomputing next time
+        let _ = fs::create_dir_all(&self.dir).and_then(|_| fs::write(path, contents));
+    }
+
+    pub fn gb_with_order(&self, sys: &System<Rat>, order: &MonomialOrder) -> System<Rat> {
+        let key = format!("gb {} {}", write_order(order), sys.canonical_form());
+
+        let cached = self.load(&key).and_then(|lines| {
+            lines
+                .iter()
+                .map(|l| read_poly(l))
+                .collect::<Option<Vec<_>>>()
+        });
+
+        if let Some(members) = cached {
+            return System {
+                var_dict: sys.var_dict.clone(),
+                members,
+            };
+        }
+
+        let gb = sys.gb_with_order(order);
+        self.store(&key, &gb.members.iter().map(write_poly).collect::<Vec<_>>());
+
+        gb
+    }
+
+    pub fn solve_real_in_box(&self, sys: &System<Rat>, bx: &[Interval], tol: f64) -> RealSolutions {
+        // the member count as given decides whether boxes are verified, so
+        // it is part of the key though the canonical form drops repeats
+        let key = format!(
+            "real {} {:x} {} {}",
+            write_box(bx),
+            tol.to_bits(),
+            sys.members.len(),
+            sys.canonical_form()
+        );
+
+        // the excluded count, then one line per box tagged with its kind
+        let cached = self.load(&key).and_then(|lines| {
+            let mut res = RealSolutions {
+                excluded: lines.first()?.parse().ok()?,
+                ..Default::default()
+            };
+
+            for line in &lines[1..] {
+                let (kind, bx) = line.split_once(' ').unwrap_or((line, ""));
+                let bx = read_box(bx)?;
+
+                match kind {
+                    "verified" => res.verified.push(bx),
+                    "candidate" => res.candidates.push(bx),
+                    "undecided" => res.undecided.push(bx),
+                    _ => return None,
+                }
+            }
+
+            Some(res)
+        });
+
+        if let Some(res) = cached {
+            return res;
+        }
+
+        let res = sys.solve_real_in_box(bx, tol);
+
+        let mut lines = vec![res.excluded.to_string()];
+        for (kind, boxes) in [
+            ("verified", &res.verified),
+            ("candidate", &res.candidates),
+            ("undecided", &res.undecided),
+        ] {
+            lines.extend(boxes.iter().map(|bx| format!("{kind} {}", write_box(bx))));
+        }
+        self.store(&key, &lines);
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::rc::Rc;
+
+    use super::{read_poly, write_poly, Cache};
+    use crate::interval::Interval;
+    use crate::poly::order::MonomialOrder;
+    use crate::poly::system::System;
+    use crate::poly::var_order::VarOrder;
+    use crate::poly::Poly;
+    use crate::system;
+
+    #[test]
+    fn canonical_form() {
+        let sys = system! {
+            x^2 + y^2 - 1,
+            2*x - y
+        };
+
+        // scaled, reordered and repeated, under other names
+        let mut same = system! {
+            6*x - 3*y,
+            x^2 + y^2 - 1,
+            4*x - 2*y
+        };
+        same.var_dict = Rc::new(vec!["a".to_string(), "b".to_string()]);
+
+        assert_eq!(sys.canonical_form(), same.canonical_form());
+        assert_ne!(
+            sys.canonical_form(),
+            system! { x^2 + y^2 - 1, 2*y - x }.canonical_form()
+        );
+
+        for p in &sys.members {
+            assert_eq!(Some(p.monic()), read_poly(&write_poly(&p.monic())));
+        }
+    }
+
+    #[test]
+    fn canonical_hash() {
+        let sys = system! {
+            x^2 + y^2 - 1,
+            2*x - y
+        };
+
+        // the same vars in the other order, with the members scaled and
+        // reordered
+        let swapped = sys.with_var_order(&VarOrder { order: vec![1, 0] });
+        let same = System {
+            var_dict: swapped.var_dict.clone(),
+            members: vec![
+                swapped.members[1].mul_ref(&sys.constant(3)),
+                swapped.members[0].clone(),
+            ],
+        };
+        assert_eq!(vec!["y", "x"], *same.var_dict);
+        assert_eq!(sys.canonical_hash(), same.canonical_hash());
+
+        // renamed is a different problem, though the canonical form agrees
+        let mut renamed = sys.clone();
+        renamed.var_dict = Rc::new(vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(sys.canonical_form(), renamed.canonical_form());
+        assert_ne!(sys.canonical_hash(), renamed.canonical_hash());
+
+        let p = &sys.members[0];
+        let reversed = Poly {
+            terms: p.terms.iter().rev().cloned().collect(),
+        };
+        assert_eq!(
+            p.canonical_hash(&sys.var_dict),
+            reversed.canonical_hash(&sys.var_dict)
+        );
+        assert_eq!(
+            p.canonical_hash(&sys.var_dict),
+            swapped.members[0].canonical_hash(&swapped.var_dict)
+        );
+        assert_ne!(
+            p.canonical_hash(&sys.var_dict),
+            sys.members[1].canonical_hash(&sys.var_dict)
+        );
+    }
+
+    #[test]
+    fn cache() {
+        let dir = std::env::temp_dir().join(format!("srs_solver_cache_{}", std::process::id()));
+        let cache = Cache::new(&dir);
+
+        let sys = system! {
+            x^2*y + 1,
+            2*x + y*z - 1,
+            x - y^2*z^2 + 1
+        };
+
+        let gb = cache.gb_with_order(&sys, &MonomialOrder::Lex);
+        assert_eq!(1, fs::read_dir(&dir).unwrap().count());
+
+        // from disk, for a renamed system with its members reordered
+        let mut renamed = system! {
+            x - y^2*z^2 + 1,
+            x^2*y + 1,
+            2*x + y*z - 1
+        };
+        renamed.var_dict = Rc::new(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        let cached = cache.gb_with_order(&renamed, &MonomialOrder::Lex);
+        assert_eq!(gb.members, cached.members);
+        assert_eq!(vec!["a", "b", "c"], *cached.var_dict);
+        assert_eq!(1, fs::read_dir(&dir).unwrap().count());
+
+        let circle = system! {
+            x^2 + y^2 - 1,
+            x^2 - y
+        };
+        let bx = [Interval::new(-2., 2.), Interval::new(-2., 2.)];
+        let solved = cache.solve_real_in_box(&circle, &bx, 1e-9);
+        let cached = cache.solve_real_in_box(&circle, &bx, 1e-9);
+        assert_eq!(solved.verified, cached.verified);
+        assert_eq!(solved.excluded, cached.excluded);
+        assert_eq!(2, fs::read_dir(&dir).unwrap().count());
+
+        // a repeated member leaves the boxes unverified, so it is a new entry
+        let mut repeated = circle.clone();
+        repeated.members.push(circle.members[1].clone());
+        let res = cache.solve_real_in_box(&repeated, &bx, 1e-9);
+        assert!(!solved.verified.is_empty() && res.verified.is_empty());
+        assert_eq!(
+            repeated.solve_real_in_box(&bx, 1e-9).candidates,
+            res.candidates
+        );
+        assert_eq!(3, fs::read_dir(&dir).unwrap().count());
+
+        // a colliding or corrupt entry is recomputed over
+        for entry in fs::read_dir(&dir).unwrap() {
+            fs::write(entry.unwrap().path(), "something else").unwrap();
+        }
+        assert_eq!(
+            gb.members,
+            cache.gb_with_order(&sys, &MonomialOrder::Lex).members
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,3 +1,6 @@
 pub mod projection;
 pub mod resultant;
+pub mod sign;
+pub mod sturm;
 pub mod tarski;
+pub mod weak_projection;
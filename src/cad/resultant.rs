@@ -1,5 +1,6 @@
 use std::collections::VecDeque;
 
+use crate::cad::sturm;
 use crate::field::Field;
 use crate::poly::Poly;
 
@@ -19,27 +20,44 @@ fn determinant<T: Field>(mut mat: Vec<Vec<Poly<T>>>, size: usize) -> Poly<T> {
             - mat[0][0].mul_ref(&mat[1][2]).mul_ref(&mat[2][1])
             - mat[0][1].mul_ref(&mat[1][0]).mul_ref(&mat[2][2])
     } else {
-        for i in 0..size {
-            for j in 0..size {
-                if i != j {
-                    for k in (i + 1)..size {
-                        mat[j][k] = mat[i][i].mul_ref(&mat[j][k]) - mat[j][i].mul_ref(&mat[i][k]);
-
-                        if i != 0 {
-                            mat[j][k] = mat[j][k].try_divide(&mat[i - 1][i - 1]).unwrap();
-                        }
+        let mut negate = false;
+        let mut prev_pivot = Poly::constant(T::one());
+
+        for k in 0..size - 1 {
+            if mat[k][k].is_zero() {
+                match (k + 1..size).find(|r| !mat[*r][k].is_zero()) {
+                    Some(r) => {
+                        mat.swap(k, r);
+                        negate = !negate;
                     }
+                    None => return Poly::constant(T::zero()),
                 }
             }
+
+            for i in (k + 1)..size {
+                for j in (k + 1)..size {
+                    mat[i][j] = (mat[k][k].mul_ref(&mat[i][j]) - mat[i][k].mul_ref(&mat[k][j]))
+                        .try_divide(&prev_pivot)
+                        .unwrap();
+                }
+            }
+
+            prev_pivot = mat[k][k].clone();
         }
 
-        mat.pop().unwrap().pop().unwrap()
+        let det = mat.pop().unwrap().pop().unwrap();
+
+        if negate {
+            det * Poly::constant(T::from(-1))
+        } else {
+            det
+        }
     }
 }
 
 // k'th order Sylvester matrix
 // see https://link.springer.com/article/10.1007/s00200-004-0158-4
-fn syl_k<T: Field>(a_coefs: &Vec<Poly<T>>, b_coefs: &Vec<Poly<T>>, k: usize) -> Vec<Vec<Poly<T>>> {
+fn syl_k<T: Field>(a_coefs: &[Poly<T>], b_coefs: &[Poly<T>], k: usize) -> Vec<Vec<Poly<T>>> {
     let mut rows = vec![];
     let a_deg = a_coefs.len() - 1;
     let b_deg = b_coefs.len() - 1;
@@ -105,9 +123,73 @@ pub fn subresultants<T: Field>(a: &Poly<T>, b: &Poly<T>, var: usize) -> Vec<Vec<
     srs
 }
 
+// principal signed subresultant coefs sRes_j(a, b) w.r.t. var, indexed by j;
+// deg(b) < deg(a). sRes_j = eps(deg(a) - j) times the classical principal
+// subresultant coef, eps(k) = (-1)^(k(k - 1)/2)
+pub fn signed_subresultant_coefficients<T: Field>(
+    a: &Poly<T>,
+    b: &Poly<T>,
+    var: usize,
+) -> Vec<Poly<T>> {
+    // no other vars: the dense univariate sequence avoids the determinants
+    if let (Some(p), Some(q)) = (a.to_upoly(var), b.to_upoly(var)) {
+        return sturm::signed_subresultant_coefficients(&p, &q)
+            .into_iter()
+            .map(Poly::constant)
+            .collect();
+    }
+
+    determinant_subresultant_coefficients(a, b, var)
+}
+
+fn determinant_subresultant_coefficients<T: Field>(
+    a: &Poly<T>,
+    b: &Poly<T>,
+    var: usize,
+) -> Vec<Poly<T>> {
+    let n = a.deg(var);
+    let m = b.deg(var);
+
+    let eps = |k: usize| {
+        if (k * k.saturating_sub(1) / 2).is_multiple_of(2) {
+            Poly::constant(T::one())
+        } else {
+            Poly::constant(T::from(-1))
+        }
+    };
+
+    let mut coefs = vec![Poly::constant(T::zero()); n + 1];
+    let srs = subresultants(a, b, var);
+
+    coefs[n] = srs[0][0].clone();
+
+    let b_lc = srs[1][0].clone();
+    if m + 1 == n {
+        coefs[m] = b_lc;
+    } else {
+        // sResP_m = eps(n - m) lc(b)^(n - m - 1) b
+        let mut lc_pow = eps(n - m);
+        for _ in 0..n - m {
+            lc_pow = lc_pow.mul_ref(&b_lc);
+        }
+        coefs[m] = lc_pow;
+    }
+
+    for (k, sres) in (0..m).rev().zip(srs.into_iter().skip(2)) {
+        coefs[k] = eps(n - k).mul_ref(&sres[0]);
+    }
+
+    coefs
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{determinant, subresultants, syl_k};
+    use super::{
+        determinant, determinant_subresultant_coefficients, signed_subresultant_coefficients,
+        subresultants, syl_k,
+    };
+    use crate::cad::sturm;
+    use crate::rational::Rat;
     use crate::system;
 
     #[test]
@@ -115,7 +197,7 @@ mod tests {
         let a_coefs = system! { 5, 4, 3, 2, 1 }.members;
         let b_coefs = system! { 4, 3, 2, 1 }.members;
 
-        let expected_deg0 = vec![
+        let expected_deg0 = [
             "5, 4, 3, 2, 1, 0, 0",
             "0, 5, 4, 3, 2, 1, 0",
             "0, 0, 5, 4, 3, 2, 1",
@@ -125,7 +207,7 @@ mod tests {
             "0, 0, 0, 4, 3, 2, 1",
         ];
 
-        let expected_deg1 = vec![
+        let expected_deg1 = [
             "5, 4, 3, 2, 1, 0",
             "0, 5, 4, 3, 2, 1",
             "4, 3, 2, 1, 0, 0",
@@ -138,7 +220,7 @@ mod tests {
         for i in 0..(a_coefs.len() + b_coefs.len() - 2) {
             let line = mat[i]
                 .iter()
-                .map(|p| p.format(&vec![]))
+                .map(|p| p.format(&[]))
                 .collect::<Vec<_>>()
                 .join(", ");
             assert_eq!(expected_deg0[i], line);
@@ -149,7 +231,7 @@ mod tests {
         for i in 0..(a_coefs.len() + b_coefs.len() - 4) {
             let line = mat[i]
                 .iter()
-                .map(|p| p.format(&vec![]))
+                .map(|p| p.format(&[]))
                 .collect::<Vec<_>>()
                 .join(", ");
             assert_eq!(expected_deg1[i], line);
@@ -166,6 +248,16 @@ mod tests {
         ];
 
         assert_eq!(-3560, determinant(mat, 4).get_constant_val().unwrap());
+
+        // needs a row swap
+        let mat = vec![
+            system! { 0, 1, 0, 0 }.members,
+            system! { 1, 0, 0, 0 }.members,
+            system! { 0, 0, 0, 1 }.members,
+            system! { 0, 0, 2, 0 }.members,
+        ];
+
+        assert_eq!(2, determinant(mat, 4).get_constant_val().unwrap());
     }
 
     #[test]
@@ -197,4 +289,62 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn signed_coefs() {
+        let sys = system! {
+            x^4 + 1,
+            x,
+            x^3 - 6*x^2 + 11*x - 6,
+            3*x^2 - 12*x + 11,
+        };
+
+        for (a, b) in [(0, 1), (2, 3)] {
+            let multi = determinant_subresultant_coefficients(&sys.members[a], &sys.members[b], 0);
+            let uni = sturm::signed_subresultant_coefficients(
+                &sys.members[a].to_upoly(0).unwrap(),
+                &sys.members[b].to_upoly(0).unwrap(),
+            );
+
+            assert_eq!(
+                uni,
+                multi
+                    .iter()
+                    .map(|c| c.terms.first().map(|t| t.val).unwrap_or(Rat::from(0)))
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        // x^2 + y has two real roots iff y < 0
+        let sys = system! {
+            x^2 + y,
+            2*x
+        };
+
+        let coefs = signed_subresultant_coefficients(&sys.members[0], &sys.members[1], 0);
+        assert_eq!(
+            "[\"-4y\", \"2\", \"1\"]",
+            format!(
+                "{:?}",
+                coefs
+                    .iter()
+                    .map(|c| c.format(&sys.var_dict))
+                    .collect::<Vec<_>>()
+            )
+        );
+
+        for (y, roots) in [(-1, 2), (1, 0)] {
+            let at_y = coefs
+                .iter()
+                .map(|c| {
+                    c.eval(1, Rat::from(y))
+                        .terms
+                        .first()
+                        .map(|t| t.val)
+                        .unwrap_or(Rat::from(0))
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(roots, sturm::pmv(&at_y));
+        }
+    }
 }
@@ -0,0 +1,143 @@
+use crate::algebraic::Algebraic;
+use crate::cad::sturm::{isolating_intervals, sign_at_root};
+use crate::cad::tarski::Cmp;
+use crate::field::Field;
+use crate::univariate::{Root, UPoly};
+
+// find a real x with sign(p(x)) = cmp for every (p, cmp) in conditions;
+// None if the conditions are inconsistent
+pub fn sign_condition_sample<T: Field>(conditions: &[(UPoly<T>, Cmp)]) -> Option<Algebraic<T>> {
+    // gcd of everything required to vanish
+    let mut zero_set: Option<UPoly<T>> = None;
+    let mut nonzero = vec![];
+
+    for (p, cmp) in conditions {
+        let p = p.clone().trim();
+
+        if p.deg() == 0 {
+            if Cmp::from(p.sign_at_pos_inf()) != *cmp {
+                return None;
+            }
+        } else if *cmp == Cmp::Eq {
+            zero_set = Some(match zero_set {
+                Some(g) => g.gcd(&p),
+                None => p,
+            });
+        } else {
+            nonzero.push((p, *cmp));
+        }
+    }
+
+    match zero_set {
+        Some(g) => {
+            if g.deg() == 0 {
+                return None;
+            }
+
+            let g = g.squarefree_part();
+
+            isolating_intervals(&g)
+                .into_iter()
+                .enumerate()
+                .find(|(_, (lo, hi))| {
+                    nonzero
+                        .iter()
+                        .all(|(p, cmp)| Cmp::from(sign_at_root(&g, p, lo, hi)) == *cmp)
+                })
+                .map(|(n, (lo, hi))| Algebraic {
+                    p: g.clone(),
+                    val: Root::Interval(lo, hi),
+                    n,
+                })
+        }
+        None => {
+            // one point from each connected component of the complement of
+            // the roots: left of every root, then right of each root
+            let all = nonzero.iter().fold(UPoly(vec![T::one()]), |acc, (p, _)| {
+                acc * p.squarefree_part()
+            });
+
+            let intervals = isolating_intervals(&all);
+
+            let candidates = match intervals.first() {
+                Some((lo, _)) => std::iter::once(lo.clone())
+                    .chain(intervals.iter().map(|(_, hi)| hi.clone()))
+                    .collect(),
+                None => vec![T::zero()],
+            };
+
+            candidates
+                .into_iter()
+                .find(|x| {
+                    nonzero
+                        .iter()
+                        .all(|(p, cmp)| Cmp::from(p.sign_at(x)) == *cmp)
+                })
+                .map(Algebraic::from_point)
+        }
+    }
+}
+
+pub fn is_consistent<T: Field>(conditions: &[(UPoly<T>, Cmp)]) -> bool {
+    sign_condition_sample(conditions).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_consistent, sign_condition_sample};
+    use crate::cad::tarski::Cmp;
+    use crate::rational::Rat;
+    use crate::univariate::{upoly, Root};
+
+    #[test]
+    fn strict() {
+        // x^2 - 2 < 0, x - 1 > 0
+        let sample =
+            sign_condition_sample(&[(upoly(&[1, 0, -2]), Cmp::Lt), (upoly(&[1, -1]), Cmp::Gt)])
+                .unwrap();
+
+        let x = f64::from(sample.val.approx());
+        assert!(1. < x && x * x < 2.);
+
+        // x^2 + 1 < 0
+        assert!(!is_consistent(&[(upoly(&[1, 0, 1]), Cmp::Lt)]));
+
+        // x^2 - 1 < 0, x^2 - 4x + 3 > 0, x > 0
+        assert!(is_consistent(&[
+            (upoly(&[1, 0, -1]), Cmp::Lt),
+            (upoly(&[1, -4, 3]), Cmp::Gt),
+            (upoly(&[1, 0]), Cmp::Gt),
+        ]));
+    }
+
+    #[test]
+    fn vanishing() {
+        // x^2 - 2 = 0, x > 0
+        let sample =
+            sign_condition_sample(&[(upoly(&[1, 0, -2]), Cmp::Eq), (upoly(&[1, 0]), Cmp::Gt)])
+                .unwrap();
+
+        assert_eq!(1, sample.n);
+        match sample.val {
+            Root::Interval(lo, hi) => {
+                assert!(f64::from(lo) < 2f64.sqrt() && 2f64.sqrt() < f64::from(hi))
+            }
+            Root::Point(_) => panic!("sqrt 2 is irrational"),
+        }
+
+        // (x - 1)(x + 1) = 0, (x - 1)(x - 2) = 0 share only x = 1
+        let both = [(upoly(&[1, 0, -1]), Cmp::Eq), (upoly(&[1, -3, 2]), Cmp::Eq)];
+        match sign_condition_sample(&both).unwrap().val {
+            Root::Interval(lo, hi) => assert!(lo < Rat::from(1) && Rat::from(1) < hi),
+            Root::Point(x) => assert_eq!(Rat::from(1), x),
+        }
+
+        let mut too_many = both.to_vec();
+        too_many.push((upoly(&[1, -1]), Cmp::Lt));
+        assert!(!is_consistent(&too_many));
+
+        // constants
+        assert!(!is_consistent(&[(upoly(&[-3]), Cmp::Gt)]));
+        assert!(is_consistent(&[(upoly(&[]), Cmp::Eq)]));
+    }
+}
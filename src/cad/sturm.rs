@@ -0,0 +1,476 @@
+use std::cmp::Ordering;
+
+use crate::field::Field;
+use crate::trace::Trace;
+use crate::univariate::UPoly;
+
+// p, q, -rem(p, q), ... until the remainder vanishes
+pub fn signed_remainder_sequence<T: Field>(p: &UPoly<T>, q: &UPoly<T>) -> Vec<UPoly<T>> {
+    signed_remainder_sequence_traced(p, q, &mut Trace::disabled())
+}
+
+// records each member as it is produced, labelled by its index
+pub fn signed_remainder_sequence_traced<T: Field>(
+    p: &UPoly<T>,
+    q: &UPoly<T>,
+    trace: &mut Trace<UPoly<T>>,
+) -> Vec<UPoly<T>> {
+    let mut seq = vec![p.clone().trim()];
+    let mut next = q.clone().trim();
+    trace.record("0", &seq[0]);
+
+    while !next.is_zero() {
+        trace.record(&seq.len().to_string(), &next);
+        let rem = seq[seq.len() - 1].rem(&next).scale(&T::from(-1));
+        seq.push(next);
+        next = rem;
+    }
+
+    seq
+}
+
+pub fn sturm_sequence<T: Field>(p: &UPoly<T>) -> Vec<UPoly<T>> {
+    signed_remainder_sequence(p, &p.derivative())
+}
+
+pub fn sturm_sequence_traced<T: Field>(p: &UPoly<T>, trace: &mut Trace<UPoly<T>>) -> Vec<UPoly<T>> {
+    signed_remainder_sequence_traced(p, &p.derivative(), trace)
+}
+
+// zeros are skipped
+pub fn sign_variations(signs: impl IntoIterator<Item = Ordering>) -> usize {
+    let mut last = Ordering::Equal;
+    let mut variations = 0;
+
+    for sign in signs {
+        if sign != Ordering::Equal {
+            if last != Ordering::Equal && last != sign {
+                variations += 1;
+            }
+            last = sign;
+        }
+    }
+
+    variations
+}
+
+pub fn variations_at<T: Field>(seq: &[UPoly<T>], x: &T) -> usize {
+    sign_variations(seq.iter().map(|p| p.sign_at(x)))
+}
+
+pub fn variations_at_neg_inf<T: Field>(seq: &[UPoly<T>]) -> usize {
+    sign_variations(seq.iter().map(|p| p.sign_at_neg_inf()))
+}
+
+pub fn variations_at_pos_inf<T: Field>(seq: &[UPoly<T>]) -> usize {
+    sign_variations(seq.iter().map(|p| p.sign_at_pos_inf()))
+}
+
+// number of distinct roots of p in (lo, hi); lo and hi must not be roots
+pub fn count_roots<T: Field>(p: &UPoly<T>, lo: &T, hi: &T) -> usize {
+    let seq = sturm_sequence(p);
+    variations_at(&seq, lo) - variations_at(&seq, hi)
+}
+
+// Ind(p'/p) over the whole line, from principal coefs alone
+pub fn count_real_roots<T: Field>(p: &UPoly<T>) -> usize {
+    let p = p.clone().trim();
+    if p.deg() == 0 {
+        return 0;
+    }
+
+    pmv(&signed_subresultant_coefficients(&p, &p.derivative())) as usize
+}
+
+// a point of (lo, hi) which is not a root of any of ps
+pub fn split_point<T: Field>(lo: &T, hi: &T, ps: &[&UPoly<T>]) -> T {
+    let mut mid = (lo.clone() + hi.clone()) / T::from(2);
+
+    while ps.iter().any(|p| p.eval(&mid).is_zero()) {
+        mid = (lo.clone() + mid) / T::from(2);
+    }
+
+    mid
+}
+
+// sorted disjoint open intervals, each containing exactly one real root of p;
+// no endpoint is a root
+pub fn isolating_intervals<T: Field>(p: &UPoly<T>) -> Vec<(T, T)> {
+    let p = p.clone().trim();
+    if p.deg() == 0 {
+        return vec![];
+    }
+
+    let seq = sturm_sequence(&p);
+    let bound = p.root_bound();
+
+    let mut intervals = vec![];
+    let mut stack = vec![(
+        bound.clone() * -1,
+        bound,
+        variations_at_neg_inf(&seq),
+        variations_at_pos_inf(&seq),
+    )];
+
+    while let Some((lo, hi, lo_vars, hi_vars)) = stack.pop() {
+        match lo_vars - hi_vars {
+            0 => {}
+            1 => intervals.push((lo, hi)),
+            _ => {
+                let mid = split_point(&lo, &hi, &[&p]);
+                let mid_vars = variations_at(&seq, &mid);
+
+                // upper half first so that intervals come out sorted
+                stack.push((mid.clone(), hi, mid_vars, hi_vars));
+                stack.push((lo, mid, lo_vars, mid_vars));
+            }
+        }
+    }
+
+    intervals
+}
+
+// sign of q at the only root of p in (lo, hi); lo and hi must not be roots of p
+pub fn sign_at_root<T: Field>(p: &UPoly<T>, q: &UPoly<T>, lo: &T, hi: &T) -> Ordering {
+    tarski_query(p, q, &(Some(lo.clone()), Some(hi.clone()))).cmp(&0)
+}
+
+// signed subresultant polynomials sResP_j(p, q), indexed by j, for
+// deg q < deg p; computed with the structure theorem recurrence
+// (see Basu, Pollack, Roy: Algorithms in Real Algebraic Geometry, ch. 8)
+pub fn signed_subresultants<T: Field>(p: &UPoly<T>, q: &UPoly<T>) -> Vec<UPoly<T>> {
+    signed_subresultants_traced(p, q, &mut Trace::disabled())
+}
+
+// records each sResP_j as the recurrence reaches it, labelled by j
+pub fn signed_subresultants_traced<T: Field>(
+    p: &UPoly<T>,
+    q: &UPoly<T>,
+    trace: &mut Trace<UPoly<T>>,
+) -> Vec<UPoly<T>> {
+    let p = p.clone().trim();
+    let q = q.clone().trim();
+    let deg_p = p.deg();
+
+    let mut sresp: Vec<UPoly<T>> = vec![UPoly(vec![]); deg_p + 1];
+    // principal and leading coefs
+    let mut s: Vec<T> = vec![T::zero(); deg_p + 1];
+    let mut t: Vec<T> = vec![T::zero(); deg_p + 1];
+
+    trace.record(&deg_p.to_string(), &p);
+    sresp[deg_p] = p;
+    s[deg_p] = T::one();
+    t[deg_p] = T::one();
+
+    if deg_p == 0 {
+        return sresp;
+    }
+
+    t[deg_p - 1] = q.lc();
+    trace.record(&(deg_p - 1).to_string(), &q);
+    sresp[deg_p - 1] = q;
+
+    let mut i = deg_p + 1;
+    let mut j = deg_p;
+
+    while j > 0 && !sresp[j - 1].is_zero() {
+        let k = sresp[j - 1].deg();
+
+        let prev_scale = if k == j - 1 {
+            s[j - 1] = t[j - 1].clone();
+            s[j - 1].clone() * s[j - 1].clone()
+        } else {
+            s[j - 1] = T::zero();
+
+            for delta in 1..j - k {
+                let t_next = t[j - 1].clone() * t[j - delta].clone() / s[j].clone();
+                t[j - delta - 1] = if delta.is_multiple_of(2) {
+                    t_next
+                } else {
+                    t_next * -1
+                };
+            }
+
+            s[k] = t[k].clone();
+            sresp[k] = sresp[j - 1].scale(&(s[k].clone() / t[j - 1].clone()));
+            trace.record(&k.to_string(), &sresp[k]);
+
+            t[j - 1].clone() * s[k].clone()
+        };
+
+        if k == 0 {
+            break;
+        }
+
+        sresp[k - 1] = sresp[i - 1]
+            .scale(&prev_scale)
+            .rem(&sresp[j - 1])
+            .scale(&(T::from(-1) / (s[j].clone() * t[i - 1].clone())));
+        t[k - 1] = sresp[k - 1].lc();
+        trace.record(&(k - 1).to_string(), &sresp[k - 1]);
+
+        i = j;
+        j = k;
+    }
+
+    sresp
+}
+
+// principal coefs sRes_j(p, q), the coefficient of x^j in sResP_j(p, q),
+// indexed by j; deg q < deg p
+pub fn signed_subresultant_coefficients<T: Field>(p: &UPoly<T>, q: &UPoly<T>) -> Vec<T> {
+    signed_subresultants(p, q)
+        .into_iter()
+        .enumerate()
+        .map(|(j, sresp)| {
+            if sresp.0.len() == j + 1 {
+                sresp.0[0].clone()
+            } else {
+                T::zero()
+            }
+        })
+        .collect()
+}
+
+// generalized permanences minus variations of s_n, ..., s_0 (given indexed by
+// j, s_n nonzero): the sum of eps(i - j) sign(s_i s_j) over consecutive nonzero
+// s_i, s_j with i - j odd, where eps(k) = (-1)^(k(k - 1)/2)
+pub fn pmv<T: Field>(coefs: &[T]) -> i64 {
+    let nonzero = coefs
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, c)| !c.is_zero())
+        .map(|(j, c)| (j, c.cmp(&T::zero())))
+        .collect::<Vec<_>>();
+
+    nonzero
+        .windows(2)
+        .filter(|w| !(w[0].0 - w[1].0).is_multiple_of(2))
+        .map(|w| {
+            let gap = w[0].0 - w[1].0;
+            let eps = if (gap * (gap - 1) / 2).is_multiple_of(2) {
+                1
+            } else {
+                -1
+            };
+            let sign = if w[0].1 == w[1].1 { 1 } else { -1 };
+            eps * sign
+        })
+        .sum()
+}
+
+// Sturm-Habicht sequence StHa(p, q) = sResP(p, p'q), listed from j = deg p
+// down; p'q is first reduced mod p, which leaves the Cauchy index unchanged
+pub fn sturm_habicht<T: Field>(p: &UPoly<T>, q: &UPoly<T>) -> Vec<UPoly<T>> {
+    let p = p.clone().trim();
+    let pq = (p.derivative() * q.clone()).rem(&p);
+
+    let mut seq = signed_subresultants(&p, &pq);
+    seq.reverse();
+    seq
+}
+
+// (lo, hi); None stands for -inf and +inf respectively
+pub type Interval<T> = (Option<T>, Option<T>);
+
+fn interval_variations<T: Field>(seq: &[UPoly<T>], interval: &Interval<T>) -> i64 {
+    let lo_vars = match &interval.0 {
+        Some(lo) => variations_at(seq, lo),
+        None => variations_at_neg_inf(seq),
+    };
+
+    let hi_vars = match &interval.1 {
+        Some(hi) => variations_at(seq, hi),
+        None => variations_at_pos_inf(seq),
+    };
+
+    lo_vars as i64 - hi_vars as i64
+}
+
+// Ind(q/p; lo, hi): the number of jumps of q/p from -inf to +inf minus the
+// number of jumps from +inf to -inf at the roots of p in the interval.
+// finite endpoints must not be roots of p
+pub fn cauchy_index<T: Field>(p: &UPoly<T>, q: &UPoly<T>, interval: &Interval<T>) -> i64 {
+    let p = p.clone().trim();
+    if p.deg() == 0 {
+        return 0;
+    }
+
+    let mut seq = signed_subresultants(&p, &q.rem(&p));
+    seq.reverse();
+
+    interval_variations(&seq, interval)
+}
+
+// TaQ(q, p; lo, hi): the sum of sign(q(x)) over the distinct roots x of p in
+// the interval, i.e. #{q > 0} - #{q < 0}. finite endpoints must not be roots of p
+pub fn tarski_query<T: Field>(p: &UPoly<T>, q: &UPoly<T>, interval: &Interval<T>) -> i64 {
+    if p.deg() == 0 {
+        return 0;
+    }
+
+    if let (None, None) = interval {
+        let p = p.clone().trim();
+        let pq = (p.derivative() * q.clone()).rem(&p);
+
+        // no evaluation needed over the whole line
+        return pmv(&signed_subresultant_coefficients(&p, &pq));
+    }
+
+    interval_variations(&sturm_habicht(p, q), interval)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::{
+        cauchy_index, count_real_roots, count_roots, isolating_intervals, pmv, sign_at_root,
+        signed_remainder_sequence, signed_subresultant_coefficients, signed_subresultants,
+        tarski_query, variations_at_neg_inf, variations_at_pos_inf,
+    };
+    use crate::rational::Rat;
+    use crate::univariate::{upoly, UPoly};
+    use rand::prelude::*;
+
+    #[test]
+    fn counts() {
+        // (x + 4)(x + 1)(x - 3)(x - 5)
+        let p = upoly(&[1, -3, -21, 43, 60]);
+
+        assert_eq!(4, count_real_roots(&p));
+        assert_eq!(2, count_roots(&p, &Rat::from(-2), &Rat::from(4)));
+
+        // x^2 + 1, and a double root
+        assert_eq!(0, count_real_roots(&upoly(&[1, 0, 1])));
+        assert_eq!(1, count_real_roots(&upoly(&[1, -2, 1])));
+    }
+
+    #[test]
+    fn isolation() {
+        // x^3 - 2x
+        let p = upoly(&[1, 0, -2, 0]);
+        let intervals = isolating_intervals(&p);
+
+        assert_eq!(3, intervals.len());
+
+        let expected = [-(2f64.sqrt()), 0., 2f64.sqrt()];
+
+        for ((lo, hi), root) in intervals.iter().zip(expected) {
+            assert!(f64::from(*lo) < root && root < f64::from(*hi));
+        }
+
+        for w in intervals.windows(2) {
+            assert!(w[0].1 <= w[1].0);
+        }
+    }
+
+    #[test]
+    fn signs_at_roots() {
+        // x^2 - 2 and x - 1
+        let p = upoly(&[1, 0, -2]);
+        let q = upoly(&[1, -1]);
+
+        let intervals = isolating_intervals(&p);
+
+        let signs = intervals
+            .iter()
+            .map(|(lo, hi)| sign_at_root(&p, &q, lo, hi))
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec![Ordering::Less, Ordering::Greater], signs);
+
+        // x^2 - 1 vanishes at the root of x - 1
+        let p = upoly(&[1, -1]);
+        let q = upoly(&[1, 0, -1]);
+        assert_eq!(
+            Ordering::Equal,
+            sign_at_root(&p, &q, &Rat::from(0), &Rat::from(2))
+        );
+    }
+
+    #[test]
+    fn subresultants() {
+        let p = upoly(&[1, -6, 11, -6]);
+        let seq = signed_subresultants(&p, &p.derivative());
+
+        assert_eq!(
+            vec![upoly(&[4]), upoly(&[6, -12]), upoly(&[3, -12, 11]), p],
+            seq
+        );
+
+        // defective: x^4 + 1 and x
+        let seq = signed_subresultants(&upoly(&[1, 0, 0, 0, 1]), &upoly(&[1, 0]));
+
+        assert_eq!(
+            vec![upoly(&[1]), upoly(&[-1, 0]), upoly(&[]), upoly(&[1, 0])],
+            seq[..4].to_vec()
+        );
+    }
+
+    #[test]
+    fn queries() {
+        // (x - 1)(x - 2)(x - 3)
+        let p = upoly(&[1, -6, 11, -6]);
+        let q = upoly(&[2, -5]);
+
+        assert_eq!(-1, tarski_query(&p, &q, &(None, None)));
+        assert_eq!(
+            -2,
+            tarski_query(&p, &q, &(None, Some(Rat::from(5) / Rat::from(2))))
+        );
+        assert_eq!(
+            1,
+            tarski_query(&p, &q, &(Some(Rat::from(5) / Rat::from(2)), None))
+        );
+        assert_eq!(3, tarski_query(&p, &upoly(&[1]), &(None, None)));
+        assert_eq!(0, tarski_query(&p, &upoly(&[1, -2]), &(None, None)));
+
+        // 1/x jumps up at 0, -1/x jumps down, 1/x^2 does not change sign
+        let one = upoly(&[1]);
+        assert_eq!(1, cauchy_index(&upoly(&[1, 0]), &one, &(None, None)));
+        assert_eq!(-1, cauchy_index(&upoly(&[-1, 0]), &one, &(None, None)));
+        assert_eq!(0, cauchy_index(&upoly(&[1, 0, 0]), &one, &(None, None)));
+        assert_eq!(
+            0,
+            cauchy_index(
+                &upoly(&[1, 0]),
+                &one,
+                &(Some(Rat::from(1)), Some(Rat::from(2)))
+            )
+        );
+    }
+
+    #[test]
+    fn pmv_fuzz() {
+        let mut rng = SmallRng::seed_from_u64(1);
+
+        fn random_upoly(rng: &mut SmallRng, deg: usize) -> UPoly<Rat> {
+            let mut coefs = vec![Rat::from(
+                rng.gen_range(1..4) * if rng.gen_bool(0.5) { 1 } else { -1 },
+            )];
+            for _ in 0..deg {
+                coefs.push(Rat::from(rng.gen_range(-3..4)));
+            }
+
+            UPoly(coefs)
+        }
+
+        // degrees are kept small enough for Rat to stay exact
+        for _ in 0..1000 {
+            let deg_p = rng.gen_range(1..4);
+            let deg_q = rng.gen_range(0..4);
+            let p = random_upoly(&mut rng, deg_p);
+            let q = random_upoly(&mut rng, deg_q);
+
+            // the Cauchy index from the signed remainder sequence
+            let srem = signed_remainder_sequence(&p, &q);
+            let index = variations_at_neg_inf(&srem) as i64 - variations_at_pos_inf(&srem) as i64;
+
+            let sres = signed_subresultant_coefficients(&p, &q.rem(&p));
+
+            assert_eq!(index, pmv(&sres));
+        }
+    }
+}
@@ -1,13 +1,25 @@
+use std::cmp::Ordering;
+
 use crate::poly::Poly;
 use crate::rational::Rat;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Cmp {
     Gt,
     Eq,
     Lt,
 }
 
+impl From<Ordering> for Cmp {
+    fn from(ord: Ordering) -> Self {
+        match ord {
+            Ordering::Greater => Cmp::Gt,
+            Ordering::Equal => Cmp::Eq,
+            Ordering::Less => Cmp::Lt,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Constraint {
     pub value: Poly<Rat>,
@@ -0,0 +1,316 @@
+use crate::cad::resultant::subresultants;
+use crate::cad::tarski::{Cmp, Constraint, T};
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+// conditions on the remaining variables after existentially eliminating some
+#[derive(Debug, Clone)]
+pub struct Projection {
+    // conjunction; every real solution of the input projects into its
+    // solution set
+    pub conditions: Vec<T>,
+    // false if information was lost along the way, so that the conditions are
+    // only necessary rather than sufficient
+    pub exact: bool,
+}
+
+// conjunction of p = 0, p > 0 or p < 0, and p >= 0
+#[derive(Default)]
+struct State {
+    eqs: Vec<Poly<Rat>>,
+    strict: Vec<(Poly<Rat>, Cmp)>,
+    nonneg: Vec<Poly<Rat>>,
+    exact: bool,
+}
+
+// p with var replaced by q
+fn substitute(p: &Poly<Rat>, var: usize, q: &Poly<Rat>) -> Poly<Rat> {
+    p.coefs(var)
+        .into_iter()
+        .fold(Poly::constant(Rat::from(0)), |acc, c| acc.mul_ref(q) + c)
+}
+
+pub(crate) fn resultant(a: &Poly<Rat>, b: &Poly<Rat>, var: usize) -> Poly<Rat> {
+    let (a, b) = if a.deg(var) >= b.deg(var) {
+        (a, b)
+    } else {
+        (b, a)
+    };
+
+    subresultants(a, b, var).pop().unwrap().pop().unwrap()
+}
+
+// b^2 - 4ac for p = ax^2 + bx + c in var
+fn discriminant(p: &Poly<Rat>, var: usize) -> Poly<Rat> {
+    let coefs = p.coefs(var);
+    coefs[1].mul_ref(&coefs[1]) - coefs[0].mul_ref(&coefs[2]) * Poly::constant(Rat::from(4))
+}
+
+fn constant_lc(p: &Poly<Rat>, var: usize) -> Option<Rat> {
+    let lc = &p.coefs(var)[0];
+    if lc.is_constant() {
+        Some(lc.lt_mono().val)
+    } else {
+        None
+    }
+}
+
+impl State {
+    fn eliminate(self, var: usize) -> State {
+        let involves = |p: &Poly<Rat>| p.deg(var) > 0;
+
+        // an equation linear in var with a constant coef can be solved for it
+        // exactly
+        if let Some(i) = self
+            .eqs
+            .iter()
+            .position(|f| f.deg(var) == 1 && constant_lc(f, var).is_some())
+        {
+            let mut eqs = self.eqs;
+            let f = eqs.swap_remove(i);
+            let coefs = f.coefs(var);
+            let val = coefs[1].mul_ref(&Poly::constant(
+                Rat::from(-1) / constant_lc(&f, var).unwrap(),
+            ));
+            let sub = |p: &Poly<Rat>| substitute(p, var, &val);
+
+            return State {
+                eqs: eqs.iter().map(sub).collect(),
+                strict: self.strict.iter().map(|(p, cmp)| (sub(p), *cmp)).collect(),
+                nonneg: self.nonneg.iter().map(sub).collect(),
+                exact: self.exact,
+            };
+        }
+
+        let (eqs_var, eqs): (Vec<_>, Vec<_>) = self.eqs.into_iter().partition(involves);
+        let (strict_var, strict): (Vec<_>, Vec<_>) =
+            self.strict.into_iter().partition(|(p, _)| involves(p));
+        let (nonneg_var, nonneg): (Vec<_>, Vec<_>) = self.nonneg.into_iter().partition(involves);
+
+        let mut next = State {
+            eqs,
+            strict,
+            nonneg,
+            exact: self.exact,
+        };
+
+        let count = eqs_var.len() + strict_var.len() + nonneg_var.len();
+
+        if eqs_var.is_empty() {
+            // each constraint on its own, which is exact when there is only one
+            next.exact &= count <= 1;
+
+            let constraints = strict_var
+                .into_iter()
+                .map(|(p, cmp)| (p, Some(cmp)))
+                .chain(nonneg_var.into_iter().map(|p| (p, None)));
+
+            for (p, cmp) in constraints {
+                let lc = match constant_lc(&p, var) {
+                    Some(lc) => lc,
+                    None => {
+                        next.exact = false;
+                        continue;
+                    }
+                };
+
+                // the leading term decides at one end of the line, so only
+                // the sign opposite to it needs p to have real roots
+                let opposite = match cmp {
+                    Some(Cmp::Gt) => lc < Rat::from(0),
+                    Some(_) => lc > Rat::from(0),
+                    None => lc < Rat::from(0),
+                };
+
+                if !p.deg(var).is_multiple_of(2) || !opposite {
+                    continue;
+                }
+
+                if p.deg(var) == 2 {
+                    match cmp {
+                        Some(_) => next.strict.push((discriminant(&p, var), Cmp::Gt)),
+                        None => next.nonneg.push(discriminant(&p, var)),
+                    }
+                } else {
+                    next.exact = false;
+                }
+            }
+        } else {
+            next.exact &=
+                count == 1 && eqs_var[0].deg(var) == 2 && constant_lc(&eqs_var[0], var).is_some();
+
+            // a real root in var needs a nonnegative discriminant
+            for f in &eqs_var {
+                if f.deg(var) == 2 {
+                    next.nonneg.push(discriminant(f, var));
+                }
+            }
+
+            // common roots in var make the resultants vanish
+            for (i, a) in eqs_var.iter().enumerate() {
+                for b in eqs_var.iter().skip(i + 1) {
+                    next.eqs.push(resultant(a, b, var));
+                }
+            }
+        }
+
+        next
+    }
+}
+
+// project the conjunction of constraints onto the variables not in eliminate.
+// equations solvable for a variable are substituted exactly; otherwise
+// equations give resultants and quadratic discriminants, and inequalities with
+// a constant leading coef in the variable give the conditions for them to be
+// satisfiable
+pub fn weak_projection(constraints: &[Constraint], eliminate: &[usize]) -> Projection {
+    let mut state = State {
+        exact: true,
+        ..Default::default()
+    };
+
+    for c in constraints {
+        match c.cmp_zero {
+            Cmp::Eq => state.eqs.push(c.value.clone()),
+            cmp => state.strict.push((c.value.clone(), cmp)),
+        }
+    }
+
+    for var in eliminate {
+        state = state.eliminate(*var);
+    }
+
+    let constraint = |value: Poly<Rat>, cmp_zero| T::C(Constraint { value, cmp_zero });
+
+    let conditions = state
+        .eqs
+        .into_iter()
+        .filter(|p| !p.is_zero())
+        .map(|p| constraint(p.norm(), Cmp::Eq))
+        .chain(state.strict.into_iter().map(|(p, cmp)| constraint(p, cmp)))
+        .chain(state.nonneg.into_iter().map(|p| {
+            T::Or(
+                Box::new(constraint(p.clone(), Cmp::Gt)),
+                Box::new(constraint(p, Cmp::Eq)),
+            )
+        }))
+        .collect();
+
+    Projection {
+        conditions,
+        exact: state.exact,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::weak_projection;
+    use crate::cad::tarski::{Cmp, Constraint, T};
+    use crate::poly::system::System;
+    use crate::rational::Rat;
+    use crate::system;
+
+    fn constraints(sys: &System<Rat>, cmps: &[Cmp]) -> Vec<Constraint> {
+        sys.members
+            .iter()
+            .zip(cmps)
+            .map(|(value, cmp_zero)| Constraint {
+                value: value.clone(),
+                cmp_zero: *cmp_zero,
+            })
+            .collect()
+    }
+
+    fn format(sys: &System<Rat>, t: &T) -> String {
+        match t {
+            T::C(c) => format!("{} {:?}", c.value.format(&sys.var_dict), c.cmp_zero),
+            T::Or(a, b) => format!("{} | {}", format(sys, a), format(sys, b)),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn substitution() {
+        // x = y + 1, x > 0, x^2 + z < 4
+        let sys = system! {
+            x - y - 1,
+            x,
+            x^2 + z - 4
+        };
+
+        let proj = weak_projection(&constraints(&sys, &[Cmp::Eq, Cmp::Gt, Cmp::Lt]), &[0]);
+
+        assert!(proj.exact);
+        let formatted = proj
+            .conditions
+            .iter()
+            .map(|c| format(&sys, c))
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["y + 1 Gt", "y^2 + 2y + z - 3 Lt"], formatted);
+    }
+
+    #[test]
+    fn discriminants() {
+        // the unit circle projects onto -1 <= y <= 1
+        let sys = system! {
+            x^2 + y^2 - 1
+        };
+
+        let proj = weak_projection(&constraints(&sys, &[Cmp::Eq]), &[0]);
+        assert!(proj.exact);
+        assert_eq!(
+            vec!["-4y^2 + 4 Gt | -4y^2 + 4 Eq"],
+            proj.conditions
+                .iter()
+                .map(|c| format(&sys, c))
+                .collect::<Vec<_>>()
+        );
+
+        // x^2 + y < 0 needs y < 0; x - y > 0 holds for some x regardless
+        let sys = system! {
+            x^2 + y,
+            x - y
+        };
+
+        let proj = weak_projection(&constraints(&sys, &[Cmp::Lt]), &[0]);
+        assert!(proj.exact);
+        assert_eq!(
+            vec!["-4y Gt"],
+            proj.conditions
+                .iter()
+                .map(|c| format(&sys, c))
+                .collect::<Vec<_>>()
+        );
+
+        let proj = weak_projection(&constraints(&sys, &[Cmp::Lt, Cmp::Gt]), &[0]);
+        assert!(!proj.exact);
+        assert_eq!(1, proj.conditions.len());
+    }
+
+    #[test]
+    fn resultants() {
+        // x^2 = y and x^2 = 2 - y meet only where y = 1
+        let sys = system! {
+            x^2 - y,
+            x^2 + y - 2,
+            x*y - 3
+        };
+
+        let proj = weak_projection(&constraints(&sys, &[Cmp::Eq, Cmp::Eq, Cmp::Gt]), &[0]);
+        assert!(!proj.exact);
+
+        let formatted = proj
+            .conditions
+            .iter()
+            .map(|c| format(&sys, c))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "y^2 - 2y + 1 Eq",
+                "4y Gt | 4y Eq",
+                "-4y + 8 Gt | -4y + 8 Eq"
+            ],
+            formatted
+        );
+    }
+}
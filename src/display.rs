@@ -0,0 +1,204 @@
+// rich display in Jupyter notebooks through evcxr: a value with an
+// evcxr_display method is shown by what it prints between the
+// EVCXR_BEGIN_CONTENT and EVCXR_END_CONTENT markers, in the given mime type.
+// polys and systems print LaTeX, and solution sets an HTML table. the
+// methods write the markup too, for other notebooks and web frontends
+
+use std::fmt::Write;
+
+use crate::poly::mono::Mono;
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+use crate::solve::Solution;
+
+fn evcxr(mime: &str, content: &str) {
+    println!("EVCXR_BEGIN_CONTENT {mime}\n{content}\nEVCXR_END_CONTENT");
+}
+
+// trailing digits as a subscript, so x12 is x_{12}
+fn latex_var(name: &str) -> String {
+    match name.find(|c: char| c.is_ascii_digit()) {
+        Some(i) if i > 0 && name[i..].chars().all(|c| c.is_ascii_digit()) => {
+            format!("{}_{{{}}}", &name[..i], &name[i..])
+        }
+        _ => name.to_string(),
+    }
+}
+
+fn latex_rat(r: Rat) -> String {
+    if r.den == 1 {
+        r.num.to_string()
+    } else {
+        format!("\\frac{{{}}}{{{}}}", r.num, r.den)
+    }
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+impl Poly<Rat> {
+    // highest terms first, as in format
+    pub fn latex(&self, var_dict: &[String]) -> String {
+        if self.terms.is_empty() {
+            return "0".to_string();
+        }
+
+        let mut s = String::new();
+        for (i, Mono { val, vars }) in self.terms.iter().rev().enumerate() {
+            let negative = *val < Rat::from(0);
+            let abs = if negative { *val * -1 } else { *val };
+
+            match (i, negative) {
+                (0, true) => s.push('-'),
+                (0, false) => {}
+                (_, true) => s.push_str(" - "),
+                (_, false) => s.push_str(" + "),
+            }
+
+            if abs != Rat::from(1) || vars.is_empty() {
+                s.push_str(&latex_rat(abs));
+            }
+
+            for (var, pow) in vars {
+                s.push_str(&latex_var(&var_dict[*var]));
+                if *pow != 1 {
+                    write!(s, "^{{{pow}}}").unwrap();
+                }
+            }
+        }
+
+        s
+    }
+
+    // a poly does not know its var names, so they show as x_0, x_1, ...
+    pub fn evcxr_display(&self) {
+        let vars = self
+            .terms
+            .iter()
+            .flat_map(|t| t.vars.iter().map(|(v, _)| v + 1))
+            .max();
+        let var_dict = (0..vars.unwrap_or(0))
+            .map(|v| format!("x{v}"))
+            .collect::<Vec<_>>();
+        evcxr("text/latex", &format!("$${}$$", self.latex(&var_dict)));
+    }
+}
+
+impl System<Rat> {
+    // the members as equations in a cases block
+    pub fn latex(&self) -> String {
+        let rows = self
+            .members
+            .iter()
+            .map(|p| format!("{} = 0", p.latex(&self.var_dict)))
+            .collect::<Vec<_>>();
+
+        format!("\\begin{{cases}} {} \\end{{cases}}", rows.join(" \\\\ "))
+    }
+
+    pub fn evcxr_display(&self) {
+        evcxr("text/latex", &format!("$${}$$", self.latex()));
+    }
+}
+
+// a solution set with the names of its vars, for display
+pub struct SolutionTable<'a> {
+    pub solution: &'a Solution,
+    pub var_dict: &'a [String],
+}
+
+impl Solution {
+    pub fn table<'a>(&'a self, var_dict: &'a [String]) -> SolutionTable<'a> {
+        SolutionTable {
+            solution: self,
+            var_dict,
+        }
+    }
+}
+
+impl SolutionTable<'_> {
+    // a row per solution and a column per var; boxes as [lo, hi]. a variety
+    // or an unknown set is a caption without rows
+    pub fn html(&self) -> String {
+        let cells: Vec<Vec<String>> = match self.solution {
+            Solution::Exact(x) => vec![x
+                .iter()
+                .map(|c| match c.den {
+                    1 => c.num.to_string(),
+                    den => format!("{}/{den}", c.num),
+                })
+                .collect()],
+            Solution::Boxes(boxes) => boxes
+                .iter()
+                .map(|bx| bx.iter().map(|i| format!("[{}, {}]", i.lo, i.hi)).collect())
+                .collect(),
+            Solution::Empty | Solution::Variety(_) | Solution::Unknown => vec![],
+        };
+
+        let caption = match self.solution {
+            Solution::Empty => "no solutions".to_string(),
+            Solution::Exact(_) => "one solution".to_string(),
+            Solution::Boxes(boxes) => format!("{} real solutions", boxes.len()),
+            Solution::Variety(gb) => format!("infinitely many solutions, with basis {gb:?}"),
+            Solution::Unknown => "unknown".to_string(),
+        };
+
+        let mut html = format!("<table><caption>{}</caption>", html_escape(&caption));
+        if !cells.is_empty() {
+            html.push_str("<tr>");
+            for v in self.var_dict {
+                write!(html, "<th>{}</th>", html_escape(v)).unwrap();
+            }
+            html.push_str("</tr>");
+        }
+        for row in cells {
+            html.push_str("<tr>");
+            for cell in row {
+                write!(html, "<td>{}</td>", html_escape(&cell)).unwrap();
+            }
+            html.push_str("</tr>");
+        }
+        html.push_str("</table>");
+
+        html
+    }
+
+    pub fn evcxr_display(&self) {
+        evcxr("text/html", &self.html());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::poly::Poly;
+    use crate::rational::Rat;
+    use crate::solve::SolveOptions;
+    use crate::system;
+
+    #[test]
+    fn latex() {
+        let mut sys = system! {
+            x1^2*y - 3,
+            2*x1 - y + 1
+        };
+        sys.members[1] = Poly::constant(Rat::from(-1) / Rat::from(3)).mul_ref(&sys.members[1]);
+
+        assert_eq!(
+            "\\begin{cases} x_{1}^{2}y - 3 = 0 \\\\ -\\frac{2}{3}x_{1} + \\frac{1}{3}y - \\frac{1}{3} = 0 \\end{cases}",
+            sys.latex()
+        );
+        assert_eq!("0", Poly::constant(Rat::from(0)).latex(&[]));
+
+        let sys = system! { x + y - 3, x - y - 1 };
+        let (solution, _) = sys.solve(&SolveOptions::default());
+        assert_eq!(
+            "<table><caption>one solution</caption><tr><th>x</th><th>y</th></tr>\
+             <tr><td>2</td><td>1</td></tr></table>",
+            solution.table(&sys.var_dict).html()
+        );
+    }
+}
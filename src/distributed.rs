@@ -0,0 +1,378 @@
+// multi-modular Groebner bases, split into jobs which other processes or
+// machines can run: each job is one line of text holding the system and the
+// index of a prime, and a worker answers it with one line holding the reduced
+// grlex basis mod that prime, by F4. how the lines travel (pipes, ssh, a
+// queue) is up to the caller. the coordinator combines the answers by the Chinese
+// remainder theorem and rational reconstruction, after dropping those whose
+// leading monomials differ from the most common ones (unlucky primes). with
+// too few primes the reconstruction can be wrong, so the result is only
+// returned if the generators reduce to 0 by it, its S-polynomials reduce to 0
+// by it, and it agrees with the basis mod CHECK_PRIME, which no worker used.
+// the first two make it a Groebner basis of an ideal holding the system; the
+// last stands in for the converse, which would need the basis over Q. it is a
+// probabilistic check: a result passing it is accepted without a full one,
+// and it is skipped when CHECK_PRIME is unlucky for the system
+//
+// a line is "k|n|member;member;..." with terms "num/den e_0 ... e_{n-1}"
+// separated by commas; answers have plain residues for coefs
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::rc::Rc;
+
+use crate::field::Field;
+use crate::gf::Gf;
+use crate::poly::builder::PolyBuilder;
+use crate::poly::f4::{f4, Sparse};
+use crate::poly::order::MonomialOrder;
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+pub const PRIMES: [u64; 6] = [
+    2147483647, 2147483629, 2147483587, 2147483579, 2147483563, 2147483549,
+];
+
+// never handed to a worker, so answers can't be tuned to pass at it
+const CHECK_PRIME: u64 = 2147483543;
+
+type Exps = Vec<(usize, u64)>;
+
+// members, by term
+type Members<C> = Vec<Vec<(Exps, C)>>;
+
+// a basis mod PRIMES[k]
+type Residues = Members<u64>;
+
+fn format_exps(vars: &Exps, n: usize) -> String {
+    (0..n)
+        .map(|v| {
+            let pow = vars
+                .iter()
+                .find(|(w, _)| *w == v)
+                .map_or(0, |(_, pow)| *pow);
+            format!(" {pow}")
+        })
+        .collect()
+}
+
+fn parse_exps(exps: &[&str]) -> Option<Exps> {
+    exps.iter()
+        .enumerate()
+        .map(|(v, e)| e.parse::<u64>().ok().map(|pow| (v, pow)))
+        .filter(|e| e.is_none_or(|(_, pow)| pow > 0))
+        .collect()
+}
+
+fn by_term(polys: &[Poly<Rat>]) -> Members<Rat> {
+    polys
+        .iter()
+        .map(|p| p.terms.iter().map(|t| (t.vars.clone(), t.val)).collect())
+        .collect()
+}
+
+// "k|n|", then the members with coefs by parse_coef
+fn parse_line<C>(
+    line: &str,
+    parse_coef: impl Fn(&str) -> Option<C>,
+) -> Option<(usize, usize, Members<C>)> {
+    let mut parts = line.trim().splitn(3, '|');
+    let k = parts.next()?.parse::<usize>().ok()?;
+    let n = parts.next()?.parse::<usize>().ok()?;
+
+    let members = parts
+        .next()?
+        .split(';')
+        .filter(|m| !m.is_empty())
+        .map(|m| {
+            m.split(',')
+                .filter(|t| !t.is_empty())
+                .map(|t| {
+                    let fields = t.split_whitespace().collect::<Vec<_>>();
+                    let (coef, exps) = fields.split_first()?;
+                    if exps.len() != n {
+                        return None;
+                    }
+                    Some((parse_exps(exps)?, parse_coef(coef)?))
+                })
+                .collect::<Option<Vec<_>>>()
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some((k, n, members))
+}
+
+fn format_line<C>(
+    k: usize,
+    n: usize,
+    members: &[Vec<(Exps, C)>],
+    coef: impl Fn(&C) -> String,
+) -> String {
+    let mut line = format!("{k}|{n}|");
+    for (i, terms) in members.iter().enumerate() {
+        if i > 0 {
+            line.push(';');
+        }
+        for (j, (vars, c)) in terms.iter().enumerate() {
+            if j > 0 {
+                line.push(',');
+            }
+            write!(line, "{}{}", coef(c), format_exps(vars, n)).unwrap();
+        }
+    }
+    line
+}
+
+fn run<const P: u64>(members: Members<Rat>) -> Option<Residues> {
+    let members = members
+        .into_iter()
+        .map(|terms| {
+            let mut exact = PolyBuilder::with_capacity(terms.len());
+            let mut residues = PolyBuilder::with_capacity(terms.len());
+            for (vars, c) in terms {
+                residues.push(Gf::<P>::from_rat(c)?, &vars);
+                exact.push(c, &vars);
+            }
+
+            // a leading coef divisible by P drops the leading term: P is
+            // unlucky for this system
+            let (exact, p) = (exact.build(), residues.build());
+            let lead = exact.terms.last().map(|t| &t.vars);
+            (lead == p.terms.last().map(|t| &t.vars)).then_some(p)
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let order = MonomialOrder::Grlex;
+    Some(
+        order
+            .interreduce(&f4(members, &order, &Sparse), |p| p.monic())
+            .into_iter()
+            .map(|p| {
+                p.terms
+                    .into_iter()
+                    .map(|t| (t.vars, t.val.value()))
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+// the job answered by a worker; None for a malformed line, or a prime
+// dividing a denominator
+pub fn worker(job: &str) -> Option<String> {
+    let (k, n, members) = parse_line(job, |c| {
+        let (num, den) = c.split_once('/')?;
+        let (num, den) = (num.parse::<i64>().ok()?, den.parse::<i64>().ok()?);
+        (den != 0).then(|| Rat::from(num) / Rat::from(den))
+    })?;
+
+    let basis = match k {
+        0 => run::<{ PRIMES[0] }>(members),
+        1 => run::<{ PRIMES[1] }>(members),
+        2 => run::<{ PRIMES[2] }>(members),
+        3 => run::<{ PRIMES[3] }>(members),
+        4 => run::<{ PRIMES[4] }>(members),
+        5 => run::<{ PRIMES[5] }>(members),
+        _ => None,
+    }?;
+
+    Some(format_line(k, n, &basis, |c| c.to_string()))
+}
+
+fn inv_mod(a: u128, p: u128) -> u128 {
+    let (mut base, mut exp, mut acc) = (a % p, p - 2, 1);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = acc * base % p;
+        }
+        base = base * base % p;
+        exp >>= 1;
+    }
+    acc
+}
+
+// r / s = a mod m with |r|, s <= sqrt(m / 2), by the half extended gcd
+fn reconstruct(a: u128, m: u128) -> Option<Rat> {
+    let bound = ((m / 2) as f64).sqrt() as i128;
+    let (mut r0, mut r1) = (m as i128, a as i128);
+    let (mut s0, mut s1) = (0i128, 1i128);
+
+    while r1 > bound {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (s0, s1) = (s1, s0 - q * s1);
+    }
+
+    if s1 == 0 || s1.abs() > bound {
+        return None;
+    }
+
+    let (num, den) = if s1 < 0 { (-r1, -s1) } else { (r1, s1) };
+    Some(Rat::from(i64::try_from(num).ok()?) / Rat::from(i64::try_from(den).ok()?))
+}
+
+impl System<Rat> {
+    // one job per prime, PRIMES.len() at most
+    pub fn modular_jobs(&self, primes: usize) -> Vec<String> {
+        let n = self.var_dict.len();
+        let members = by_term(&self.members);
+
+        (0..primes.min(PRIMES.len()))
+            .map(|k| format_line(k, n, &members, |c: &Rat| format!("{}/{}", c.num, c.den)))
+            .collect()
+    }
+
+    // the reduced grlex basis from the workers' answers, the same as gb();
+    // None if no answers parse, or the reconstruction fails the checks above
+    pub fn combine_modular(&self, answers: &[String]) -> Option<System<Rat>> {
+        let answers = answers
+            .iter()
+            .filter_map(|line| parse_line(line, |c| c.parse::<u64>().ok()))
+            .filter(|(k, n, _)| *k < PRIMES.len() && *n == self.var_dict.len())
+            .map(|(k, _, basis)| (PRIMES[k] as u128, basis))
+            .collect::<Vec<_>>();
+
+        // the leading monomials of each answer, and how many answers share them
+        let shape = |basis: &Residues| {
+            basis
+                .iter()
+                .map(|terms| terms.last().map(|(vars, _)| vars.clone()))
+                .collect::<Vec<_>>()
+        };
+        let mut counts = HashMap::new();
+        for (_, basis) in &answers {
+            *counts.entry(shape(basis)).or_insert(0) += 1;
+        }
+        let (common, _) = counts.into_iter().max_by_key(|(_, count)| *count)?;
+
+        let mut crt: Vec<HashMap<Exps, u128>> = vec![HashMap::new(); common.len()];
+        let mut modulus = 1u128;
+        for (p, basis) in answers.iter().filter(|(_, basis)| shape(basis) == common) {
+            if modulus.checked_mul(*p).is_none_or(|m| m >= 1 << 126) {
+                break;
+            }
+
+            let monomials = crt
+                .iter()
+                .zip(basis)
+                .map(|(acc, terms)| {
+                    let mut vars = acc.keys().cloned().collect::<Vec<_>>();
+                    vars.extend(terms.iter().map(|(v, _)| v.clone()));
+                    vars.sort();
+                    vars.dedup();
+                    vars
+                })
+                .collect::<Vec<_>>();
+
+            let inv = inv_mod(modulus % p, *p);
+            for ((acc, terms), monomials) in crt.iter_mut().zip(basis).zip(monomials) {
+                for vars in monomials {
+                    let r = terms
+                        .iter()
+                        .find(|(v, _)| *v == vars)
+                        .map_or(0, |(_, c)| *c) as u128;
+                    let x = acc.get(&vars).copied().unwrap_or(0);
+                    let t = (r + p - x % p) % p * inv % p;
+                    acc.insert(vars, x + modulus * t);
+                }
+            }
+            modulus *= p;
+        }
+
+        let members = crt
+            .into_iter()
+            .map(|acc| {
+                let mut p = PolyBuilder::with_capacity(acc.len());
+                for (vars, a) in acc {
+                    p.push(reconstruct(a, modulus)?, &vars);
+                }
+                Some(p.build().norm())
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let basis = System {
+            var_dict: Rc::clone(&self.var_dict),
+            members,
+        };
+
+        // the generators must be in the ideal of the result, and the result a
+        // Groebner basis of it
+        let order = MonomialOrder::Grlex;
+        let reduces = |p: &Poly<Rat>| order.reduce(p, &basis.members).is_zero();
+        let holds_system = self.members.iter().all(reduces);
+        let is_gb = (0..basis.len())
+            .all(|j| (0..j).all(|i| reduces(&order.s_poly(&basis.members[i], &basis.members[j]))));
+
+        // but a wrong or forged answer can give a basis of a larger ideal,
+        // such as [1]; mod a fresh prime, it should still be the basis
+        let at_check = run::<CHECK_PRIME>(by_term(&self.members));
+        let agrees =
+            at_check.is_none_or(|gb| run::<CHECK_PRIME>(by_term(&basis.members)) == Some(gb));
+
+        (holds_system && is_gb && agrees).then_some(basis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{worker, PRIMES};
+    use crate::system;
+
+    #[test]
+    fn modular() {
+        let sys = system! {
+            x^2 + y^2 - 5,
+            3*x*y - 2
+        };
+
+        // each job could go to another process; here they run in turn
+        let jobs = sys.modular_jobs(3);
+        assert_eq!(3, jobs.len());
+        let answers = jobs
+            .iter()
+            .map(|job| worker(job).unwrap())
+            .collect::<Vec<_>>();
+
+        let combined = sys.combine_modular(&answers).unwrap();
+        assert_eq!(format!("{:?}", sys.gb()), format!("{:?}", combined));
+
+        // answers for an unknown prime or another var count are ignored, and
+        // a wrong answer is caught
+        assert!(sys.combine_modular(&["9|2|1 0 0".to_string()]).is_none());
+        assert!(sys.combine_modular(&["0|2|1 0 0".to_string()]).is_none());
+        // the generators themselves hold the system, but aren't a basis
+        let gens = "0|2|1 2 0,1 0 2,2147483642 0 0;3 1 1,2147483645 0 0";
+        assert!(sys.combine_modular(&[gens.to_string()]).is_none());
+        assert!(worker("9|1|1/1 1").is_none());
+        assert!(worker("not a job").is_none());
+
+        // coefs divisible by the prime: a lower term drops out, and a
+        // leading one makes the prime unlucky
+        assert_eq!(
+            Some("0|1|1 1".to_string()),
+            worker("0|1|1/1 1,2147483647/1 0")
+        );
+        assert!(worker("0|1|2147483647/1 1,-1/1 0").is_none());
+    }
+
+    #[test]
+    fn unlucky_prime() {
+        // x + (p + 1)y and x + y + 1 are inconsistent mod p = PRIMES[0], so
+        // that worker answers [1]; the other answers outvote it
+        let mut sys = system! {
+            x + y,
+            x + y + 1
+        };
+        sys.members[0] = sys.var("x", 1) + sys.constant(2147483648) * sys.var("y", 1);
+
+        let answers = sys
+            .modular_jobs(PRIMES.len())
+            .iter()
+            .map(|job| worker(job).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!("0|2|1 0 0", answers[0]);
+
+        let combined = sys.combine_modular(&answers).unwrap();
+        assert_eq!(format!("{:?}", sys.gb()), format!("{:?}", combined));
+    }
+}
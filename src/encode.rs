@@ -0,0 +1,141 @@
+use std::rc::Rc;
+
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+// graphs are given as adjacency lists, neighbors of vertex v at adjacency[v];
+// an edge may be listed from either end or both
+
+fn edges(adjacency: &[Vec<usize>]) -> Vec<(usize, usize)> {
+    let mut edges = adjacency
+        .iter()
+        .enumerate()
+        .flat_map(|(u, ns)| ns.iter().map(move |v| (u.min(*v), u.max(*v))))
+        .collect::<Vec<_>>();
+
+    edges.sort();
+    edges.dedup();
+    edges
+}
+
+fn one() -> Poly<Rat> {
+    Poly::constant(Rat::from(1))
+}
+
+fn system(n: usize, members: Vec<Poly<Rat>>) -> System<Rat> {
+    System {
+        var_dict: Rc::new((0..n).map(|v| format!("x{v}")).collect()),
+        members,
+    }
+}
+
+// x^2 - x for each var, so that every solution is 0/1
+fn field_equations(n: usize) -> Vec<Poly<Rat>> {
+    (0..n).map(|v| Poly::var(v, 2) - Poly::var(v, 1)).collect()
+}
+
+// Bayer's encoding: x_v is a kth root of unity standing for the color of v,
+// and for each edge (x_u^k - x_v^k) / (x_u - x_v) = 0 says the colors differ.
+// solutions are exactly the colorings, so the Groebner basis is [1] iff the
+// graph is not k-colorable; a loop makes any graph uncolorable
+pub fn coloring(adjacency: &[Vec<usize>], k: u64) -> System<Rat> {
+    let n = adjacency.len();
+    let mut members = (0..n).map(|v| Poly::var(v, k) - one()).collect::<Vec<_>>();
+
+    for (u, v) in edges(adjacency) {
+        members.push((0..k).fold(Poly::constant(Rat::from(0)), |acc, i| {
+            acc + Poly::var(u, k - 1 - i).mul_ref(&Poly::var(v, i))
+        }));
+    }
+
+    system(n, members)
+}
+
+// x_v = 1 iff v is chosen: no edge has both ends chosen, and exactly size are
+pub fn independent_set(adjacency: &[Vec<usize>], size: usize) -> System<Rat> {
+    let n = adjacency.len();
+    let mut members = field_equations(n);
+
+    for (u, v) in edges(adjacency) {
+        members.push(Poly::var(u, 1).mul_ref(&Poly::var(v, 1)));
+    }
+
+    members.push(
+        (0..n).fold(Poly::constant(Rat::from(-(size as i64))), |acc, v| {
+            acc + Poly::var(v, 1)
+        }),
+    );
+
+    system(n, members)
+}
+
+// clauses in DIMACS form: literal i > 0 is var i - 1, -i its negation. with
+// x = 1 for true, a clause holds iff the product of (1 - literal) vanishes.
+// None if a literal is 0, which DIMACS reserves for ending a clause, or names
+// a var past vars
+pub fn sat(vars: usize, clauses: &[Vec<i64>]) -> Option<System<Rat>> {
+    let mut members = field_equations(vars);
+
+    for clause in clauses {
+        members.push(clause.iter().try_fold(one(), |acc, lit| {
+            let var = usize::try_from(lit.unsigned_abs()).ok()?.checked_sub(1)?;
+            if var >= vars {
+                return None;
+            }
+
+            let x = Poly::var(var, 1);
+            let falsified = if *lit > 0 { one() - x } else { x };
+            Some(acc.mul_ref(&falsified))
+        })?);
+    }
+
+    Some(system(vars, members))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{coloring, independent_set, sat};
+    use crate::poly::quotient::Quotient;
+    use crate::poly::system::System;
+    use crate::rational::Rat;
+
+    fn count(sys: &System<Rat>) -> usize {
+        Quotient::new(sys).unwrap().dim()
+    }
+
+    #[test]
+    fn colorings() {
+        let triangle = vec![vec![1, 2], vec![2], vec![0]];
+
+        assert_eq!("[1]", format!("{:?}", coloring(&triangle, 2).gb()));
+        assert_eq!(6, count(&coloring(&triangle, 3)));
+
+        // a path has 2 two-colorings
+        let path = vec![vec![1], vec![2], vec![]];
+        assert_eq!(2, count(&coloring(&path, 2)));
+    }
+
+    #[test]
+    fn independent_sets() {
+        // the 4-cycle: {0, 2} and {1, 3}
+        let square = vec![vec![1, 3], vec![2], vec![3], vec![]];
+
+        assert_eq!(2, count(&independent_set(&square, 2)));
+        assert_eq!(4, count(&independent_set(&square, 1)));
+        assert_eq!("[1]", format!("{:?}", independent_set(&square, 3).gb()));
+    }
+
+    #[test]
+    fn satisfiability() {
+        // (a or b) and (not a or b) and (not b or c)
+        let clauses = vec![vec![1, 2], vec![-1, 2], vec![-2, 3]];
+        assert_eq!(2, count(&sat(3, &clauses).unwrap()));
+
+        let unsat = vec![vec![1], vec![-1, 2], vec![-2]];
+        assert_eq!("[1]", format!("{:?}", sat(2, &unsat).unwrap().gb()));
+
+        assert!(sat(2, &[vec![1, 0]]).is_none());
+        assert!(sat(2, &[vec![-3]]).is_none());
+    }
+}
@@ -0,0 +1,141 @@
+// solutions as CSV for dataframe tools, which all read it, so the crate needs
+// no Arrow or Parquet dependency: a header row, then one row per solution
+// with a column per var and the metadata columns
+//
+//   multiplicity: 1 when it is known to be simple, i.e. the solution is the
+//     one of a linear system, or there are as many boxes as the degree of the
+//     system; empty otherwise
+//   residual: a bound on the largest |f| over the members at the values
+//     written, which are box midpoints for boxes
+//   real: whether the row is checked to be a real solution: an exact point
+//     with zero residual, or a box over which every member can vanish
+//
+// values are written with the given number of digits after the point
+
+use crate::interval::{self, Interval};
+use crate::poly::system::System;
+use crate::rational::Rat;
+use crate::solve::{Solution, SolveReport};
+
+fn residual(sys: &System<Rat>, point: &[f64]) -> f64 {
+    let bx = point
+        .iter()
+        .map(|x| Interval::new(*x, *x))
+        .collect::<Vec<_>>();
+
+    sys.members
+        .iter()
+        .map(|f| {
+            let range = interval::eval(f, &bx);
+            range.lo.abs().max(range.hi.abs())
+        })
+        .fold(0., f64::max)
+}
+
+impl Solution {
+    // None for a variety or an unknown solution set, which have no rows
+    pub fn to_csv(
+        &self,
+        sys: &System<Rat>,
+        report: &SolveReport,
+        precision: usize,
+    ) -> Option<String> {
+        // (values, exact residual if known, real)
+        let rows: Vec<(Vec<f64>, Option<Rat>, bool)> = match self {
+            Solution::Empty => vec![],
+            Solution::Exact(x) => {
+                let residual = sys
+                    .members
+                    .iter()
+                    .map(|f| {
+                        let val = (0..x.len()).fold(f.clone(), |p, v| p.eval(v, x[v]));
+                        val.terms.first().map_or(Rat::from(0), |t| t.val)
+                    })
+                    .map(|r| if r < Rat::from(0) { r * -1 } else { r })
+                    .max()
+                    .unwrap_or(Rat::from(0));
+                let values = x.iter().map(|c| f64::from(*c)).collect();
+                vec![(values, Some(residual), residual.is_zero())]
+            }
+            Solution::Boxes(boxes) => boxes
+                .iter()
+                .map(|bx| {
+                    let real = sys
+                        .members
+                        .iter()
+                        .all(|f| interval::eval(f, bx).contains(0.));
+                    (bx.iter().map(|i| i.mid()).collect(), None, real)
+                })
+                .collect(),
+            Solution::Variety(_) | Solution::Unknown => return None,
+        };
+
+        let simple = match self {
+            Solution::Exact(_) => true,
+            _ => report.degree == Some(rows.len()),
+        };
+
+        let mut csv = sys.var_dict.join(",");
+        csv.push_str(",multiplicity,residual,real\n");
+
+        for (values, exact, real) in rows {
+            let residual = exact.map_or_else(|| residual(sys, &values), f64::from);
+
+            for x in &values {
+                csv.push_str(&format!("{x:.precision$},"));
+            }
+            let multiplicity = if simple { "1" } else { "" };
+            csv.push_str(&format!("{multiplicity},{residual:e},{real}\n"));
+        }
+
+        Some(csv)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rational::Rat;
+    use crate::solve::{Solution, SolveOptions};
+    use crate::system;
+
+    #[test]
+    fn csv() {
+        let options = SolveOptions::default();
+
+        let sys = system! { x + y - 3, x - 2*y - 1 };
+        let (solution, report) = sys.solve(&options);
+        assert_eq!(
+            Some("x,y,multiplicity,residual,real\n2.333,0.667,1,0e0,true\n".to_string()),
+            solution.to_csv(&sys, &report, 3)
+        );
+
+        // not a solution, so not marked real
+        let origin = Solution::Exact(vec![Rat::from(0); 2]);
+        assert_eq!(
+            Some("x,y,multiplicity,residual,real\n0.0,0.0,1,3e0,false\n".to_string()),
+            origin.to_csv(&sys, &report, 1)
+        );
+
+        let sys = system! { x^2 + y^2 - 5, x*y - 2 };
+        let (solution, report) = sys.solve(&options);
+        let csv = solution.to_csv(&sys, &report, 6).unwrap();
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(5, lines.len());
+
+        for line in &lines[1..] {
+            let fields = line.split(',').collect::<Vec<_>>();
+            let (x, y) = (
+                fields[0].parse::<f64>().unwrap(),
+                fields[1].parse::<f64>().unwrap(),
+            );
+            assert!((x * y - 2.).abs() < 1e-4);
+            assert_eq!("1", fields[2]);
+            assert!(fields[3].parse::<f64>().unwrap() < 1e-4);
+            assert_eq!("true", fields[4]);
+        }
+
+        let sys = system! { x*y - 1 };
+        let (solution, report) = sys.solve(&options);
+        assert_eq!(None, solution.to_csv(&sys, &report, 3));
+    }
+}
@@ -0,0 +1,362 @@
+// symbolic expressions as the user wrote them, rewritten before lowering to
+// Poly: expanding (x * x * 2)^8 + 0 term by term builds far more than the
+// rewritten 256 x^16. the built-in rules flatten nested sums and products,
+// fold constants, combine like factors into powers and apply the power
+// rules; user rules run before them at every node, so they can substitute,
+// or rewrite before a built-in one fires. rewriting is bottom-up, and a node
+// is rewritten until no rule applies; a rule must return None when it would
+// not change the node, or rewriting never ends
+
+use std::ops::{Add, Mul, Sub};
+
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Const(Rat),
+    Var(String),
+    Add(Vec<Expr>),
+    Mul(Vec<Expr>),
+    Pow(Box<Expr>, u64),
+    // a symbolic exponent, bound to a number before lowering
+    PowVar(Box<Expr>, String),
+}
+
+pub type Rule = Box<dyn Fn(&Expr) -> Option<Expr>>;
+
+impl Expr {
+    pub fn var(name: &str) -> Expr {
+        Expr::Var(name.to_string())
+    }
+
+    pub fn constant(val: i64) -> Expr {
+        Expr::Const(Rat::from(val))
+    }
+
+    pub fn pow(self, exp: u64) -> Expr {
+        Expr::Pow(Box::new(self), exp)
+    }
+
+    pub fn pow_var(self, exp: &str) -> Expr {
+        Expr::PowVar(Box::new(self), exp.to_string())
+    }
+
+    // the exponents named in exps replaced by their values
+    pub fn bind_exponents(&self, exps: &[(&str, u64)]) -> Expr {
+        let mut e = self.clone();
+        for child in e.children_mut() {
+            *child = child.bind_exponents(exps);
+        }
+
+        match e {
+            Expr::PowVar(base, name) => match exps.iter().find(|(n, _)| *n == name) {
+                Some((_, exp)) => Expr::Pow(base, *exp),
+                None => Expr::PowVar(base, name),
+            },
+            e => e,
+        }
+    }
+
+    // the symbolic exponents, in order of first appearance
+    pub fn exponent_vars(&self) -> Vec<String> {
+        let mut names = match self {
+            Expr::PowVar(_, name) => vec![name.clone()],
+            _ => vec![],
+        };
+
+        let children = match self {
+            Expr::Const(_) | Expr::Var(_) => vec![],
+            Expr::Add(es) | Expr::Mul(es) => es.iter().collect(),
+            Expr::Pow(base, _) | Expr::PowVar(base, _) => vec![base.as_ref()],
+        };
+        for name in children.into_iter().flat_map(|c| c.exponent_vars()) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+
+        names
+    }
+
+    // None if a var is not in var_dict, or an exponent is not bound
+    pub fn lower(&self, var_dict: &[String]) -> Option<Poly<Rat>> {
+        match self {
+            Expr::Const(c) => Some(Poly::constant(*c)),
+            Expr::Var(name) => var_dict
+                .iter()
+                .position(|v| v == name)
+                .map(|i| Poly::var(i, 1)),
+            Expr::Add(terms) => terms
+                .iter()
+                .try_fold(Poly::constant(Rat::from(0)), |acc, t| {
+                    Some(acc + t.lower(var_dict)?)
+                }),
+            Expr::Mul(factors) => factors
+                .iter()
+                .try_fold(Poly::constant(Rat::from(1)), |acc, f| {
+                    Some(acc.mul_ref(&f.lower(var_dict)?))
+                }),
+            Expr::Pow(base, exp) => {
+                let base = base.lower(var_dict)?;
+                Some((0..*exp).fold(Poly::constant(Rat::from(1)), |acc, _| acc.mul_ref(&base)))
+            }
+            Expr::PowVar(_, _) => None,
+        }
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Expr> {
+        match self {
+            Expr::Const(_) | Expr::Var(_) => vec![],
+            Expr::Add(es) | Expr::Mul(es) => es.iter_mut().collect(),
+            Expr::Pow(base, _) | Expr::PowVar(base, _) => vec![base.as_mut()],
+        }
+    }
+}
+
+impl Add for Expr {
+    type Output = Expr;
+
+    fn add(self, rhs: Expr) -> Expr {
+        Expr::Add(vec![self, rhs])
+    }
+}
+
+impl Sub for Expr {
+    type Output = Expr;
+
+    fn sub(self, rhs: Expr) -> Expr {
+        Expr::Add(vec![self, Expr::Mul(vec![Expr::constant(-1), rhs])])
+    }
+}
+
+impl Mul for Expr {
+    type Output = Expr;
+
+    fn mul(self, rhs: Expr) -> Expr {
+        Expr::Mul(vec![self, rhs])
+    }
+}
+
+fn flatten(e: &Expr) -> Option<Expr> {
+    let nested = |es: &[Expr], is_same: fn(&Expr) -> bool| es.iter().any(is_same);
+
+    match e {
+        Expr::Add(es) if nested(es, |e| matches!(e, Expr::Add(_))) => Some(Expr::Add(
+            es.iter()
+                .flat_map(|e| match e {
+                    Expr::Add(inner) => inner.clone(),
+                    e => vec![e.clone()],
+                })
+                .collect(),
+        )),
+        Expr::Mul(es) if nested(es, |e| matches!(e, Expr::Mul(_))) => Some(Expr::Mul(
+            es.iter()
+                .flat_map(|e| match e {
+                    Expr::Mul(inner) => inner.clone(),
+                    e => vec![e.clone()],
+                })
+                .collect(),
+        )),
+        _ => None,
+    }
+}
+
+// one constant first in a sum or product, none if it is the identity
+fn fold_constants(e: &Expr) -> Option<Expr> {
+    let (es, identity, op): (_, _, fn(Rat, Rat) -> Rat) = match e {
+        Expr::Add(es) => (es, Rat::from(0), |a, b| a + b),
+        Expr::Mul(es) => (es, Rat::from(1), |a, b| a * b),
+        _ => return None,
+    };
+
+    let consts = es
+        .iter()
+        .filter_map(|e| match e {
+            Expr::Const(c) => Some(*c),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let folded = consts.iter().fold(identity, |acc, c| op(acc, *c));
+
+    if matches!(e, Expr::Mul(_)) && folded == Rat::from(0) {
+        return Some(Expr::Const(folded));
+    }
+
+    let in_place = match es.first() {
+        Some(Expr::Const(c)) => consts.len() == 1 && *c != identity,
+        _ => consts.is_empty(),
+    };
+    if in_place {
+        return None;
+    }
+
+    let mut rest = es
+        .iter()
+        .filter(|e| !matches!(e, Expr::Const(_)))
+        .cloned()
+        .collect::<Vec<_>>();
+    if folded != identity {
+        rest.insert(0, Expr::Const(folded));
+    }
+
+    Some(match e {
+        Expr::Add(_) => Expr::Add(rest),
+        _ => Expr::Mul(rest),
+    })
+}
+
+// x * y * x^2 to x^3 * y
+fn combine_factors(e: &Expr) -> Option<Expr> {
+    let Expr::Mul(es) = e else {
+        return None;
+    };
+
+    let split = |e: &Expr| match e {
+        Expr::Pow(base, exp) => ((**base).clone(), *exp),
+        e => (e.clone(), 1),
+    };
+
+    let mut powers: Vec<(Expr, u64)> = vec![];
+    for f in es {
+        let (base, exp) = split(f);
+        match powers.iter_mut().find(|(b, _)| *b == base) {
+            Some((_, acc)) => *acc += exp,
+            None => powers.push((base, exp)),
+        }
+    }
+
+    if powers.len() == es.len() {
+        return None;
+    }
+
+    Some(Expr::Mul(
+        powers
+            .into_iter()
+            .map(|(base, exp)| base.pow(exp))
+            .collect(),
+    ))
+}
+
+fn power_rules(e: &Expr) -> Option<Expr> {
+    let Expr::Pow(base, exp) = e else {
+        return None;
+    };
+
+    match (base.as_ref(), *exp) {
+        (_, 0) => Some(Expr::constant(1)),
+        (base, 1) => Some(base.clone()),
+        (Expr::Const(c), exp) => Some(Expr::Const((0..exp).fold(Rat::from(1), |acc, _| acc * *c))),
+        (Expr::Pow(inner, m), n) => Some((**inner).clone().pow(m * n)),
+        (Expr::Mul(fs), n) => Some(Expr::Mul(fs.iter().map(|f| f.clone().pow(n)).collect())),
+        _ => None,
+    }
+}
+
+fn singleton(e: &Expr) -> Option<Expr> {
+    match e {
+        Expr::Add(es) if es.is_empty() => Some(Expr::constant(0)),
+        Expr::Mul(es) if es.is_empty() => Some(Expr::constant(1)),
+        Expr::Add(es) | Expr::Mul(es) if es.len() == 1 => Some(es[0].clone()),
+        _ => None,
+    }
+}
+
+pub struct Rewriter {
+    rules: Vec<Rule>,
+}
+
+impl Default for Rewriter {
+    fn default() -> Self {
+        Rewriter::new()
+    }
+}
+
+impl Rewriter {
+    // the built-in rules only
+    pub fn new() -> Rewriter {
+        Rewriter { rules: vec![] }
+    }
+
+    pub fn with_rule(mut self, rule: impl Fn(&Expr) -> Option<Expr> + 'static) -> Rewriter {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    fn apply(&self, e: &Expr) -> Option<Expr> {
+        let builtin: [fn(&Expr) -> Option<Expr>; 5] = [
+            flatten,
+            fold_constants,
+            combine_factors,
+            power_rules,
+            singleton,
+        ];
+
+        self.rules
+            .iter()
+            .find_map(|rule| rule(e))
+            .or_else(|| builtin.iter().find_map(|rule| rule(e)))
+    }
+
+    pub fn rewrite(&self, e: &Expr) -> Expr {
+        let mut e = e.clone();
+
+        loop {
+            for child in e.children_mut() {
+                *child = self.rewrite(child);
+            }
+
+            match self.apply(&e) {
+                Some(next) => e = next,
+                None => return e,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Expr, Rewriter};
+
+    #[test]
+    fn rewrite() {
+        let (x, y) = (Expr::var("x"), Expr::var("y"));
+        let var_dict = ["x".to_string(), "y".to_string()];
+
+        // (x * x * 2)^8 * y^0 + 0 - 3 + 3
+        let e = (x.clone() * x.clone() * Expr::constant(2)).pow(8) * y.clone().pow(0)
+            + Expr::constant(0)
+            - Expr::constant(3)
+            + Expr::constant(3);
+
+        let rewritten = Rewriter::new().rewrite(&e);
+        assert_eq!(
+            Expr::Mul(vec![Expr::constant(256), x.clone().pow(16)]),
+            rewritten
+        );
+        assert_eq!(e.lower(&var_dict), rewritten.lower(&var_dict));
+        assert_eq!(
+            "256x^16",
+            rewritten.lower(&var_dict).unwrap().format(&var_dict)
+        );
+
+        // a user rule: y^2 = x, say from a constraint known to hold
+        let rewriter = Rewriter::new().with_rule(|e| match e {
+            Expr::Pow(base, exp) if **base == Expr::var("y") && *exp >= 2 => {
+                Some(Expr::Mul(vec![Expr::var("x"), Expr::var("y").pow(exp - 2)]))
+            }
+            _ => None,
+        });
+        let e = y.clone().pow(5) * x.clone();
+        assert_eq!(
+            "x^3y",
+            rewriter
+                .rewrite(&e)
+                .lower(&var_dict)
+                .unwrap()
+                .format(&var_dict)
+        );
+
+        assert!(Expr::var("z").lower(&var_dict).is_none());
+    }
+}
@@ -0,0 +1,1122 @@
+// factorization over Q: Zassenhaus (factor mod p, Hensel lift, recombine)
+// for univariate polys, Kronecker substitution for multivariate ones
+
+use crate::field::Zero;
+use crate::poly::mono::Mono;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+// dense integer poly, highest degree coef first; reduced mod m the coefs are
+// in 0..m and there are no leading zeros
+pub(crate) type ZPoly = Vec<i128>;
+
+fn trim(mut a: ZPoly) -> ZPoly {
+    let leading_zeros = a.iter().take_while(|c| **c == 0).count();
+    a.drain(..leading_zeros);
+    a
+}
+
+pub(crate) fn reduce(a: &[i128], m: i128) -> ZPoly {
+    trim(a.iter().map(|c| c.rem_euclid(m)).collect())
+}
+
+fn add(a: &[i128], b: &[i128], m: i128) -> ZPoly {
+    let len = a.len().max(b.len());
+    let mut out = vec![0; len];
+
+    for (i, c) in a.iter().enumerate() {
+        out[len - a.len() + i] += c;
+    }
+
+    for (i, c) in b.iter().enumerate() {
+        out[len - b.len() + i] += c;
+    }
+
+    reduce(&out, m)
+}
+
+fn sub(a: &[i128], b: &[i128], m: i128) -> ZPoly {
+    add(a, &b.iter().map(|c| m - c).collect::<Vec<_>>(), m)
+}
+
+// coefs must be reduced mod m < 2^62 so products fit
+fn mul(a: &[i128], b: &[i128], m: i128) -> ZPoly {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+
+    let mut out = vec![0; a.len() + b.len() - 1];
+
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            out[i + j] = (out[i + j] + x * y) % m;
+        }
+    }
+
+    trim(out)
+}
+
+fn inv_mod(a: i128, m: i128) -> i128 {
+    let (mut r0, mut r1) = (a.rem_euclid(m), m);
+    let (mut s0, mut s1) = (1, 0);
+
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (s0, s1) = (s1, s0 - q * s1);
+    }
+
+    s0.rem_euclid(m)
+}
+
+// lc(b) must be invertible mod m
+fn div_rem(a: &[i128], b: &[i128], m: i128) -> (ZPoly, ZPoly) {
+    if a.len() < b.len() {
+        return (vec![], a.to_vec());
+    }
+
+    let inv = inv_mod(b[0], m);
+    let mut rem = a.to_vec();
+    let mut quot = vec![];
+
+    for i in 0..rem.len() + 1 - b.len() {
+        let c = rem[i] * inv % m;
+
+        for (j, d) in b.iter().enumerate() {
+            rem[i + j] = (rem[i + j] - c * d).rem_euclid(m);
+        }
+
+        quot.push(c);
+    }
+
+    rem.drain(..quot.len());
+
+    (trim(quot), trim(rem))
+}
+
+pub(crate) fn monic(a: &[i128], m: i128) -> ZPoly {
+    match a.first() {
+        Some(lc) => mul(a, &[inv_mod(*lc, m)], m),
+        None => vec![],
+    }
+}
+
+pub(crate) fn gcd(a: &[i128], b: &[i128], p: i128) -> ZPoly {
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+
+    while !b.is_empty() {
+        let r = div_rem(&a, &b, p).1;
+        a = b;
+        b = r;
+    }
+
+    monic(&a, p)
+}
+
+// s, t with sa + tb = 1 for coprime a, b
+fn ext_gcd(a: &[i128], b: &[i128], p: i128) -> (ZPoly, ZPoly) {
+    let (mut r0, mut r1) = (a.to_vec(), b.to_vec());
+    let (mut s0, mut s1) = (vec![1], vec![]);
+    let (mut t0, mut t1) = (vec![], vec![1]);
+
+    while !r1.is_empty() {
+        let (q, r) = div_rem(&r0, &r1, p);
+        let s = sub(&s0, &mul(&q, &s1, p), p);
+        let t = sub(&t0, &mul(&q, &t1, p), p);
+
+        (r0, r1) = (r1, r);
+        (s0, s1) = (s1, s);
+        (t0, t1) = (t1, t);
+    }
+
+    let inv = [inv_mod(r0[0], p)];
+
+    (mul(&s0, &inv, p), mul(&t0, &inv, p))
+}
+
+// base^exp mod (modulus, p)
+fn pow_mod(base: &[i128], mut exp: i128, modulus: &[i128], p: i128) -> ZPoly {
+    let mut base = div_rem(base, modulus, p).1;
+    let mut acc = vec![1];
+
+    while exp > 0 {
+        if exp % 2 == 1 {
+            acc = div_rem(&mul(&acc, &base, p), modulus, p).1;
+        }
+
+        base = div_rem(&mul(&base, &base, p), modulus, p).1;
+        exp /= 2;
+    }
+
+    acc
+}
+
+pub(crate) fn derivative(a: &[i128]) -> ZPoly {
+    let deg = a.len().saturating_sub(1);
+
+    trim(
+        a.iter()
+            .take(deg)
+            .enumerate()
+            .map(|(i, c)| c * (deg - i) as i128)
+            .collect(),
+    )
+}
+
+// over Z
+fn sub_z(a: &[i128], b: &[i128]) -> ZPoly {
+    let len = a.len().max(b.len());
+    let mut out = vec![0; len];
+
+    for (i, c) in a.iter().enumerate() {
+        out[len - a.len() + i] += c;
+    }
+
+    for (i, c) in b.iter().enumerate() {
+        out[len - b.len() + i] -= c;
+    }
+
+    trim(out)
+}
+
+// xorshift; splitting only needs the choices to be spread out
+pub(crate) struct Rng(pub(crate) u64);
+
+impl Rng {
+    pub(crate) fn next(&mut self, below: i128) -> i128 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 as i128 % below
+    }
+}
+
+// squarefree monic f mod p into (product of all irreducible factors of
+// degree d, d)
+pub(crate) fn distinct_degree(f: &[i128], p: i128) -> Vec<(ZPoly, usize)> {
+    let mut factors = vec![];
+    let mut f = f.to_vec();
+    let x = vec![1, 0];
+    let mut h = x.clone();
+    let mut d = 1;
+
+    while f.len() > 2 * d {
+        h = pow_mod(&h, p, &f, p);
+        let g = gcd(&sub(&h, &x, p), &f, p);
+
+        if g.len() > 1 {
+            f = div_rem(&f, &g, p).0;
+            h = div_rem(&h, &f, p).1;
+            factors.push((g, d));
+        }
+
+        d += 1;
+    }
+
+    if f.len() > 1 {
+        let deg = f.len() - 1;
+        factors.push((f, deg));
+    }
+
+    factors
+}
+
+// Cantor-Zassenhaus split of f whose irreducible factors all have degree d
+fn equal_degree(f: &[i128], d: usize, p: i128, rng: &mut Rng) -> Vec<ZPoly> {
+    if f.len() == d + 1 {
+        return vec![f.to_vec()];
+    }
+
+    loop {
+        let a = trim((1..f.len()).map(|_| rng.next(p)).collect());
+
+        if a.len() < 2 {
+            continue;
+        }
+
+        // a^((p^d - 1)/2) = (a^(1 + p + ... + p^(d - 1)))^((p - 1)/2)
+        let mut frob = a.clone();
+        let mut norm = a;
+        for _ in 1..d {
+            frob = pow_mod(&frob, p, f, p);
+            norm = div_rem(&mul(&norm, &frob, p), f, p).1;
+        }
+
+        let b = sub(&pow_mod(&norm, (p - 1) / 2, f, p), &[1], p);
+        let g = gcd(&b, f, p);
+
+        if g.len() > 1 && g.len() < f.len() {
+            let h = div_rem(f, &g, p).0;
+            let mut factors = equal_degree(&g, d, p, rng);
+            factors.extend(equal_degree(&h, d, p, rng));
+            return factors;
+        }
+    }
+}
+
+// monic irreducible factors of squarefree monic f mod odd prime p
+fn factor_mod_p(f: &[i128], p: i128) -> Vec<ZPoly> {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+    distinct_degree(f, p)
+        .into_iter()
+        .flat_map(|(g, d)| equal_degree(&g, d, p, &mut rng))
+        .collect()
+}
+
+// lift monic factors mod p of monic target mod p^k to factors mod p^k
+fn hensel_lift(target: &[i128], factors: &[ZPoly], p: i128, k: u32) -> Vec<ZPoly> {
+    if factors.len() == 1 {
+        return vec![target.to_vec()];
+    }
+
+    let mut g = factors[0].clone();
+    let mut h = factors[1..].iter().fold(vec![1], |acc, u| mul(&acc, u, p));
+    let (s, t) = ext_gcd(&g, &h, p);
+
+    let mut q = p;
+    for _ in 1..k {
+        let next_q = q * p;
+
+        // target - gh = q e (mod pq); find G, H with gH + hG = e (mod p)
+        let e: ZPoly = reduce(
+            &sub(&reduce(target, next_q), &mul(&g, &h, next_q), next_q)
+                .iter()
+                .map(|c| c / q)
+                .collect::<Vec<_>>(),
+            p,
+        );
+
+        // te = (quot)g + G, H = se + (quot)h
+        let (quot, big_g) = div_rem(&mul(&t, &e, p), &g, p);
+        let big_h = add(&mul(&s, &e, p), &mul(&quot, &h, p), p);
+
+        g = add(&g, &mul(&big_g, &[q], next_q), next_q);
+        h = add(&h, &mul(&big_h, &[q], next_q), next_q);
+        q = next_q;
+    }
+
+    let mut lifted = vec![g];
+    lifted.extend(hensel_lift(&h, &factors[1..], p, k));
+    lifted
+}
+
+pub(crate) fn content(a: &[i128]) -> i128 {
+    a.iter().fold(0, |acc, c| {
+        let (mut x, mut y) = (acc, c.abs());
+        while y != 0 {
+            (x, y) = (y, x % y);
+        }
+        x
+    })
+}
+
+// coefs in (-m/2, m/2]
+fn symmetric(a: &[i128], m: i128) -> ZPoly {
+    a.iter()
+        .map(|c| if 2 * c > m { c - m } else { *c })
+        .collect()
+}
+
+// divide out the content, positive leading coef
+pub(crate) fn primitive(a: &[i128]) -> ZPoly {
+    let c = content(a) * a[0].signum();
+    a.iter().map(|x| x / c).collect()
+}
+
+// exact quotient over Z; None on overflow too, which true factors of polys
+// with i64 coefs never hit
+fn exact_div(a: &[i128], b: &[i128]) -> Option<ZPoly> {
+    if a.len() < b.len() {
+        return None;
+    }
+
+    let mut rem = a.to_vec();
+    let mut quot = vec![];
+
+    for i in 0..rem.len() + 1 - b.len() {
+        if rem[i] % b[0] != 0 {
+            return None;
+        }
+
+        let c = rem[i] / b[0];
+        for (j, d) in b.iter().enumerate() {
+            rem[i + j] = rem[i + j].checked_sub(c.checked_mul(*d)?)?;
+        }
+
+        quot.push(c);
+    }
+
+    if rem.iter().all(|c| *c == 0) {
+        Some(quot)
+    } else {
+        None
+    }
+}
+
+// Miller-Rabin with bases that are deterministic below 2^64
+pub(crate) fn is_prime(n: i128) -> bool {
+    const BASES: [i128; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if n < 2 {
+        return false;
+    }
+
+    if let Some(b) = BASES.iter().find(|b| n % *b == 0) {
+        return n == *b;
+    }
+
+    let pow = |mut base: i128, mut exp: i128| {
+        let mut acc = 1;
+        while exp > 0 {
+            if exp % 2 == 1 {
+                acc = acc * base % n;
+            }
+            base = base * base % n;
+            exp /= 2;
+        }
+        acc
+    };
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    BASES.iter().all(|b| {
+        let mut x = pow(*b, d);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+
+        for _ in 1..s {
+            x = x * x % n;
+            if x == n - 1 {
+                return true;
+            }
+        }
+
+        false
+    })
+}
+
+// all size-k index subsets of 0..n, in lexicographic order
+fn subsets(n: usize, k: usize) -> Vec<Vec<usize>> {
+    if k == 0 {
+        return vec![vec![]];
+    }
+
+    (k - 1..n)
+        .flat_map(|last| {
+            subsets(last, k - 1).into_iter().map(move |mut s| {
+                s.push(last);
+                s
+            })
+        })
+        .collect()
+}
+
+// irreducible factors over Z of primitive, squarefree f with positive leading
+// coef
+fn factor_squarefree(f: &[i128]) -> Vec<ZPoly> {
+    if f.len() <= 2 {
+        return vec![f.to_vec()];
+    }
+
+    // try a few good primes, keep the one with fewest modular factors
+    let (p, modular) = (3..)
+        .filter(|p| is_prime(*p) && f[0] % p != 0)
+        .filter_map(|p| {
+            let f_p = monic(&reduce(f, p), p);
+            if gcd(&f_p, &reduce(&derivative(&f_p), p), p).len() == 1 {
+                Some((p, factor_mod_p(&f_p, p)))
+            } else {
+                None
+            }
+        })
+        .take(5)
+        .min_by_key(|(_, factors)| factors.len())
+        .unwrap();
+
+    if modular.len() == 1 {
+        return vec![f.to_vec()];
+    }
+
+    // Mignotte: coefs of lc(f) times any monic factor are below half this;
+    // capped so products mod m fit, which only matters for factors with
+    // coefs far outside i64
+    let norm = f.iter().map(|c| (*c as f64).powi(2)).sum::<f64>().sqrt();
+    let bound = 2. * (f[0].abs() as f64) * 2f64.powi(f.len() as i32 - 1) * norm;
+
+    let mut k = 1;
+    let mut m = p;
+    while (m as f64) <= bound && m < (1 << 61) / p {
+        m *= p;
+        k += 1;
+    }
+
+    let mut lifted = hensel_lift(&monic(&reduce(f, m), m), &modular, p, k);
+
+    // recombine: smallest subsets of lifted factors that give true factors
+    let mut factors = vec![];
+    let mut rest = f.to_vec();
+    let mut size = 1;
+
+    while 2 * size <= lifted.len() {
+        let found = subsets(lifted.len(), size).into_iter().find_map(|s| {
+            let prod = s.iter().fold(vec![rest[0].rem_euclid(m)], |acc, i| {
+                mul(&acc, &lifted[*i], m)
+            });
+            let candidate = primitive(&symmetric(&prod, m));
+
+            exact_div(&rest, &candidate).map(|quot| (s, candidate, quot))
+        });
+
+        match found {
+            Some((s, candidate, quot)) => {
+                factors.push(candidate);
+                rest = quot;
+                for i in s.into_iter().rev() {
+                    lifted.remove(i);
+                }
+            }
+            None => size += 1,
+        }
+    }
+
+    factors.push(primitive(&rest));
+    factors
+}
+
+// primitive gcd over Z from the gcd mod one large prime, checked by exact
+// division; an unlucky prime (or a gcd with coefs beyond 2^60) fails the
+// check, so move on to the next one, and after a few fall back to Euclid
+fn primitive_gcd(a: &[i128], b: &[i128]) -> Option<ZPoly> {
+    if a.is_empty() || b.is_empty() {
+        return Some(primitive(if a.is_empty() { b } else { a }));
+    }
+
+    let scale = content(&[a[0], b[0]]);
+
+    (0..)
+        .map(|i| (1 << 61) - 1 - 2 * i)
+        .filter(|p| is_prime(*p) && a[0] % p != 0 && b[0] % p != 0)
+        .take(8)
+        .find_map(|p| {
+            let g = gcd(&reduce(a, p), &reduce(b, p), p);
+            let candidate = primitive(&symmetric(&mul(&g, &[scale % p], p), p));
+
+            exact_div(a, &candidate)?;
+            exact_div(b, &candidate)?;
+            Some(candidate)
+        })
+        .or_else(|| euclid_gcd(a, b))
+}
+
+// primitive remainder sequence; None on overflow
+fn euclid_gcd(a: &[i128], b: &[i128]) -> Option<ZPoly> {
+    let (mut a, mut b) = (primitive(a), primitive(b));
+    if a.len() < b.len() {
+        (a, b) = (b, a);
+    }
+
+    while !b.is_empty() {
+        // pseudo-remainder, kept primitive at each step
+        while a.len() >= b.len() {
+            let c = a[0];
+            for (i, x) in a.iter_mut().enumerate() {
+                let y = b.get(i).copied().unwrap_or(0);
+                *x = x.checked_mul(b[0])?.checked_sub(c.checked_mul(y)?)?;
+            }
+
+            a = trim(a);
+            if a.is_empty() {
+                break;
+            }
+            a = primitive(&a);
+        }
+
+        (a, b) = (b, a);
+    }
+
+    Some(primitive(&a))
+}
+
+// Yun's algorithm: (squarefree, pairwise coprime part, multiplicity) for
+// primitive f; None if a gcd is out of reach
+fn squarefree_decomposition(f: &[i128]) -> Option<Vec<(ZPoly, usize)>> {
+    let mut parts = vec![];
+    let df = derivative(f);
+
+    let c = primitive_gcd(f, &df)?;
+    let mut w = exact_div(f, &c)?;
+    let mut z = sub_z(&exact_div(&df, &c)?, &derivative(&w));
+    let mut i = 1;
+
+    while w.len() > 1 {
+        let g = primitive_gcd(&w, &z)?;
+        w = exact_div(&w, &g)?;
+        z = sub_z(&exact_div(&z, &g).unwrap_or_default(), &derivative(&w));
+
+        if g.len() > 1 {
+            parts.push((g, i));
+        }
+
+        i += 1;
+    }
+
+    Some(parts)
+}
+
+// the constant and the irreducible factors with multiplicities
+pub type Factorization = (Rat, Vec<(Poly<Rat>, usize)>);
+
+impl Poly<Rat> {
+    // irreducible factors over Q with multiplicities, each normalized with
+    // `norm`, and the constant left over
+    pub fn factor(&self) -> Factorization {
+        if self.is_constant() {
+            return (self.lt_mono().val, vec![]);
+        }
+
+        let mut vars = self
+            .terms
+            .iter()
+            .flat_map(|term| term.vars.iter().map(|(var, _)| *var))
+            .collect::<Vec<_>>();
+        vars.sort();
+        vars.dedup();
+
+        // Kronecker substitution: x_i -> x^w_i with w_(i + 1) = (deg_i + 1) w_i;
+        // injective on polys with the degree bounds of self and its factors
+        let mut weights = vec![1];
+        for var in &vars {
+            weights.push(weights[weights.len() - 1] * (self.deg(*var) + 1));
+        }
+
+        let image_deg = self
+            .terms
+            .iter()
+            .map(|term| {
+                term.vars
+                    .iter()
+                    .map(|(var, pow)| weights[vars.binary_search(var).unwrap()] * *pow as usize)
+                    .sum::<usize>()
+            })
+            .collect::<Vec<_>>();
+
+        let top = *image_deg.iter().max().unwrap();
+        let mut image = vec![0; top + 1];
+        for (term, deg) in self.norm().terms.iter().zip(&image_deg) {
+            image[top - deg] = term.val.num as i128;
+        }
+
+        let preimage = |a: &[i128]| {
+            a.iter().enumerate().filter(|(_, c)| **c != 0).try_fold(
+                Poly::constant(Rat::zero()),
+                |acc, (i, c)| {
+                    let mut deg = a.len() - 1 - i;
+                    let mut term_vars = vec![];
+
+                    for (j, var) in vars.iter().enumerate().rev() {
+                        let pow = deg / weights[j];
+                        deg %= weights[j];
+                        if pow > 0 {
+                            term_vars.push((*var, pow as u64));
+                        }
+                    }
+                    term_vars.reverse();
+
+                    Some(
+                        acc + Poly {
+                            terms: vec![Mono {
+                                val: Rat::from(i64::try_from(*c).ok()?),
+                                vars: term_vars,
+                            }],
+                        },
+                    )
+                },
+            )
+        };
+
+        // with no image factors, self is left unsplit below
+        let mut image_factors = vec![];
+        for (part, mult) in squarefree_decomposition(&image).unwrap_or_default() {
+            for factor in factor_squarefree(&part) {
+                image_factors.extend(std::iter::repeat_n(factor, mult));
+            }
+        }
+
+        // recombine: the image of a true factor is a product of image factors
+        let mut factors: Vec<(Poly<Rat>, usize)> = vec![];
+        let mut rest = self.clone();
+        let mut size = 1;
+
+        while size <= image_factors.len() {
+            let found = subsets(image_factors.len(), size)
+                .into_iter()
+                .find_map(|s| {
+                    // the image of a true factor has the same (small) coefs, so
+                    // overflow rules a subset out
+                    let prod = s.iter().try_fold(vec![1i128], |acc, i| {
+                        let mut out = vec![0i128; acc.len() + image_factors[*i].len() - 1];
+                        for (j, x) in acc.iter().enumerate() {
+                            for (k, y) in image_factors[*i].iter().enumerate() {
+                                out[j + k] = out[j + k].checked_add(x.checked_mul(*y)?)?;
+                            }
+                        }
+                        Some(out)
+                    })?;
+                    let candidate = preimage(&prod)?.norm();
+
+                    rest.try_divide(&candidate).map(|quot| (s, candidate, quot))
+                });
+
+            match found {
+                Some((s, candidate, quot)) => {
+                    match factors.iter_mut().find(|(f, _)| *f == candidate) {
+                        Some((_, mult)) => *mult += 1,
+                        None => factors.push((candidate, 1)),
+                    }
+
+                    rest = quot;
+                    for i in s.into_iter().rev() {
+                        image_factors.remove(i);
+                    }
+                }
+                None => size += 1,
+            }
+        }
+
+        // a part no subset accounts for stays a factor as it is
+        if !rest.is_constant() {
+            let part = rest.norm();
+            let c = rest.lt_mono().val / part.lt_mono().val;
+            match factors.iter_mut().find(|(f, _)| *f == part) {
+                Some((_, mult)) => *mult += 1,
+                None => factors.push((part, 1)),
+            }
+            rest = Poly::constant(c);
+        }
+
+        (rest.lt_mono().val, factors)
+    }
+
+    // factor, for polys in var alone; None if another var occurs
+    pub fn factor_univariate(&self, var: usize) -> Option<Factorization> {
+        if self
+            .terms
+            .iter()
+            .any(|term| term.vars.iter().any(|(v, _)| *v != var))
+        {
+            return None;
+        }
+
+        Some(self.factor())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Irreducibility {
+    Absolute,
+    NotAbsolute,
+    Unknown,
+}
+
+// rank of integer vectors, by elimination over Q
+fn rank(mut rows: Vec<Vec<Rat>>) -> usize {
+    let mut rank = 0;
+    let cols = rows.first().map(|r| r.len()).unwrap_or(0);
+
+    for col in 0..cols {
+        if let Some(pivot) = (rank..rows.len()).find(|r| !rows[*r][col].is_zero()) {
+            rows.swap(rank, pivot);
+
+            let (top, below) = rows.split_at_mut(rank + 1);
+            let pivot_row = &top[rank];
+
+            for row in below {
+                let c = row[col] / pivot_row[col];
+                for (x, y) in row.iter_mut().zip(pivot_row).skip(col) {
+                    *x = *x - c * *y;
+                }
+            }
+
+            rank += 1;
+        }
+    }
+
+    rank
+}
+
+impl Poly<Rat> {
+    fn exponents(&self, vars: &[usize]) -> Vec<Vec<i64>> {
+        self.terms
+            .iter()
+            .map(|term| vars.iter().map(|var| term.deg(*var) as i64).collect())
+            .collect()
+    }
+
+    // Gao: if the Newton polytope is conv(v, S) with S in a hyperplane missing
+    // v and the coords of all s - v coprime, it is integrally indecomposable
+    fn newton_pyramid(&self, vars: &[usize]) -> bool {
+        let support = self.exponents(vars);
+
+        (0..support.len()).any(|apex| {
+            let v = &support[apex];
+            let base = support
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != apex)
+                .map(|(_, s)| s)
+                .collect::<Vec<_>>();
+
+            let diffs = |from: &[i64]| {
+                base.iter()
+                    .skip(1)
+                    .map(|s| s.iter().zip(from).map(|(a, b)| Rat::from(a - b)).collect())
+                    .collect::<Vec<Vec<Rat>>>()
+            };
+
+            let in_hull = rank(diffs(base[0])) == {
+                let mut rows = diffs(base[0]);
+                rows.push(
+                    v.iter()
+                        .zip(base[0])
+                        .map(|(a, b)| Rat::from(a - b))
+                        .collect(),
+                );
+                rank(rows)
+            };
+
+            let coord_gcd = base
+                .iter()
+                .flat_map(|s| s.iter().zip(v).map(|(a, b)| (a - b) as i128))
+                .collect::<Vec<_>>();
+
+            !in_hull && content(&coord_gcd) == 1
+        })
+    }
+
+    // look for a nonsingular point with rational coords: setting all but one
+    // var to small integers and taking the rational roots in the last
+    fn smooth_rational_point(&self, vars: &[usize]) -> bool {
+        let (main, rest) = vars.split_first().unwrap();
+        let gradient = vars
+            .iter()
+            .map(|var| self.derivative(*var))
+            .collect::<Vec<_>>();
+
+        let mut assignments: Vec<Vec<Rat>> = vec![vec![]];
+        for _ in rest {
+            assignments = assignments
+                .into_iter()
+                .flat_map(|a| {
+                    [0, 1, -1, 2, -2].into_iter().map(move |x| {
+                        let mut a = a.clone();
+                        a.push(Rat::from(x));
+                        a
+                    })
+                })
+                .take(125)
+                .collect();
+        }
+
+        let eval_at = |p: &Poly<Rat>, point: &[(usize, Rat)]| {
+            point
+                .iter()
+                .fold(p.clone(), |acc, (var, val)| acc.eval(*var, *val))
+        };
+
+        assignments.into_iter().any(|a| {
+            let partial = rest.iter().copied().zip(a).collect::<Vec<_>>();
+            let line = eval_at(self, &partial);
+
+            if line.is_zero() {
+                return false;
+            }
+
+            line.factor()
+                .1
+                .iter()
+                .filter(|(f, _)| f.deg(*main) == 1)
+                .any(|(f, _)| {
+                    let coefs = f.coefs(*main);
+                    let root = coefs[1].lt_mono().val / coefs[0].lt_mono().val * Rat::from(-1);
+
+                    let mut point = partial.clone();
+                    point.push((*main, root));
+
+                    gradient.iter().any(|d| !eval_at(d, &point).is_zero())
+                })
+        })
+    }
+
+    // for self irreducible over Q: whether it stays irreducible over C
+    pub fn absolute_irreducibility(&self) -> Irreducibility {
+        let vars = self
+            .terms
+            .iter()
+            .flat_map(|term| term.vars.iter().map(|(var, _)| *var))
+            .fold(vec![], |mut vars, var| {
+                if !vars.contains(&var) {
+                    vars.push(var);
+                }
+                vars
+            });
+
+        let total_degs = self
+            .terms
+            .iter()
+            .map(|term| term.vars.iter().map(|(_, pow)| pow).sum::<u64>())
+            .collect::<Vec<_>>();
+
+        if vars.is_empty() {
+            Irreducibility::Unknown
+        } else if vars.len() == 1 {
+            // splits into linear factors
+            if total_degs.iter().max() == Some(&1) {
+                Irreducibility::Absolute
+            } else {
+                Irreducibility::NotAbsolute
+            }
+        } else if vars.len() == 2
+            && total_degs[0] >= 2
+            && total_degs.iter().all(|d| *d == total_degs[0])
+        {
+            // binary forms of degree 2 and up split into linear forms
+            Irreducibility::NotAbsolute
+        } else if vars.iter().any(|var| self.deg(*var) == 1) {
+            // ax + b with a, b coprime over Q, so over C too
+            Irreducibility::Absolute
+        } else if self.newton_pyramid(&vars) {
+            Irreducibility::Absolute
+        } else if self.smooth_rational_point(&vars) {
+            // the conjugate components would all pass through a rational
+            // point, making it singular
+            Irreducibility::Absolute
+        } else {
+            Irreducibility::Unknown
+        }
+    }
+
+    // `factor`, with the absolute irreducibility of each factor
+    pub fn factor_absolute(&self) -> (Rat, Vec<(Poly<Rat>, usize, Irreducibility)>) {
+        let (c, factors) = self.factor();
+
+        (
+            c,
+            factors
+                .into_iter()
+                .map(|(f, mult)| {
+                    let irreducibility = f.absolute_irreducibility();
+                    (f, mult, irreducibility)
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Irreducibility;
+    use crate::poly::Poly;
+    use crate::rational::Rat;
+    use crate::system;
+    use rand::prelude::*;
+
+    fn factor_strings(p: &Poly<Rat>, var_dict: &[String]) -> (Rat, Vec<String>) {
+        let (c, factors) = p.factor();
+
+        let mut product = Poly::constant(c);
+        for (f, mult) in &factors {
+            for _ in 0..*mult {
+                product = product * f.clone();
+            }
+        }
+        assert_eq!(*p, product);
+
+        let mut strings = factors
+            .iter()
+            .map(|(f, mult)| format!("({})^{mult}", f.format(var_dict)))
+            .collect::<Vec<_>>();
+        strings.sort();
+
+        (c, strings)
+    }
+
+    #[test]
+    fn univariate() {
+        let sys = system! {
+            2*x^4 - 2,
+            x^4 + 1,
+            x^6 - 3*x^5 + 3*x^4 - x^3,
+            x^4 - 10*x^2 + 1
+        };
+
+        assert_eq!(
+            (
+                Rat::from(2),
+                vec![
+                    "(x + 1)^1".to_string(),
+                    "(x - 1)^1".to_string(),
+                    "(x^2 + 1)^1".to_string()
+                ]
+            ),
+            factor_strings(&sys.members[0], &sys.var_dict)
+        );
+
+        // irreducible over Q but splits mod every prime
+        assert_eq!(
+            vec!["(x^4 + 1)^1"],
+            factor_strings(&sys.members[1], &sys.var_dict).1
+        );
+        assert_eq!(
+            vec!["(x - 1)^3", "(x)^3"],
+            factor_strings(&sys.members[2], &sys.var_dict).1
+        );
+        assert_eq!(
+            vec!["(x^4 - 10x^2 + 1)^1"],
+            factor_strings(&sys.members[3], &sys.var_dict).1
+        );
+
+        assert_eq!(
+            sys.members[2].factor(),
+            sys.members[2].factor_univariate(0).unwrap()
+        );
+        assert!(sys.members[2].factor_univariate(1).is_none());
+    }
+
+    #[test]
+    fn multivariate() {
+        let sys = system! {
+            x^2 - y^2,
+            x*y + 1,
+            x^2 + y^2 + z,
+            x - y + 2*z
+        };
+
+        let [a, b, c, d] = [0, 1, 2, 3].map(|i| sys.get(i));
+
+        assert_eq!(
+            vec!["(x + y)^1", "(x - y)^1", "(xy + 1)^1"],
+            factor_strings(&(a.clone() * b.clone()), &sys.var_dict).1
+        );
+
+        assert_eq!(
+            vec![
+                "(x + y)^2",
+                "(x - y + 2z)^1",
+                "(x - y)^1",
+                "(x^2 + y^2 + z)^1"
+            ],
+            factor_strings(
+                &(a.clone() * c.clone() * d.clone() * (sys.var("x", 1) + sys.var("y", 1))),
+                &sys.var_dict
+            )
+            .1
+        );
+
+        assert_eq!(
+            (Rat::from(-3), vec!["(x^2 + y^2 + z)^1".to_string()]),
+            factor_strings(&(c * sys.constant(-3)), &sys.var_dict)
+        );
+    }
+
+    #[test]
+    fn large_coefs() {
+        let x = Poly::var(0, 1);
+        let c = |val: i64| Poly::constant(Rat::from(val));
+        let var_dict = ["x".to_string()];
+
+        // (2^31 x + 1)^2: the scaled gcd with the derivative is out of reach
+        // mod a 61 bit prime, so Euclid finds it
+        let p = c(1 << 62) * x.clone() * x.clone() + c(1 << 32) * x.clone() + c(1);
+        assert_eq!(
+            (Rat::from(1), vec!["(2147483648x + 1)^2".to_string()]),
+            factor_strings(&p, &var_dict)
+        );
+
+        // where Euclid overflows as well, the poly is left unsplit, but the
+        // constant and factors still multiply back to it
+        let g = c(1 << 30) * x.clone() + c(1);
+        let p = c(-1) * g.clone() * g * (x.clone() * x + c(1));
+        assert_eq!(Rat::from(-1), factor_strings(&p, &var_dict).0);
+    }
+
+    #[test]
+    fn factor_fuzz() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        let var_dict = ["x".to_string(), "y".to_string()];
+
+        fn random_poly(rng: &mut SmallRng) -> Poly<Rat> {
+            let mut p = Poly::constant(Rat::from(rng.gen_range(1..4)));
+
+            for _ in 0..rng.gen_range(1..4) {
+                p = p + Poly::constant(Rat::from(rng.gen_range(-3..4)))
+                    * Poly::var(0, rng.gen_range(0..3))
+                    * Poly::var(1, rng.gen_range(0..2));
+            }
+
+            p
+        }
+
+        for _ in 0..100 {
+            let factors = (0..rng.gen_range(1..4))
+                .map(|_| random_poly(&mut rng))
+                .filter(|p| !p.is_constant())
+                .collect::<Vec<_>>();
+
+            let p = factors
+                .iter()
+                .fold(Poly::constant(Rat::from(1)), |acc, f| acc * f.clone());
+
+            // recombines, and agrees with factoring the pieces
+            factor_strings(&p, &var_dict);
+
+            let count = |p: &Poly<Rat>| p.factor().1.iter().map(|(_, m)| m).sum::<usize>();
+            assert_eq!(factors.iter().map(count).sum::<usize>(), count(&p));
+        }
+    }
+
+    #[test]
+    fn absolute() {
+        let sys = system! {
+            x^2 - 2,
+            x^2 + y^2,
+            x*y^2 - 1,
+            x^2 + y^3 + 1,
+            x^2 + y^2 - 1,
+            x^2 + y^2 + 1,
+            x^3 - 2*y^3 + z^2,
+            x + y,
+            2*x - 3*y
+        };
+
+        assert_eq!(
+            vec![
+                Irreducibility::NotAbsolute,
+                Irreducibility::NotAbsolute,
+                Irreducibility::Absolute,
+                Irreducibility::Absolute,
+                Irreducibility::Absolute,
+                // absolutely irreducible, but no criterion applies
+                Irreducibility::Unknown,
+                Irreducibility::Absolute,
+                // linear forms are already linear
+                Irreducibility::Absolute,
+                Irreducibility::Absolute,
+            ],
+            sys.members
+                .iter()
+                .map(|p| p.absolute_irreducibility())
+                .collect::<Vec<_>>()
+        );
+
+        let (_, factors) = (sys.get(0) * sys.get(3)).factor_absolute();
+        let mut found = factors.iter().map(|(_, _, a)| *a).collect::<Vec<_>>();
+        found.sort_by_key(|a| *a as u8);
+        assert_eq!(
+            vec![Irreducibility::Absolute, Irreducibility::NotAbsolute],
+            found
+        );
+    }
+}
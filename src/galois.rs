@@ -0,0 +1,443 @@
+// Galois groups of irreducible polys over Q of degree <= 7. the discriminant
+// (the resolvent for sqrt(disc)) fixes the parity and, in degree 4, the
+// resolvent cubic decides the group exactly; otherwise the group is the
+// smallest transitive group containing every Frobenius cycle type seen mod
+// the first primes (Dedekind), which are exactly the cycle types of the
+// group once enough primes are used (Chebotarev)
+
+use std::collections::HashSet;
+
+use crate::algebraic::{self, Algebraic};
+use crate::factor::{self, ZPoly};
+use crate::rational::Rat;
+use crate::univariate::UPoly;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GaloisGroup {
+    pub name: &'static str,
+    pub degree: usize,
+    pub order: usize,
+    pub solvable: bool,
+}
+
+type Perm = Vec<usize>;
+
+// transitive groups up to conjugacy: name, solvable, generators as images of
+// 0..degree
+const TRANSITIVE_GROUPS: &[(&str, bool, &[&[usize]])] = &[
+    ("C1", true, &[&[0]]),
+    ("C2", true, &[&[1, 0]]),
+    ("A3", true, &[&[1, 2, 0]]),
+    ("S3", true, &[&[1, 2, 0], &[1, 0, 2]]),
+    ("C4", true, &[&[1, 2, 3, 0]]),
+    ("V4", true, &[&[1, 0, 3, 2], &[2, 3, 0, 1]]),
+    ("D4", true, &[&[1, 2, 3, 0], &[2, 1, 0, 3]]),
+    ("A4", true, &[&[1, 2, 0, 3], &[1, 0, 3, 2]]),
+    ("S4", true, &[&[1, 2, 3, 0], &[1, 0, 2, 3]]),
+    ("C5", true, &[&[1, 2, 3, 4, 0]]),
+    ("D5", true, &[&[1, 2, 3, 4, 0], &[0, 4, 3, 2, 1]]),
+    ("F20", true, &[&[1, 2, 3, 4, 0], &[0, 2, 4, 1, 3]]),
+    ("A5", false, &[&[1, 2, 3, 4, 0], &[1, 2, 0, 3, 4]]),
+    ("S5", false, &[&[1, 2, 3, 4, 0], &[1, 0, 2, 3, 4]]),
+    ("C6", true, &[&[1, 2, 3, 4, 5, 0]]),
+    (
+        "S3 (regular)",
+        true,
+        &[&[1, 0, 3, 2, 5, 4], &[2, 4, 0, 5, 1, 3]],
+    ),
+    ("D6", true, &[&[0, 1, 3, 2, 5, 4], &[2, 4, 0, 5, 1, 3]]),
+    ("A4 (6)", true, &[&[0, 1, 3, 2, 5, 4], &[2, 3, 4, 5, 0, 1]]),
+    ("F18", true, &[&[0, 1, 2, 4, 5, 3], &[3, 4, 5, 0, 1, 2]]),
+    ("2A4", true, &[&[0, 1, 2, 3, 5, 4], &[1, 4, 3, 5, 0, 2]]),
+    (
+        "S4 (6, odd)",
+        true,
+        &[&[0, 1, 3, 4, 5, 2], &[2, 4, 0, 5, 1, 3]],
+    ),
+    (
+        "S4 (6, even)",
+        true,
+        &[&[0, 1, 3, 2, 5, 4], &[2, 4, 1, 5, 0, 3]],
+    ),
+    ("S3 x S3", true, &[&[0, 1, 3, 2, 5, 4], &[1, 2, 4, 5, 3, 0]]),
+    ("F36", true, &[&[0, 1, 2, 4, 5, 3], &[3, 4, 5, 0, 2, 1]]),
+    ("2S4", true, &[&[0, 1, 3, 2, 5, 4], &[2, 4, 1, 3, 0, 5]]),
+    (
+        "PSL(2,5)",
+        false,
+        &[&[0, 1, 3, 2, 5, 4], &[1, 2, 0, 4, 5, 3]],
+    ),
+    ("F36:2", true, &[&[0, 1, 2, 3, 5, 4], &[1, 2, 4, 5, 0, 3]]),
+    (
+        "PGL(2,5)",
+        false,
+        &[&[0, 1, 3, 2, 5, 4], &[1, 2, 3, 4, 5, 0]],
+    ),
+    ("A6", false, &[&[0, 1, 2, 4, 5, 3], &[1, 2, 3, 0, 5, 4]]),
+    ("S6", false, &[&[0, 1, 2, 3, 5, 4], &[1, 2, 3, 4, 0, 5]]),
+    ("C7", true, &[&[1, 2, 3, 4, 5, 6, 0]]),
+    (
+        "D7",
+        true,
+        &[&[1, 2, 3, 4, 5, 6, 0], &[0, 6, 5, 4, 3, 2, 1]],
+    ),
+    (
+        "F21",
+        true,
+        &[&[1, 2, 3, 4, 5, 6, 0], &[0, 2, 4, 6, 1, 3, 5]],
+    ),
+    (
+        "F42",
+        true,
+        &[&[1, 2, 3, 4, 5, 6, 0], &[0, 3, 6, 2, 5, 1, 4]],
+    ),
+    (
+        "PSL(3,2)",
+        false,
+        &[&[1, 2, 3, 4, 5, 6, 0], &[0, 1, 4, 3, 2, 6, 5]],
+    ),
+    (
+        "A7",
+        false,
+        &[&[1, 2, 3, 4, 5, 6, 0], &[1, 2, 0, 3, 4, 5, 6]],
+    ),
+    (
+        "S7",
+        false,
+        &[&[1, 2, 3, 4, 5, 6, 0], &[1, 0, 2, 3, 4, 5, 6]],
+    ),
+];
+
+const PRIMES_TRIED: usize = 500;
+
+fn closure(gens: &[&[usize]]) -> HashSet<Perm> {
+    let id: Perm = (0..gens[0].len()).collect();
+    let mut group = HashSet::from([id.clone()]);
+    let mut frontier = vec![id];
+
+    while let Some(g) = frontier.pop() {
+        for h in gens {
+            let hg: Perm = g.iter().map(|i| h[*i]).collect();
+            if group.insert(hg.clone()) {
+                frontier.push(hg);
+            }
+        }
+    }
+
+    group
+}
+
+// cycle lengths, longest first
+fn cycle_type(perm: &[usize]) -> Vec<usize> {
+    let mut seen = vec![false; perm.len()];
+    let mut lengths = vec![];
+
+    for start in 0..perm.len() {
+        let mut len = 0;
+        let mut i = start;
+        while !seen[i] {
+            seen[i] = true;
+            i = perm[i];
+            len += 1;
+        }
+
+        if len > 0 {
+            lengths.push(len);
+        }
+    }
+
+    lengths.sort_by(|a, b| b.cmp(a));
+    lengths
+}
+
+fn is_even(cycle_type: &[usize]) -> bool {
+    cycle_type
+        .iter()
+        .map(|l| l - 1)
+        .sum::<usize>()
+        .is_multiple_of(2)
+}
+
+struct Candidate {
+    group: GaloisGroup,
+    cycle_types: HashSet<Vec<usize>>,
+}
+
+fn candidates(degree: usize) -> Vec<Candidate> {
+    TRANSITIVE_GROUPS
+        .iter()
+        .filter(|(_, _, gens)| gens[0].len() == degree)
+        .map(|(name, solvable, gens)| {
+            let elements = closure(gens);
+
+            Candidate {
+                group: GaloisGroup {
+                    name,
+                    degree,
+                    order: elements.len(),
+                    solvable: *solvable,
+                },
+                cycle_types: elements.iter().map(|g| cycle_type(g)).collect(),
+            }
+        })
+        .collect()
+}
+
+// disc(f) = (-1)^(n(n - 1)/2) Res(f, f')/lc(f), with Bareiss elimination on
+// the Sylvester matrix; None on overflow
+fn discriminant(f: &[i128]) -> Option<i128> {
+    let df = factor::derivative(f);
+    let n = f.len() - 1;
+    let size = 2 * n - 1;
+
+    let mut mat = vec![vec![0i128; size]; size];
+    for i in 0..n - 1 {
+        mat[i][i..i + f.len()].copy_from_slice(f);
+    }
+    for i in 0..n {
+        mat[n - 1 + i][i..i + df.len()].copy_from_slice(&df);
+    }
+
+    let mut negate = false;
+    let mut prev_pivot = 1i128;
+
+    for k in 0..size - 1 {
+        if mat[k][k] == 0 {
+            match (k + 1..size).find(|r| mat[*r][k] != 0) {
+                Some(r) => {
+                    mat.swap(k, r);
+                    negate = !negate;
+                }
+                None => return Some(0),
+            }
+        }
+
+        for i in k + 1..size {
+            for j in k + 1..size {
+                mat[i][j] = mat[k][k]
+                    .checked_mul(mat[i][j])?
+                    .checked_sub(mat[i][k].checked_mul(mat[k][j])?)?
+                    / prev_pivot;
+            }
+        }
+
+        prev_pivot = mat[k][k];
+    }
+
+    let res = if negate {
+        -mat[size - 1][size - 1]
+    } else {
+        mat[size - 1][size - 1]
+    };
+
+    let sign = if (n * (n - 1) / 2).is_multiple_of(2) {
+        1
+    } else {
+        -1
+    };
+
+    Some(sign * res / f[0])
+}
+
+fn is_square(r: Rat) -> bool {
+    let is_square = |x: i64| x >= 0 && x.isqrt() * x.isqrt() == x;
+    is_square(r.num) && is_square(r.den)
+}
+
+// Kappe-Warren: factor the resolvent cubic of monic x^4 + ax^3 + bx^2 + cx + d
+fn quartic(coefs: &[Rat], disc: Rat) -> &'static str {
+    let (a, b, c, d) = (coefs[1], coefs[2], coefs[3], coefs[4]);
+    let four = Rat::from(4);
+
+    let resolvent = UPoly(vec![
+        Rat::from(1),
+        b * -1,
+        a * c - four * d,
+        (a * a * d - four * b * d + c * c) * -1,
+    ]);
+
+    let roots = resolvent
+        .factor()
+        .1
+        .iter()
+        .filter(|(f, _)| f.deg() == 1)
+        .flat_map(|(f, mult)| std::iter::repeat_n(f.0[1] / f.0[0] * -1, *mult))
+        .collect::<Vec<_>>();
+
+    match roots.len() {
+        0 if is_square(disc) => "A4",
+        0 => "S4",
+        1 => {
+            let r = roots[0];
+            // both quadratics split over Q(sqrt(disc))
+            let splits = |delta: Rat| delta.num == 0 || is_square(delta) || is_square(delta * disc);
+
+            if splits(r * r - four * d) && splits(a * a - four * (b - r)) {
+                "C4"
+            } else {
+                "D4"
+            }
+        }
+        _ => "V4",
+    }
+}
+
+// Galois group of the splitting field of p; None unless p is irreducible over
+// Q with degree 1 to 7
+pub fn galois_group(p: &UPoly<Rat>) -> Option<GaloisGroup> {
+    let p = p.clone().trim();
+    let degree = p.deg();
+
+    if !(1..=7).contains(&degree) {
+        return None;
+    }
+
+    let (_, factors) = p.factor();
+    if factors.len() != 1 || factors[0].1 != 1 {
+        return None;
+    }
+
+    let (factor, _) = &factors[0];
+    let f: ZPoly = factor.0.iter().map(|c| c.num as i128).collect();
+
+    let candidates = candidates(degree);
+    let disc = discriminant(&f)
+        .and_then(|d| i64::try_from(d).ok())
+        .map(Rat::from);
+
+    if let (4, Some(disc)) = (degree, disc) {
+        let lc = Rat::from(f[0] as i64);
+        let monic = f
+            .iter()
+            .map(|c| Rat::from(*c as i64) / lc)
+            .collect::<Vec<_>>();
+        let name = quartic(&monic, disc);
+
+        return candidates
+            .into_iter()
+            .find(|c| c.group.name == name)
+            .map(|c| c.group);
+    }
+
+    // Frobenius cycle types from the degrees of the factors mod p
+    let mut seen = HashSet::new();
+    for prime in (3..)
+        .filter(|p| factor::is_prime(*p) && f[0] % p != 0)
+        .take(PRIMES_TRIED)
+    {
+        let f_p = factor::monic(&factor::reduce(&f, prime), prime);
+        if factor::gcd(
+            &f_p,
+            &factor::reduce(&factor::derivative(&f_p), prime),
+            prime,
+        )
+        .len()
+            > 1
+        {
+            continue;
+        }
+
+        let mut cycle_type = factor::distinct_degree(&f_p, prime)
+            .iter()
+            .flat_map(|(g, d)| std::iter::repeat_n(*d, (g.len() - 1) / d))
+            .collect::<Vec<_>>();
+        cycle_type.sort_by(|a, b| b.cmp(a));
+
+        seen.insert(cycle_type);
+    }
+
+    candidates
+        .into_iter()
+        .filter(|c| seen.is_subset(&c.cycle_types))
+        .filter(|c| match disc {
+            Some(disc) => c.cycle_types.iter().all(|t| is_even(t)) == is_square(disc),
+            None => true,
+        })
+        .min_by_key(|c| c.group.order)
+        .map(|c| c.group)
+}
+
+// real roots of an eliminant, along with the Galois group of its splitting
+// field when it has one
+pub fn get_roots_with_galois_group(
+    p: UPoly<Rat>,
+    tolerance: Rat,
+) -> (Vec<Algebraic<Rat>>, Option<GaloisGroup>) {
+    let group = galois_group(&p);
+    (algebraic::get_roots(p, tolerance), group)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{candidates, galois_group, get_roots_with_galois_group};
+    use crate::rational::Rat;
+    use crate::univariate::upoly;
+
+    #[test]
+    fn groups() {
+        // the table has the right number of transitive groups, told apart by
+        // their cycle types
+        for (degree, count) in [(1, 1), (2, 1), (3, 2), (4, 5), (5, 5), (6, 16), (7, 7)] {
+            let groups = candidates(degree);
+            assert_eq!(count, groups.len());
+
+            for (i, a) in groups.iter().enumerate() {
+                for b in groups.iter().skip(i + 1) {
+                    assert_ne!(a.cycle_types, b.cycle_types);
+                }
+            }
+        }
+
+        let orders = candidates(6)
+            .iter()
+            .map(|c| c.group.order)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![6, 6, 12, 12, 18, 24, 24, 24, 36, 36, 48, 60, 72, 120, 360, 720],
+            orders
+        );
+    }
+
+    #[test]
+    fn small_degree() {
+        let name = |coefs: &[i64]| galois_group(&upoly(coefs)).map(|g| g.name);
+
+        assert_eq!(Some("C2"), name(&[1, 0, -2]));
+        assert_eq!(None, name(&[1, 0, -1]));
+        assert_eq!(Some("A3"), name(&[1, 0, -3, 1]));
+        assert_eq!(Some("S3"), name(&[1, 0, 0, -2]));
+
+        assert_eq!(Some("V4"), name(&[1, 0, -10, 0, 1]));
+        assert_eq!(Some("C4"), name(&[1, 1, 1, 1, 1]));
+        assert_eq!(Some("D4"), name(&[1, 0, 0, 0, -2]));
+        assert_eq!(Some("A4"), name(&[1, 0, 0, 8, 12]));
+        assert_eq!(Some("S4"), name(&[1, 0, 0, 1, 1]));
+    }
+
+    #[test]
+    fn higher_degree() {
+        let group = |coefs: &[i64]| galois_group(&upoly(coefs)).unwrap();
+
+        // cyclotomic and radical extensions
+        assert_eq!("C5", group(&[1, 1, -4, -3, 3, 1]).name);
+        assert_eq!("F20", group(&[1, 0, 0, 0, 0, -2]).name);
+        assert_eq!("C6", group(&[1, -1, 1, -1, 1, -1, 1]).name);
+        assert_eq!("F42", group(&[1, 0, 0, 0, 0, 0, 0, -2]).name);
+
+        let s5 = group(&[1, 0, 0, 0, -1, -1]);
+        assert_eq!(("S5", 120, false), (s5.name, s5.order, s5.solvable));
+
+        let d5 = group(&[1, 0, 0, 0, -5, 12]);
+        assert_eq!(("D5", 10, true), (d5.name, d5.order, d5.solvable));
+    }
+
+    #[test]
+    fn with_roots() {
+        // cyclic cubic with three real roots
+        let (roots, group) =
+            get_roots_with_galois_group(upoly(&[1, 0, -3, 1]), Rat::new(1) / Rat::new(1000));
+
+        assert_eq!(3, roots.len());
+        assert_eq!(Some(("A3", true)), group.map(|g| (g.name, g.solvable)));
+    }
+}
@@ -0,0 +1,197 @@
+// the prime fields Z/P, and the Frobenius map f -> f^P which fixes their
+// elements. repeated P-th powers mod a poly are the building block of distinct
+// degree factorization and of counting points over GF(P)
+
+use std::fmt;
+use std::ops;
+
+use crate::field::{One, Zero};
+use crate::poly::Poly;
+use crate::rational::Rat;
+use crate::univariate::UPoly;
+
+// P must be a prime below 2^63. ordered by the representative in 0..P, which
+// is only for sorting and is not compatible with the arithmetic
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Gf<const P: u64>(u64);
+
+impl<const P: u64> Gf<P> {
+    pub fn new(val: u64) -> Self {
+        Gf(val % P)
+    }
+
+    // the representative in 0..P
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    pub fn pow(&self, mut exp: u64) -> Self {
+        let (mut base, mut acc) = (*self, Gf(1 % P));
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = acc * base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+
+        acc
+    }
+
+    // by Fermat; None for zero
+    pub fn inv(&self) -> Option<Self> {
+        (self.0 != 0).then(|| self.pow(P - 2))
+    }
+
+    // the image of num / den; None if P divides den
+    pub fn from_rat(r: Rat) -> Option<Self> {
+        Some(Gf::from(r.num) * Gf::from(r.den).inv()?)
+    }
+}
+
+impl<const P: u64> From<i64> for Gf<P> {
+    fn from(val: i64) -> Self {
+        Gf((val as i128).rem_euclid(P as i128) as u64)
+    }
+}
+
+impl<const P: u64> Zero for Gf<P> {
+    fn zero() -> Self {
+        Gf(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl<const P: u64> One for Gf<P> {
+    fn one() -> Self {
+        Gf(1 % P)
+    }
+}
+
+impl<const P: u64> ops::Add for Gf<P> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Gf(((self.0 as u128 + rhs.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> ops::Sub for Gf<P> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Gf(((self.0 as u128 + P as u128 - rhs.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> ops::Mul for Gf<P> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Gf((self.0 as u128 * rhs.0 as u128 % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> ops::Mul<i64> for Gf<P> {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self {
+        self * Gf::from(rhs)
+    }
+}
+
+impl<const P: u64> ops::Div for Gf<P> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        ops::Mul::mul(self, rhs.inv().expect("division by zero"))
+    }
+}
+
+impl<const P: u64> fmt::Display for Gf<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const P: u64> Poly<Gf<P>> {
+    // f^(P^k), which is f with every exponent multiplied by P^k since the
+    // coefs are fixed; None if an exponent overflows
+    pub fn frobenius(&self, k: u32) -> Option<Poly<Gf<P>>> {
+        let q = P.checked_pow(k)?;
+        let mut p = self.clone();
+
+        // scaling every exponent keeps the term order
+        for term in &mut p.terms {
+            for (_, pow) in &mut term.vars {
+                *pow = pow.checked_mul(q)?;
+            }
+        }
+
+        Some(p)
+    }
+}
+
+impl<const P: u64> UPoly<Gf<P>> {
+    // self^(P^k) mod m, as k P-th powers
+    pub fn frobenius_mod(&self, k: u32, m: &UPoly<Gf<P>>) -> UPoly<Gf<P>> {
+        (0..k).fold(self.rem(m), |a, _| a.pow_mod(P as u128, m))
+    }
+
+    // distinct roots in GF(P), the degree of gcd(self, x^P - x)
+    pub fn count_roots(&self) -> usize {
+        let x = UPoly(vec![Gf::one(), Gf::zero()]);
+        let frob = x.frobenius_mod(1, self);
+
+        (frob - x).gcd(self).deg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Gf;
+    use crate::field::{One, Zero};
+    use crate::poly::Poly;
+    use crate::univariate::UPoly;
+
+    type F5 = Gf<5>;
+
+    fn upoly(coefs: &[i64]) -> UPoly<F5> {
+        UPoly(coefs.iter().map(|c| F5::from(*c)).collect())
+    }
+
+    #[test]
+    fn field() {
+        assert_eq!(F5::from(3), F5::from(-2));
+        assert_eq!(F5::one(), F5::from(3) * F5::from(2));
+        assert_eq!(F5::from(4), F5::from(1) / F5::from(4));
+        assert_eq!(None, F5::zero().inv());
+        assert_eq!(F5::from(2), F5::from(2).pow(5));
+    }
+
+    #[test]
+    fn frobenius() {
+        // (x + 2y)^5 = x^5 + 2y^5 in characteristic 5
+        let f = Poly::var(0, 1) + Poly::var(1, 1) * Poly::constant(F5::from(2));
+        let fifth = (0..5).fold(Poly::constant(F5::one()), |acc, _| acc.mul_ref(&f));
+        assert_eq!(Some(fifth), f.frobenius(1));
+        assert_eq!(None, f.frobenius(40));
+
+        // x^2 + 2 is irreducible over GF(5): x^(5^2) = x but x^5 != x mod it
+        let m = upoly(&[1, 0, 2]);
+        let x = upoly(&[1, 0]);
+        assert_eq!(x, x.frobenius_mod(2, &m));
+        assert_ne!(x, x.frobenius_mod(1, &m));
+        assert_eq!(x.pow_mod(125, &m), x.frobenius_mod(3, &m));
+
+        assert_eq!(0, m.count_roots());
+        assert_eq!(2, upoly(&[1, 0, -1]).count_roots());
+        // every element is a root of x^5 - x, counted once in x^10 - x^2
+        assert_eq!(5, upoly(&[1, 0, 0, 0, -1, 0]).count_roots());
+        assert_eq!(5, upoly(&[1, 0, 0, 0, 0, 0, 0, 0, -1, 0, 0]).count_roots());
+    }
+}
@@ -0,0 +1,512 @@
+// univariate polys over GF(2), bit-packed: bit i of the words is the coef of
+// x^i, 64 to a word. addition is a XOR per word and multiplication a
+// carry-less product per pair of words, where dense polys spend an i128
+// operation per coef. factorization is squarefree decomposition, using square
+// roots where the derivative vanishes, then distinct degree, then
+// Cantor-Zassenhaus splitting by the trace map, since (p - 1) / 2 powers need
+// an odd p
+
+use std::fmt;
+use std::ops;
+
+use crate::factor::Rng;
+use crate::rational::Rat;
+use crate::univariate::UPoly;
+
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct Gf2Poly {
+    // lowest first, without trailing zero words
+    words: Vec<u64>,
+}
+
+// the 128 bit carry-less product of two words
+fn clmul(a: u64, b: u64) -> u128 {
+    let mut out = 0u128;
+    let mut a = a;
+
+    while a != 0 {
+        let i = a.trailing_zeros();
+        out ^= (b as u128) << i;
+        a &= a - 1;
+    }
+
+    out
+}
+
+// the bits of a at the even positions of the result
+fn spread(a: u32) -> u64 {
+    let mut x = a as u64;
+    x = (x | (x << 16)) & 0x0000_ffff_0000_ffff;
+    x = (x | (x << 8)) & 0x00ff_00ff_00ff_00ff;
+    x = (x | (x << 4)) & 0x0f0f_0f0f_0f0f_0f0f;
+    x = (x | (x << 2)) & 0x3333_3333_3333_3333;
+    (x | (x << 1)) & 0x5555_5555_5555_5555
+}
+
+// the even bits of a, packed into the low half
+fn unspread(a: u64) -> u32 {
+    let mut x = a & 0x5555_5555_5555_5555;
+    x = (x | (x >> 1)) & 0x3333_3333_3333_3333;
+    x = (x | (x >> 2)) & 0x0f0f_0f0f_0f0f_0f0f;
+    x = (x | (x >> 4)) & 0x00ff_00ff_00ff_00ff;
+    x = (x | (x >> 8)) & 0x0000_ffff_0000_ffff;
+    (x | (x >> 16)) as u32
+}
+
+impl Gf2Poly {
+    fn from_words(mut words: Vec<u64>) -> Gf2Poly {
+        while words.last() == Some(&0) {
+            words.pop();
+        }
+        Gf2Poly { words }
+    }
+
+    pub fn zero() -> Gf2Poly {
+        Gf2Poly::default()
+    }
+
+    pub fn one() -> Gf2Poly {
+        Gf2Poly::monomial(0)
+    }
+
+    // x^k
+    pub fn monomial(k: usize) -> Gf2Poly {
+        let mut words = vec![0; k / 64 + 1];
+        words[k / 64] = 1 << (k % 64);
+        Gf2Poly { words }
+    }
+
+    // integer coefs mod 2, highest first as for UPoly
+    pub fn from_coefs(coefs: &[i64]) -> Gf2Poly {
+        let mut words = vec![0; coefs.len() / 64 + 1];
+
+        for (i, c) in coefs.iter().rev().enumerate() {
+            if c.rem_euclid(2) == 1 {
+                words[i / 64] |= 1 << (i % 64);
+            }
+        }
+
+        Gf2Poly::from_words(words)
+    }
+
+    // None unless every coef is an integer
+    pub fn from_upoly(p: &UPoly<Rat>) -> Option<Gf2Poly> {
+        let coefs =
+            p.0.iter()
+                .map(|c| c.try_int())
+                .collect::<Option<Vec<_>>>()?;
+        Some(Gf2Poly::from_coefs(&coefs))
+    }
+
+    pub fn to_upoly(&self) -> UPoly<Rat> {
+        let coefs = (0..=self.deg())
+            .rev()
+            .map(|i| Rat::from(self.coef(i) as i64));
+        UPoly(coefs.collect()).trim()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn is_one(&self) -> bool {
+        self.words == [1]
+    }
+
+    // 0 for the zero poly
+    pub fn deg(&self) -> usize {
+        match self.words.last() {
+            Some(w) => (self.words.len() - 1) * 64 + 63 - w.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    pub fn coef(&self, i: usize) -> bool {
+        self.words
+            .get(i / 64)
+            .is_some_and(|w| w >> (i % 64) & 1 == 1)
+    }
+
+    // x^k self
+    pub fn shl(&self, k: usize) -> Gf2Poly {
+        if self.is_zero() {
+            return Gf2Poly::zero();
+        }
+
+        let (words, bits) = (k / 64, k % 64);
+        let mut out = vec![0; self.words.len() + words + 1];
+
+        for (i, w) in self.words.iter().enumerate() {
+            out[i + words] ^= w << bits;
+            if bits > 0 {
+                out[i + words + 1] ^= w >> (64 - bits);
+            }
+        }
+
+        Gf2Poly::from_words(out)
+    }
+
+    // self^2, by spreading the bits out
+    pub fn square(&self) -> Gf2Poly {
+        let words = self
+            .words
+            .iter()
+            .flat_map(|w| [spread(*w as u32), spread((w >> 32) as u32)])
+            .collect();
+
+        Gf2Poly::from_words(words)
+    }
+
+    // the square root, when every odd coef is zero
+    pub fn sqrt(&self) -> Option<Gf2Poly> {
+        if self.words.iter().any(|w| w & 0xaaaa_aaaa_aaaa_aaaa != 0) {
+            return None;
+        }
+
+        let halves = self
+            .words
+            .iter()
+            .map(|w| unspread(*w) as u64)
+            .collect::<Vec<_>>();
+        let words = halves
+            .chunks(2)
+            .map(|pair| pair[0] | pair.get(1).map_or(0, |h| h << 32))
+            .collect();
+
+        Some(Gf2Poly::from_words(words))
+    }
+
+    // coef i of the derivative is (i + 1) c_(i + 1), so the odd coefs move
+    // down by one and the even ones vanish
+    pub fn derivative(&self) -> Gf2Poly {
+        let odd = self
+            .words
+            .iter()
+            .map(|w| w & 0xaaaa_aaaa_aaaa_aaaa)
+            .collect();
+        let mut words = Gf2Poly::from_words(odd).words;
+
+        for i in 0..words.len() {
+            let carry = words.get(i + 1).map_or(0, |w| w << 63);
+            words[i] = (words[i] >> 1) | carry;
+        }
+
+        Gf2Poly::from_words(words)
+    }
+
+    pub fn div_rem(&self, divisor: &Gf2Poly) -> (Gf2Poly, Gf2Poly) {
+        assert!(!divisor.is_zero(), "division by zero");
+
+        let d = divisor.deg();
+        let mut rem = self.clone();
+        let mut quot = vec![0u64; self.words.len()];
+
+        while !rem.is_zero() && rem.deg() >= d {
+            let k = rem.deg() - d;
+            quot[k / 64] |= 1 << (k % 64);
+            rem = &rem + &divisor.shl(k);
+        }
+
+        (Gf2Poly::from_words(quot), rem)
+    }
+
+    pub fn rem(&self, divisor: &Gf2Poly) -> Gf2Poly {
+        self.div_rem(divisor).1
+    }
+
+    // monic, since every nonzero poly is
+    pub fn gcd(&self, other: &Gf2Poly) -> Gf2Poly {
+        let (mut a, mut b) = (self.clone(), other.clone());
+
+        while !b.is_zero() {
+            let r = a.rem(&b);
+            a = b;
+            b = r;
+        }
+
+        a
+    }
+
+    // self^(2^k) mod m
+    fn frobenius(&self, k: usize, m: &Gf2Poly) -> Gf2Poly {
+        (0..k).fold(self.rem(m), |a, _| a.square().rem(m))
+    }
+
+    // (factor, multiplicity) with the factors squarefree and coprime
+    pub fn squarefree_decomposition(&self) -> Vec<(Gf2Poly, usize)> {
+        if self.deg() == 0 {
+            return vec![];
+        }
+
+        let derivative = self.derivative();
+        if derivative.is_zero() {
+            // a square, since only even powers are left
+            let root = self.sqrt().unwrap();
+            return root
+                .squarefree_decomposition()
+                .into_iter()
+                .map(|(f, mult)| (f, 2 * mult))
+                .collect();
+        }
+
+        let mut factors = vec![];
+        let mut c = self.gcd(&derivative);
+        let mut w = self.div_rem(&c).0;
+        let mut mult = 1;
+
+        while !w.is_one() {
+            let y = w.gcd(&c);
+            let z = w.div_rem(&y).0;
+            if !z.is_one() {
+                factors.push((z, mult));
+            }
+
+            mult += 1;
+            w = y;
+            c = c.div_rem(&w).0;
+        }
+
+        // what is left has multiplicities divisible by 2
+        if !c.is_one() {
+            let root = c.sqrt().unwrap();
+            factors.extend(
+                root.squarefree_decomposition()
+                    .into_iter()
+                    .map(|(f, m)| (f, 2 * m)),
+            );
+        }
+
+        factors.sort_by_key(|(f, m)| (*m, f.deg()));
+        factors
+    }
+
+    // squarefree self into (product of all irreducible factors of degree d, d)
+    fn distinct_degree(&self) -> Vec<(Gf2Poly, usize)> {
+        let mut factors = vec![];
+        let mut f = self.clone();
+        let x = Gf2Poly::monomial(1);
+        let mut h = x.clone();
+        let mut d = 1;
+
+        while f.deg() >= 2 * d {
+            h = h.frobenius(1, &f);
+            let g = (&h + &x).gcd(&f);
+
+            if g.deg() > 0 {
+                f = f.div_rem(&g).0;
+                h = h.rem(&f);
+                factors.push((g, d));
+            }
+
+            d += 1;
+        }
+
+        if f.deg() > 0 {
+            let deg = f.deg();
+            factors.push((f, deg));
+        }
+
+        factors
+    }
+
+    // split f whose irreducible factors all have degree d: the trace
+    // a + a^2 + ... + a^(2^(d - 1)) is 0 or 1 mod each factor, each with
+    // probability 1/2
+    fn equal_degree(&self, d: usize, rng: &mut Rng) -> Vec<Gf2Poly> {
+        if self.deg() == d {
+            return vec![self.clone()];
+        }
+
+        loop {
+            let words = (0..self.words.len())
+                .map(|_| rng.next(1 << 64) as u64)
+                .collect();
+            let a = Gf2Poly::from_words(words).rem(self);
+
+            if a.deg() == 0 {
+                continue;
+            }
+
+            let mut trace = a.clone();
+            let mut power = a;
+            for _ in 1..d {
+                power = power.frobenius(1, self);
+                trace = &trace + &power;
+            }
+
+            let g = trace.gcd(self);
+
+            if g.deg() > 0 && g.deg() < self.deg() {
+                let h = self.div_rem(&g).0;
+                let mut factors = g.equal_degree(d, rng);
+                factors.extend(h.equal_degree(d, rng));
+                return factors;
+            }
+        }
+    }
+
+    // irreducible factors with multiplicity, sorted by degree then bits
+    pub fn factor(&self) -> Vec<(Gf2Poly, usize)> {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        let mut factors = vec![];
+
+        for (f, mult) in self.squarefree_decomposition() {
+            for (g, d) in f.distinct_degree() {
+                factors.extend(g.equal_degree(d, &mut rng).into_iter().map(|h| (h, mult)));
+            }
+        }
+
+        factors.sort_by(|(f, _), (g, _)| {
+            (f.deg(), f.words.iter().rev().collect::<Vec<_>>())
+                .cmp(&(g.deg(), g.words.iter().rev().collect::<Vec<_>>()))
+        });
+        factors
+    }
+}
+
+impl ops::Add for &Gf2Poly {
+    type Output = Gf2Poly;
+
+    fn add(self, other: &Gf2Poly) -> Gf2Poly {
+        let (long, short) = if self.words.len() >= other.words.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        let mut words = long.words.clone();
+        for (w, v) in words.iter_mut().zip(&short.words) {
+            *w ^= v;
+        }
+
+        Gf2Poly::from_words(words)
+    }
+}
+
+impl ops::Mul for &Gf2Poly {
+    type Output = Gf2Poly;
+
+    fn mul(self, other: &Gf2Poly) -> Gf2Poly {
+        if self.is_zero() || other.is_zero() {
+            return Gf2Poly::zero();
+        }
+
+        let mut words = vec![0; self.words.len() + other.words.len()];
+
+        for (i, a) in self.words.iter().enumerate() {
+            for (j, b) in other.words.iter().enumerate() {
+                let product = clmul(*a, *b);
+                words[i + j] ^= product as u64;
+                words[i + j + 1] ^= (product >> 64) as u64;
+            }
+        }
+
+        Gf2Poly::from_words(words)
+    }
+}
+
+impl fmt::Debug for Gf2Poly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        let terms = (0..=self.deg())
+            .rev()
+            .filter(|i| self.coef(*i))
+            .map(|i| match i {
+                0 => "1".to_string(),
+                1 => "x".to_string(),
+                i => format!("x^{i}"),
+            });
+
+        write!(f, "{}", terms.collect::<Vec<_>>().join(" + "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Gf2Poly;
+    use crate::rational::Rat;
+    use crate::univariate::UPoly;
+
+    #[test]
+    fn arithmetic() {
+        let a = Gf2Poly::from_coefs(&[1, 1, 0, 1]);
+        assert_eq!("x^3 + x^2 + 1", format!("{:?}", a));
+
+        // across words, and squaring as multiplication
+        let big = &Gf2Poly::monomial(100) + &Gf2Poly::one();
+        let product = &big * &big;
+        assert_eq!("x^200 + 1", format!("{:?}", product));
+        assert_eq!(product, big.square());
+        assert_eq!(Some(big.clone()), product.sqrt());
+        assert_eq!(None, (&big + &Gf2Poly::monomial(1)).sqrt());
+
+        let (q, r) = (&(&a * &big) + &Gf2Poly::monomial(1)).div_rem(&big);
+        assert_eq!((a.clone(), Gf2Poly::monomial(1)), (q, r));
+
+        // x^3 + x^2 + 1 -> 3x^2 + 2x = x^2
+        assert_eq!(Gf2Poly::monomial(2), a.derivative());
+        assert!(big.derivative().is_zero());
+        assert_eq!(
+            Gf2Poly::monomial(100),
+            (&big + &Gf2Poly::monomial(101)).derivative()
+        );
+
+        let b = Gf2Poly::from_coefs(&[1, 1, 1]);
+        assert_eq!(b, (&a * &b).gcd(&(&b * &big)));
+
+        let upoly = UPoly(vec![Rat::from(3), Rat::from(-2), Rat::from(5)]);
+        assert_eq!(
+            "x^2 + 1",
+            format!("{:?}", Gf2Poly::from_upoly(&upoly).unwrap())
+        );
+        assert_eq!(
+            UPoly(vec![Rat::from(1), Rat::from(0), Rat::from(1)]),
+            Gf2Poly::from_upoly(&upoly).unwrap().to_upoly()
+        );
+    }
+
+    #[test]
+    fn factor() {
+        // x^15 - 1 is the product of the irreducibles of degree 1, 2 and 4
+        // other than x
+        let p = &Gf2Poly::monomial(15) + &Gf2Poly::one();
+        let factors = p.factor();
+
+        assert_eq!(
+            vec![
+                "x + 1",
+                "x^2 + x + 1",
+                "x^4 + x + 1",
+                "x^4 + x^3 + 1",
+                "x^4 + x^3 + x^2 + x + 1"
+            ],
+            factors
+                .iter()
+                .map(|(f, _)| format!("{:?}", f))
+                .collect::<Vec<_>>()
+        );
+
+        // (x + 1)^4 (x^2 + x + 1)^3 x
+        let x1 = Gf2Poly::from_coefs(&[1, 1]);
+        let x2 = Gf2Poly::from_coefs(&[1, 1, 1]);
+        let x = Gf2Poly::monomial(1);
+        let p = [&x1, &x1, &x1, &x1, &x2, &x2, &x2, &x]
+            .iter()
+            .fold(Gf2Poly::one(), |acc, f| &acc * f);
+
+        assert_eq!(
+            vec![(x.clone(), 1), (x1.clone(), 4), (x2.clone(), 3)],
+            p.factor()
+        );
+        assert_eq!(
+            vec![(1, 1), (2, 3), (1, 4)],
+            p.squarefree_decomposition()
+                .into_iter()
+                .map(|(f, m)| (f.deg(), m))
+                .collect::<Vec<_>>()
+        );
+    }
+}
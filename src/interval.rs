@@ -0,0 +1,415 @@
+// closed f64 intervals with outward rounding, and HC4 contraction of boxes
+// against polynomial constraints: evaluate each constraint forward over the
+// box, intersect with the allowed range, then narrow every subterm backward
+// down to the variables
+
+use crate::cad::tarski::{Cmp, Constraint};
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+// stop once a round shrinks no variable by more than this fraction
+const MIN_PROGRESS: f64 = 1e-3;
+const MAX_ROUNDS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+    pub lo: f64,
+    pub hi: f64,
+}
+
+// slack for operations which are not correctly rounded
+fn widen(lo: f64, hi: f64) -> Interval {
+    Interval {
+        lo: (lo - lo.abs() * 1e-12).next_down(),
+        hi: (hi + hi.abs() * 1e-12).next_up(),
+    }
+}
+
+// 0 * inf is 0 for intervals
+fn mul_bound(a: f64, b: f64) -> f64 {
+    if a == 0. || b == 0. {
+        0.
+    } else {
+        a * b
+    }
+}
+
+impl Interval {
+    pub fn new(lo: f64, hi: f64) -> Interval {
+        Interval { lo, hi }
+    }
+
+    pub fn point(val: Rat) -> Interval {
+        let val = f64::from(val);
+        widen(val, val)
+    }
+
+    pub fn whole() -> Interval {
+        Interval::new(f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    pub fn width(&self) -> f64 {
+        self.hi - self.lo
+    }
+
+    pub fn mid(&self) -> f64 {
+        match (self.lo.is_finite(), self.hi.is_finite()) {
+            (true, true) => self.lo + (self.hi - self.lo) / 2.,
+            (false, true) => self.hi.min(0.) - 1.,
+            (true, false) => self.lo.max(0.) + 1.,
+            (false, false) => 0.,
+        }
+    }
+
+    pub fn contains(&self, x: f64) -> bool {
+        self.lo <= x && x <= self.hi
+    }
+
+    pub fn intersect(&self, other: &Interval) -> Option<Interval> {
+        let (lo, hi) = (self.lo.max(other.lo), self.hi.min(other.hi));
+
+        if lo <= hi && lo < f64::INFINITY && hi > f64::NEG_INFINITY {
+            Some(Interval { lo, hi })
+        } else {
+            None
+        }
+    }
+
+    pub fn hull(&self, other: &Interval) -> Interval {
+        Interval::new(self.lo.min(other.lo), self.hi.max(other.hi))
+    }
+
+    pub fn add(&self, other: &Interval) -> Interval {
+        Interval::new(
+            (self.lo + other.lo).next_down(),
+            (self.hi + other.hi).next_up(),
+        )
+    }
+
+    pub fn sub(&self, other: &Interval) -> Interval {
+        Interval::new(
+            (self.lo - other.hi).next_down(),
+            (self.hi - other.lo).next_up(),
+        )
+    }
+
+    pub fn mul(&self, other: &Interval) -> Interval {
+        let products = [
+            mul_bound(self.lo, other.lo),
+            mul_bound(self.lo, other.hi),
+            mul_bound(self.hi, other.lo),
+            mul_bound(self.hi, other.hi),
+        ];
+
+        Interval::new(
+            products
+                .iter()
+                .cloned()
+                .fold(f64::INFINITY, f64::min)
+                .next_down(),
+            products
+                .iter()
+                .cloned()
+                .fold(f64::NEG_INFINITY, f64::max)
+                .next_up(),
+        )
+    }
+
+    // None if other contains 0
+    pub fn div(&self, other: &Interval) -> Option<Interval> {
+        if other.contains(0.) {
+            return None;
+        }
+
+        let quotients = [
+            self.lo / other.lo,
+            self.lo / other.hi,
+            self.hi / other.lo,
+            self.hi / other.hi,
+        ];
+
+        // inf / inf
+        if quotients.iter().any(|q| q.is_nan()) {
+            return Some(Interval::whole());
+        }
+
+        Some(Interval::new(
+            quotients
+                .iter()
+                .cloned()
+                .fold(f64::INFINITY, f64::min)
+                .next_down(),
+            quotients
+                .iter()
+                .cloned()
+                .fold(f64::NEG_INFINITY, f64::max)
+                .next_up(),
+        ))
+    }
+
+    pub fn pow(&self, pow: u64) -> Interval {
+        let p = |x: f64| x.powi(pow as i32);
+
+        if pow.is_multiple_of(2) {
+            let (near, far) = if self.contains(0.) {
+                (0., self.lo.abs().max(self.hi.abs()))
+            } else {
+                (
+                    self.lo.abs().min(self.hi.abs()),
+                    self.lo.abs().max(self.hi.abs()),
+                )
+            };
+
+            let near = widen(p(near), p(near)).lo.max(0.);
+            Interval::new(near, widen(p(far), p(far)).hi)
+        } else {
+            Interval::new(
+                widen(p(self.lo), p(self.lo)).lo,
+                widen(p(self.hi), p(self.hi)).hi,
+            )
+        }
+    }
+
+    // narrow x given that x^pow lies in self
+    pub fn root(&self, pow: u64, x: &Interval) -> Option<Interval> {
+        let r = |v: f64| v.signum() * v.abs().powf(1. / pow as f64);
+
+        if pow.is_multiple_of(2) {
+            let y = self.intersect(&Interval::new(0., f64::INFINITY))?;
+            let pos = widen(r(y.lo), r(y.hi));
+            let pos = Interval::new(pos.lo.max(0.), pos.hi);
+            let neg = Interval::new(-pos.hi, -pos.lo);
+
+            match (x.intersect(&neg), x.intersect(&pos)) {
+                (Some(a), Some(b)) => Some(a.hull(&b)),
+                (a, b) => a.or(b),
+            }
+        } else {
+            let lo = widen(r(self.lo), r(self.lo)).lo;
+            let hi = widen(r(self.hi), r(self.hi)).hi;
+            x.intersect(&Interval::new(lo, hi))
+        }
+    }
+}
+
+// range of p over bx
+pub fn eval(p: &Poly<Rat>, bx: &[Interval]) -> Interval {
+    p.terms.iter().fold(Interval::new(0., 0.), |acc, term| {
+        let mono = term
+            .vars
+            .iter()
+            .fold(Interval::point(term.val), |acc, (var, pow)| {
+                acc.mul(&bx[*var].pow(*pow))
+            });
+
+        acc.add(&mono)
+    })
+}
+
+fn allowed(cmp: Cmp) -> Interval {
+    match cmp {
+        Cmp::Eq => Interval::new(0., 0.),
+        Cmp::Gt => Interval::new(0., f64::INFINITY),
+        Cmp::Lt => Interval::new(f64::NEG_INFINITY, 0.),
+    }
+}
+
+// one HC4-revise step for p in range; None if the box is infeasible
+fn revise(p: &Poly<Rat>, range: Interval, bx: &mut [Interval]) -> Option<()> {
+    let coefs = p
+        .terms
+        .iter()
+        .map(|term| Interval::point(term.val))
+        .collect::<Vec<_>>();
+
+    let factors = p
+        .terms
+        .iter()
+        .map(|term| {
+            term.vars
+                .iter()
+                .map(|(var, pow)| bx[*var].pow(*pow))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let product = |fs: &[Interval]| {
+        fs.iter()
+            .fold(Interval::point(Rat::from(1)), |acc, f| acc.mul(f))
+    };
+
+    let monos = factors.iter().map(|fs| product(fs)).collect::<Vec<_>>();
+    let terms = coefs
+        .iter()
+        .zip(&monos)
+        .map(|(c, m)| c.mul(m))
+        .collect::<Vec<_>>();
+
+    let sum = |skip: usize| {
+        terms
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != skip)
+            .fold(Interval::new(0., 0.), |acc, (_, t)| acc.add(t))
+    };
+
+    let total = sum(terms.len()).intersect(&range)?;
+
+    for (i, term) in p.terms.iter().enumerate() {
+        let t = terms[i].intersect(&total.sub(&sum(i)))?;
+
+        if term.vars.is_empty() {
+            continue;
+        }
+
+        let m = match t.div(&coefs[i]) {
+            Some(m) => monos[i].intersect(&m)?,
+            None => continue,
+        };
+
+        for (k, (var, pow)) in term.vars.iter().enumerate() {
+            let others = factors[i]
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != k)
+                .map(|(_, f)| *f)
+                .collect::<Vec<_>>();
+
+            let f = match m.div(&product(&others)) {
+                Some(f) => factors[i][k].intersect(&f)?,
+                None => continue,
+            };
+
+            bx[*var] = f.root(*pow, &bx[*var])?;
+        }
+    }
+
+    Some(())
+}
+
+// contract bx (indexed by var) by HC4 until it stops shrinking; every real
+// solution of the constraints in bx lies in the result, and None means there
+// are none
+pub fn hc4(constraints: &[Constraint], bx: &[Interval]) -> Option<Vec<Interval>> {
+    let mut bx = bx.to_vec();
+
+    for _ in 0..MAX_ROUNDS {
+        let before = bx.clone();
+
+        for c in constraints {
+            revise(&c.value, allowed(c.cmp_zero), &mut bx)?;
+        }
+
+        let progress = before.iter().zip(&bx).any(|(b, a)| {
+            let shrunk = b.width() - a.width();
+            shrunk.is_nan() || shrunk > MIN_PROGRESS * b.width().min(f64::MAX)
+        });
+
+        if !progress {
+            break;
+        }
+    }
+
+    Some(bx)
+}
+
+impl System<Rat> {
+    // contract bounds on the variables against the members as equations
+    pub fn contract(&self, bounds: &[Interval]) -> Option<Vec<Interval>> {
+        let constraints = self
+            .members
+            .iter()
+            .map(|p| Constraint {
+                value: p.clone(),
+                cmp_zero: Cmp::Eq,
+            })
+            .collect::<Vec<_>>();
+
+        hc4(&constraints, bounds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hc4, Interval};
+    use crate::cad::tarski::{Cmp, Constraint};
+    use crate::system;
+
+    #[test]
+    fn arithmetic() {
+        let a = Interval::new(-1., 2.);
+        let b = Interval::new(3., f64::INFINITY);
+
+        let sum = a.add(&b);
+        assert!(sum.lo < 2. && 1.99 < sum.lo && sum.hi == f64::INFINITY);
+
+        let prod = a.mul(&b);
+        assert_eq!((f64::NEG_INFINITY, f64::INFINITY), (prod.lo, prod.hi));
+
+        assert!(b.div(&a).is_none());
+        let quot = a.div(&b).unwrap();
+        assert!(quot.contains(0.) && quot.contains(-1. / 3.) && quot.contains(2. / 3.));
+
+        let sq = a.pow(2);
+        assert!(sq.lo == 0. && sq.contains(4.) && sq.hi < 4.01);
+
+        // x^2 in [4, 9], x in [-10, 1]
+        let x = Interval::new(4., 9.)
+            .root(2, &Interval::new(-10., 1.))
+            .unwrap();
+        assert!(x.contains(-3.) && x.contains(-2.) && x.hi < -1.99 && x.lo > -3.01);
+        assert!(Interval::new(4., 9.)
+            .root(2, &Interval::new(-1., 1.))
+            .is_none());
+    }
+
+    #[test]
+    fn contract() {
+        let sys = system! {
+            x^2 + y^2 - 1,
+            x - y
+        };
+
+        let bx = sys
+            .contract(&[Interval::new(0., 10.), Interval::new(-10., 10.)])
+            .unwrap();
+
+        // x >= 0 carries over to y through x - y, and the circle bounds both
+        // by 1; the fixed point of HC4 is no tighter than that
+        let root = 0.5f64.sqrt();
+        for i in &bx {
+            assert!(i.contains(root));
+            assert!(i.lo > -1e-9 && i.hi < 1. + 1e-9);
+        }
+
+        // no real solutions
+        let sys = system! {
+            x^2 + 1
+        };
+        assert!(sys.contract(&[Interval::whole()]).is_none());
+    }
+
+    #[test]
+    fn inequalities() {
+        let sys = system! {
+            x*y - 4,
+            x - 2*y
+        };
+
+        let c = |i: usize, cmp_zero| Constraint {
+            value: sys.members[i].clone(),
+            cmp_zero,
+        };
+
+        // xy <= 3 in the box
+        let bx = [Interval::new(0., 1.), Interval::new(0., 3.)];
+        assert!(hc4(&[c(0, Cmp::Gt)], &bx).is_none());
+
+        // x < 2y with x in [1, 4] and y in [0, 1] leaves x in [1, 2]
+        let bx = [Interval::new(1., 4.), Interval::new(0., 1.)];
+        let bx = hc4(&[c(1, Cmp::Lt)], &bx).unwrap();
+        assert!(bx[0].contains(1.) && bx[0].contains(2.) && bx[0].hi < 2.01);
+        assert!(bx[1].contains(0.5) && bx[1].contains(1.) && bx[1].lo > 0.49);
+    }
+}
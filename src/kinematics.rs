@@ -0,0 +1,162 @@
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+// constraints for the usual polynomial parametrizations of rotations, given
+// the indices of their variables
+
+fn var(v: usize) -> Poly<Rat> {
+    Poly::var(v, 1)
+}
+
+fn constant(val: i64) -> Poly<Rat> {
+    Poly::constant(Rat::from(val))
+}
+
+fn sum_of_squares(vars: &[usize]) -> Poly<Rat> {
+    vars.iter()
+        .fold(constant(0), |acc, v| acc + Poly::var(*v, 2))
+}
+
+// c^2 + s^2 - 1, for c = cos(t) and s = sin(t) of an angle t
+pub fn cos_sin(c: usize, s: usize) -> Poly<Rat> {
+    sum_of_squares(&[c, s]) - constant(1)
+}
+
+// w^2 + x^2 + y^2 + z^2 - 1, for q = (w, x, y, z)
+pub fn unit_quaternion(q: [usize; 4]) -> Poly<Rat> {
+    sum_of_squares(&q) - constant(1)
+}
+
+// the rotation matrix of a unit quaternion q = (w, x, y, z), as polys in its
+// vars; without the unit constraint, this is the rotation scaled by |q|^2
+pub fn quaternion_rotation(q: [usize; 4]) -> [[Poly<Rat>; 3]; 3] {
+    let [w, x, y, z] = q.map(var);
+    let prod = |a: &Poly<Rat>, b: &Poly<Rat>| a.mul_ref(b) * constant(2);
+    let diag = |a: &Poly<Rat>, b: &Poly<Rat>| {
+        constant(1) - a.mul_ref(a) * constant(2) - b.mul_ref(b) * constant(2)
+    };
+
+    [
+        [
+            diag(&y, &z),
+            prod(&x, &y) - prod(&w, &z),
+            prod(&x, &z) + prod(&w, &y),
+        ],
+        [
+            prod(&x, &y) + prod(&w, &z),
+            diag(&x, &z),
+            prod(&y, &z) - prod(&w, &x),
+        ],
+        [
+            prod(&x, &z) - prod(&w, &y),
+            prod(&y, &z) + prod(&w, &x),
+            diag(&x, &y),
+        ],
+    ]
+}
+
+// R^T R = I (the 6 entries on and above the diagonal) and det R = 1, for the
+// matrix of vars r, row by row
+pub fn rotation_matrix(r: [[usize; 3]; 3]) -> Vec<Poly<Rat>> {
+    let m = r.map(|row| row.map(var));
+    let mut constraints = vec![];
+
+    for i in 0..3 {
+        for j in i..3 {
+            let dot = (0..3).fold(constant(0), |acc, k| acc + m[k][i].mul_ref(&m[k][j]));
+            constraints.push(if i == j { dot - constant(1) } else { dot });
+        }
+    }
+
+    let det = (0..3).fold(constant(0), |acc, j| {
+        let minor = m[1][(j + 1) % 3].mul_ref(&m[2][(j + 2) % 3])
+            - m[1][(j + 2) % 3].mul_ref(&m[2][(j + 1) % 3]);
+        acc + m[0][j].mul_ref(&minor)
+    });
+
+    constraints.push(det - constant(1));
+    constraints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cos_sin, quaternion_rotation, rotation_matrix, unit_quaternion};
+    use crate::poly::Poly;
+    use crate::rational::Rat;
+    use crate::system;
+
+    fn eval(p: &Poly<Rat>, point: &[Rat]) -> Rat {
+        let p = point
+            .iter()
+            .enumerate()
+            .fold(p.clone(), |p, (v, val)| p.eval(v, *val));
+
+        p.terms.first().map(|t| t.val).unwrap_or(Rat::from(0))
+    }
+
+    #[test]
+    fn rotations() {
+        // a quarter turn about z: q = (1, 0, 0, 1) / sqrt(2), which is
+        // irrational, so check the scaled identity on q = (1, 0, 0, 1)
+        let rot = quaternion_rotation([0, 1, 2, 3]);
+        let q = [1, 0, 0, 1].map(Rat::from);
+
+        let mut m = [[Rat::from(0); 3]; 3];
+        for i in 0..3 {
+            for j in 0..3 {
+                m[i][j] = eval(&rot[i][j], &q);
+            }
+        }
+
+        // 1 - 2|v|^2 on the diagonal with |q|^2 = 2
+        assert_eq!([-1, -2, 1].map(Rat::from), [m[0][0], m[0][1], m[2][2]]);
+        assert_eq!(Rat::from(2), m[1][0]);
+        assert_eq!(Rat::from(1), eval(&unit_quaternion([0, 1, 2, 3]), &q));
+
+        // the quarter turn as a matrix satisfies every constraint, a reflection
+        // fails only the determinant
+        let constraints = rotation_matrix([[0, 1, 2], [3, 4, 5], [6, 7, 8]]);
+        assert_eq!(7, constraints.len());
+
+        let turn = [0, -1, 0, 1, 0, 0, 0, 0, 1].map(Rat::from);
+        assert!(constraints.iter().all(|c| eval(c, &turn) == Rat::from(0)));
+
+        let flip = [0, 1, 0, 1, 0, 0, 0, 0, 1].map(Rat::from);
+        let failed = constraints
+            .iter()
+            .map(|c| eval(c, &flip) != Rat::from(0))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![false, false, false, false, false, false, true], failed);
+    }
+
+    #[test]
+    fn planar_arm() {
+        // two unit links reaching (1, 1); vars c, d, s, t, with (c, s) and
+        // (d, t) the joint angles
+        let mut sys = system! {
+            c + c*d - s*t - 1,
+            s + s*d + c*t - 1
+        };
+
+        sys.members.push(cos_sin(0, 2));
+        sys.members.push(cos_sin(1, 3));
+
+        // elbow up and elbow down
+        for point in [[1, 0, 0, 1], [0, 0, 1, -1]] {
+            let point = point.map(Rat::from);
+            assert!(sys.members.iter().all(|p| eval(p, &point) == Rat::from(0)));
+        }
+
+        // a single link at 45 degrees: cos^2 = 1/2
+        let mut sys = system! {
+            c - s
+        };
+        sys.members.push(cos_sin(0, 1));
+
+        let min_poly = sys.min_poly(&sys.var("c", 1)).unwrap();
+        assert_eq!(
+            vec![Rat::from(1), Rat::from(0), Rat::from(-1) / Rat::from(2)],
+            min_poly.0
+        );
+    }
+}
@@ -0,0 +1,269 @@
+// best-fit points of inconsistent systems, e.g. built from noisy measurements:
+// Levenberg-Marquardt on the sum of squared residuals of the members, with the
+// exact Jacobian. the result is a local minimum near the start, which is a
+// solution whenever the minimum is zero. systems linear in their vars, such as
+// fitting the coefs of a model to data, are solved exactly instead
+
+use crate::branch_and_prune::inverse;
+use crate::linear::linear_row;
+use crate::matrix::Matrix;
+use crate::poly::slp::Slp;
+use crate::poly::system::System;
+use crate::rational::Rat;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExactFit {
+    pub point: Vec<Rat>,
+    pub residuals: Vec<Rat>,
+    pub cost: Rat,
+}
+
+impl ExactFit {
+    // whether the data fits the model exactly, i.e. the point is a solution
+    pub fn is_exact(&self) -> bool {
+        self.cost.is_zero()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LeastSquares {
+    pub point: Vec<f64>,
+    // of each member at point
+    pub residuals: Vec<f64>,
+    // sum of squared residuals
+    pub cost: f64,
+    pub iterations: usize,
+}
+
+fn cost(residuals: &[f64]) -> f64 {
+    residuals.iter().map(|r| r * r).sum()
+}
+
+impl System<Rat> {
+    // the least squares point of members A x + b, from the normal equations
+    // A^T A x = -A^T b over Rat; None if a member is not linear or the point
+    // is not unique
+    pub fn linear_least_squares(&self) -> Option<ExactFit> {
+        let n = self.var_dict.len();
+        let (a, b): (Vec<_>, Vec<_>) = self
+            .members
+            .iter()
+            .map(|f| linear_row(f, n))
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+
+        // the normal equations, with a unique solution when A^T A is regular
+        let ata = (0..n)
+            .map(|u| {
+                (0..n)
+                    .map(|v| a.iter().fold(Rat::from(0), |acc, r| acc + r[u] * r[v]))
+                    .collect()
+            })
+            .collect();
+        let rhs = (0..n)
+            .map(|u| {
+                a.iter()
+                    .zip(&b)
+                    .fold(Rat::from(0), |acc, (r, c)| acc - r[u] * *c)
+            })
+            .collect::<Vec<_>>();
+
+        let ata = Matrix::new(n, ata)?;
+        if ata.rank() < n {
+            return None;
+        }
+        let point = ata.solve(&rhs)?;
+
+        let residuals = a
+            .iter()
+            .zip(&b)
+            .map(|(r, c)| r.iter().zip(&point).fold(*c, |acc, (a, x)| acc + *a * *x))
+            .collect::<Vec<_>>();
+        let cost = residuals.iter().fold(Rat::from(0), |acc, r| acc + *r * *r);
+
+        Some(ExactFit {
+            point,
+            residuals,
+            cost,
+        })
+    }
+
+    // None if start is not a point of the system
+    pub fn least_squares(&self, start: &[f64], max_iterations: usize) -> Option<LeastSquares> {
+        let n = self.var_dict.len();
+        let m = self.members.len();
+
+        if start.len() != n {
+            return None;
+        }
+
+        let residuals = self.to_slp();
+        let partials = self.jacobian().concat();
+        let jacobian = Slp::new(&partials);
+
+        let mut x = start.to_vec();
+        let mut r = residuals.eval(&x);
+        let mut lambda = 1e-3;
+        let mut iterations = 0;
+
+        while iterations < max_iterations && cost(&r) > 0. {
+            iterations += 1;
+
+            let jac = jacobian.eval(&x);
+            let jac = |i: usize, v: usize| jac[i * n + v];
+
+            // J^T J and the gradient J^T r
+            let jtj = (0..n)
+                .map(|u| {
+                    (0..n)
+                        .map(|v| (0..m).map(|i| jac(i, u) * jac(i, v)).sum::<f64>())
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let grad = (0..n)
+                .map(|v| (0..m).map(|i| jac(i, v) * r[i]).sum::<f64>())
+                .collect::<Vec<_>>();
+
+            if grad.iter().all(|g| g.abs() < 1e-15) {
+                break;
+            }
+
+            // damp until a step lowers the cost, scaling by the diagonal of
+            // J^T J (Marquardt)
+            let mut accepted = false;
+
+            while lambda < 1e20 {
+                let mut damped = jtj.clone();
+                for (v, row) in damped.iter_mut().enumerate() {
+                    row[v] += lambda * jtj[v][v].max(1e-12);
+                }
+
+                let step = inverse(damped).map(|inv| {
+                    inv.iter()
+                        .map(|row| -row.iter().zip(&grad).map(|(a, g)| a * g).sum::<f64>())
+                        .collect::<Vec<_>>()
+                });
+
+                if let Some(step) = step {
+                    let next = x.iter().zip(&step).map(|(x, s)| x + s).collect::<Vec<_>>();
+                    let next_r = residuals.eval(&next);
+
+                    if cost(&next_r) < cost(&r) {
+                        let small = step
+                            .iter()
+                            .zip(&x)
+                            .all(|(s, x)| s.abs() <= 1e-15 * (1. + x.abs()));
+
+                        x = next;
+                        r = next_r;
+                        lambda = (lambda / 10.).max(1e-15);
+                        accepted = !small;
+                        break;
+                    }
+                }
+
+                lambda *= 10.;
+            }
+
+            if !accepted {
+                break;
+            }
+        }
+
+        Some(LeastSquares {
+            cost: cost(&r),
+            point: x,
+            residuals: r,
+            iterations,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rational::Rat;
+    use crate::system;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn least_squares() {
+        // three measurements of one quantity; the best fit is their mean
+        let sys = system! {
+            x - 1,
+            x - 2,
+            x - 6
+        };
+
+        let fit = sys.least_squares(&[0.], 100).unwrap();
+        assert!(close(3., fit.point[0]));
+        assert!(close(2., fit.residuals[0]) && close(-3., fit.residuals[2]));
+        assert!(close(14., fit.cost));
+        assert!(sys.least_squares(&[0., 0.], 100).is_none());
+
+        // a consistent system is solved
+        let sys = system! {
+            x^2 + y^2 - 2,
+            x - y
+        };
+
+        let fit = sys.least_squares(&[2., 0.5], 100).unwrap();
+        assert!(close(1., fit.point[0]) && close(1., fit.point[1]));
+        assert!(fit.cost < 1e-20);
+
+        // a point near the unit circle and the line x = 2; the best fit
+        // balances the two, and the gradient 2 J^T r vanishes there. the cost
+        // is flat to second order at a minimum, so the point is only good to
+        // about the square root of the precision
+        let sys = system! {
+            x^2 + y^2 - 1,
+            x - 2
+        };
+
+        let fit = sys.least_squares(&[1.5, 0.], 100).unwrap();
+        let (x, r) = (fit.point[0], &fit.residuals);
+        assert!(fit.point[1].abs() < 1e-9);
+        assert!((2. * x * r[0] + r[1]).abs() < 1e-6);
+        assert!(1. < x && x < 2. && fit.cost > 0.);
+    }
+
+    #[test]
+    fn linear_least_squares() {
+        // the line a + b t through (0, 1), (1, 3), (2, 5)
+        let sys = system! {
+            a - 1,
+            a + b - 3,
+            a + 2*b - 5
+        };
+
+        let fit = sys.linear_least_squares().unwrap();
+        assert_eq!(vec![Rat::from(1), Rat::from(2)], fit.point);
+        assert!(fit.is_exact());
+
+        // through (0, 0), (1, 1), (2, 3), which are not collinear
+        let sys = system! {
+            a,
+            a + b - 1,
+            a + 2*b - 3
+        };
+
+        let fit = sys.linear_least_squares().unwrap();
+        let rat = |num: i64, den: i64| Rat::from(num) / Rat::from(den);
+        assert_eq!(vec![rat(-1, 6), rat(3, 2)], fit.point);
+        assert_eq!(vec![rat(-1, 6), rat(1, 3), rat(-1, 6)], fit.residuals);
+        assert_eq!(rat(1, 6), fit.cost);
+        assert!(!fit.is_exact());
+
+        // the same numerically
+        let numeric = sys.least_squares(&[0., 0.], 100).unwrap();
+        assert!(close(f64::from(fit.cost), numeric.cost));
+
+        assert!(system! { a*b - 1 }.linear_least_squares().is_none());
+        assert!(system! { a + b - 1, 2*a + 2*b - 3 }
+            .linear_least_squares()
+            .is_none());
+    }
+}
@@ -1,8 +1,88 @@
 #![feature(trait_alias)]
 
+// the documented API is the prelude; the modules below stay public for
+// experimentation (see the stability note there) but are hidden from the docs
+
+#[doc(hidden)]
 pub mod cad;
+#[doc(hidden)]
 pub mod poly;
+#[doc(hidden)]
 pub mod algebraic;
+#[doc(hidden)]
 pub mod univariate;
+#[doc(hidden)]
 pub mod rational;
-pub mod field;
\ No newline at end of file
+#[doc(hidden)]
+pub mod field;
+#[doc(hidden)]
+pub mod factor;
+#[doc(hidden)]
+pub mod galois;
+#[doc(hidden)]
+pub mod interval;
+#[doc(hidden)]
+pub mod branch_and_prune;
+#[doc(hidden)]
+pub mod sensitivity;
+#[doc(hidden)]
+pub mod trace;
+#[doc(hidden)]
+pub mod basis;
+#[doc(hidden)]
+pub mod symmetric;
+#[doc(hidden)]
+pub mod encode;
+#[doc(hidden)]
+pub mod reaction_network;
+#[doc(hidden)]
+pub mod kinematics;
+#[doc(hidden)]
+pub mod portfolio;
+#[doc(hidden)]
+pub mod cache;
+#[doc(hidden)]
+pub mod gf2;
+#[doc(hidden)]
+pub mod gf;
+#[doc(hidden)]
+pub mod least_squares;
+#[doc(hidden)]
+pub mod blocks;
+#[doc(hidden)]
+pub mod solve;
+#[doc(hidden)]
+pub mod roots;
+#[doc(hidden)]
+pub mod piecewise;
+#[doc(hidden)]
+pub mod union;
+#[doc(hidden)]
+pub mod quantifier;
+#[doc(hidden)]
+pub mod profile;
+#[doc(hidden)]
+pub mod expr;
+#[doc(hidden)]
+pub mod linear;
+#[doc(hidden)]
+pub mod distributed;
+#[doc(hidden)]
+pub mod matrix;
+#[doc(hidden)]
+pub mod export;
+#[doc(hidden)]
+pub mod sparse;
+#[doc(hidden)]
+pub mod display;
+pub mod prelude;
+
+// what the exported macros expand to, so they do not depend on where these
+// live; not part of the API
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::poly::mono::Mono;
+    pub use crate::poly::system::System;
+    pub use crate::poly::Poly;
+    pub use crate::rational::Rat;
+}
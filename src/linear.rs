@@ -0,0 +1,125 @@
+// the fast path for systems whose members all have degree at most 1: no
+// basis, just fraction-free elimination of the matrix (see Matrix), with
+// each row first scaled to integers so the entries stay integers. the
+// solution set is a point plus any combination of a null space basis
+
+use crate::matrix::Matrix;
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::{gcd, Rat};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinearSolution {
+    Inconsistent,
+    // particular + sum c_i null_space[i] for any c, with the free vars of
+    // particular zero and one free var 1 in each null space vector
+    Affine {
+        particular: Vec<Rat>,
+        null_space: Vec<Vec<Rat>>,
+    },
+}
+
+// the coefs of the vars and the constant of a member A x + c; None if the
+// member has degree above 1
+pub(crate) fn linear_row(f: &Poly<Rat>, n: usize) -> Option<(Vec<Rat>, Rat)> {
+    let mut row = vec![Rat::from(0); n];
+    let mut c = Rat::from(0);
+
+    for t in &f.terms {
+        match t.vars[..] {
+            [] => c = t.val,
+            [(v, 1)] => row[v] = t.val,
+            _ => return None,
+        }
+    }
+
+    Some((row, c))
+}
+
+// the row times the lcm of its denominators
+fn integral(row: Vec<Rat>) -> Vec<Rat> {
+    let den = row.iter().fold(1, |acc, c| acc / gcd(acc, c.den) * c.den);
+    row.into_iter().map(|c| c * den).collect()
+}
+
+impl System<Rat> {
+    // None if a member has degree above 1
+    pub fn solve_linear(&self) -> Option<LinearSolution> {
+        let n = self.var_dict.len();
+
+        // A x = b
+        let (mut a, mut b) = (vec![], vec![]);
+        for f in &self.members {
+            let (mut row, c) = linear_row(f, n)?;
+            row.push(c * -1);
+
+            let mut row = integral(row);
+            b.push(row.pop().unwrap());
+            a.push(row);
+        }
+
+        let a = Matrix::new(n, a).unwrap();
+
+        Some(match a.solve(&b) {
+            None => LinearSolution::Inconsistent,
+            Some(particular) => LinearSolution::Affine {
+                particular,
+                null_space: a.null_space(),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LinearSolution;
+    use crate::poly::Poly;
+    use crate::rational::Rat;
+    use crate::system;
+
+    #[test]
+    fn solve_linear() {
+        let mut sys = system! {
+            x + y + z - 1,
+            4*x - 2*y + z - 6,
+            6*x + 3*z - 8
+        };
+        let half = Poly::constant(Rat::from(1) / Rat::from(2));
+        sys.members[1] = half.mul_ref(&sys.members[1]);
+        sys.members[2] = half.mul_ref(&sys.members[2]);
+
+        // the third is the sum of the first two, so a line of solutions
+        let Some(LinearSolution::Affine {
+            particular,
+            null_space,
+        }) = sys.solve_linear()
+        else {
+            panic!("expected an affine solution set");
+        };
+        assert_eq!(1, null_space.len());
+
+        let at = |x: &[Rat]| {
+            sys.members
+                .iter()
+                .map(|f| {
+                    (0..3)
+                        .fold(f.clone(), |p, v| p.eval(v, x[v]))
+                        .get_constant_val()
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(vec![Some(0); 3], at(&particular));
+        let other = particular
+            .iter()
+            .zip(&null_space[0])
+            .map(|(p, d)| *p + *d * 5)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![Some(0); 3], at(&other));
+
+        assert_eq!(
+            Some(LinearSolution::Inconsistent),
+            system! { x + y - 1, 2*x + 2*y - 1 }.solve_linear()
+        );
+        assert_eq!(None, system! { x*y - 1 }.solve_linear());
+    }
+}
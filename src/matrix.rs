@@ -0,0 +1,262 @@
+// dense matrices over any field, with exact elimination. the elimination is
+// fraction-free (Bareiss): each step divides by the previous pivot, which
+// divides exactly, so over the rationals scaled to integers every entry stays
+// an integer, a minor of the matrix, and the numbers grow no faster than
+// determinants do. over Gf it is ordinary elimination with delayed division
+
+use std::ops::Index;
+
+use crate::field::Field;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix<T: Field> {
+    cols: usize,
+    rows: Vec<Vec<T>>,
+}
+
+// the row echelon form, the pivot column of each nonzero row, and whether
+// the rows were swapped an odd number of times
+fn bareiss<T: Field>(mut a: Vec<Vec<T>>, cols: usize) -> (Vec<Vec<T>>, Vec<usize>, bool) {
+    let mut prev = T::one();
+    let mut pivots = vec![];
+    let mut odd = false;
+
+    for col in 0..cols {
+        let r = pivots.len();
+        let Some(p) = (r..a.len()).find(|i| !a[*i][col].is_zero()) else {
+            continue;
+        };
+        if p != r {
+            a.swap(r, p);
+            odd = !odd;
+        }
+
+        for i in r + 1..a.len() {
+            let f = a[i][col].clone();
+            for j in col + 1..a[i].len() {
+                a[i][j] = (a[r][col].clone() * a[i][j].clone() - f.clone() * a[r][j].clone())
+                    / prev.clone();
+            }
+            a[i][col] = T::zero();
+        }
+
+        prev = a[r][col].clone();
+        pivots.push(col);
+    }
+
+    (a, pivots, odd)
+}
+
+// the solution of rows x = rhs with the free vars given, by back substitution
+fn back_substitute<T: Field>(
+    a: &[Vec<T>],
+    pivots: &[usize],
+    rhs: impl Fn(usize) -> T,
+    mut x: Vec<T>,
+) -> Vec<T> {
+    for (r, col) in pivots.iter().enumerate().rev() {
+        let rest =
+            (col + 1..x.len()).fold(T::zero(), |acc, j| acc + a[r][j].clone() * x[j].clone());
+        x[*col] = (rhs(r) - rest) / a[r][*col].clone();
+    }
+
+    x
+}
+
+impl<T: Field> Matrix<T> {
+    // None unless every row has cols entries
+    pub fn new(cols: usize, rows: Vec<Vec<T>>) -> Option<Matrix<T>> {
+        rows.iter()
+            .all(|row| row.len() == cols)
+            .then_some(Matrix { cols, rows })
+    }
+
+    pub fn zero(rows: usize, cols: usize) -> Matrix<T> {
+        Matrix {
+            cols,
+            rows: vec![vec![T::zero(); cols]; rows],
+        }
+    }
+
+    pub fn identity(n: usize) -> Matrix<T> {
+        let mut m = Matrix::zero(n, n);
+        for i in 0..n {
+            m.rows[i][i] = T::one();
+        }
+        m
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.rows[i]
+    }
+
+    pub fn transpose(&self) -> Matrix<T> {
+        Matrix {
+            cols: self.rows(),
+            rows: (0..self.cols)
+                .map(|j| self.rows.iter().map(|row| row[j].clone()).collect())
+                .collect(),
+        }
+    }
+
+    // None if the lengths do not match
+    pub fn mul_vec(&self, x: &[T]) -> Option<Vec<T>> {
+        (x.len() == self.cols).then(|| {
+            self.rows
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .zip(x)
+                        .fold(T::zero(), |acc, (a, b)| acc + a.clone() * b.clone())
+                })
+                .collect()
+        })
+    }
+
+    // a row echelon form, and the pivot column of each nonzero row
+    pub fn echelon(&self) -> (Matrix<T>, Vec<usize>) {
+        let (rows, pivots, _) = bareiss(self.rows.clone(), self.cols);
+        (
+            Matrix {
+                cols: self.cols,
+                rows,
+            },
+            pivots,
+        )
+    }
+
+    pub fn rank(&self) -> usize {
+        self.echelon().1.len()
+    }
+
+    // None unless square; the last Bareiss pivot is the determinant
+    pub fn det(&self) -> Option<T> {
+        if self.rows() != self.cols {
+            return None;
+        }
+
+        let (a, pivots, odd) = bareiss(self.rows.clone(), self.cols);
+        Some(match a.last() {
+            None => T::one(),
+            Some(_) if pivots.len() < self.cols => T::zero(),
+            Some(last) if odd => last[self.cols - 1].clone() * -1,
+            Some(last) => last[self.cols - 1].clone(),
+        })
+    }
+
+    // a basis, with one free var 1 and the others 0 in each vector
+    pub fn null_space(&self) -> Vec<Vec<T>> {
+        let (a, pivots, _) = bareiss(self.rows.clone(), self.cols);
+
+        (0..self.cols)
+            .filter(|v| !pivots.contains(v))
+            .map(|free| {
+                let mut x = vec![T::zero(); self.cols];
+                x[free] = T::one();
+                back_substitute(&a, &pivots, |_| T::zero(), x)
+            })
+            .collect()
+    }
+
+    // a solution of self x = b with the free vars 0; None if there is none,
+    // or b has the wrong length
+    pub fn solve(&self, b: &[T]) -> Option<Vec<T>> {
+        if b.len() != self.rows() {
+            return None;
+        }
+
+        let augmented = self
+            .rows
+            .iter()
+            .zip(b)
+            .map(|(row, b)| {
+                let mut row = row.clone();
+                row.push(b.clone());
+                row
+            })
+            .collect();
+        let (a, pivots, _) = bareiss(augmented, self.cols);
+
+        let n = self.cols;
+        if a[pivots.len()..].iter().any(|row| !row[n].is_zero()) {
+            return None;
+        }
+
+        Some(back_substitute(
+            &a,
+            &pivots,
+            |r| a[r][n].clone(),
+            vec![T::zero(); n],
+        ))
+    }
+}
+
+impl<T: Field> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        &self.rows[i][j]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Matrix;
+    use crate::gf::Gf;
+    use crate::rational::Rat;
+
+    fn rat(rows: &[&[i64]]) -> Matrix<Rat> {
+        let rows = rows
+            .iter()
+            .map(|row| row.iter().map(|c| Rat::from(*c)).collect())
+            .collect();
+        Matrix::new(3, rows).unwrap()
+    }
+
+    #[test]
+    fn elimination() {
+        let a = rat(&[&[0, 2, 1], &[1, -1, 3], &[2, 0, 4]]);
+        assert_eq!(Some(Rat::from(6)), a.det());
+        assert_eq!(Some(Rat::from(6)), a.transpose().det());
+        assert_eq!(3, a.rank());
+        assert!(a.null_space().is_empty());
+
+        let b = [Rat::from(3), Rat::from(3), Rat::from(6)];
+        let x = a.solve(&b).unwrap();
+        assert_eq!(Some(b.to_vec()), a.mul_vec(&x));
+
+        // the third row is the sum of the first two
+        let singular = rat(&[&[1, 2, 3], &[4, 5, 6], &[5, 7, 9]]);
+        assert_eq!(Some(Rat::from(0)), singular.det());
+        assert_eq!(2, singular.rank());
+        let null = singular.null_space();
+        assert_eq!(1, null.len());
+        assert_eq!(Some(vec![Rat::from(0); 3]), singular.mul_vec(&null[0]));
+        assert_eq!(
+            None,
+            singular.solve(&[Rat::from(1), Rat::from(1), Rat::from(3)])
+        );
+
+        assert_eq!(None, Matrix::new(2, vec![vec![Rat::from(1)]]));
+        assert_eq!(Some(Rat::from(1)), Matrix::<Rat>::identity(4).det());
+
+        // det [[1, 2], [3, 4]] = -2 = 5 mod 7
+        let m = Matrix::new(
+            2,
+            vec![
+                vec![Gf::<7>::from(1), Gf::from(2)],
+                vec![Gf::from(3), Gf::from(4)],
+            ],
+        )
+        .unwrap();
+        assert_eq!(Some(Gf::from(5)), m.det());
+    }
+}
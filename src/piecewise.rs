@@ -0,0 +1,199 @@
+// non-smooth constraints as polynomial systems. t = |e| is a new var with
+// t^2 = e^2 and t >= 0, and max, min are (a + b +- |a - b|) / 2. over the
+// reals e >= 0 is e = s^2 for a new slack var s. a model is encoded either
+// as one system, whose real solutions projected to the original vars are
+// those of the model, or as a system per sign case of the abs values, each
+// with t = e or t = -e, which keeps the degrees down at the price of 2^k
+// systems
+
+use std::rc::Rc;
+
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+#[derive(Debug, Clone)]
+pub struct Piecewise {
+    pub var_dict: Vec<String>,
+    pub members: Vec<Poly<Rat>>,
+    // (t, e) for t = |e|
+    abs: Vec<(usize, Poly<Rat>)>,
+    nonneg: Vec<Poly<Rat>>,
+}
+
+fn half() -> Poly<Rat> {
+    Poly::constant(Rat::from(1) / Rat::from(2))
+}
+
+impl Piecewise {
+    pub fn new(vars: &[&str]) -> Piecewise {
+        Piecewise {
+            var_dict: vars.iter().map(|v| v.to_string()).collect(),
+            members: vec![],
+            abs: vec![],
+            nonneg: vec![],
+        }
+    }
+
+    // starts from the vars and members of sys
+    pub fn from_system(sys: &System<Rat>) -> Piecewise {
+        Piecewise {
+            var_dict: sys.var_dict.to_vec(),
+            members: sys.members.clone(),
+            abs: vec![],
+            nonneg: vec![],
+        }
+    }
+
+    pub fn var(&self, var: &str) -> Poly<Rat> {
+        match self.var_dict.iter().position(|v| v == var) {
+            Some(i) => Poly::var(i, 1),
+            None => panic!("variable {} not in system variable dict", var),
+        }
+    }
+
+    // p = 0
+    pub fn push(&mut self, p: Poly<Rat>) {
+        self.members.push(p);
+    }
+
+    // p >= 0
+    pub fn nonneg(&mut self, p: Poly<Rat>) {
+        self.nonneg.push(p);
+    }
+
+    // a new var equal to |e|
+    pub fn abs(&mut self, e: &Poly<Rat>) -> Poly<Rat> {
+        let t = self.var_dict.len();
+        self.var_dict.push(format!("_abs{}", self.abs.len()));
+        self.abs.push((t, e.clone()));
+        Poly::var(t, 1)
+    }
+
+    pub fn max(&mut self, a: &Poly<Rat>, b: &Poly<Rat>) -> Poly<Rat> {
+        let t = self.abs(&(a.clone() - b.clone()));
+        (a.clone() + b.clone() + t).mul_ref(&half())
+    }
+
+    pub fn min(&mut self, a: &Poly<Rat>, b: &Poly<Rat>) -> Poly<Rat> {
+        let t = self.abs(&(a.clone() - b.clone()));
+        (a.clone() + b.clone() - t).mul_ref(&half())
+    }
+
+    // the members, and each p >= 0 in nonneg as p = s^2 with a new slack var
+    fn system(&self, mut members: Vec<Poly<Rat>>, nonneg: &[Poly<Rat>]) -> System<Rat> {
+        let mut var_dict = self.var_dict.clone();
+
+        for (i, p) in self.nonneg.iter().chain(nonneg).enumerate() {
+            members.push(p.clone() - Poly::var(var_dict.len(), 2));
+            var_dict.push(format!("_slack{}", i));
+        }
+
+        System {
+            var_dict: Rc::new(var_dict),
+            members,
+        }
+    }
+
+    // one system: (t - e)(t + e) = 0 and t >= 0 for each t = |e|
+    pub fn encode(&self) -> System<Rat> {
+        let mut members = self.members.clone();
+        let mut nonneg = vec![];
+
+        for (t, e) in &self.abs {
+            let t = Poly::var(*t, 1);
+            members.push(t.mul_ref(&t) - e.mul_ref(e));
+            nonneg.push(t);
+        }
+
+        self.system(members, &nonneg)
+    }
+
+    // a system per sign case, the ith with t = e and e >= 0 for the jth abs
+    // value if bit j of i is 0, else t = -e and -e >= 0
+    pub fn cases(&self) -> Vec<System<Rat>> {
+        (0..1usize << self.abs.len())
+            .map(|case| {
+                let mut members = self.members.clone();
+                let mut nonneg = vec![];
+
+                for (j, (t, e)) in self.abs.iter().enumerate() {
+                    let e = if case >> j & 1 == 0 {
+                        e.clone()
+                    } else {
+                        e.mul_ref(&Poly::constant(Rat::from(-1)))
+                    };
+
+                    members.push(Poly::var(*t, 1) - e.clone());
+                    nonneg.push(e);
+                }
+
+                self.system(members, &nonneg)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Piecewise;
+    use crate::poly::system::System;
+    use crate::poly::Poly;
+    use crate::rational::Rat;
+
+    // the distinct values of var 0 over the real solutions
+    fn xs(sys: &System<Rat>) -> Vec<f64> {
+        let mut xs = sys
+            .solve_triangular(Rat::from(1) / Rat::from(1000000))
+            .unwrap()
+            .iter()
+            .map(|point| f64::from(point[0].approx()))
+            .collect::<Vec<_>>();
+        xs.sort_by(f64::total_cmp);
+        xs.dedup_by(|a, b| (*a - *b).abs() < 1e-6);
+        xs
+    }
+
+    #[test]
+    fn abs() {
+        // |x - 1| = 2
+        let mut model = Piecewise::new(&["x"]);
+        let x = model.var("x");
+        let t = model.abs(&(x - Poly::constant(Rat::from(1))));
+        model.push(t - Poly::constant(Rat::from(2)));
+
+        let sys = model.encode();
+        assert_eq!(
+            vec!["x", "_abs0", "_slack0"],
+            sys.var_dict.iter().map(|v| v.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![-1., 3.], xs(&sys));
+
+        let cases = model.cases();
+        assert_eq!(2, cases.len());
+        assert_eq!(vec![3.], xs(&cases[0]));
+        assert_eq!(vec![-1.], xs(&cases[1]));
+    }
+
+    #[test]
+    fn max_min() {
+        // max(x, 0) = 2, so x = 2, and min(x, 0) = -2 with x >= -3 has x = -2
+        let mut model = Piecewise::new(&["x"]);
+        let x = model.var("x");
+        let zero = Poly::constant(Rat::from(0));
+        let m = model.max(&x, &zero);
+        model.push(m - Poly::constant(Rat::from(2)));
+        assert_eq!(vec![2.], xs(&model.encode()));
+
+        let mut model = Piecewise::new(&["x"]);
+        let m = model.min(&x, &zero);
+        model.push(m + Poly::constant(Rat::from(2)));
+        model.nonneg(x + Poly::constant(Rat::from(3)));
+        assert_eq!(vec![-2.], xs(&model.encode()));
+
+        // the case x >= 0 has no solution
+        let cases = model.cases();
+        assert!(cases[0].solve_triangular(Rat::from(1)).unwrap().is_empty());
+        assert_eq!(vec![-2.], xs(&cases[1]));
+    }
+}
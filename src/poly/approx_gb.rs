@@ -0,0 +1,222 @@
+// heuristic Groebner bases of systems with floating point coefs, e.g. fitted
+// to measurements, where making the coefs exact is meaningless: nearby exact
+// systems can have bases of different shapes. the members are shifted up to a
+// degree bound as in the Macaulay matrix, which is row reduced in floating
+// point with partial pivoting, taking entries at or below tol as zero after
+// scaling each row to a largest entry of 1. the result is a basis of a system
+// near the input only if deg is high enough and tol separates the pivots from
+// the noise; neither is checked
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::field::One;
+use crate::poly::macaulay::monomials;
+use crate::poly::mono::{grlex, monomial_div, monomial_mul, Mono};
+use crate::poly::system::System;
+use crate::rational::Rat;
+
+// exponents as in Mono, and a coef
+pub type FloatTerm = (Vec<(usize, u64)>, f64);
+
+#[derive(Debug, Clone)]
+pub struct FloatSystem {
+    pub var_dict: Rc<Vec<String>>,
+    // terms ascending in grlex, as in Poly
+    pub members: Vec<Vec<FloatTerm>>,
+}
+
+fn mono(vars: &[(usize, u64)]) -> Mono<Rat> {
+    Mono {
+        val: Rat::one(),
+        vars: vars.to_vec(),
+    }
+}
+
+fn total_deg(vars: &[(usize, u64)]) -> u64 {
+    vars.iter().map(|(_, pow)| *pow).sum()
+}
+
+impl System<Rat> {
+    pub fn to_float(&self) -> FloatSystem {
+        let members = self
+            .members
+            .iter()
+            .map(|p| {
+                p.terms
+                    .iter()
+                    .map(|t| (t.vars.clone(), f64::from(t.val)))
+                    .collect()
+            })
+            .collect();
+
+        FloatSystem::new(self.var_dict.clone(), members)
+    }
+}
+
+impl FloatSystem {
+    // sorts the vars and terms, merging repeated exponents
+    pub fn new(var_dict: Rc<Vec<String>>, members: Vec<Vec<FloatTerm>>) -> FloatSystem {
+        let members = members
+            .into_iter()
+            .map(|terms| {
+                let mut merged: Vec<FloatTerm> = vec![];
+
+                for (mut vars, coef) in terms {
+                    vars.retain(|(_, pow)| *pow > 0);
+                    vars.sort();
+
+                    match merged.iter_mut().find(|(v, _)| *v == vars) {
+                        Some((_, c)) => *c += coef,
+                        None => merged.push((vars, coef)),
+                    }
+                }
+
+                merged.retain(|(_, c)| *c != 0.);
+                merged.sort_by(|a, b| grlex(&mono(&a.0), &mono(&b.0)));
+                merged
+            })
+            .collect();
+
+        FloatSystem { var_dict, members }
+    }
+
+    // heuristic reduced basis (grlex) from the shifts of degree <= deg, with
+    // leading coefs 1 and ascending in leading term
+    pub fn approx_gb(&self, deg: u64, tol: f64) -> FloatSystem {
+        let mut columns = monomials(0, self.var_dict.len(), deg);
+        columns.sort_by(|a, b| grlex(&mono(a), &mono(b)).reverse());
+
+        let index = columns
+            .iter()
+            .enumerate()
+            .map(|(i, m)| (m.clone(), i))
+            .collect::<HashMap<_, _>>();
+
+        let mut rows = vec![];
+
+        for f in &self.members {
+            let Some(f_deg) = f.iter().map(|(vars, _)| total_deg(vars)).max() else {
+                continue;
+            };
+
+            for m in columns.iter().filter(|m| total_deg(m) + f_deg <= deg) {
+                let mut row = vec![0.; columns.len()];
+                for (vars, coef) in f {
+                    row[index[&monomial_mul(&mono(m), &mono(vars)).vars]] = *coef;
+                }
+
+                let max = row.iter().fold(0., |acc: f64, c| acc.max(c.abs()));
+                rows.push(row.iter().map(|c| c / max).collect::<Vec<_>>());
+            }
+        }
+
+        // reduced row echelon form, pivoting on the largest entry of each
+        // column
+        let mut pivots = vec![];
+
+        for col in 0..columns.len() {
+            let r = pivots.len();
+            let Some(p) =
+                (r..rows.len()).max_by(|i, j| rows[*i][col].abs().total_cmp(&rows[*j][col].abs()))
+            else {
+                break;
+            };
+
+            if rows[p][col].abs() <= tol {
+                for row in &mut rows[r..] {
+                    row[col] = 0.;
+                }
+                continue;
+            }
+
+            rows.swap(r, p);
+            let pivot = rows[r][col];
+            rows[r] = rows[r].iter().map(|c| c / pivot).collect();
+
+            for i in 0..rows.len() {
+                if i != r && rows[i][col] != 0. {
+                    let f = rows[i][col];
+                    rows[i] = rows[i]
+                        .iter()
+                        .zip(&rows[r])
+                        .map(|(c, d)| c - f * d)
+                        .collect();
+                    rows[i][col] = 0.;
+                }
+            }
+
+            pivots.push(col);
+        }
+
+        // the rows whose leading term no other leading term divides
+        let leads = pivots
+            .iter()
+            .map(|col| mono(&columns[*col]))
+            .collect::<Vec<_>>();
+        let members = (0..pivots.len())
+            .rev()
+            .filter(|r| {
+                (0..pivots.len()).all(|q| q == *r || monomial_div(&leads[*r], &leads[q]).is_none())
+            })
+            .map(|r| {
+                (0..columns.len())
+                    .rev()
+                    .filter(|col| rows[r][*col].abs() > tol)
+                    .map(|col| (columns[col].clone(), rows[r][col]))
+                    .collect()
+            })
+            .collect();
+
+        FloatSystem {
+            var_dict: self.var_dict.clone(),
+            members,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FloatSystem;
+    use crate::system;
+
+    #[test]
+    fn approx_gb() {
+        let sys = system! {
+            x^2 + y^2 - 1,
+            x - y
+        };
+
+        // x - y, y^2 - 1/2
+        let gb = sys.to_float().approx_gb(2, 1e-9);
+        assert_eq!(2, gb.members.len());
+        let expected = [
+            vec![(vec![(1, 1)], -1.), (vec![(0, 1)], 1.)],
+            vec![(vec![], -0.5), (vec![(1, 2)], 1.)],
+        ];
+        for (member, expected) in gb.members.iter().zip(expected) {
+            assert_eq!(expected.len(), member.len());
+            for ((vars, c), (e_vars, e)) in member.iter().zip(expected) {
+                assert_eq!(e_vars, *vars);
+                assert!((c - e).abs() < 1e-12);
+            }
+        }
+
+        // two lines through the origin at an angle of about 1e-13: only the
+        // origin exactly, but numerically the same line
+        let lines = FloatSystem::new(
+            sys.var_dict.clone(),
+            vec![
+                vec![(vec![(0, 1)], 1.), (vec![(1, 1)], -1.)],
+                vec![(vec![(1, 1)], -1.0000000000001), (vec![(0, 1)], 1.)],
+            ],
+        );
+
+        assert_eq!(1, lines.approx_gb(1, 1e-9).members.len());
+        let exact = lines.approx_gb(1, 1e-15);
+        assert_eq!(
+            vec![vec![(vec![(1, 1)], 1.)], vec![(vec![(0, 1)], 1.)]],
+            exact.members
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+
+use crate::field::Field;
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+// past this many variables, trying every grouping is too slow
+const MAX_VARS_FOR_GROUPINGS: usize = 8;
+
+impl<T: Field> Poly<T> {
+    // total degree in just the given vars
+    pub fn group_deg(&self, group: &[usize]) -> u64 {
+        self.terms
+            .iter()
+            .map(|term| {
+                term.vars
+                    .iter()
+                    .filter(|(v, _)| group.contains(v))
+                    .map(|(_, pow)| pow)
+                    .sum()
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+// coefficient of prod a_j^sizes[j] in prod_i (sum_j degs[i][j] a_j), assigning
+// the equations to groups one at a time
+fn assignments(
+    degs: &[Vec<u64>],
+    remaining: &mut Vec<usize>,
+    memo: &mut HashMap<(usize, Vec<usize>), u64>,
+) -> u64 {
+    let i = degs.len() - remaining.iter().sum::<usize>();
+
+    if i == degs.len() {
+        return 1;
+    }
+
+    if let Some(count) = memo.get(&(i, remaining.clone())) {
+        return *count;
+    }
+
+    let mut count = 0;
+
+    for j in 0..remaining.len() {
+        if remaining[j] > 0 && degs[i][j] > 0 {
+            remaining[j] -= 1;
+            count += degs[i][j] * assignments(degs, remaining, memo);
+            remaining[j] += 1;
+        }
+    }
+
+    memo.insert((i, remaining.clone()), count);
+    count
+}
+
+// all ways to split 0..n into nonempty groups
+fn partitions(n: usize) -> Vec<Vec<Vec<usize>>> {
+    let mut parts: Vec<Vec<Vec<usize>>> = vec![vec![]];
+
+    for v in 0..n {
+        let mut next = vec![];
+
+        for part in parts {
+            for j in 0..part.len() {
+                let mut with = part.clone();
+                with[j].push(v);
+                next.push(with);
+            }
+
+            let mut alone = part;
+            alone.push(vec![v]);
+            next.push(alone);
+        }
+
+        parts = next;
+    }
+
+    parts
+}
+
+impl System<Rat> {
+    // product of the total degrees: the number of solutions of a square
+    // system in projective space, counted with multiplicity, when finite
+    pub fn bezout_number(&self) -> u64 {
+        self.members.iter().map(|p| p.total_deg()).product()
+    }
+
+    // the Bezout number of the system read in the product of the projective
+    // spaces spanned by each group of variables; it bounds the isolated
+    // solutions in affine space, and is often far below the total degree
+    // bound. None unless the groups split the variables and the system is
+    // square
+    pub fn multihomogeneous_bezout(&self, groups: &[Vec<usize>]) -> Option<u64> {
+        let n = self.var_dict.len();
+        let mut vars = groups.concat();
+        vars.sort();
+
+        if self.members.len() != n || vars != (0..n).collect::<Vec<_>>() {
+            return None;
+        }
+
+        let degs = self
+            .members
+            .iter()
+            .map(|p| groups.iter().map(|g| p.group_deg(g)).collect())
+            .collect::<Vec<_>>();
+
+        let mut remaining = groups.iter().map(|g| g.len()).collect();
+
+        Some(assignments(&degs, &mut remaining, &mut HashMap::new()))
+    }
+
+    // the grouping with the smallest multihomogeneous Bezout number, found by
+    // trying all of them; None if the system isn't square or is too large
+    pub fn best_multihomogeneous_bezout(&self) -> Option<(Vec<Vec<usize>>, u64)> {
+        let n = self.var_dict.len();
+
+        if self.members.len() != n || n > MAX_VARS_FOR_GROUPINGS {
+            return None;
+        }
+
+        partitions(n)
+            .into_iter()
+            .filter_map(|groups| {
+                self.multihomogeneous_bezout(&groups)
+                    .map(|bound| (groups, bound))
+            })
+            .min_by_key(|(_, bound)| *bound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::partitions;
+    use crate::poly::quotient::Quotient;
+    use crate::system;
+
+    #[test]
+    fn bounds() {
+        // bilinear in {x, y} and {z, w} (vars 1, 2 and 0, 3): 6 from two
+        // groups against 16 from the total degree
+        let sys = system! {
+            x*z + y*w + 1,
+            x*w - y*z + 2,
+            x*z - y*z + x - 3,
+            y*w + 2*x*w - z + 1
+        };
+
+        assert_eq!(16, sys.bezout_number());
+        assert_eq!(
+            Some(6),
+            sys.multihomogeneous_bezout(&[vec![1, 2], vec![0, 3]])
+        );
+        assert_eq!(None, sys.multihomogeneous_bezout(&[vec![0, 1]]));
+
+        let (_, best) = sys.best_multihomogeneous_bezout().unwrap();
+        assert_eq!(6, best);
+
+        // the eigenvalue problem: an n x n matrix has n eigenpairs
+        let sys = system! {
+            2*x + y - l*x,
+            x + 3*y - l*y,
+            x + y - 1
+        };
+
+        assert_eq!(4, sys.bezout_number());
+        let (groups, best) = sys.best_multihomogeneous_bezout().unwrap();
+        assert_eq!(2, best);
+        assert_eq!(2, groups.len());
+
+        // sharp here
+        assert_eq!(2, Quotient::new(&sys).unwrap().dim());
+    }
+
+    #[test]
+    fn groupings() {
+        // Bell numbers
+        let counts = (0..6).map(|n| partitions(n).len()).collect::<Vec<_>>();
+        assert_eq!(vec![1, 1, 2, 5, 15, 52], counts);
+    }
+}
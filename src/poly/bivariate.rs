@@ -0,0 +1,116 @@
+use crate::cad::sturm::isolating_intervals;
+use crate::cad::weak_projection::resultant;
+use crate::interval::{eval, Interval};
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+use crate::univariate::Root;
+
+// distinct real roots of p, univariate in var, to within tolerance
+fn real_roots(p: &Poly<Rat>, var: usize, tolerance: Rat) -> Vec<Root<Rat>> {
+    let p = p.to_upoly(var).unwrap().squarefree_part();
+    let derivative = p.derivative();
+
+    isolating_intervals(&p)
+        .into_iter()
+        .map(|(lo, hi)| p.refine_root_interval(&derivative, lo, hi, tolerance))
+        .collect()
+}
+
+pub(crate) fn enclosure(root: &Root<Rat>) -> Interval {
+    match root {
+        Root::Point(x) => Interval::point(*x),
+        Root::Interval(lo, hi) => Interval::point(*lo).hull(&Interval::point(*hi)),
+    }
+}
+
+impl System<Rat> {
+    // real solutions of two equations in two unknowns, without a Groebner
+    // basis: the resultant in each var has the other coordinates of the
+    // solutions among its roots, and pairs of roots are kept where both
+    // members can vanish. returns (var 0, var 1) root pairs; None unless the
+    // system has that shape and its members share no factor
+    pub fn solve_bivariate(&self, tolerance: Rat) -> Option<Vec<(Root<Rat>, Root<Rat>)>> {
+        if self.members.len() != 2 || self.var_dict.len() != 2 {
+            return None;
+        }
+
+        let (f, g) = (&self.members[0], &self.members[1]);
+
+        // in var 0 once var 1 is eliminated, and the other way around
+        let res_0 = resultant(f, g, 1);
+        let res_1 = resultant(f, g, 0);
+
+        if res_0.is_zero() || res_1.is_zero() {
+            return None;
+        }
+
+        let xs = real_roots(&res_0, 0, tolerance);
+        let ys = real_roots(&res_1, 1, tolerance);
+
+        let mut solutions = vec![];
+
+        for x in &xs {
+            for y in &ys {
+                let bx = [enclosure(x), enclosure(y)];
+
+                if eval(f, &bx).contains(0.) && eval(g, &bx).contains(0.) {
+                    solutions.push((x.clone(), y.clone()));
+                }
+            }
+        }
+
+        Some(solutions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interval::Interval;
+    use crate::poly::quotient::Quotient;
+    use crate::rational::Rat;
+    use crate::system;
+
+    #[test]
+    fn bivariate() {
+        let tolerance = Rat::from(1) / Rat::from(1000000);
+
+        let sys = system! {
+            x^2 + y^2 - 5,
+            x*y - 2
+        };
+
+        let solutions = sys.solve_bivariate(tolerance).unwrap();
+        let points = solutions
+            .iter()
+            .map(|(x, y)| (f64::from(x.approx()).round(), f64::from(y.approx()).round()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec![(-2., -1.), (-1., -2.), (1., 2.), (2., 1.)], points);
+
+        // the same count from the general paths
+        assert_eq!(4, Quotient::new(&sys).unwrap().dim());
+        let bx = vec![Interval::new(-3., 3.); 2];
+        assert_eq!(4, sys.solve_real_in_box(&bx, 1e-6).verified.len());
+
+        // irrational: y = x^2 meets x + y = 1 at x = (-1 +- sqrt 5) / 2
+        let sys = system! {
+            y - x^2,
+            x + y - 1
+        };
+
+        let solutions = sys.solve_bivariate(tolerance).unwrap();
+        assert_eq!(2, solutions.len());
+        for (x, y) in solutions {
+            let (x, y) = (f64::from(x.approx()), f64::from(y.approx()));
+            assert!((y - x * x).abs() < 1e-5 && (x + y - 1.).abs() < 1e-5);
+        }
+
+        // no real solutions, a shared factor, and the wrong shape
+        let sys = system! { x^2 + y^2 + 1, x - y };
+        assert_eq!(Some(vec![]), sys.solve_bivariate(tolerance));
+        let sys = system! { x*y - x, x^2 - x };
+        assert!(sys.solve_bivariate(tolerance).is_none());
+        assert!(system! { x - 1 }.solve_bivariate(tolerance).is_none());
+    }
+}
@@ -0,0 +1,286 @@
+// border bases of zero-dimensional ideals: an order ideal O of monomials, closed
+// under division, and for each monomial b of the border x_i O \ O a member
+// b - (its normal form over O). normal forms and multiplication matrices only
+// need the members, not a term order, so they stay meaningful when the
+// coefficients are perturbed, e.g. when they come from measurements; the
+// members are a border basis exactly when the multiplication matrices of the
+// vars commute. this one is read off the Groebner basis, whose standard
+// monomials are an order ideal
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::field::{One, Zero};
+use crate::poly::mono::{grlex, monomial_mul, Mono};
+use crate::poly::quotient::{char_poly, Quotient};
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+use crate::univariate::UPoly;
+
+pub struct BorderBasis {
+    pub var_dict: Rc<Vec<String>>,
+    // starting at 1, unless the ideal is the whole ring
+    pub order_ideal: Vec<Mono<Rat>>,
+    pub border: Vec<Mono<Rat>>,
+    // one per border monomial, which leads it
+    pub members: Vec<Poly<Rat>>,
+    index: HashMap<Vec<(usize, u64)>, usize>,
+    border_index: HashMap<Vec<(usize, u64)>, usize>,
+}
+
+fn var(v: usize) -> Mono<Rat> {
+    Mono {
+        val: Rat::one(),
+        vars: vec![(v, 1)],
+    }
+}
+
+fn mat_vec(m: &[Vec<Rat>], v: &[Rat]) -> Vec<Rat> {
+    m.iter()
+        .map(|row| {
+            row.iter()
+                .zip(v)
+                .fold(Rat::zero(), |acc, (a, b)| acc + *a * *b)
+        })
+        .collect()
+}
+
+fn mat_mul(a: &[Vec<Rat>], b: &[Vec<Rat>]) -> Vec<Vec<Rat>> {
+    let n = b.first().map_or(0, |row| row.len());
+    a.iter()
+        .map(|row| {
+            (0..n)
+                .map(|j| {
+                    row.iter()
+                        .zip(b)
+                        .fold(Rat::zero(), |acc, (x, b)| acc + *x * b[j])
+                })
+                .collect()
+        })
+        .collect()
+}
+
+impl BorderBasis {
+    // None unless sys has finitely many (complex) solutions
+    pub fn new(sys: &System<Rat>) -> Option<BorderBasis> {
+        let q = Quotient::new(sys)?;
+        let n_vars = sys.var_dict.len();
+
+        let index = q
+            .basis
+            .iter()
+            .enumerate()
+            .map(|(i, o)| (o.vars.clone(), i))
+            .collect::<HashMap<_, _>>();
+
+        let mut border = if q.basis.is_empty() {
+            vec![Mono {
+                val: Rat::one(),
+                vars: vec![],
+            }]
+        } else {
+            vec![]
+        };
+
+        for o in &q.basis {
+            for v in 0..n_vars {
+                let b = monomial_mul(o, &var(v));
+                if !index.contains_key(&b.vars) && !border.contains(&b) {
+                    border.push(b);
+                }
+            }
+        }
+
+        border.sort_by(grlex);
+
+        let members = border
+            .iter()
+            .map(|b| {
+                let b = Poly {
+                    terms: vec![b.clone()],
+                };
+
+                q.reduce(&b)
+                    .into_iter()
+                    .zip(&q.basis)
+                    .fold(b, |acc, (c, o)| {
+                        acc - Poly {
+                            terms: vec![o.clone()],
+                        }
+                        .mul_ref(&Poly::constant(c))
+                    })
+            })
+            .collect();
+
+        let border_index = border
+            .iter()
+            .enumerate()
+            .map(|(i, b)| (b.vars.clone(), i))
+            .collect();
+
+        Some(BorderBasis {
+            var_dict: sys.var_dict.clone(),
+            order_ideal: q.basis,
+            border,
+            members,
+            index,
+            border_index,
+        })
+    }
+
+    pub fn dim(&self) -> usize {
+        self.order_ideal.len()
+    }
+
+    // multiplication by var v in the order ideal basis, from the members:
+    // x_v o is either in O or a border monomial, equal to its tail
+    pub fn var_matrix(&self, v: usize) -> Vec<Vec<Rat>> {
+        let mut mat = vec![vec![Rat::zero(); self.dim()]; self.dim()];
+
+        for (j, o) in self.order_ideal.iter().enumerate() {
+            let next = monomial_mul(o, &var(v));
+
+            match self.index.get(&next.vars) {
+                Some(i) => mat[*i][j] = Rat::one(),
+                None => {
+                    let member = &self.members[self.border_index[&next.vars]];
+                    for term in &member.terms {
+                        if let Some(i) = self.index.get(&term.vars) {
+                            mat[*i][j] = term.val * -1;
+                        }
+                    }
+                }
+            }
+        }
+
+        mat
+    }
+
+    fn reduce_with(&self, p: &Poly<Rat>, matrices: &[Vec<Vec<Rat>>]) -> Vec<Rat> {
+        let mut coords = vec![Rat::zero(); self.dim()];
+
+        if self.dim() == 0 {
+            return coords;
+        }
+
+        for term in &p.terms {
+            let mut v = vec![Rat::zero(); self.dim()];
+            v[0] = Rat::one();
+
+            for (var, pow) in &term.vars {
+                for _ in 0..*pow {
+                    v = mat_vec(&matrices[*var], &v);
+                }
+            }
+
+            for (c, x) in coords.iter_mut().zip(v) {
+                *c = *c + term.val * x;
+            }
+        }
+
+        coords
+    }
+
+    fn var_matrices(&self) -> Vec<Vec<Vec<Rat>>> {
+        (0..self.var_dict.len())
+            .map(|v| self.var_matrix(v))
+            .collect()
+    }
+
+    // coordinates of the normal form of p in the order ideal basis
+    pub fn reduce(&self, p: &Poly<Rat>) -> Vec<Rat> {
+        self.reduce_with(p, &self.var_matrices())
+    }
+
+    // matrix of multiplication by g; column j is g times the jth basis monomial
+    pub fn mult_matrix(&self, g: &Poly<Rat>) -> Vec<Vec<Rat>> {
+        let matrices = self.var_matrices();
+        let mut mat = vec![vec![Rat::zero(); self.dim()]; self.dim()];
+
+        for (j, o) in self.order_ideal.iter().enumerate() {
+            let go = g.mul_ref(&Poly {
+                terms: vec![o.clone()],
+            });
+
+            for (i, c) in self.reduce_with(&go, &matrices).into_iter().enumerate() {
+                mat[i][j] = c;
+            }
+        }
+
+        mat
+    }
+
+    pub fn char_poly(&self, g: &Poly<Rat>) -> UPoly<Rat> {
+        char_poly(self.mult_matrix(g))
+    }
+
+    // whether the members are a border basis: the var matrices commute
+    pub fn check(&self) -> bool {
+        let matrices = self.var_matrices();
+
+        (0..matrices.len()).all(|u| {
+            (u + 1..matrices.len())
+                .all(|v| mat_mul(&matrices[u], &matrices[v]) == mat_mul(&matrices[v], &matrices[u]))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BorderBasis;
+    use crate::poly::mono::{print_exps, Mono};
+    use crate::poly::quotient::Quotient;
+    use crate::poly::Poly;
+    use crate::rational::Rat;
+    use crate::system;
+
+    #[test]
+    fn border_basis() {
+        let sys = system! {
+            x^2 - 1,
+            y - x
+        };
+
+        let bb = BorderBasis::new(&sys).unwrap();
+        let names = |ms: &[Mono<Rat>]| {
+            ms.iter()
+                .map(|m| print_exps(m, &sys.var_dict))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(vec!["", "y"], names(&bb.order_ideal));
+        assert_eq!(vec!["x", "y^2", "xy"], names(&bb.border));
+        assert_eq!(
+            vec!["x - y", "y^2 - 1", "xy - 1"],
+            bb.members
+                .iter()
+                .map(|p| p.format(&sys.var_dict))
+                .collect::<Vec<_>>()
+        );
+        assert!(bb.check());
+
+        let (x, y) = (sys.var("x", 1), sys.var("y", 1));
+        assert_eq!(
+            vec![Rat::from(2), Rat::from(0)],
+            bb.reduce(&(x.mul_ref(&y) + x.mul_ref(&x)))
+        );
+
+        let q = Quotient::new(&sys).unwrap();
+        assert_eq!(q.mult_matrix(&y), bb.mult_matrix(&y));
+        assert_eq!(
+            q.char_poly(&(x.clone() + y.clone())),
+            bb.char_poly(&(x + y))
+        );
+
+        // y^2 = 101/100 alone does not commute with x = y, but perturbing xy
+        // the same way does
+        let mut perturbed = bb;
+        let c = Poly::constant(Rat::from(1) / Rat::from(100));
+        perturbed.members[1] = perturbed.members[1].clone() - c.clone();
+        assert!(!perturbed.check());
+        perturbed.members[2] = perturbed.members[2].clone() - c;
+        assert!(perturbed.check());
+
+        assert!(BorderBasis::new(&system! { x*y - 1 }).is_none());
+    }
+}
@@ -0,0 +1,119 @@
+// building a poly from many terms: each + merges two sorted term lists, so
+// adding n terms one at a time takes O(n^2). the builder buffers the terms
+// in any order, repeats included, and sorts and combines them once at the
+// end, in O(n log n)
+
+use std::cmp::Ordering;
+
+use crate::field::Field;
+use crate::poly::mono::{grlex, Mono};
+use crate::poly::Poly;
+
+#[derive(Debug, Clone)]
+pub struct PolyBuilder<T: Field> {
+    terms: Vec<Mono<T>>,
+}
+
+impl<T: Field> Default for PolyBuilder<T> {
+    fn default() -> Self {
+        PolyBuilder::new()
+    }
+}
+
+impl<T: Field> PolyBuilder<T> {
+    pub fn new() -> PolyBuilder<T> {
+        PolyBuilder { terms: vec![] }
+    }
+
+    pub fn with_capacity(terms: usize) -> PolyBuilder<T> {
+        PolyBuilder {
+            terms: Vec::with_capacity(terms),
+        }
+    }
+
+    // vars in any order, possibly repeated: [(0, 1), (1, 1), (0, 2)] is x^3 y
+    pub fn push(&mut self, val: T, vars: &[(usize, u64)]) {
+        let mut vars = vars
+            .iter()
+            .filter(|(_, pow)| *pow > 0)
+            .copied()
+            .collect::<Vec<_>>();
+        vars.sort();
+        vars.dedup_by(|(v, pow), (w, acc)| {
+            let same = v == w;
+            if same {
+                *acc += *pow;
+            }
+            same
+        });
+
+        self.terms.push(Mono { val, vars });
+    }
+
+    pub fn push_poly(&mut self, p: Poly<T>) {
+        self.terms.extend(p.terms);
+    }
+
+    // the terms buffered so far, before combining
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn build(mut self) -> Poly<T> {
+        self.terms.sort_by(grlex);
+
+        let mut terms: Vec<Mono<T>> = Vec::with_capacity(self.terms.len());
+        for term in self.terms {
+            match terms.last_mut() {
+                Some(last) if grlex(last, &term) == Ordering::Equal => {
+                    last.val = last.val.clone() + term.val;
+                }
+                _ => terms.push(term),
+            }
+        }
+
+        terms.retain(|t| !t.val.is_zero());
+        Poly { terms }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PolyBuilder;
+    use crate::rational::Rat;
+    use crate::system;
+
+    #[test]
+    fn builder() {
+        let sys = system! { x + y - 1 };
+        let p = &sys.members[0];
+        let cube = p.mul_ref(p).mul_ref(p);
+
+        // the terms of (x + y - 1)^3 unexpanded and reversed
+        let mut builder = PolyBuilder::new();
+        for a in p.terms.iter().rev() {
+            for b in &p.terms {
+                for c in &p.terms {
+                    let vars = [&a.vars[..], &b.vars[..], &c.vars[..]].concat();
+                    builder.push(a.val * b.val * c.val, &vars);
+                }
+            }
+        }
+        assert_eq!(27, builder.len());
+        assert_eq!(cube, builder.build());
+
+        // x^2 y written as y x x, and terms which cancel
+        let mut builder = PolyBuilder::new();
+        builder.push(Rat::from(2), &[(1, 1), (0, 1), (0, 1)]);
+        builder.push(Rat::from(3), &[(1, 0)]);
+        builder.push_poly(sys.var("x", 2).mul_ref(&sys.var("y", 1)));
+        builder.push(Rat::from(-3), &[]);
+        assert_eq!("3x^2y", builder.build().format(&sys.var_dict));
+
+        assert!(PolyBuilder::<Rat>::new().build().is_zero());
+    }
+}
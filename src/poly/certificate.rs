@@ -0,0 +1,303 @@
+// ideal membership certificates, goal = sum_j cofactors[j] generators[j], read
+// off an extended Groebner basis, and their export as Lean (Mathlib) theorems
+// proved by linear_combination, or Coq lemmas over R proved by field. a
+// Nullstellensatz certificate is one for the goal 1: the generators have no
+// common complex zero, so the hypotheses are contradictory
+
+use std::fmt::Write;
+use std::rc::Rc;
+
+use crate::poly::extended_gb::ExtendedBasis;
+use crate::poly::mono::monomial_div;
+use crate::poly::order::MonomialOrder;
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+#[derive(Debug, Clone)]
+pub struct Certificate {
+    pub var_dict: Rc<Vec<String>>,
+    pub generators: Vec<Poly<Rat>>,
+    pub goal: Poly<Rat>,
+    // one per generator
+    pub cofactors: Vec<Poly<Rat>>,
+}
+
+impl ExtendedBasis {
+    // None unless p is in the ideal
+    pub fn certificate(&self, p: &Poly<Rat>) -> Option<Certificate> {
+        let mut rest = p.clone();
+        let mut cofactors = vec![Poly::constant(Rat::from(0)); self.generators.len()];
+
+        // the normal form of a member is zero, so every leading term is
+        // divisible by some member's
+        while !rest.is_zero() {
+            let lt = self.order.lt(&rest).clone();
+
+            let (m, i) = self
+                .members
+                .iter()
+                .enumerate()
+                .find_map(|(i, b)| monomial_div(&lt, self.order.lt(b)).map(|m| (m, i)))?;
+
+            let m = Poly { terms: vec![m] };
+            rest = rest - m.mul_ref(&self.members[i]);
+
+            for (c, b) in cofactors.iter_mut().zip(&self.cofactors[i]) {
+                *c = c.clone() + m.mul_ref(b);
+            }
+        }
+
+        Some(Certificate {
+            var_dict: self.var_dict.clone(),
+            generators: self.generators.clone(),
+            goal: p.clone(),
+            cofactors,
+        })
+    }
+}
+
+impl System<Rat> {
+    pub fn membership_certificate(&self, p: &Poly<Rat>) -> Option<Certificate> {
+        self.extended_gb_with_order(&MonomialOrder::Grlex)
+            .certificate(p)
+    }
+
+    // None if the members have a common complex zero
+    pub fn nullstellensatz_certificate(&self) -> Option<Certificate> {
+        self.membership_certificate(&Poly::constant(Rat::from(1)))
+    }
+
+    // just the multipliers: h with sum_i h[i] members[i] = 1, one per member
+    pub fn unsat_certificate(&self) -> Option<Vec<Poly<Rat>>> {
+        self.nullstellensatz_certificate()
+            .map(|cert| cert.cofactors)
+    }
+}
+
+// with explicit multiplication, parsed the same by Lean and Coq
+fn expr(p: &Poly<Rat>, var_dict: &[String]) -> String {
+    if p.is_zero() {
+        return "0".to_string();
+    }
+
+    let mut s = String::new();
+
+    for (i, term) in p.terms.iter().rev().enumerate() {
+        let (num, den) = (term.val.num, term.val.den);
+
+        if i == 0 && num < 0 {
+            s.push('-');
+        } else if i > 0 {
+            s.push_str(if num < 0 { " - " } else { " + " });
+        }
+
+        let mut factors = vec![];
+        if num.abs() != 1 || den != 1 || term.vars.is_empty() {
+            factors.push(if den == 1 {
+                num.abs().to_string()
+            } else {
+                format!("{}/{den}", num.abs())
+            });
+        }
+        for (v, pow) in &term.vars {
+            factors.push(if *pow == 1 {
+                var_dict[*v].clone()
+            } else {
+                format!("{}^{pow}", var_dict[*v])
+            });
+        }
+
+        s.push_str(&factors.join("*"));
+    }
+
+    s
+}
+
+impl Certificate {
+    pub fn check(&self) -> bool {
+        let combination = self
+            .cofactors
+            .iter()
+            .zip(&self.generators)
+            .fold(Poly::constant(Rat::from(0)), |acc, (c, g)| {
+                acc + c.mul_ref(g)
+            });
+
+        combination == self.goal
+    }
+
+    // whether the hypotheses are contradictory, rather than implying goal = 0
+    fn contradiction(&self) -> bool {
+        self.goal.is_constant() && !self.goal.is_zero()
+    }
+
+    // the nonzero terms (cofactor) * (generator)
+    fn combination(&self, hypotheses: bool) -> Vec<String> {
+        self.cofactors
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !c.is_zero())
+            .map(|(j, c)| {
+                let of = if hypotheses {
+                    format!("h{j}")
+                } else {
+                    format!("({})", expr(&self.generators[j], &self.var_dict))
+                };
+                format!("({}) * {of}", expr(c, &self.var_dict))
+            })
+            .collect()
+    }
+
+    // needs Mathlib
+    pub fn to_lean(&self, name: &str) -> String {
+        let mut s = format!("theorem {name} ({} : ℚ)", self.var_dict.join(" "));
+
+        for (j, g) in self.generators.iter().enumerate() {
+            write!(s, " (h{j} : {} = 0)", expr(g, &self.var_dict)).unwrap();
+        }
+
+        let mut combination = self.combination(true).join(" + ");
+        if combination.is_empty() {
+            combination = "0".to_string();
+        }
+        let goal = expr(&self.goal, &self.var_dict);
+
+        if self.contradiction() {
+            write!(
+                s,
+                " : False := by\n  have h : ({goal} : ℚ) = 0 := by linear_combination {combination}\n  norm_num at h\n"
+            )
+            .unwrap();
+        } else {
+            write!(
+                s,
+                " : {goal} = 0 := by\n  linear_combination {combination}\n"
+            )
+            .unwrap();
+        }
+
+        s
+    }
+
+    // needs Require Import Reals Lra and Open Scope R_scope
+    pub fn to_coq(&self, name: &str) -> String {
+        let mut s = format!("Lemma {name} : forall {} : R,", self.var_dict.join(" "));
+
+        for g in &self.generators {
+            write!(s, " {} = 0 ->", expr(g, &self.var_dict)).unwrap();
+        }
+
+        let hypotheses = (0..self.generators.len())
+            .map(|j| format!("h{j}"))
+            .collect::<Vec<_>>();
+        let used = (0..self.generators.len())
+            .filter(|j| !self.cofactors[*j].is_zero())
+            .map(|j| format!("h{j}"))
+            .collect::<Vec<_>>();
+
+        let goal = expr(&self.goal, &self.var_dict);
+        let mut combination = self.combination(false).join(" + ");
+        if combination.is_empty() {
+            combination = "0".to_string();
+        }
+
+        if self.contradiction() {
+            s.push_str(" False.\n");
+        } else {
+            writeln!(s, " {goal} = 0.").unwrap();
+        }
+
+        write!(
+            s,
+            "Proof.\n  intros {} {}.\n  assert (E : {goal} = {combination}) by field.\n",
+            self.var_dict.join(" "),
+            hypotheses.join(" ")
+        )
+        .unwrap();
+
+        if self.contradiction() {
+            if !used.is_empty() {
+                writeln!(s, "  rewrite {} in E.", used.join(", ")).unwrap();
+            }
+            s.push_str("  ring_simplify in E.\n  lra.\n");
+        } else {
+            write!(s, "  rewrite E").unwrap();
+            for h in &used {
+                write!(s, ", {h}").unwrap();
+            }
+            s.push_str(".\n  ring.\n");
+        }
+
+        s.push_str("Qed.\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::system;
+
+    #[test]
+    fn certificates() {
+        let sys = system! {
+            x*y - 1,
+            x - y
+        };
+
+        let goal = sys.var("y", 2) - sys.constant(1);
+        let cert = sys.membership_certificate(&goal).unwrap();
+        assert!(cert.check());
+
+        assert_eq!(
+            concat!(
+                "theorem square (x y : ℚ) (h0 : x*y - 1 = 0) (h1 : x - y = 0) : y^2 - 1 = 0 := by\n",
+                "  linear_combination (1) * h0 + (-y) * h1\n"
+            ),
+            cert.to_lean("square")
+        );
+        assert_eq!(
+            concat!(
+                "Lemma square : forall x y : R, x*y - 1 = 0 -> x - y = 0 -> y^2 - 1 = 0.\n",
+                "Proof.\n",
+                "  intros x y h0 h1.\n",
+                "  assert (E : y^2 - 1 = (1) * (x*y - 1) + (-y) * (x - y)) by field.\n",
+                "  rewrite E, h0, h1.\n",
+                "  ring.\n",
+                "Qed.\n"
+            ),
+            cert.to_coq("square")
+        );
+
+        // the zero goal needs no hypotheses
+        let cert = sys.membership_certificate(&sys.constant(0)).unwrap();
+        assert!(cert
+            .to_lean("zero")
+            .ends_with(":= by\n  linear_combination 0\n"));
+
+        assert!(sys.membership_certificate(&sys.var("y", 1)).is_none());
+        assert!(sys.nullstellensatz_certificate().is_none());
+        assert!(sys.unsat_certificate().is_none());
+
+        // two parallel lines
+        let sys = system! {
+            2*x + y,
+            4*x + 2*y - 3
+        };
+
+        let cert = sys.nullstellensatz_certificate().unwrap();
+        assert!(cert.check());
+        assert_eq!(Some(cert.cofactors.clone()), sys.unsat_certificate());
+        assert_eq!(
+            concat!(
+                "theorem parallel (x y : ℚ) (h0 : 2*x + y = 0) (h1 : 4*x + 2*y - 3 = 0) : False := by\n",
+                "  have h : (1 : ℚ) = 0 := by linear_combination (2/3) * h0 + (-1/3) * h1\n",
+                "  norm_num at h\n"
+            ),
+            cert.to_lean("parallel")
+        );
+        assert!(cert
+            .to_coq("parallel")
+            .contains("rewrite h0, h1 in E.\n  ring_simplify in E.\n  lra."));
+    }
+}
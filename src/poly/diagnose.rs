@@ -0,0 +1,164 @@
+use crate::field::{One, Zero};
+use crate::linear::linear_row;
+use crate::matrix::Matrix;
+use crate::poly::system::System;
+use crate::rational::Rat;
+
+// coefs of one member spread over more orders of magnitude than this are
+// likely a units mistake
+const MAX_SCALE_SPREAD: f64 = 1e6;
+
+// cheap static checks for modeling mistakes; members and vars by index
+#[derive(Debug, Clone, PartialEq)]
+pub enum Diagnostic {
+    UnusedVar(usize),
+    // a nonzero constant can never vanish, zero always does
+    ConstantMember { member: usize, satisfiable: bool },
+    // equal up to a constant factor; the later one adds nothing
+    DuplicateMember { member: usize, of: usize },
+    // linear members which together have no solution
+    InconsistentLinear(Vec<usize>),
+    // largest over smallest coef magnitude
+    ScaleSpread { member: usize, ratio: f64 },
+}
+
+// elimination on the linear members, each row followed by a column per member
+// recording which members it is a combination of; the members behind a row
+// reading 0 = c
+fn inconsistent_linear(sys: &System<Rat>) -> Option<Vec<usize>> {
+    let n = sys.var_dict.len();
+
+    // constants are reported on their own
+    let linear = (0..sys.members.len())
+        .filter(|i| sys.members[*i].total_deg() == 1)
+        .collect::<Vec<_>>();
+
+    // coefs of the vars, then the constant, then the identity
+    let rows = linear
+        .iter()
+        .enumerate()
+        .map(|(k, i)| {
+            let (mut row, c) = linear_row(&sys.members[*i], n)?;
+            row.push(c);
+            let mut unit = vec![Rat::zero(); linear.len()];
+            unit[k] = Rat::one();
+            row.extend(unit);
+            Some(row)
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let (a, pivots) = Matrix::new(n + 1 + linear.len(), rows)?.echelon();
+    let r = pivots.iter().filter(|col| **col < n).count();
+    if pivots.get(r) != Some(&n) {
+        return None;
+    }
+
+    Some(
+        (0..linear.len())
+            .filter(|k| !a[(r, n + 1 + k)].is_zero())
+            .map(|k| linear[k])
+            .collect(),
+    )
+}
+
+impl System<Rat> {
+    pub fn diagnose(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+
+        for v in 0..self.var_dict.len() {
+            if self.members.iter().all(|p| p.deg(v) == 0) {
+                diagnostics.push(Diagnostic::UnusedVar(v));
+            }
+        }
+
+        for (i, p) in self.members.iter().enumerate() {
+            if p.is_constant() {
+                diagnostics.push(Diagnostic::ConstantMember {
+                    member: i,
+                    satisfiable: p.is_zero(),
+                });
+                continue;
+            }
+
+            if let Some(of) = (0..i).find(|j| self.members[*j].monic() == p.monic()) {
+                diagnostics.push(Diagnostic::DuplicateMember { member: i, of });
+            }
+
+            let magnitudes = p.terms.iter().map(|t| f64::from(t.val).abs());
+            let (lo, hi) =
+                magnitudes.fold((f64::INFINITY, 0f64), |(lo, hi), m| (lo.min(m), hi.max(m)));
+
+            if hi / lo > MAX_SCALE_SPREAD {
+                diagnostics.push(Diagnostic::ScaleSpread {
+                    member: i,
+                    ratio: hi / lo,
+                });
+            }
+        }
+
+        if let Some(members) = inconsistent_linear(self) {
+            diagnostics.push(Diagnostic::InconsistentLinear(members));
+        }
+
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::Diagnostic;
+    use crate::system;
+
+    #[test]
+    fn diagnose() {
+        let mut sys = system! {
+            x + y - 1,
+            x^2 - y,
+            2*x + 2*y - 2,
+            x - y - 3,
+            0,
+            3,
+            1000000000*x^2 + y,
+            x + y + z - 5,
+            2*x + 2*y + 7
+        };
+
+        sys.var_dict = Rc::new(vec![
+            "x".to_string(),
+            "y".to_string(),
+            "z".to_string(),
+            "w".to_string(),
+        ]);
+
+        let diagnostics = sys.diagnose();
+
+        assert_eq!(
+            vec![
+                Diagnostic::UnusedVar(3),
+                Diagnostic::DuplicateMember { member: 2, of: 0 },
+                Diagnostic::ConstantMember {
+                    member: 4,
+                    satisfiable: true
+                },
+                Diagnostic::ConstantMember {
+                    member: 5,
+                    satisfiable: false
+                },
+                Diagnostic::ScaleSpread {
+                    member: 6,
+                    ratio: 1e9
+                },
+                Diagnostic::InconsistentLinear(vec![0, 8]),
+            ],
+            diagnostics
+        );
+
+        let sys = system! {
+            x*y - 1,
+            x - 2
+        };
+        assert!(sys.diagnose().is_empty());
+    }
+}
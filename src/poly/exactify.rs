@@ -0,0 +1,216 @@
+// rationals near the float coefs of a system, so measured data can go
+// through the exact pipeline deliberately; the report says how far each
+// member moved. a small perturbation can still change the solutions a lot,
+// e.g. by making an inconsistent system consistent
+
+use crate::poly::approx_gb::FloatSystem;
+use crate::poly::mono::Mono;
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exactify {
+    // each coef to the nearest rational with at most this denominator
+    BoundedDenominator(i64),
+    // the coefs of a member to multiples of 1/d, for the d up to this bound
+    // that moves them least
+    CommonDenominator(i64),
+}
+
+#[derive(Debug, Clone)]
+pub struct Exactified {
+    pub sys: System<Rat>,
+    // the largest change of a coef, per member
+    pub errors: Vec<f64>,
+    // the same, over the largest coef of the member
+    pub relative_errors: Vec<f64>,
+}
+
+impl Exactified {
+    pub fn max_error(&self) -> f64 {
+        self.errors.iter().copied().fold(0., f64::max)
+    }
+}
+
+// largest numerator magnitude that is safe to round to
+const MAX_NUM: f64 = (1u64 << 62) as f64;
+
+// closest to x with den <= max_den, from the convergents of the continued
+// fraction of x and the last semiconvergent
+fn best_rational(x: f64, max_den: i64) -> (i64, i64) {
+    let (mut p0, mut q0, mut p1, mut q1) = (0., 1., 1., 0.);
+    let mut r = x;
+
+    loop {
+        let a = r.floor();
+        let q2 = a * q1 + q0;
+
+        if q2 > max_den as f64 {
+            let k = ((max_den as f64 - q0) / q1).floor();
+            let (p, q) = (p0 + k * p1, q0 + k * q1);
+
+            return if (x - p / q).abs() < (x - p1 / q1).abs() {
+                (p as i64, q as i64)
+            } else {
+                (p1 as i64, q1 as i64)
+            };
+        }
+
+        (p0, q0, p1, q1) = (p1, q1, a * p1 + p0, q2);
+
+        if r == a || (x - p1 / q1).abs() == 0. {
+            return (p1 as i64, q1 as i64);
+        }
+
+        r = 1. / (r - a);
+    }
+}
+
+impl FloatSystem {
+    // None if a coef is not finite or too large for Rat, or the bound is not
+    // positive
+    pub fn exactify(&self, strategy: Exactify) -> Option<Exactified> {
+        let (Exactify::BoundedDenominator(bound) | Exactify::CommonDenominator(bound)) = strategy;
+
+        if bound < 1
+            || self
+                .members
+                .iter()
+                .flatten()
+                .any(|(_, c)| !c.is_finite() || c.abs() >= MAX_NUM / bound as f64)
+        {
+            return None;
+        }
+
+        let mut members = vec![];
+        let mut errors = vec![];
+        let mut relative_errors = vec![];
+
+        for f in &self.members {
+            let rounded = match strategy {
+                Exactify::BoundedDenominator(_) => f
+                    .iter()
+                    .map(|(_, c)| best_rational(*c, bound))
+                    .collect::<Vec<_>>(),
+                Exactify::CommonDenominator(_) => {
+                    let error = |d: f64| {
+                        f.iter()
+                            .map(|(_, c)| (c - (c * d).round() / d).abs())
+                            .fold(0., f64::max)
+                    };
+                    let d = (1..=bound)
+                        .map(|d| d as f64)
+                        .min_by(|a, b| error(*a).total_cmp(&error(*b)))
+                        .unwrap();
+
+                    f.iter()
+                        .map(|(_, c)| ((c * d).round() as i64, d as i64))
+                        .collect()
+                }
+            };
+
+            let error = f
+                .iter()
+                .zip(&rounded)
+                .map(|((_, c), (num, den))| (c - *num as f64 / *den as f64).abs())
+                .fold(0., f64::max);
+            let scale = f.iter().map(|(_, c)| c.abs()).fold(0., f64::max);
+            errors.push(error);
+            relative_errors.push(if scale > 0. { error / scale } else { 0. });
+
+            // the terms stay ascending; rounding to zero only drops some
+            let terms = f
+                .iter()
+                .zip(rounded)
+                .filter(|(_, (num, _))| *num != 0)
+                .map(|((vars, _), (num, den))| Mono {
+                    val: Rat::from(num) / Rat::from(den),
+                    vars: vars.clone(),
+                })
+                .collect();
+            members.push(Poly { terms });
+        }
+
+        Some(Exactified {
+            sys: System {
+                var_dict: self.var_dict.clone(),
+                members,
+            },
+            errors,
+            relative_errors,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Exactify;
+    use crate::poly::approx_gb::FloatSystem;
+    use crate::poly::system::System;
+    use crate::rational::Rat;
+    use crate::system;
+
+    #[test]
+    fn exactify() {
+        let sys = system! { x^2 - 3*x*y + 2, y - 7 };
+        let exact = sys
+            .to_float()
+            .exactify(Exactify::BoundedDenominator(1))
+            .unwrap();
+        assert_eq!(format!("{:?}", sys), format!("{:?}", exact.sys));
+        assert_eq!(0., exact.max_error());
+
+        // measured: x / 3 - y / 7 + 1 / 2, and 0.33 x + 0.67 y - 1 / 3
+        let measured = FloatSystem::new(
+            sys.var_dict.clone(),
+            vec![
+                vec![
+                    (vec![], 0.5),
+                    (vec![(1, 1)], -0.142857),
+                    (vec![(0, 1)], 0.333333),
+                ],
+                vec![
+                    (vec![], -0.3333),
+                    (vec![(1, 1)], 0.67),
+                    (vec![(0, 1)], 0.33),
+                ],
+            ],
+        );
+
+        let bounded = measured.exactify(Exactify::BoundedDenominator(10)).unwrap();
+        let rat = |num: i64, den: i64| Rat::from(num) / Rat::from(den);
+        let coefs = |sys: &System<Rat>, i: usize| {
+            sys.members[i]
+                .terms
+                .iter()
+                .map(|t| t.val)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            vec![rat(1, 2), rat(-1, 7), rat(1, 3)],
+            coefs(&bounded.sys, 0)
+        );
+        assert_eq!(
+            vec![rat(-1, 3), rat(2, 3), rat(1, 3)],
+            coefs(&bounded.sys, 1)
+        );
+        assert!(bounded.errors[0] < 1e-6 && bounded.errors[1] > 1e-3);
+        assert!((bounded.relative_errors[1] - bounded.errors[1] / 0.67).abs() < 1e-12);
+
+        // a single denominator of 42 fits the first member
+        let common = measured.exactify(Exactify::CommonDenominator(42)).unwrap();
+        assert_eq!(
+            vec![rat(21, 42), rat(-6, 42), rat(14, 42)],
+            coefs(&common.sys, 0)
+        );
+        assert_eq!(coefs(&bounded.sys, 1), coefs(&common.sys, 1));
+
+        let common = measured.exactify(Exactify::CommonDenominator(20)).unwrap();
+        assert!(common.errors[0] > 1e-2);
+
+        assert!(measured.exactify(Exactify::BoundedDenominator(0)).is_none());
+        let nan = FloatSystem::new(sys.var_dict.clone(), vec![vec![(vec![], f64::NAN)]]);
+        assert!(nan.exactify(Exactify::BoundedDenominator(10)).is_none());
+    }
+}
@@ -0,0 +1,315 @@
+// a reduced Groebner basis which remembers how each member is made from the
+// generators: members[i] = sum_j cofactors[i][j] generators[j]. with these, a
+// proof assistant can check membership certificates without trusting the
+// basis computation
+
+use std::rc::Rc;
+
+use crate::poly::groebner::GroebnerConfig;
+use crate::poly::json::{json_list, json_poly, json_string};
+use crate::poly::mono::{monomial_div, monomial_lcm, Mono};
+use crate::poly::order::MonomialOrder;
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+#[derive(Debug, Clone)]
+pub struct ExtendedBasis {
+    pub var_dict: Rc<Vec<String>>,
+    pub order: MonomialOrder,
+    pub generators: Vec<Poly<Rat>>,
+    pub members: Vec<Poly<Rat>>,
+    // one per member, each with one cofactor per generator
+    pub cofactors: Vec<Vec<Poly<Rat>>>,
+}
+
+// a poly with its cofactors
+type Tracked = (Poly<Rat>, Vec<Poly<Rat>>);
+
+fn scalar(val: Rat) -> Poly<Rat> {
+    Poly {
+        terms: vec![Mono { val, vars: vec![] }],
+    }
+}
+
+// m p
+fn mul(m: &Poly<Rat>, p: &Tracked) -> Tracked {
+    (m.mul_ref(&p.0), p.1.iter().map(|c| m.mul_ref(c)).collect())
+}
+
+fn sub(p: Tracked, q: Tracked) -> Tracked {
+    let cofactors = p.1.into_iter().zip(q.1).map(|(a, b)| a - b).collect();
+    (p.0 - q.0, cofactors)
+}
+
+fn s_poly(order: &MonomialOrder, p: &Tracked, q: &Tracked) -> Tracked {
+    let (p_lt, q_lt) = (order.lt(&p.0), order.lt(&q.0));
+    let lcm = Mono {
+        val: Rat::from(1),
+        ..monomial_lcm(p_lt.clone(), q_lt.clone())
+    };
+
+    let coef = |lt: &Mono<Rat>| Poly {
+        terms: vec![monomial_div(&lcm, lt).unwrap()],
+    };
+
+    sub(mul(&coef(p_lt), p), mul(&coef(q_lt), q))
+}
+
+// normal form of p modulo divisors, as order.rs, keeping the leading term of
+// p when tail_only
+fn reduce(order: &MonomialOrder, p: &Tracked, divisors: &[Tracked], tail_only: bool) -> Tracked {
+    let mut p = p.clone();
+    let mut rem = Poly::constant(Rat::from(0));
+
+    if tail_only && !p.0.is_zero() {
+        let lt = Poly {
+            terms: vec![order.lt(&p.0).clone()],
+        };
+        p.0 = p.0 - lt.clone();
+        rem = lt;
+    }
+
+    // p + rem keeps the cofactors of p
+    while !p.0.is_zero() {
+        let lt = order.lt(&p.0).clone();
+
+        let quot = divisors
+            .iter()
+            .filter(|d| !d.0.is_zero())
+            .find_map(|d| monomial_div(&lt, order.lt(&d.0)).map(|m| (m, d)));
+
+        match quot {
+            Some((m, d)) => p = sub(p, mul(&Poly { terms: vec![m] }, d)),
+            None => {
+                let lt = Poly { terms: vec![lt] };
+                p.0 = p.0 - lt.clone();
+                rem = rem + lt;
+            }
+        }
+    }
+
+    (rem, p.1)
+}
+
+impl System<Rat> {
+    // the same basis as gb_with_order, with cofactors
+    pub fn extended_gb_with_order(&self, order: &MonomialOrder) -> ExtendedBasis {
+        let n = self.members.len();
+
+        let mut members = self
+            .members
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| !p.is_zero())
+            .map(|(i, p)| {
+                let mut cofactors = vec![Poly::constant(Rat::from(0)); n];
+                cofactors[i] = Poly::constant(Rat::from(1));
+                (p.clone(), cofactors)
+            })
+            .collect::<Vec<_>>();
+
+        let mut pairs = vec![];
+        for i in 0..members.len() {
+            for j in i + 1..members.len() {
+                pairs.push((i, j));
+            }
+        }
+
+        // buchberger
+
+        while let Some((i, j)) = pairs.pop() {
+            let s = s_poly(order, &members[i], &members[j]);
+            let rem = reduce(order, &s, &members, false);
+
+            if !rem.0.is_zero() {
+                pairs.extend((0..members.len()).map(|k| (k, members.len())));
+                members.push(rem);
+            }
+        }
+
+        // minimize, then interreduce
+
+        let mut keep: Vec<Tracked> = vec![];
+
+        for (i, (p, cofactors)) in members.iter().enumerate() {
+            let redundant = members.iter().enumerate().any(|(j, (q, _))| {
+                i != j
+                    && monomial_div(order.lt(p), order.lt(q)).is_some()
+                    && (order.cmp(order.lt(p), order.lt(q)).is_ne() || j < i)
+            });
+
+            if !redundant {
+                keep.push((p.clone(), cofactors.clone()));
+            }
+        }
+
+        let mut reduced = (0..keep.len())
+            .map(|i| {
+                let others = keep
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, q)| q.clone())
+                    .collect::<Vec<_>>();
+
+                let (p, cofactors) = reduce(order, &keep[i], &others, true);
+
+                // norm scales by a constant, which the cofactors follow
+                let normed = p.norm();
+                let c = scalar(normed.terms[0].val / p.terms[0].val);
+                (normed, cofactors.iter().map(|q| c.mul_ref(q)).collect())
+            })
+            .collect::<Vec<Tracked>>();
+
+        reduced.sort_by(|(p, _), (q, _)| order.cmp(order.lt(p), order.lt(q)).reverse());
+
+        let (members, cofactors) = reduced.into_iter().unzip();
+
+        ExtendedBasis {
+            var_dict: self.var_dict.clone(),
+            order: order.clone(),
+            generators: self.members.clone(),
+            members,
+            cofactors,
+        }
+    }
+}
+
+impl System<Rat> {
+    // the basis gb_with_config gives, with cofactors; only the order and the
+    // normalization of config matter, the tracked run reduces every pair
+    pub fn extended_gb_with_config(&self, config: &GroebnerConfig) -> ExtendedBasis {
+        let mut extended = self.extended_gb_with_order(&config.order);
+
+        // normalizing scales by a constant, which the cofactors follow
+        for (p, cofactors) in extended.members.iter_mut().zip(&mut extended.cofactors) {
+            let normed = config.normalization.apply(p.clone(), &config.order);
+            let c = scalar(normed.terms[0].val / p.terms[0].val);
+            *cofactors = cofactors.iter().map(|q| c.mul_ref(q)).collect();
+            *p = normed;
+        }
+
+        extended
+    }
+}
+
+impl ExtendedBasis {
+    // recompute every member from its cofactors
+    pub fn check(&self) -> bool {
+        self.members
+            .iter()
+            .zip(&self.cofactors)
+            .all(|(p, cofactors)| {
+                let combination = cofactors
+                    .iter()
+                    .zip(&self.generators)
+                    .fold(Poly::constant(Rat::from(0)), |acc, (c, g)| {
+                        acc + c.mul_ref(g)
+                    });
+
+                combination == *p
+            })
+    }
+
+    // {"vars", "order", "generators", "basis": [{"poly", "cofactors"}]}
+    pub fn to_json(&self) -> String {
+        let n = self.var_dict.len();
+
+        let order = match &self.order {
+            MonomialOrder::Grlex => json_string("grlex"),
+            MonomialOrder::Grevlex => json_string("grevlex"),
+            MonomialOrder::Lex => json_string("lex"),
+            MonomialOrder::Block(blocks) => format!(
+                "{{\"block\":{}}}",
+                json_list(
+                    blocks
+                        .iter()
+                        .map(|block| json_list(block.iter().map(|v| v.to_string())))
+                )
+            ),
+        };
+
+        let basis = self
+            .members
+            .iter()
+            .zip(&self.cofactors)
+            .map(|(p, cofactors)| {
+                format!(
+                    "{{\"poly\":{},\"cofactors\":{}}}",
+                    json_poly(p, n),
+                    json_list(cofactors.iter().map(|c| json_poly(c, n)))
+                )
+            });
+
+        format!(
+            "{{\"vars\":{},\"order\":{},\"generators\":{},\"basis\":{}}}",
+            json_list(self.var_dict.iter().map(|v| json_string(v))),
+            order,
+            json_list(self.generators.iter().map(|g| json_poly(g, n))),
+            json_list(basis)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::poly::groebner::{GroebnerConfig, Normalization};
+    use crate::poly::order::MonomialOrder;
+    use crate::system;
+
+    #[test]
+    fn extended_gb() {
+        let sys = system! {
+            x^2*y + 1,
+            2*x + y*z - 1,
+            x - y^2*z^2 + 1
+        };
+
+        for order in [
+            MonomialOrder::Grlex,
+            MonomialOrder::Grevlex,
+            MonomialOrder::Lex,
+        ] {
+            let extended = sys.extended_gb_with_order(&order);
+            assert_eq!(sys.gb_with_order(&order).members, extended.members);
+            assert!(extended.check());
+        }
+
+        let sys = system! {
+            x*y - 1,
+            x - y,
+            0
+        };
+
+        let mut extended = sys.extended_gb_with_order(&MonomialOrder::Lex);
+        assert_eq!(
+            "[x - y, y^2 - 1]",
+            format!("{:?}", sys.gb_with_order(&MonomialOrder::Lex))
+        );
+        assert_eq!(
+            concat!(
+                r#"{"vars":["x","y"],"order":"lex","#,
+                r#""generators":[[["-1/1",[0,0]],["1/1",[1,1]]],[["-1/1",[0,1]],["1/1",[1,0]]],[]],"#,
+                r#""basis":[{"poly":[["-1/1",[0,1]],["1/1",[1,0]]],"cofactors":[[],[["1/1",[0,0]]],[]]},"#,
+                r#"{"poly":[["-1/1",[0,0]],["1/1",[0,2]]],"cofactors":[[["1/1",[0,0]]],[["-1/1",[0,1]]],[]]}]}"#
+            ),
+            extended.to_json()
+        );
+
+        extended.cofactors[1][1] = extended.cofactors[1][0].clone();
+        assert!(!extended.check());
+
+        let config = GroebnerConfig::new(MonomialOrder::Lex).normalization(Normalization::Monic);
+        let extended = system! { 2*x - 3*y, 3*y^2 - 2 }.extended_gb_with_config(&config);
+        assert_eq!(
+            vec!["x - 1.5y", "y^2 - 0.6666666666666666"],
+            extended
+                .members
+                .iter()
+                .map(|p| p.format(&extended.var_dict))
+                .collect::<Vec<_>>()
+        );
+        assert!(extended.check());
+    }
+}
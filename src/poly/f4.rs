@@ -0,0 +1,421 @@
+// Groebner bases by F4: instead of reducing one S-poly at a time, take all
+// the pairs whose lcm has the lowest degree, put both halves of each S-poly
+// as rows of a matrix together with every multiple of a member needed to
+// reduce them (symbolic preprocessing), and row reduce it once. the rows
+// whose leading monomial was not a leading monomial before are new members.
+// the rows are sparse, and the elimination only touches their nonzero
+// entries. pairs with coprime leading terms are skipped (Buchberger's first
+// criterion). the same loop runs over Q or, for modular methods, over GF(P)
+
+use std::collections::{HashMap, HashSet};
+
+use crate::field::Field;
+use crate::gf::Gf;
+use crate::poly::builder::PolyBuilder;
+use crate::poly::mono::{monomial_div, monomial_lcm, Mono};
+use crate::poly::order::MonomialOrder;
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::profile::{in_phase, phase};
+use crate::rational::Rat;
+use crate::sparse::Csr;
+
+type Exps = Vec<(usize, u64)>;
+
+// (column, coef), by increasing column, i.e. decreasing monomial
+pub type Row<T = Rat> = Vec<(usize, T)>;
+
+// the row reduction of the F4 matrices, where the time goes on large systems,
+// so the part an accelerated backend replaces (see f4_gpu for one mod P).
+// given nonzero rows sorted by leading column, returns rows spanning the
+// same space with distinct leading columns, each with leading coef 1
+pub trait Elimination<T: Field = Rat> {
+    fn echelon(&self, matrix: Vec<Row<T>>) -> Vec<Row<T>>;
+}
+
+// sparse elimination on the CPU, touching only the nonzero entries
+pub struct Sparse;
+
+impl<T: Field> Elimination<T> for Sparse {
+    fn echelon(&self, matrix: Vec<Row<T>>) -> Vec<Row<T>> {
+        let mut pivots: HashMap<usize, Row<T>> = HashMap::new();
+        let mut echelon = vec![];
+
+        for mut row in matrix {
+            while let Some(pivot) = row.first().and_then(|(col, _)| pivots.get(col)) {
+                row = sub_row(&row, row[0].1.clone(), pivot);
+            }
+
+            let Some((col, lc)) = row.first().cloned() else {
+                continue;
+            };
+
+            let inv = T::one() / lc;
+            let row = row
+                .into_iter()
+                .map(|(c, v)| (c, v * inv.clone()))
+                .collect::<Row<T>>();
+
+            echelon.push(row.clone());
+            pivots.insert(col, row);
+        }
+
+        echelon
+    }
+}
+
+fn mono<T: Field>(vars: &Exps) -> Mono<T> {
+    Mono {
+        val: T::one(),
+        vars: vars.clone(),
+    }
+}
+
+fn deg(vars: &Exps) -> u64 {
+    vars.iter().map(|(_, pow)| *pow).sum()
+}
+
+fn coprime<T: Field>(a: &Mono<T>, b: &Mono<T>) -> bool {
+    a.vars
+        .iter()
+        .all(|(v, _)| b.vars.iter().all(|(w, _)| v != w))
+}
+
+// row - c * pivot, both sorted by column
+fn sub_row<T: Field>(row: &Row<T>, c: T, pivot: &Row<T>) -> Row<T> {
+    let mut out = Vec::with_capacity(row.len() + pivot.len());
+    let (mut i, mut j) = (0, 0);
+
+    while i < row.len() || j < pivot.len() {
+        if j == pivot.len() || i < row.len() && row[i].0 < pivot[j].0 {
+            out.push(row[i].clone());
+            i += 1;
+        } else if i == row.len() || pivot[j].0 < row[i].0 {
+            out.push((pivot[j].0, pivot[j].1.clone() * -1 * c.clone()));
+            j += 1;
+        } else {
+            let val = row[i].1.clone() - c.clone() * pivot[j].1.clone();
+            if !val.is_zero() {
+                out.push((row[i].0, val));
+            }
+            i += 1;
+            j += 1;
+        }
+    }
+
+    out
+}
+
+// the rows as a Csr matrix, with its structured elimination: the rows which
+// lead their column are pivots as they are, and only the rest are reduced
+pub struct Structured;
+
+impl Elimination for Structured {
+    fn echelon(&self, matrix: Vec<Row>) -> Vec<Row> {
+        let cols = matrix
+            .iter()
+            .filter_map(|row| row.last())
+            .map(|(c, _)| c + 1)
+            .max();
+        let echelon = Csr::from_rows(cols.unwrap_or(0), matrix).unwrap().echelon();
+
+        (0..echelon.rows())
+            .map(|i| {
+                let (columns, vals) = echelon.row(i);
+                columns.iter().copied().zip(vals.iter().copied()).collect()
+            })
+            .collect()
+    }
+}
+
+impl System<Rat> {
+    // the reduced Groebner basis for order, the same as gb_with_order
+    pub fn gb_f4(&self, order: &MonomialOrder) -> System<Rat> {
+        self.gb_f4_with(order, &Sparse)
+    }
+
+    // the same, with the matrices row reduced by backend
+    pub fn gb_f4_with(&self, order: &MonomialOrder, backend: &dyn Elimination) -> System<Rat> {
+        System {
+            var_dict: self.var_dict.clone(),
+            members: f4(self.members.clone(), order, backend),
+        }
+        .reduce_gb(order)
+    }
+
+    // the reduced basis for order, monic, of the image of the system mod P,
+    // for modular methods; None if P divides a denominator. the basis need
+    // not be the image of the one over Q when P is unlucky
+    pub fn gb_f4_mod<const P: u64>(
+        &self,
+        order: &MonomialOrder,
+        backend: &dyn Elimination<Gf<P>>,
+    ) -> Option<Vec<Poly<Gf<P>>>> {
+        let members = self
+            .members
+            .iter()
+            .map(|p| {
+                let mut image = PolyBuilder::with_capacity(p.terms.len());
+                for t in &p.terms {
+                    image.push(Gf::from_rat(t.val)?, &t.vars);
+                }
+                Some(image.build())
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let basis = f4(members, order, backend);
+        Some(order.interreduce(&basis, |p| p.monic()))
+    }
+}
+
+// a Groebner basis for order, not reduced
+pub(crate) fn f4<T: Field>(
+    mut members: Vec<Poly<T>>,
+    order: &MonomialOrder,
+    backend: &dyn Elimination<T>,
+) -> Vec<Poly<T>> {
+    members.retain(|p| !p.is_zero());
+
+    let mut pairs = vec![];
+    for j in 0..members.len() {
+        for i in 0..j {
+            pairs.push((i, j));
+        }
+    }
+
+    while !pairs.is_empty() {
+        let lcm = |(i, j): (usize, usize)| {
+            monomial_lcm(order.lt(&members[i]).clone(), order.lt(&members[j]).clone())
+        };
+
+        let d = pairs.iter().map(|p| deg(&lcm(*p).vars)).min().unwrap();
+        let (selected, rest) = pairs
+            .into_iter()
+            .partition::<Vec<_>, _>(|p| deg(&lcm(*p).vars) == d);
+        pairs = rest;
+
+        let mut rows: Vec<Poly<T>> = vec![];
+        for (i, j) in selected {
+            if coprime(order.lt(&members[i]), order.lt(&members[j])) {
+                continue;
+            }
+
+            let lcm = mono(&lcm((i, j)).vars);
+            for k in [i, j] {
+                let m = monomial_div(&lcm, order.lt(&members[k])).unwrap();
+                let row = Poly { terms: vec![m] }.mul_ref(&members[k]);
+                if !rows.contains(&row) {
+                    rows.push(row);
+                }
+            }
+        }
+
+        let new = reduce(rows, &members, order, backend);
+        for p in new {
+            pairs.extend((0..members.len()).map(|k| (k, members.len())));
+            members.push(p);
+        }
+    }
+
+    members
+}
+
+// the rows with a reducer for every monomial some member's leading term
+// divides, row reduced; returns the rows whose leading monomial no row
+// had before
+fn reduce<T: Field>(
+    mut rows: Vec<Poly<T>>,
+    members: &[Poly<T>],
+    order: &MonomialOrder,
+    backend: &dyn Elimination<T>,
+) -> Vec<Poly<T>> {
+    in_phase("f4 symbolic preprocessing", || {
+        let mut done = rows
+            .iter()
+            .map(|r| order.lt(r).vars.clone())
+            .collect::<HashSet<_>>();
+        let mut next = 0;
+        while next < rows.len() {
+            let terms = rows[next].terms.clone();
+            next += 1;
+
+            for t in terms {
+                if !done.insert(t.vars.clone()) {
+                    continue;
+                }
+
+                let reducer = members
+                    .iter()
+                    .find_map(|g| monomial_div(&mono(&t.vars), order.lt(g)).map(|m| (m, g)));
+                if let Some((m, g)) = reducer {
+                    rows.push(Poly { terms: vec![m] }.mul_ref(g));
+                }
+            }
+        }
+    });
+
+    let _phase = phase("f4 reduction");
+    let leading = rows
+        .iter()
+        .map(|r| order.lt(r).vars.clone())
+        .collect::<HashSet<_>>();
+
+    let mut columns = rows
+        .iter()
+        .flat_map(|r| r.terms.iter().map(|t| t.vars.clone()))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    columns.sort_by(|a, b| order.cmp(&mono::<T>(a), &mono(b)).reverse());
+    let index = columns
+        .iter()
+        .enumerate()
+        .map(|(i, m)| (m.clone(), i))
+        .collect::<HashMap<_, _>>();
+
+    // rows with the leftmost leading entries first, so they become pivots
+    let mut matrix = rows
+        .iter()
+        .map(|r| {
+            let mut row = r
+                .terms
+                .iter()
+                .map(|t| (index[&t.vars], t.val.clone()))
+                .collect::<Row<T>>();
+            row.sort_by_key(|(col, _)| *col);
+            row
+        })
+        .collect::<Vec<_>>();
+    matrix.sort_by_key(|row| row[0].0);
+
+    backend
+        .echelon(matrix)
+        .into_iter()
+        .filter(|row| !leading.contains(&columns[row[0].0]))
+        .map(|row| {
+            let mut builder = PolyBuilder::with_capacity(row.len());
+            for (c, v) in row {
+                builder.push(v, &columns[c]);
+            }
+            builder.build()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::{Elimination, Row, Sparse, Structured};
+    use crate::distributed::PRIMES;
+    use crate::gf::Gf;
+    use crate::poly::order::MonomialOrder;
+    use crate::poly::system::System;
+    use crate::poly::Poly;
+    use crate::system;
+
+    // counts the matrices it is given
+    struct Counting(Cell<usize>);
+
+    impl Elimination for Counting {
+        fn echelon(&self, matrix: Vec<Row>) -> Vec<Row> {
+            self.0.set(self.0.get() + 1);
+            Sparse.echelon(matrix)
+        }
+    }
+
+    #[test]
+    fn f4() {
+        let systems = [
+            system! {
+                x + y^2 + z,
+                x - y + 3*z + 5,
+                x - 2*y + 3
+            },
+            system! {
+                x^2*y + 1,
+                2*x + y*z - 1,
+                x - y^2*z^2 + 1
+            },
+            system! {
+                x^2 + y^2 - 1,
+                x*y - 2
+            },
+            system! {
+                x*y,
+                x*z
+            },
+        ];
+
+        for sys in &systems {
+            for order in [
+                MonomialOrder::Grlex,
+                MonomialOrder::Grevlex,
+                MonomialOrder::Lex,
+            ] {
+                let expected = format!("{:?}", sys.gb_with_order(&order));
+                assert_eq!(expected, format!("{:?}", sys.gb_f4(&order)));
+                assert_eq!(
+                    expected,
+                    format!("{:?}", sys.gb_f4_with(&order, &Structured))
+                );
+            }
+        }
+
+        assert_eq!(
+            "[1]",
+            format!("{:?}", system! { x - 1, x - 2 }.gb_f4(&MonomialOrder::Lex))
+        );
+    }
+
+    #[test]
+    fn backend() {
+        let sys = system! {
+            x^2 + y^2 - 1,
+            x*y - 2
+        };
+
+        let counting = Counting(Cell::new(0));
+        assert_eq!(
+            format!("{:?}", sys.gb_f4(&MonomialOrder::Lex)),
+            format!("{:?}", sys.gb_f4_with(&MonomialOrder::Lex, &counting))
+        );
+        assert!(counting.0.get() > 0);
+
+        // mod a lucky prime, the image of the basis over Q made monic
+        const P: u64 = PRIMES[0];
+        for order in [MonomialOrder::Grevlex, MonomialOrder::Lex] {
+            let image = sys
+                .gb_with_order(&order)
+                .members
+                .iter()
+                .map(|p| {
+                    let p = p.monic();
+                    p.terms
+                        .iter()
+                        .map(|t| (t.vars.clone(), Gf::<P>::from_rat(t.val).unwrap()))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let basis = sys.gb_f4_mod::<P>(&order, &Sparse).unwrap();
+            let basis = basis
+                .iter()
+                .map(|p| {
+                    p.terms
+                        .iter()
+                        .map(|t| (t.vars.clone(), t.val))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(image, basis);
+        }
+
+        // 3 has no inverse mod 3
+        let mut var_dict = vec![];
+        let third = Poly::parse("x/3 - 1", &mut var_dict).unwrap();
+        let thirds = System::new(var_dict, vec![third]).unwrap();
+        assert!(thirds
+            .gb_f4_mod::<3>(&MonomialOrder::Lex, &Sparse)
+            .is_none());
+    }
+}
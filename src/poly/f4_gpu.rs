@@ -0,0 +1,382 @@
+// F4 row reduction mod P on the GPU, behind the experimental gpu feature.
+// the matrix is made dense and reduced by Gauss-Jordan elimination, three
+// dispatches per column: pick and scale the pivot row, read off each other
+// row's multiple of it, and subtract. all of them are recorded up front and
+// the matrix is read back once. WGSL has no 64-bit ints, so products mod P
+// are taken by doubling and adding, and P must be below 2^31. without an
+// adapter, for a larger P, or for a matrix past the device's buffer limits
+// the rows go to Sparse on the CPU instead
+
+use wgpu::util::DeviceExt;
+
+use crate::field::Zero;
+use crate::gf::Gf;
+use crate::poly::f4::{Elimination, Row, Sparse};
+
+const SHADER: &str = "
+struct State {
+    rank: u32,
+    found: u32,
+    col: u32,
+}
+
+// rows, cols, p
+@group(0) @binding(0) var<uniform> dims: vec4<u32>;
+@group(0) @binding(1) var<storage, read_write> a: array<u32>;
+@group(0) @binding(2) var<storage, read_write> state: State;
+@group(0) @binding(3) var<storage, read_write> factor: array<u32>;
+
+// x * y mod p, with every partial sum below 2^32
+fn mul_mod(x: u32, y: u32) -> u32 {
+    let p = dims.z;
+    var acc = 0u;
+    for (var bit = 31i; bit >= 0i; bit--) {
+        acc = (acc * 2u) % p;
+        if ((y >> u32(bit)) & 1u) == 1u {
+            acc = (acc + x) % p;
+        }
+    }
+    return acc;
+}
+
+fn inv_mod(x: u32) -> u32 {
+    var base = x;
+    var exp = dims.z - 2u;
+    var acc = 1u;
+    while exp > 0u {
+        if (exp & 1u) == 1u {
+            acc = mul_mod(acc, base);
+        }
+        base = mul_mod(base, base);
+        exp = exp >> 1u;
+    }
+    return acc;
+}
+
+// the next column: swap the first row at or below rank with an entry in it
+// up to rank, and scale it to a leading 1
+@compute @workgroup_size(1)
+fn pivot() {
+    let cols = dims.y;
+    state.col += 1u;
+    state.found = 0u;
+
+    let c = state.col;
+    let r = state.rank;
+    for (var i = r; i < dims.x; i++) {
+        if a[i * cols + c] != 0u {
+            for (var j = 0u; j < cols; j++) {
+                let t = a[i * cols + j];
+                a[i * cols + j] = a[r * cols + j];
+                a[r * cols + j] = t;
+            }
+            let inv = inv_mod(a[r * cols + c]);
+            for (var j = 0u; j < cols; j++) {
+                a[r * cols + j] = mul_mod(a[r * cols + j], inv);
+            }
+            state.found = 1u;
+            state.rank = r + 1u;
+            return;
+        }
+    }
+}
+
+@compute @workgroup_size(64)
+fn factors(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if i >= dims.x {
+        return;
+    }
+    if state.found == 0u || i == state.rank - 1u {
+        factor[i] = 0u;
+    } else {
+        factor[i] = a[i * dims.y + state.col];
+    }
+}
+
+@compute @workgroup_size(8, 8)
+fn eliminate(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.y;
+    let j = id.x;
+    if i >= dims.x || j >= dims.y || factor[i] == 0u {
+        return;
+    }
+    let pivot = a[(state.rank - 1u) * dims.y + j];
+    let k = i * dims.y + j;
+    a[k] = (a[k] + dims.z - mul_mod(factor[i], pivot)) % dims.z;
+}
+";
+
+struct Context {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    layout: wgpu::BindGroupLayout,
+    // pivot, factors, eliminate
+    pipelines: [wgpu::ComputePipeline; 3],
+}
+
+pub struct Gpu {
+    context: Option<Context>,
+}
+
+impl Gpu {
+    // on the first adapter wgpu finds; with none, every matrix goes to Sparse
+    pub fn new() -> Gpu {
+        Gpu {
+            context: pollster::block_on(context()),
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.context.is_some()
+    }
+}
+
+impl Default for Gpu {
+    fn default() -> Self {
+        Gpu::new()
+    }
+}
+
+async fn context() -> Option<Context> {
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+    let adapter = instance
+        .request_adapter(&wgpu::RequestAdapterOptions::default())
+        .await?;
+    let (device, queue) = adapter
+        .request_device(&wgpu::DeviceDescriptor::default(), None)
+        .await
+        .ok()?;
+
+    let entry = |binding, ty| wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::COMPUTE,
+        ty: wgpu::BindingType::Buffer {
+            ty,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    };
+    let storage = wgpu::BufferBindingType::Storage { read_only: false };
+    let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: None,
+        entries: &[
+            entry(0, wgpu::BufferBindingType::Uniform),
+            entry(1, storage),
+            entry(2, storage),
+            entry(3, storage),
+        ],
+    });
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: None,
+        bind_group_layouts: &[&layout],
+        push_constant_ranges: &[],
+    });
+
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+    });
+    let pipelines = ["pivot", "factors", "eliminate"].map(|entry_point| {
+        device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: None,
+            layout: Some(&pipeline_layout),
+            module: &module,
+            entry_point: Some(entry_point),
+            compilation_options: Default::default(),
+            cache: None,
+        })
+    });
+
+    Some(Context {
+        device,
+        queue,
+        layout,
+        pipelines,
+    })
+}
+
+fn bytes(words: &[u32]) -> Vec<u8> {
+    words.iter().flat_map(|w| w.to_le_bytes()).collect()
+}
+
+impl<const P: u64> Elimination<Gf<P>> for Gpu {
+    fn echelon(&self, matrix: Vec<Row<Gf<P>>>) -> Vec<Row<Gf<P>>> {
+        let dense = self
+            .context
+            .as_ref()
+            .filter(|_| P < 1 << 31)
+            .and_then(|context| echelon_on(context, &matrix));
+
+        dense
+            .map(|rows| {
+                rows.into_iter()
+                    .map(|row| {
+                        row.into_iter()
+                            .enumerate()
+                            .map(|(c, v)| (c, Gf::new(v as u64)))
+                            .filter(|(_, v)| !v.is_zero())
+                            .collect()
+                    })
+                    .collect()
+            })
+            .unwrap_or_else(|| Sparse.echelon(matrix))
+    }
+}
+
+// the nonzero rows of the reduced row echelon form, dense, or None if the
+// matrix doesn't fit the device's buffers
+fn echelon_on<const P: u64>(context: &Context, matrix: &[Row<Gf<P>>]) -> Option<Vec<Vec<u32>>> {
+    let device = &context.device;
+    let rows = matrix.len();
+    let cols = matrix
+        .iter()
+        .filter_map(|row| row.last())
+        .map(|(c, _)| c + 1)
+        .max()?;
+
+    let size = (rows * cols * 4) as u64;
+    let limits = device.limits();
+    if size > limits.max_storage_buffer_binding_size as u64 || size > limits.max_buffer_size {
+        return None;
+    }
+
+    let mut dense = vec![0u32; rows * cols];
+    for (i, row) in matrix.iter().enumerate() {
+        for (c, v) in row {
+            dense[i * cols + c] = v.value() as u32;
+        }
+    }
+
+    let init = |contents: &[u32], usage| {
+        device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: None,
+            contents: &bytes(contents),
+            usage,
+        })
+    };
+    let storage = wgpu::BufferUsages::STORAGE;
+    let dims = init(
+        &[rows as u32, cols as u32, P as u32, 0],
+        wgpu::BufferUsages::UNIFORM,
+    );
+    let a = init(&dense, storage | wgpu::BufferUsages::COPY_SRC);
+    // the first pivot dispatch moves col to 0
+    let state = init(&[0, 0, u32::MAX], storage);
+    let factor = init(&vec![0; rows], storage);
+    let read = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let entries = [&dims, &a, &state, &factor]
+        .iter()
+        .enumerate()
+        .map(|(i, buffer)| wgpu::BindGroupEntry {
+            binding: i as u32,
+            resource: buffer.as_entire_binding(),
+        })
+        .collect::<Vec<_>>();
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: None,
+        layout: &context.layout,
+        entries: &entries,
+    });
+
+    let groups = |n: usize, size: usize| n.div_ceil(size) as u32;
+    let mut encoder = device.create_command_encoder(&Default::default());
+    {
+        let mut pass = encoder.begin_compute_pass(&Default::default());
+        pass.set_bind_group(0, &bind_group, &[]);
+        let [pivot, factors, eliminate] = &context.pipelines;
+        for _ in 0..cols {
+            pass.set_pipeline(pivot);
+            pass.dispatch_workgroups(1, 1, 1);
+            pass.set_pipeline(factors);
+            pass.dispatch_workgroups(groups(rows, 64), 1, 1);
+            pass.set_pipeline(eliminate);
+            pass.dispatch_workgroups(groups(cols, 8), groups(rows, 8), 1);
+        }
+    }
+    encoder.copy_buffer_to_buffer(&a, 0, &read, 0, size);
+    context.queue.submit([encoder.finish()]);
+
+    let slice = read.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| {});
+    device.poll(wgpu::Maintain::Wait);
+    let words = slice
+        .get_mapped_range()
+        .chunks_exact(4)
+        .map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]]))
+        .collect::<Vec<_>>();
+
+    Some(
+        words
+            .chunks(cols)
+            .filter(|row| row.iter().any(|v| *v != 0))
+            .map(|row| row.to_vec())
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Gpu;
+    use crate::distributed::PRIMES;
+    use crate::gf::Gf;
+    use crate::poly::f4::{Elimination, Row, Sparse};
+    use crate::poly::order::MonomialOrder;
+    use crate::system;
+
+    #[test]
+    fn gpu() {
+        // on the GPU if there is one, and on the CPU otherwise
+        let gpu = Gpu::new();
+
+        // Gauss-Jordan clears above the pivots too, unlike Sparse
+        type F = Gf<{ PRIMES[0] }>;
+        let matrix: Vec<Row<F>> = vec![
+            vec![(0, F::from(1)), (1, F::from(2)), (2, F::from(3))],
+            vec![(0, F::from(2)), (1, F::from(4)), (2, F::from(7))],
+        ];
+        let expected = if gpu.is_available() {
+            vec![
+                vec![(0, F::from(1)), (1, F::from(2))],
+                vec![(2, F::from(1))],
+            ]
+        } else {
+            Sparse.echelon(matrix.clone())
+        };
+        assert_eq!(expected, gpu.echelon(matrix));
+        let systems = [
+            system! {
+                x^2*y + 1,
+                2*x + y*z - 1,
+                x - y^2*z^2 + 1
+            },
+            system! {
+                x^2 + y^2 - 5,
+                3*x*y - 2
+            },
+        ];
+
+        for sys in &systems {
+            for order in [MonomialOrder::Grevlex, MonomialOrder::Lex] {
+                assert_eq!(
+                    sys.gb_f4_mod::<{ PRIMES[0] }>(&order, &Sparse),
+                    sys.gb_f4_mod::<{ PRIMES[0] }>(&order, &gpu)
+                );
+
+                // too large a prime for the shader
+                const MERSENNE: u64 = (1 << 61) - 1;
+                assert_eq!(
+                    sys.gb_f4_mod::<MERSENNE>(&order, &Sparse),
+                    sys.gb_f4_mod::<MERSENNE>(&order, &gpu)
+                );
+            }
+        }
+    }
+}
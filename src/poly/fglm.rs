@@ -0,0 +1,200 @@
+// FGLM: convert a Groebner basis of a zero-dimensional ideal from one order
+// to another by linear algebra in Q[x]/I, without another Buchberger run.
+// the monomials are visited in increasing new order; the normal form of each
+// under the old basis is either a combination of the normal forms of the
+// monomials kept so far, giving a new basis member, or independent, making
+// the monomial a new standard monomial. multiples of new leading terms are
+// skipped. computing in grevlex and converting to lex is usually much faster
+// than computing in lex directly
+
+use std::collections::HashMap;
+
+use crate::field::{One, Zero};
+use crate::poly::builder::PolyBuilder;
+use crate::poly::mono::{monomial_div, monomial_mul, Mono};
+use crate::poly::order::MonomialOrder;
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::profile::phase;
+use crate::rational::Rat;
+
+type Exps = Vec<(usize, u64)>;
+
+// a normal form as coordinates by monomial, and the combination of the
+// standard monomials' normal forms it equals
+struct Pivot {
+    mono: Exps,
+    vec: HashMap<Exps, Rat>,
+    combo: Vec<Rat>,
+}
+
+fn mono(vars: Exps) -> Mono<Rat> {
+    Mono {
+        val: Rat::one(),
+        vars,
+    }
+}
+
+fn axpy(y: &mut HashMap<Exps, Rat>, c: Rat, x: &HashMap<Exps, Rat>) {
+    for (m, v) in x {
+        let entry = y.entry(m.clone()).or_insert(Rat::zero());
+        *entry = *entry + c * *v;
+        if entry.is_zero() {
+            y.remove(m);
+        }
+    }
+}
+
+impl System<Rat> {
+    // self must be a Groebner basis for from, e.g. from gb_with_order; the
+    // reduced basis of the same ideal for to, or None unless the ideal is
+    // zero-dimensional
+    pub fn convert_order(&self, from: &MonomialOrder, to: &MonomialOrder) -> Option<System<Rat>> {
+        let gb = self
+            .members
+            .iter()
+            .filter(|p| !p.is_zero())
+            .cloned()
+            .collect::<Vec<_>>();
+        let n_vars = self.var_dict.len();
+
+        let lts = gb.iter().map(|p| from.lt(p)).collect::<Vec<_>>();
+        let bounded = lts.iter().any(|lt| lt.vars.is_empty())
+            || (0..n_vars).all(|var| {
+                lts.iter()
+                    .any(|lt| lt.vars.len() == 1 && lt.vars[0].0 == var)
+            });
+        if !bounded {
+            return None;
+        }
+
+        let _phase = phase("fglm");
+        let mut members: Vec<Poly<Rat>> = vec![];
+        let mut standard: Vec<Exps> = vec![];
+        let mut pivots: Vec<Pivot> = vec![];
+        let mut candidates = vec![vec![]];
+
+        while !candidates.is_empty() {
+            let next = (0..candidates.len())
+                .min_by(|i, j| to.cmp(&mono(candidates[*i].clone()), &mono(candidates[*j].clone())))
+                .unwrap();
+            let m = mono(candidates.swap_remove(next));
+
+            if members.iter().any(|g| monomial_div(&m, to.lt(g)).is_some()) {
+                continue;
+            }
+
+            let nf = from.reduce(
+                &Poly {
+                    terms: vec![m.clone()],
+                },
+                &gb,
+            );
+            let mut vec = nf
+                .terms
+                .into_iter()
+                .map(|t| (t.vars, t.val))
+                .collect::<HashMap<_, _>>();
+            let mut combo = vec![Rat::zero(); standard.len()];
+
+            // vec is nf minus the combination in combo
+            for pivot in &pivots {
+                if let Some(c) = vec.get(&pivot.mono).copied() {
+                    axpy(&mut vec, Rat::zero() - c, &pivot.vec);
+                    for (acc, p) in combo.iter_mut().zip(&pivot.combo) {
+                        *acc = *acc + c * *p;
+                    }
+                }
+            }
+
+            if vec.is_empty() {
+                let mut g = PolyBuilder::with_capacity(standard.len() + 1);
+                g.push(Rat::one(), &m.vars);
+                for (c, s) in combo.iter().zip(&standard) {
+                    g.push(*c * -1, s);
+                }
+                members.push(g.build());
+                continue;
+            }
+
+            let pivot_mono = vec.keys().max().unwrap().clone();
+            let inv = Rat::one() / vec[&pivot_mono];
+            for v in vec.values_mut() {
+                *v = *v * inv;
+            }
+            let mut combo = combo.into_iter().map(|c| c * -1 * inv).collect::<Vec<_>>();
+            combo.push(inv);
+            for pivot in &mut pivots {
+                pivot.combo.push(Rat::zero());
+            }
+
+            pivots.push(Pivot {
+                mono: pivot_mono,
+                vec,
+                combo,
+            });
+
+            for var in 0..n_vars {
+                let next = monomial_mul(&m, &mono(vec![(var, 1)])).vars;
+                if !candidates.contains(&next) {
+                    candidates.push(next);
+                }
+            }
+            standard.push(m.vars);
+        }
+
+        Some(
+            System {
+                var_dict: self.var_dict.clone(),
+                members,
+            }
+            .reduce_gb(to),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::poly::order::MonomialOrder;
+    use crate::system;
+
+    #[test]
+    fn fglm() {
+        let systems = [
+            system! {
+                x^2 + y^2 - 1,
+                x*y - 2
+            },
+            system! {
+                x^2 - 2*y,
+                y^2 - x*y + 1,
+                z - x - y
+            },
+            system! {
+                x - 1,
+                x - 2
+            },
+        ];
+
+        for sys in &systems {
+            let grevlex = sys.gb_with_order(&MonomialOrder::Grevlex);
+            for order in [MonomialOrder::Lex, MonomialOrder::Grlex] {
+                assert_eq!(
+                    format!("{:?}", sys.gb_with_order(&order)),
+                    format!(
+                        "{:?}",
+                        grevlex
+                            .convert_order(&MonomialOrder::Grevlex, &order)
+                            .unwrap()
+                    )
+                );
+            }
+        }
+
+        // a curve has infinitely many solutions
+        let circle = system! { x^2 + y^2 - 1 }.gb_with_order(&MonomialOrder::Grevlex);
+        assert!(circle
+            .convert_order(&MonomialOrder::Grevlex, &MonomialOrder::Lex)
+            .is_none());
+    }
+}
@@ -0,0 +1,361 @@
+use crate::field::Field;
+use crate::poly::Poly;
+use crate::trace::Trace;
+
+impl<T: Field> Poly<T> {
+    pub fn is_constant(&self) -> bool {
+        self.terms.iter().all(|term| term.vars.is_empty())
+    }
+
+    // highest variable index appearing in any term
+    fn main_var(&self) -> Option<usize> {
+        self.terms
+            .iter()
+            .flat_map(|term| term.vars.iter().map(|(var, _)| *var))
+            .max()
+    }
+
+    // scale so the (grlex) leading coef is 1
+    pub fn monic(&self) -> Poly<T> {
+        match self.terms.last() {
+            Some(lt) => self.mul_ref(&Poly::constant(T::one() / lt.val.clone())),
+            None => self.clone(),
+        }
+    }
+
+    // lc(b)^(deg(a) - deg(b) + 1) a mod b, treating both as univariate in var
+    fn pseudo_rem(&self, divisor: &Poly<T>, var: usize) -> Poly<T> {
+        let mut rem = self.coefs(var);
+        let div = divisor.coefs(var);
+        // steps skipped by leading zero coefs still owe a factor of lc(b)
+        let mut steps = (rem.len() + 1).saturating_sub(div.len());
+
+        while rem.len() >= div.len() && !rem.is_empty() {
+            let lc_rem = rem[0].clone();
+
+            for (i, r) in rem.iter_mut().enumerate() {
+                *r = div[0].mul_ref(r);
+                if i < div.len() {
+                    *r = r.clone() - lc_rem.mul_ref(&div[i]);
+                }
+            }
+
+            steps -= 1;
+            rem.remove(0);
+            while rem.first().is_some_and(|c| c.is_zero()) {
+                rem.remove(0);
+            }
+        }
+
+        if rem.is_empty() {
+            Poly::constant(T::zero())
+        } else {
+            Poly::from_uni_fmt(rem, var).mul_ref(&pow(&div[0], steps))
+        }
+    }
+
+    // gcd of the coefs w.r.t. var, monic
+    pub fn content(&self, var: usize) -> Poly<T> {
+        self.coefs(var)
+            .iter()
+            .fold(Poly::constant(T::zero()), |acc, c| acc.gcd(c))
+    }
+
+    // self divided by its content w.r.t. var
+    pub fn primitive_part(&self, var: usize) -> Poly<T> {
+        if self.is_zero() {
+            return self.clone();
+        }
+
+        self.try_divide(&self.content(var)).unwrap()
+    }
+
+    // the product of the distinct irreducible factors, monic; self over its gcd
+    // with all of its partial derivatives, assuming characteristic zero
+    pub fn squarefree_part(&self) -> Poly<T> {
+        let Some(var) = self.main_var() else {
+            return self.monic();
+        };
+
+        let g = (0..=var).fold(self.clone(), |g, v| g.gcd(&self.derivative(v)));
+        self.try_divide(&g).unwrap().monic()
+    }
+
+    // monic squarefree, pairwise coprime (factor, multiplicity) with self a
+    // constant times the product of factor^multiplicity, assuming
+    // characteristic zero. Yun on the primitive part in the main variable,
+    // whose divisions are exact by Gauss' lemma; the content is decomposed
+    // recursively
+    pub fn squarefree_decomposition(&self) -> Vec<(Poly<T>, usize)> {
+        let Some(var) = self.main_var() else {
+            return vec![];
+        };
+
+        let mut factors = self.content(var).squarefree_decomposition();
+        let mut merge = |f: Poly<T>, mult: usize| match factors.iter_mut().find(|(_, m)| *m == mult)
+        {
+            Some((g, _)) => *g = g.mul_ref(&f).monic(),
+            None => factors.push((f, mult)),
+        };
+
+        let p = self.primitive_part(var);
+        let derivative = p.derivative(var);
+        let a = p.gcd(&derivative);
+        let mut b = p.try_divide(&a).unwrap();
+        let mut d = derivative.try_divide(&a).unwrap() - b.derivative(var);
+        let mut mult = 1;
+
+        while b.deg(var) > 0 {
+            let a = b.gcd(&d);
+            b = b.try_divide(&a).unwrap();
+            d = d.try_divide(&a).unwrap() - b.derivative(var);
+
+            if !a.is_constant() {
+                merge(a, mult);
+            }
+            mult += 1;
+        }
+
+        factors.sort_by_key(|(_, mult)| *mult);
+        factors
+    }
+
+    // monic gcd, by recursion on the main variable with subresultant
+    // remainder sequences
+    pub fn gcd(&self, other: &Poly<T>) -> Poly<T> {
+        self.gcd_traced(other, &mut Trace::disabled())
+    }
+
+    // records the content gcd and the subresultant remainder sequence in the
+    // main variable; the gcds of the contents are not traced
+    pub fn gcd_traced(&self, other: &Poly<T>, trace: &mut Trace<Poly<T>>) -> Poly<T> {
+        if self.is_zero() {
+            return other.monic();
+        }
+
+        if other.is_zero() {
+            return self.monic();
+        }
+
+        let var = match self.main_var().max(other.main_var()) {
+            Some(var) => var,
+            None => return Poly::constant(T::one()),
+        };
+
+        let (self_content, other_content) = (self.content(var), other.content(var));
+        let content = self_content.gcd(&other_content);
+        trace.record("content", &content);
+
+        let mut a = self.try_divide(&self_content).unwrap();
+        let mut b = other.try_divide(&other_content).unwrap();
+
+        if a.deg(var) < b.deg(var) {
+            std::mem::swap(&mut a, &mut b);
+        }
+
+        trace.record("a", &a);
+        trace.record("b", &b);
+
+        // divide each pseudo remainder by g h^delta, which keeps the coefs
+        // from growing exponentially without taking contents at every step
+        let (mut g, mut h) = (Poly::constant(T::one()), Poly::constant(T::one()));
+
+        while b.deg(var) > 0 {
+            let delta = a.deg(var) - b.deg(var);
+            let rem = a.pseudo_rem(&b, var);
+
+            if rem.is_zero() {
+                break;
+            }
+
+            a = b;
+            b = rem.try_divide(&g.mul_ref(&pow(&h, delta))).unwrap();
+            trace.record("sres", &b);
+
+            g = a.coefs(var).swap_remove(0);
+            if delta > 0 {
+                h = pow(&g, delta).try_divide(&pow(&h, delta - 1)).unwrap();
+            }
+        }
+
+        let gcd = if b.deg(var) == 0 {
+            // primitive parts are coprime
+            content
+        } else {
+            content.mul_ref(&b.primitive_part(var)).monic()
+        };
+
+        trace.record("gcd", &gcd);
+        gcd
+    }
+}
+
+fn pow<T: Field>(p: &Poly<T>, exp: usize) -> Poly<T> {
+    (0..exp).fold(Poly::constant(T::one()), |acc, _| acc.mul_ref(p))
+}
+
+// pairwise coprime, monic, non-constant polys such that each nonzero p in ps
+// is a constant times a product of their powers
+pub fn gcd_free_basis<T: Field>(ps: &[Poly<T>]) -> Vec<Poly<T>> {
+    let mut basis: Vec<Poly<T>> = vec![];
+    let mut pending: Vec<Poly<T>> = ps.iter().map(|p| p.monic()).collect();
+
+    // each split strictly lowers the total degree of basis + pending
+    while let Some(p) = pending.pop() {
+        if p.is_constant() {
+            continue;
+        }
+
+        let common = basis
+            .iter()
+            .enumerate()
+            .map(|(i, b)| (i, b.gcd(&p)))
+            .find(|(_, g)| !g.is_constant());
+
+        match common {
+            Some((i, g)) => {
+                let b = basis.swap_remove(i);
+                pending.push(b.try_divide(&g).unwrap().monic());
+                pending.push(p.try_divide(&g).unwrap().monic());
+                pending.push(g);
+            }
+            None => basis.push(p),
+        }
+    }
+
+    basis
+}
+
+#[cfg(test)]
+mod tests {
+    use super::gcd_free_basis;
+    use crate::poly::Poly;
+    use crate::rational::Rat;
+    use crate::system;
+
+    #[test]
+    fn gcd() {
+        let sys = system! {
+            x^2*y - y^3 + 2*x*y - 2*y^2,
+            x^2 + x*y - 2*x - 2*y,
+            3*x^2 - 6*x + 3,
+            2*x + 2
+        };
+
+        let p = &sys.members;
+
+        // y(x - y)(x + y + 2), (x + y)(x - 2)
+        assert_eq!("1", p[0].gcd(&p[1]).format(&sys.var_dict));
+        // y(x + y)(x - y + 2), (x + y)(x - 2)
+        let q = (sys.var("x", 1) + sys.var("y", 1))
+            * (sys.var("x", 1) - sys.var("y", 1) + sys.constant(2))
+            * sys.var("y", 1);
+        assert_eq!("x + y", q.gcd(&p[1]).format(&sys.var_dict));
+
+        assert_eq!(
+            "x^2 - 2x + 1",
+            p[2].gcd(&sys.var("x", 2).mul_ref(&p[2]))
+                .format(&sys.var_dict)
+        );
+        assert_eq!("1", p[2].gcd(&p[3]).format(&sys.var_dict));
+        assert_eq!(
+            "x + 1",
+            p[3].gcd(&Poly::constant(Rat::from(0)))
+                .format(&sys.var_dict)
+        );
+
+        // degrees in y differ by 2, so the subresultant divisor h is not 1
+        let (x, y) = (sys.var("x", 1), sys.var("y", 1));
+        let common = x.mul_ref(&y) + sys.constant(1);
+        let a = common.mul_ref(&(sys.var("y", 3) + x.clone()));
+        let b = common.mul_ref(&(y.clone() - x.clone()));
+        assert_eq!("xy + 1", a.gcd(&b).format(&sys.var_dict));
+    }
+
+    #[test]
+    fn content() {
+        let sys = system! {
+            2*x^2*y - 2*y,
+            x^3 + x^2 - x - 1
+        };
+
+        let p = &sys.members;
+        let (x, y) = (0, 1);
+
+        assert_eq!("y", p[0].content(x).format(&sys.var_dict));
+        assert_eq!("x^2 - 1", p[0].content(y).format(&sys.var_dict));
+        assert_eq!("2x^2 - 2", p[0].primitive_part(x).format(&sys.var_dict));
+        assert_eq!("2y", p[0].primitive_part(y).format(&sys.var_dict));
+
+        // (x - 1)(x + 1)^2
+        assert_eq!("x^2 - 1", p[1].squarefree_part().format(&sys.var_dict));
+        let q = p[0].mul_ref(&p[0]).mul_ref(&p[1]);
+        assert_eq!("x^2y - y", q.squarefree_part().format(&sys.var_dict));
+        assert_eq!("1", sys.constant(3).squarefree_part().format(&sys.var_dict));
+    }
+
+    #[test]
+    fn squarefree_decomposition() {
+        // y^2 (x - 1)^3 (x + y)^2 (x - y), with x - y and y^2 in different
+        // parts of the recursion
+        let sys = system! { x - 1, x + y, x - y, y };
+        let p = &sys.members;
+        let q = p[3].mul_ref(&p[3])
+            * p[0].mul_ref(&p[0]).mul_ref(&p[0])
+            * p[1].mul_ref(&p[1])
+            * p[2].clone();
+
+        let factors = q
+            .squarefree_decomposition()
+            .into_iter()
+            .map(|(f, mult)| (f.format(&sys.var_dict), mult))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("x - y".to_string(), 1),
+                ("xy + y^2".to_string(), 2),
+                ("x - 1".to_string(), 3)
+            ],
+            factors
+        );
+
+        assert!(sys.constant(5).squarefree_decomposition().is_empty());
+    }
+
+    #[test]
+    fn basis() {
+        let sys = system! {
+            x^2 - 1,
+            x^2 + 2*x + 1,
+            x*y - y + x - 1,
+            y^2 - 1,
+            5
+        };
+
+        let basis = gcd_free_basis(&sys.members);
+
+        let mut formatted = basis
+            .iter()
+            .map(|b| b.format(&sys.var_dict))
+            .collect::<Vec<_>>();
+        formatted.sort();
+        assert_eq!(vec!["x + 1", "x - 1", "y + 1", "y - 1"], formatted);
+
+        for (i, a) in basis.iter().enumerate() {
+            for b in basis.iter().skip(i + 1) {
+                assert!(a.gcd(b).is_constant());
+            }
+        }
+
+        // every input is recovered up to a constant
+        for p in &sys.members {
+            let mut rest = p.clone();
+            for b in &basis {
+                while let Some(q) = rest.try_divide(b) {
+                    rest = q;
+                }
+            }
+            assert!(rest.is_constant());
+        }
+    }
+}
@@ -0,0 +1,401 @@
+// Buchberger's algorithm with a choice of pair selection and the two
+// criteria for skipping S-pairs which reduce to zero anyway: the first skips
+// pairs whose leading terms are coprime; the second skips (i, j) when some
+// other member's leading term divides their lcm and its pairs with both i
+// and j are already done. the normal strategy takes the pair with the
+// smallest lcm in the order; the sugar strategy the one with the smallest
+// sugar, the degree the S-poly would have if the input were homogenized,
+// which keeps the degrees from blowing up in lex and block orders
+
+use std::collections::HashSet;
+use std::sync::atomic::{self, AtomicBool};
+
+use crate::poly::mono::{monomial_div, monomial_lcm, Mono};
+use crate::poly::order::MonomialOrder;
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::profile::in_phase;
+use crate::rational::Rat;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Selection {
+    Normal,
+    Sugar,
+}
+
+// how the members of the reduced basis are scaled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Normalization {
+    // leading coefficient 1 in the order
+    Monic,
+    // integer coefficients with gcd 1, see Poly::norm
+    Primitive,
+    // as the interreduction left them
+    Raw,
+}
+
+#[derive(Debug, Clone)]
+pub struct GroebnerConfig {
+    pub order: MonomialOrder,
+    pub selection: Selection,
+    // Buchberger's criteria; off reduces every pair, only useful to compare
+    pub criteria: bool,
+    pub normalization: Normalization,
+    // pairs reduced at once, each on its own thread with the parallel
+    // feature; the basis is the same for any number
+    pub threads: usize,
+}
+
+// what happened to the pairs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GroebnerStats {
+    pub pairs: usize,
+    pub coprime: usize,
+    pub chain: usize,
+    pub reduced: usize,
+    // the reductions which gave a new member
+    pub nonzero: usize,
+}
+
+impl GroebnerConfig {
+    // the normal strategy with both criteria, and primitive members
+    pub fn new(order: MonomialOrder) -> GroebnerConfig {
+        GroebnerConfig {
+            order,
+            selection: Selection::Normal,
+            criteria: true,
+            normalization: Normalization::Primitive,
+            threads: 1,
+        }
+    }
+
+    pub fn selection(mut self, selection: Selection) -> GroebnerConfig {
+        self.selection = selection;
+        self
+    }
+
+    pub fn criteria(mut self, criteria: bool) -> GroebnerConfig {
+        self.criteria = criteria;
+        self
+    }
+
+    pub fn normalization(mut self, normalization: Normalization) -> GroebnerConfig {
+        self.normalization = normalization;
+        self
+    }
+
+    pub fn threads(mut self, threads: usize) -> GroebnerConfig {
+        self.threads = threads.max(1);
+        self
+    }
+}
+
+impl Normalization {
+    pub(crate) fn apply(&self, p: Poly<Rat>, order: &MonomialOrder) -> Poly<Rat> {
+        match self {
+            Normalization::Monic if !p.is_zero() => {
+                Poly::constant(Rat::from(1) / order.lt(&p).val).mul_ref(&p)
+            }
+            Normalization::Monic | Normalization::Raw => p,
+            Normalization::Primitive => p.norm(),
+        }
+    }
+}
+
+struct Pair {
+    i: usize,
+    j: usize,
+    lcm: Mono<Rat>,
+    sugar: u64,
+}
+
+fn deg(m: &Mono<Rat>) -> u64 {
+    m.vars.iter().map(|(_, pow)| *pow).sum()
+}
+
+fn coprime(a: &Mono<Rat>, b: &Mono<Rat>) -> bool {
+    a.vars
+        .iter()
+        .all(|(v, _)| b.vars.iter().all(|(w, _)| v != w))
+}
+
+type Batch = [(usize, usize, u64)];
+
+// the remainders of the S-polys of the pairs in batch by members
+#[cfg(feature = "parallel")]
+fn reduce_batch(order: &MonomialOrder, members: &[Poly<Rat>], batch: &Batch) -> Vec<Poly<Rat>> {
+    std::thread::scope(|scope| {
+        let handles = batch
+            .iter()
+            .map(|(i, j, _)| {
+                scope
+                    .spawn(move || order.reduce(&order.s_poly(&members[*i], &members[*j]), members))
+            })
+            .collect::<Vec<_>>();
+
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    })
+}
+
+#[cfg(not(feature = "parallel"))]
+fn reduce_batch(order: &MonomialOrder, members: &[Poly<Rat>], batch: &Batch) -> Vec<Poly<Rat>> {
+    batch
+        .iter()
+        .map(|(i, j, _)| order.reduce(&order.s_poly(&members[*i], &members[*j]), members))
+        .collect()
+}
+
+impl System<Rat> {
+    // the reduced Groebner basis for config.order, the same for any config
+    pub fn gb_with_config(&self, config: &GroebnerConfig) -> (System<Rat>, GroebnerStats) {
+        self.gb_with_config_until(config, &AtomicBool::new(false))
+            .unwrap()
+    }
+
+    // the same, checking stop between batches of pairs; None once it is set
+    pub fn gb_with_config_until(
+        &self,
+        config: &GroebnerConfig,
+        stop: &AtomicBool,
+    ) -> Option<(System<Rat>, GroebnerStats)> {
+        let order = &config.order;
+        let mut stats = GroebnerStats::default();
+
+        let mut members = self
+            .members
+            .iter()
+            .filter(|p| !p.is_zero())
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut sugars = members
+            .iter()
+            .map(|p| p.terms.iter().map(deg).max().unwrap())
+            .collect::<Vec<_>>();
+
+        let pair = |members: &[Poly<Rat>], sugars: &[u64], i: usize, j: usize| {
+            let (lt_i, lt_j) = (order.lt(&members[i]), order.lt(&members[j]));
+            let lcm = monomial_lcm(lt_i.clone(), lt_j.clone());
+            let sugar = (sugars[i] + deg(&lcm) - deg(lt_i)).max(sugars[j] + deg(&lcm) - deg(lt_j));
+            Pair { i, j, lcm, sugar }
+        };
+
+        let mut pairs = vec![];
+        for j in 0..members.len() {
+            for i in 0..j {
+                pairs.push(pair(&members, &sugars, i, j));
+            }
+        }
+        stats.pairs = pairs.len();
+
+        // the pairs not yet done, for the second criterion
+        let mut pending = pairs.iter().map(|p| (p.i, p.j)).collect::<HashSet<_>>();
+
+        let done = in_phase("buchberger", || {
+            while !pairs.is_empty() {
+                if stop.load(atomic::Ordering::Relaxed) {
+                    return false;
+                }
+
+                // up to config.threads pairs which pass the criteria
+                let mut batch = vec![];
+                while batch.len() < config.threads && !pairs.is_empty() {
+                    let next = (0..pairs.len())
+                        .min_by(|a, b| {
+                            let (a, b) = (&pairs[*a], &pairs[*b]);
+                            let lcm = order.cmp(&a.lcm, &b.lcm);
+                            match config.selection {
+                                Selection::Normal => lcm,
+                                Selection::Sugar => a.sugar.cmp(&b.sugar).then(lcm),
+                            }
+                        })
+                        .unwrap();
+                    let Pair { i, j, lcm, sugar } = pairs.swap_remove(next);
+
+                    if config.criteria {
+                        if coprime(order.lt(&members[i]), order.lt(&members[j])) {
+                            stats.coprime += 1;
+                            pending.remove(&(i, j));
+                            continue;
+                        }
+
+                        let done = |a: usize, b: usize| !pending.contains(&(a.min(b), a.max(b)));
+                        let chain = (0..members.len()).any(|k| {
+                            k != i
+                                && k != j
+                                && done(i, k)
+                                && done(j, k)
+                                && monomial_div(&lcm, order.lt(&members[k])).is_some()
+                        });
+                        if chain {
+                            stats.chain += 1;
+                            pending.remove(&(i, j));
+                            continue;
+                        }
+                    }
+
+                    batch.push((i, j, sugar));
+                }
+
+                stats.reduced += batch.len();
+                let rems = reduce_batch(order, &members, &batch);
+                let before = members.len();
+
+                for ((i, j, sugar), rem) in batch.into_iter().zip(rems) {
+                    pending.remove(&(i, j));
+
+                    // by the members the batch has added so far
+                    let rem = if members.len() > before {
+                        order.reduce(&rem, &members)
+                    } else {
+                        rem
+                    };
+                    if rem.is_zero() {
+                        continue;
+                    }
+
+                    stats.nonzero += 1;
+
+                    let new = members.len();
+                    members.push(rem);
+                    sugars.push(sugar);
+
+                    for k in 0..new {
+                        pairs.push(pair(&members, &sugars, k, new));
+                        pending.insert((k, new));
+                        stats.pairs += 1;
+                    }
+                }
+            }
+
+            true
+        });
+        if !done {
+            return None;
+        }
+
+        let gb = System {
+            var_dict: self.var_dict.clone(),
+            members,
+        }
+        .reduce_gb_with(order, config.normalization);
+
+        Some((gb, stats))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+
+    use super::{GroebnerConfig, GroebnerStats, Normalization, Selection};
+    use crate::poly::order::MonomialOrder;
+    use crate::system;
+
+    #[test]
+    fn criteria() {
+        let sys = system! {
+            x^2*y - 1,
+            x*y^2 - x,
+            x^3 - y
+        };
+
+        for order in [MonomialOrder::Grlex, MonomialOrder::Lex] {
+            let expected = format!("{:?}", sys.gb_with_order(&order));
+            let mut counts = vec![];
+
+            for selection in [Selection::Normal, Selection::Sugar] {
+                for criteria in [true, false] {
+                    let config = GroebnerConfig::new(order.clone())
+                        .selection(selection)
+                        .criteria(criteria);
+                    let (gb, stats) = sys.gb_with_config(&config);
+                    assert_eq!(expected, format!("{:?}", gb));
+                    counts.push(stats);
+                }
+            }
+
+            // the criteria skip pairs the plain run has to reduce
+            assert!(counts[0].reduced < counts[1].reduced);
+            assert!(counts[0].coprime + counts[0].chain > 0);
+            assert_eq!(0, counts[1].coprime + counts[1].chain);
+        }
+
+        // x^2 - 1 and y^2 - 1 have coprime leading terms
+        let (_, stats) = system! { x^2 - 1, y^2 - 1 }
+            .gb_with_config(&GroebnerConfig::new(MonomialOrder::Grevlex));
+        assert_eq!(
+            GroebnerStats {
+                pairs: 1,
+                coprime: 1,
+                ..GroebnerStats::default()
+            },
+            stats
+        );
+    }
+
+    #[test]
+    fn stop() {
+        let sys = system! {
+            x^2*y - 1,
+            x*y^2 - x
+        };
+        let config = GroebnerConfig::new(MonomialOrder::Lex);
+
+        assert!(sys
+            .gb_with_config_until(&config, &AtomicBool::new(true))
+            .is_none());
+        assert_eq!(
+            format!("{:?}", sys.gb_with_config(&config).0),
+            format!(
+                "{:?}",
+                sys.gb_with_config_until(&config, &AtomicBool::new(false))
+                    .unwrap()
+                    .0
+            )
+        );
+    }
+
+    #[test]
+    fn threads() {
+        let sys = system! {
+            x^2 + y^2 + z^2 - 4,
+            x*y - z,
+            x - y + z^2
+        };
+
+        let config = GroebnerConfig::new(MonomialOrder::Grevlex);
+        let gb = sys.gb_with_config(&config).0;
+        for threads in [2, 8] {
+            let parallel = sys.gb_with_config(&config.clone().threads(threads)).0;
+            assert_eq!(format!("{:?}", gb), format!("{:?}", parallel));
+        }
+    }
+
+    #[test]
+    fn normalization() {
+        let sys = system! {
+            2*x - 3*y,
+            3*y^2 - 2
+        };
+        let gb = |normalization| {
+            let config = GroebnerConfig::new(MonomialOrder::Lex).normalization(normalization);
+            sys.gb_with_config(&config).0
+        };
+
+        assert_eq!(
+            "[2x - 3y, 3y^2 - 2]",
+            format!("{:?}", gb(Normalization::Primitive))
+        );
+        assert_eq!(
+            "[x - 1.5y, y^2 - 0.6666666666666666]",
+            format!("{:?}", gb(Normalization::Monic))
+        );
+
+        // raw members are multiples of the primitive ones
+        let raw = gb(Normalization::Raw);
+        let primitive = gb(Normalization::Primitive);
+        for (p, q) in raw.members.iter().zip(&primitive.members) {
+            assert_eq!(p.norm(), *q);
+        }
+    }
+}
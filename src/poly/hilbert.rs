@@ -0,0 +1,213 @@
+// the Hilbert series of Q[x]/LT(I), with LT(I) the leading term ideal of the
+// grlex basis: the sum of h(d) t^d, with h(d) the number of standard
+// monomials of degree d. it is N(t) / (1 - t)^n for n vars, with N from the
+// recursion N(J + <m>) = N(J) - t^deg(m) N(J : m) on the generators of the
+// monomial ideal. cancelling the factors 1 - t leaves Q(t) / (1 - t)^k, with
+// k the dimension and Q(1) the degree; for large d, h(d) is the Hilbert
+// polynomial, the sum of q_i C(d - i + k - 1, k - 1)
+
+use crate::field::{One, Zero};
+use crate::poly::system::System;
+use crate::rational::Rat;
+use crate::univariate::UPoly;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HilbertSeries {
+    pub numerator: UPoly<Rat>,
+    // the power of 1 - t
+    pub denominator: usize,
+}
+
+// t^pow
+fn t_pow(pow: u64) -> UPoly<Rat> {
+    let mut coefs = vec![Rat::zero(); pow as usize + 1];
+    coefs[0] = Rat::one();
+    UPoly(coefs)
+}
+
+fn divides(a: &[u64], b: &[u64]) -> bool {
+    a.iter().zip(b).all(|(a, b)| a <= b)
+}
+
+// drop the generators divisible by another
+fn minimize(mut gens: Vec<Vec<u64>>) -> Vec<Vec<u64>> {
+    gens.sort_by_key(|g| g.iter().sum::<u64>());
+    gens.dedup();
+
+    let mut minimal: Vec<Vec<u64>> = vec![];
+    for g in gens {
+        if !minimal.iter().any(|m| divides(m, &g)) {
+            minimal.push(g);
+        }
+    }
+
+    minimal
+}
+
+fn numerator(gens: &[Vec<u64>]) -> UPoly<Rat> {
+    let Some((m, rest)) = gens.split_last() else {
+        return UPoly(vec![Rat::one()]);
+    };
+
+    let quotient = minimize(
+        rest.iter()
+            .map(|g| g.iter().zip(m).map(|(a, b)| a.saturating_sub(*b)).collect())
+            .collect(),
+    );
+
+    numerator(rest) - numerator(&quotient) * t_pow(m.iter().sum())
+}
+
+// C(d + shift, k) as a poly in d
+fn binomial(shift: i64, k: usize) -> UPoly<Rat> {
+    (1..=k as i64).fold(UPoly(vec![Rat::one()]), |acc, j| {
+        acc * UPoly(vec![Rat::one(), Rat::from(shift - k as i64 + j)])
+            .scale(&(Rat::one() / Rat::from(j)))
+    })
+}
+
+impl HilbertSeries {
+    // with the common factors 1 - t cancelled
+    pub fn reduced(&self) -> HilbertSeries {
+        let one_minus_t = UPoly(vec![Rat::from(-1), Rat::one()]);
+        let mut reduced = self.clone();
+
+        while reduced.denominator > 0
+            && !reduced.numerator.is_zero()
+            && reduced.numerator.eval(&Rat::one()).is_zero()
+        {
+            reduced.numerator = reduced.numerator.div_rem(&one_minus_t).0;
+            reduced.denominator -= 1;
+        }
+
+        reduced
+    }
+
+    // of the solutions over the complex numbers; None if there are none
+    pub fn dimension(&self) -> Option<usize> {
+        let reduced = self.reduced();
+        (!reduced.numerator.is_zero()).then_some(reduced.denominator)
+    }
+
+    // for a zero-dimensional system, its solutions counted with multiplicity
+    pub fn degree(&self) -> Rat {
+        self.reduced().numerator.eval(&Rat::one())
+    }
+
+    // the number of standard monomials of degree d
+    pub fn hilbert_function(&self, d: u64) -> Rat {
+        let n = self.numerator.0.len();
+
+        (0..n)
+            .filter(|pow| *pow as u64 <= d)
+            .fold(Rat::zero(), |acc, pow| {
+                // the coef of t^(d - pow) in 1 / (1 - t)^k
+                let count = match self.denominator {
+                    0 => Rat::from((pow as u64 == d) as i64),
+                    k => binomial(k as i64 - 1 - pow as i64, k - 1).eval(&Rat::from(d as i64)),
+                };
+
+                acc + self.numerator.0[n - 1 - pow] * count
+            })
+    }
+
+    // equal to the Hilbert function from some degree on
+    pub fn polynomial(&self) -> UPoly<Rat> {
+        let reduced = self.reduced();
+
+        if reduced.denominator == 0 {
+            return UPoly(vec![]);
+        }
+
+        let n = reduced.numerator.0.len();
+        reduced
+            .numerator
+            .0
+            .iter()
+            .enumerate()
+            .fold(UPoly(vec![]), |acc, (i, c)| {
+                let pow = (n - 1 - i) as i64;
+                acc + binomial(
+                    reduced.denominator as i64 - 1 - pow,
+                    reduced.denominator - 1,
+                )
+                .scale(c)
+            })
+    }
+}
+
+impl System<Rat> {
+    pub fn hilbert_series(&self) -> HilbertSeries {
+        let n = self.var_dict.len();
+        let gens = self
+            .gb()
+            .members
+            .iter()
+            .filter(|p| !p.is_zero())
+            .map(|p| {
+                let mut exps = vec![0; n];
+                for (v, pow) in p.lt_mono().vars {
+                    exps[v] = pow;
+                }
+                exps
+            })
+            .collect();
+
+        HilbertSeries {
+            numerator: numerator(&minimize(gens)),
+            denominator: n,
+        }
+    }
+
+    pub fn hilbert_polynomial(&self) -> UPoly<Rat> {
+        self.hilbert_series().polynomial()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HilbertSeries;
+    use crate::rational::Rat;
+    use crate::system;
+    use crate::univariate::upoly;
+
+    #[test]
+    fn hilbert_series() {
+        // a conic: 1 - t^2 over (1 - t)^2, so 2 monomials of each degree > 0
+        let sys = system! { x^2 + y^2 - 1 };
+        let series = sys.hilbert_series();
+        assert_eq!(
+            HilbertSeries {
+                numerator: upoly(&[-1, 0, 1]),
+                denominator: 2
+            },
+            series
+        );
+        assert_eq!(upoly(&[1, 1]), series.reduced().numerator);
+        assert_eq!(Some(1), series.dimension());
+        assert_eq!(Rat::from(2), series.degree());
+        assert_eq!(upoly(&[2]), sys.hilbert_polynomial());
+
+        // the plane x = 0 and the line y = z = 0: x^d and the d + 1
+        // monomials in y, z
+        let sys = system! { x*y, x*z };
+        let series = sys.hilbert_series();
+        assert_eq!(Some(2), series.dimension());
+        assert_eq!(Rat::from(1), series.degree());
+        assert_eq!(upoly(&[1, 2]), series.polynomial());
+        for d in 1..6 {
+            assert_eq!(Rat::from(d as i64 + 2), series.hilbert_function(d));
+        }
+
+        // two points: the polynomial is 0 and the degree counts them
+        let sys = system! { x^2 - 1, y - x };
+        let series = sys.hilbert_series();
+        assert_eq!(Some(0), series.dimension());
+        assert_eq!(Rat::from(2), series.degree());
+        assert_eq!(upoly(&[]), series.polynomial());
+        assert_eq!(Rat::from(1), series.hilbert_function(1));
+        assert_eq!(Rat::from(0), series.hilbert_function(2));
+
+        assert_eq!(None, system! { x - 1, x - 2 }.hilbert_series().dimension());
+    }
+}
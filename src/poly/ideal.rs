@@ -0,0 +1,285 @@
+use std::cell::{OnceCell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::poly::order::MonomialOrder;
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+// an ideal given by generators; the Groebner basis and saturations are
+// computed on first use and kept
+pub struct Ideal {
+    pub gens: System<Rat>,
+    gb: OnceCell<System<Rat>>,
+    saturations: RefCell<HashMap<Poly<Rat>, Rc<Ideal>>>,
+}
+
+impl Ideal {
+    pub fn new(gens: System<Rat>) -> Ideal {
+        Ideal {
+            gens,
+            gb: OnceCell::new(),
+            saturations: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn gb(&self) -> &System<Rat> {
+        self.gb.get_or_init(|| self.gens.gb())
+    }
+
+    // the remainder of p by the reduced basis, the same for any two polys
+    // which differ by a member of the ideal
+    pub fn normal_form(&self, p: &Poly<Rat>) -> Poly<Rat> {
+        p.div_rem(&self.gb().members).1
+    }
+
+    pub fn contains(&self, p: &Poly<Rat>) -> bool {
+        self.normal_form(p).is_zero()
+    }
+
+    // whether p vanishes on V(I) over the complex numbers, i.e. some power of
+    // p is in I: exactly when I + <1 - tp> has no solutions (Rabinowitsch)
+    pub fn radical_contains(&self, p: &Poly<Rat>) -> bool {
+        if self.contains(p) {
+            return true;
+        }
+
+        let mut var_dict = self.gens.var_dict.as_ref().clone();
+        let t = Poly::var(var_dict.len(), 1);
+        var_dict.push("_t".to_string());
+
+        let mut members = self.gens.members.clone();
+        members.push(Poly::constant(Rat::from(1)) - t.mul_ref(p));
+
+        let extended = System {
+            var_dict: Rc::new(var_dict),
+            members,
+        };
+
+        extended
+            .gb()
+            .members
+            .iter()
+            .any(|g| g.is_constant() && !g.is_zero())
+    }
+
+    // I : f^inf, the polys g with f^k g in I for some k; geometrically, the
+    // closure of V(I) minus V(f). computed as the elimination of s from
+    // I + <1 - sf> (Rabinowitsch)
+    pub fn saturate_by(&self, f: &Poly<Rat>) -> Rc<Ideal> {
+        let key = f.norm();
+
+        if let Some(sat) = self.saturations.borrow().get(&key) {
+            return sat.clone();
+        }
+
+        let s = Poly::var(self.gens.var_dict.len(), 1);
+        let mut members = self.gens.members.clone();
+        members.push(Poly::constant(Rat::from(1)) - s.mul_ref(&key));
+
+        let sat = Rc::new(self.eliminate_tag(members));
+        self.saturations.borrow_mut().insert(key, sat.clone());
+        sat
+    }
+
+    // I and J as t I + (1 - t) J, with t eliminated. None unless both have
+    // the same vars
+    pub fn intersect(&self, other: &Ideal) -> Option<Ideal> {
+        if self.gens.var_dict != other.gens.var_dict {
+            return None;
+        }
+
+        let t = Poly::var(self.gens.var_dict.len(), 1);
+        let one_minus_t = Poly::constant(Rat::from(1)) - t.clone();
+
+        let members = self
+            .gens
+            .members
+            .iter()
+            .map(|f| t.mul_ref(f))
+            .chain(other.gens.members.iter().map(|g| one_minus_t.mul_ref(g)))
+            .collect();
+
+        Some(self.eliminate_tag(members))
+    }
+
+    // I : J, the polys g with g J in I; geometrically, the closure of V(I)
+    // minus V(J). the intersection of I : f over the generators f of J, with
+    // I : f = (I and <f>) / f. None unless both have the same vars
+    pub fn quotient(&self, other: &Ideal) -> Option<Ideal> {
+        if self.gens.var_dict != other.gens.var_dict {
+            return None;
+        }
+
+        let one = Ideal::new(System {
+            var_dict: self.gens.var_dict.clone(),
+            members: vec![Poly::constant(Rat::from(1))],
+        });
+
+        other
+            .gens
+            .members
+            .iter()
+            .filter(|f| !f.is_zero())
+            .try_fold(one, |acc, f| {
+                let by_f = Ideal::new(System {
+                    var_dict: self.gens.var_dict.clone(),
+                    members: vec![f.clone()],
+                });
+
+                let members = self
+                    .intersect(&by_f)?
+                    .gb()
+                    .members
+                    .iter()
+                    .map(|g| g.try_divide(f).unwrap())
+                    .collect();
+
+                acc.intersect(&Ideal::new(System {
+                    var_dict: self.gens.var_dict.clone(),
+                    members,
+                }))
+            })
+    }
+
+    // members in the vars of the ideal and one more tag var after them, which
+    // is eliminated
+    fn eliminate_tag(&self, members: Vec<Poly<Rat>>) -> Ideal {
+        let t = self.gens.var_dict.len();
+        let mut var_dict = self.gens.var_dict.as_ref().clone();
+        var_dict.push("_t".to_string());
+
+        let extended = System {
+            var_dict: Rc::new(var_dict),
+            members,
+        };
+
+        let eliminated = extended
+            .eliminate_with_order(&[t], &MonomialOrder::eliminate(&[t]))
+            .unwrap();
+
+        Ideal::new(System {
+            var_dict: self.gens.var_dict.clone(),
+            members: eliminated.members,
+        })
+    }
+}
+
+impl System<Rat> {
+    // whether p is in the ideal the members generate, i.e. follows from
+    // them; computes the basis on every call, an Ideal keeps it for testing
+    // or reducing (Ideal::normal_form) many polys
+    pub fn contains(&self, p: &Poly<Rat>) -> bool {
+        Ideal::new(self.clone()).contains(p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::Ideal;
+    use crate::poly::system::System;
+    use crate::poly::Poly;
+    use crate::rational::Rat;
+    use crate::system;
+
+    #[test]
+    fn normal_form() {
+        let sys = system! {
+            x^2 + y^2 - 1,
+            x - y
+        };
+
+        let (x, y) = (sys.var("x", 1), sys.var("y", 1));
+        let ideal = Ideal::new(sys.clone());
+
+        // x^2 = y^2 = xy = 1/2 on the solutions
+        let nf = ideal.normal_form(&(x.mul_ref(&y) + x.clone()));
+        assert_eq!("y + 0.5", nf.format(&sys.var_dict));
+        assert_eq!(nf, ideal.normal_form(&(y.mul_ref(&y) + y.clone())));
+        assert_eq!(nf, ideal.normal_form(&(x.mul_ref(&x) + x.clone())));
+        assert!(ideal.normal_form(&sys.members[0]).is_zero());
+    }
+
+    #[test]
+    fn intersect_quotient() {
+        let sys = system! { x, y, z };
+        let ideal = |ps: &[usize]| {
+            Ideal::new(System {
+                var_dict: sys.var_dict.clone(),
+                members: ps.iter().map(|i| sys.members[*i].clone()).collect(),
+            })
+        };
+
+        // the plane x = 0 and the line y = z = 0
+        let union = ideal(&[0]).intersect(&ideal(&[1, 2])).unwrap();
+        assert_eq!("[xy, xz]", format!("{:?}", union.gb()));
+
+        // removing either part leaves the other
+        let plane = union.quotient(&ideal(&[1, 2])).unwrap();
+        assert_eq!("[x]", format!("{:?}", plane.gb()));
+        let line = union.quotient(&ideal(&[0])).unwrap();
+        assert_eq!("[y, z]", format!("{:?}", line.gb()));
+
+        let other = Ideal::new(system! { x });
+        assert!(union.intersect(&other).is_none());
+    }
+
+    #[test]
+    fn saturate() {
+        // the plane x = 0 union the line y = z = 0
+        let sys = system! {
+            x*y,
+            x*z
+        };
+
+        let ideal = Ideal::new(sys.clone());
+        let x = sys.var("x", 1);
+
+        let sat = ideal.saturate_by(&x);
+        assert_eq!("[y, z]", format!("{:?}", sat.gb()));
+        assert!(sat.contains(&sys.var("y", 2)) && !sat.contains(&x));
+        assert!(!ideal.contains(&sys.var("y", 1)));
+
+        // cached, also when asked for by a multiple
+        let again = ideal.saturate_by(&(x.clone() + x.clone()));
+        assert!(Rc::ptr_eq(&sat, &again));
+
+        // embedded component at the origin, removed by saturating by y
+        let sys = system! {
+            x^2,
+            x*y
+        };
+
+        let ideal = Ideal::new(sys.clone());
+        assert!(ideal.radical_contains(&sys.var("x", 1)));
+        assert!(!ideal.contains(&sys.var("x", 1)));
+        assert!(!ideal.radical_contains(&sys.var("y", 1)));
+        assert_eq!(
+            "[x]",
+            format!("{:?}", ideal.saturate_by(&sys.var("y", 1)).gb())
+        );
+        // x vanishes everywhere on V(I), so nothing is left
+        assert_eq!(
+            "[1]",
+            format!("{:?}", ideal.saturate_by(&sys.var("x", 1)).gb())
+        );
+    }
+
+    #[test]
+    fn contains() {
+        let sys = system! {
+            x^2 + y^2 - 1,
+            x - y
+        };
+        let (x, y) = (sys.var("x", 1), sys.var("y", 1));
+
+        // 2y^2 - 1 = (x^2 + y^2 - 1) - (x + y)(x - y)
+        let derived = y.mul_ref(&y) + y.mul_ref(&y) - Poly::constant(Rat::from(1));
+        assert!(sys.contains(&derived));
+        assert!(sys.contains(&Poly::constant(Rat::from(0))));
+        assert!(!sys.contains(&(x.mul_ref(&y) - Poly::constant(Rat::from(1)))));
+    }
+}
@@ -0,0 +1,96 @@
+// polynomial identity testing: evaluate at random points of GF(P) for the
+// Mersenne prime P = 2^61 - 1. a nonzero value proves the poly nonzero; a
+// nonzero poly of total degree d vanishes at a random point with probability
+// at most d / P (Schwartz-Zippel), so zero at every trial makes it zero with
+// high probability. comparing values of two polys this way never builds
+// their difference, which is the point for huge ones. the points come from a
+// fixed seed, so the answers are reproducible
+
+use crate::gf::Gf;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+const P: u64 = (1 << 61) - 1;
+
+type F = Gf<P>;
+
+// splitmix64
+fn next(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+fn points(vars: usize, trials: usize) -> Vec<Vec<F>> {
+    let mut state = 0;
+    (0..trials)
+        .map(|_| (0..vars).map(|_| F::new(next(&mut state))).collect())
+        .collect()
+}
+
+fn vars(p: &Poly<Rat>) -> usize {
+    p.terms
+        .iter()
+        .flat_map(|t| t.vars.iter().map(|(v, _)| v + 1))
+        .max()
+        .unwrap_or(0)
+}
+
+impl Poly<Rat> {
+    // None if a denominator is divisible by P
+    fn eval_mod(&self, point: &[F]) -> Option<F> {
+        self.terms.iter().try_fold(F::from(0), |acc, t| {
+            let val = F::from(t.val.num) * F::from(t.val.den).inv()?;
+            let mono = t
+                .vars
+                .iter()
+                .fold(F::from(1), |m, (v, pow)| m * point[*v].pow(*pow));
+            Some(acc + val * mono)
+        })
+    }
+
+    // false means certainly nonzero, true zero with high probability
+    pub fn is_zero_probabilistic(&self, trials: usize) -> bool {
+        self.probably_equal(&Poly::constant(Rat::from(0)), trials)
+    }
+
+    // false means certainly different, true equal with high probability.
+    // falls back to comparing exactly if a coef has no value mod P
+    pub fn probably_equal(&self, other: &Poly<Rat>, trials: usize) -> bool {
+        for point in points(vars(self).max(vars(other)), trials) {
+            match (self.eval_mod(&point), other.eval_mod(&point)) {
+                (Some(a), Some(b)) if a != b => return false,
+                (Some(_), Some(_)) => (),
+                _ => return self == other,
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::system;
+
+    #[test]
+    fn identity() {
+        let sys = system! { x + y, x - y };
+        let (a, b) = (&sys.members[0], &sys.members[1]);
+
+        // (x + y)^2 (x - y)^2 = (x^2 - y^2)^2
+        let lhs = a.mul_ref(a).mul_ref(b).mul_ref(b);
+        let square = a.mul_ref(b);
+        let rhs = square.mul_ref(&square);
+        assert!(lhs.probably_equal(&rhs, 5));
+        assert!((lhs.clone() - rhs.clone()).is_zero_probabilistic(5));
+
+        // off by one term
+        let off = rhs + sys.var("x", 1).mul_ref(&sys.var("y", 3));
+        assert!(!lhs.probably_equal(&off, 5));
+        assert!(!a.is_zero_probabilistic(1));
+        assert!(sys.constant(0).is_zero_probabilistic(1));
+    }
+}
@@ -0,0 +1,327 @@
+// exact JSON for polys and systems, to and from a frontend:
+//
+//   poly:   [term, ...] with term = [coef, [exponent of each var]]
+//   system: {"vars": [name, ...], "members": [poly, ...]}
+//
+// a coef is a string "num/den" or "num", or a JSON integer; written coefs are
+// always "num/den" strings, so nothing is lost to floats. terms may be in any
+// order and repeated when read, and the exponent lists must have one entry
+// per var. object keys other than these are ignored when reading
+
+use std::fmt::Write;
+use std::iter::Peekable;
+use std::rc::Rc;
+use std::str::Chars;
+
+use crate::poly::builder::PolyBuilder;
+use crate::poly::system::{unknown_var, System};
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+pub(crate) fn json_string(s: &str) -> String {
+    let mut escaped = String::from("\"");
+
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if (c as u32) < 0x20 => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+pub(crate) fn json_list(items: impl IntoIterator<Item = String>) -> String {
+    format!("[{}]", items.into_iter().collect::<Vec<_>>().join(","))
+}
+
+// terms as ["num/den", [exponent of each var]], exact; a var past vars
+// would be dropped, so callers check, or have a system's members
+pub(crate) fn json_poly(p: &Poly<Rat>, vars: usize) -> String {
+    json_list(p.terms.iter().map(|term| {
+        let exponents = (0..vars).map(|v| term.deg(v).to_string());
+        format!(
+            "[{},{}]",
+            json_string(&format!("{}/{}", term.val.num, term.val.den)),
+            json_list(exponents)
+        )
+    }))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    // as written, parsed by whoever needs it
+    Num(String),
+    Str(String),
+    List(Vec<Json>),
+    Obj(Vec<(String, Json)>),
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn skip_space(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn expect(&mut self, c: char) -> Option<()> {
+        self.skip_space();
+        self.chars.next_if_eq(&c).map(|_| ())
+    }
+
+    fn word(&mut self, word: &str, val: Json) -> Option<Json> {
+        word.chars()
+            .all(|c| self.chars.next() == Some(c))
+            .then_some(val)
+    }
+
+    fn string(&mut self) -> Option<String> {
+        self.expect('"')?;
+        let mut s = String::new();
+
+        loop {
+            match self.chars.next()? {
+                '"' => return Some(s),
+                '\\' => s.push(match self.chars.next()? {
+                    'n' => '\n',
+                    't' => '\t',
+                    'r' => '\r',
+                    'b' => '\u{8}',
+                    'f' => '\u{c}',
+                    'u' => {
+                        let hex = (0..4)
+                            .map(|_| self.chars.next())
+                            .collect::<Option<String>>()?;
+                        char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?
+                    }
+                    c @ ('"' | '\\' | '/') => c,
+                    _ => return None,
+                }),
+                c => s.push(c),
+            }
+        }
+    }
+
+    // a list or object body after the opening bracket, up to close
+    fn items<T>(
+        &mut self,
+        close: char,
+        mut item: impl FnMut(&mut Self) -> Option<T>,
+    ) -> Option<Vec<T>> {
+        let mut items = vec![];
+        if self.expect(close).is_some() {
+            return Some(items);
+        }
+
+        loop {
+            items.push(item(self)?);
+            if self.expect(close).is_some() {
+                return Some(items);
+            }
+            self.expect(',')?;
+        }
+    }
+
+    fn value(&mut self) -> Option<Json> {
+        self.skip_space();
+
+        match *self.chars.peek()? {
+            '"' => self.string().map(Json::Str),
+            '[' => {
+                self.chars.next();
+                self.items(']', Parser::value).map(Json::List)
+            }
+            '{' => {
+                self.chars.next();
+                self.items('}', |p| {
+                    let key = p.string()?;
+                    p.expect(':')?;
+                    Some((key, p.value()?))
+                })
+                .map(Json::Obj)
+            }
+            'n' => self.word("null", Json::Null),
+            't' => self.word("true", Json::Bool(true)),
+            'f' => self.word("false", Json::Bool(false)),
+            c if c == '-' || c.is_ascii_digit() => {
+                let mut num = String::new();
+                while let Some(c) = self
+                    .chars
+                    .next_if(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+                {
+                    num.push(c);
+                }
+                Some(Json::Num(num))
+            }
+            _ => None,
+        }
+    }
+}
+
+// the whole of src as one value
+fn parse(src: &str) -> Option<Json> {
+    let mut parser = Parser {
+        chars: src.chars().peekable(),
+    };
+    let val = parser.value()?;
+    parser.skip_space();
+    parser.chars.peek().is_none().then_some(val)
+}
+
+fn coef(json: &Json) -> Option<Rat> {
+    let s = match json {
+        Json::Str(s) | Json::Num(s) => s.trim(),
+        _ => return None,
+    };
+
+    let (num, den) = s.split_once('/').unwrap_or((s, "1"));
+    let (num, den) = (
+        num.trim().parse::<i64>().ok()?,
+        den.trim().parse::<i64>().ok()?,
+    );
+    (den != 0).then(|| Rat::from(num) / Rat::from(den))
+}
+
+fn poly(json: &Json, vars: usize) -> Option<Poly<Rat>> {
+    let Json::List(terms) = json else {
+        return None;
+    };
+
+    let mut builder = PolyBuilder::with_capacity(terms.len());
+    for term in terms {
+        let Json::List(term) = term else {
+            return None;
+        };
+        let [c, Json::List(exponents)] = &term[..] else {
+            return None;
+        };
+        if exponents.len() != vars {
+            return None;
+        }
+
+        let exponents = exponents
+            .iter()
+            .enumerate()
+            .map(|(v, e)| match e {
+                Json::Num(e) => Some((v, e.parse::<u64>().ok()?)),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        builder.push(coef(c)?, &exponents);
+    }
+
+    Some(builder.build())
+}
+
+impl Poly<Rat> {
+    // None if a term has a var past vars, which the exponent lists cannot
+    // hold
+    pub fn to_json(&self, vars: usize) -> Option<String> {
+        unknown_var(self, vars).is_none().then(|| json_poly(self, vars))
+    }
+
+    // None if src is not a poly in vars vars
+    pub fn from_json(src: &str, vars: usize) -> Option<Poly<Rat>> {
+        poly(&parse(src)?, vars)
+    }
+}
+
+impl System<Rat> {
+    pub fn to_json(&self) -> String {
+        let n = self.var_dict.len();
+
+        format!(
+            "{{\"vars\":{},\"members\":{}}}",
+            json_list(self.var_dict.iter().map(|v| json_string(v))),
+            json_list(self.members.iter().map(|p| json_poly(p, n)))
+        )
+    }
+
+    // None if src does not follow the schema
+    pub fn from_json(src: &str) -> Option<System<Rat>> {
+        let Json::Obj(fields) = parse(src)? else {
+            return None;
+        };
+        let field = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+
+        let Some(Json::List(vars)) = field("vars") else {
+            return None;
+        };
+        let var_dict = vars
+            .iter()
+            .map(|v| match v {
+                Json::Str(v) => Some(v.clone()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let Some(Json::List(members)) = field("members") else {
+            return None;
+        };
+        let members = members
+            .iter()
+            .map(|p| poly(p, var_dict.len()))
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(System {
+            var_dict: Rc::new(var_dict),
+            members,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::poly::system::System;
+    use crate::poly::Poly;
+    use crate::rational::Rat;
+    use crate::system;
+
+    #[test]
+    fn round_trip() {
+        let mut sys = system! {
+            x^2*y - 3,
+            2*x - y*z + 1
+        };
+        sys.members[1] = Poly::constant(Rat::from(1) / Rat::from(3)).mul_ref(&sys.members[1]);
+
+        let json = sys.to_json();
+        assert_eq!(
+            r#"{"vars":["x","y","z"],"members":[[["-3/1",[0,0,0]],["1/1",[2,1,0]]],[["1/3",[0,0,0]],["2/3",[1,0,0]],["-1/3",[0,1,1]]]]}"#,
+            json
+        );
+        let back = System::from_json(&json).unwrap();
+        assert_eq!(sys.var_dict, back.var_dict);
+        assert_eq!(sys.members, back.members);
+
+        // as a frontend might write it: integer coefs, terms out of order and
+        // repeated, spaces, and an extra key
+        let sys = System::from_json(
+            r#" { "members": [ [[1, [0, 2]], ["2", [1, 0]], [" -1/2 ", [0, 2]]] ],
+                  "vars": ["a", "b"], "source": null } "#,
+        )
+        .unwrap();
+        assert_eq!("[0.5b^2 + 2a]", format!("{:?}", sys));
+        assert_eq!(
+            Some(sys.members[0].clone()),
+            Poly::from_json(&sys.members[0].to_json(2).unwrap(), 2)
+        );
+        assert!(sys.members[0].to_json(1).is_none());
+
+        for bad in [
+            r#"{"vars":["x"],"members":[[["1",[1,0]]]]}"#,
+            r#"{"vars":["x"],"members":[[["1/0",[1]]]]}"#,
+            r#"{"vars":["x"],"members":[[["1",[1]]]]"#,
+            r#"{"vars":["x"]}"#,
+        ] {
+            assert!(System::from_json(bad).is_none());
+        }
+    }
+}
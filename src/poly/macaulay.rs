@@ -0,0 +1,247 @@
+// the Macaulay matrix of a system at degree d: a row for each shift m f of a
+// member f by a monomial m with deg(m f) <= d, and a column for each monomial
+// of degree <= d. for a zero-dimensional system and large enough d, its null
+// space is spanned by the monomials evaluated at the solutions. the rank is
+// computed exactly, and numerically from the singular values, whose gap shows
+// how close the system is to one with more solutions
+
+use std::collections::HashMap;
+
+use crate::field::{One, Zero};
+use crate::matrix::Matrix;
+use crate::poly::mono::{grlex, monomial_mul, Mono};
+use crate::poly::system::System;
+use crate::rational::Rat;
+
+#[derive(Debug, Clone)]
+pub struct MacaulayMatrix {
+    // the monomials of the columns, highest first
+    pub columns: Vec<Mono<Rat>>,
+    // the member and the monomial it is shifted by, for each row
+    pub shifts: Vec<(usize, Mono<Rat>)>,
+    pub rows: Vec<Vec<Rat>>,
+}
+
+// the exponent vectors of degree <= deg in vars.., as sparse vars
+pub(crate) fn monomials(var: usize, vars: usize, deg: u64) -> Vec<Vec<(usize, u64)>> {
+    if var == vars {
+        return vec![vec![]];
+    }
+
+    (0..=deg)
+        .flat_map(|pow| {
+            monomials(var + 1, vars, deg - pow)
+                .into_iter()
+                .map(move |mut rest| {
+                    if pow > 0 {
+                        rest.insert(0, (var, pow));
+                    }
+                    rest
+                })
+        })
+        .collect()
+}
+
+fn total_deg(m: &Mono<Rat>) -> u64 {
+    m.vars.iter().map(|(_, pow)| *pow).sum()
+}
+
+// one-sided Jacobi: rotate pairs of columns of a until they are orthogonal,
+// then a = U S V^T with the column norms as S. returns S and V, by column
+fn jacobi_svd(mut a: Vec<Vec<f64>>, cols: usize) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let mut v = (0..cols)
+        .map(|i| {
+            (0..cols)
+                .map(|j| if i == j { 1. } else { 0. })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    for _ in 0..100 {
+        let mut rotated = false;
+
+        for p in 0..cols {
+            for q in p + 1..cols {
+                let (mut alpha, mut beta, mut gamma) = (0., 0., 0.);
+                for row in &a {
+                    alpha += row[p] * row[p];
+                    beta += row[q] * row[q];
+                    gamma += row[p] * row[q];
+                }
+
+                if gamma.abs() <= 1e-15 * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+
+                let zeta = (beta - alpha) / (2. * gamma);
+                let t = zeta.signum() / (zeta.abs() + (1. + zeta * zeta).sqrt());
+                let c = 1. / (1. + t * t).sqrt();
+                let s = c * t;
+
+                for row in a.iter_mut().chain(v.iter_mut()) {
+                    let (x, y) = (row[p], row[q]);
+                    row[p] = c * x - s * y;
+                    row[q] = s * x + c * y;
+                }
+            }
+        }
+
+        if !rotated {
+            break;
+        }
+    }
+
+    let values = (0..cols)
+        .map(|j| a.iter().map(|row| row[j] * row[j]).sum::<f64>().sqrt())
+        .collect();
+
+    (values, v)
+}
+
+impl System<Rat> {
+    pub fn macaulay_matrix(&self, deg: u64) -> MacaulayMatrix {
+        let mut columns = monomials(0, self.var_dict.len(), deg)
+            .into_iter()
+            .map(|vars| Mono {
+                val: Rat::one(),
+                vars,
+            })
+            .collect::<Vec<_>>();
+        columns.sort_by(|a, b| grlex(a, b).reverse());
+
+        let index = columns
+            .iter()
+            .enumerate()
+            .map(|(i, m)| (m.vars.clone(), i))
+            .collect::<HashMap<_, _>>();
+
+        let mut shifts = vec![];
+        let mut rows = vec![];
+
+        for (i, f) in self.members.iter().enumerate() {
+            let Some(f_deg) = f.terms.iter().map(total_deg).max() else {
+                continue;
+            };
+
+            // the lowest shifts first
+            for m in columns.iter().rev().filter(|m| total_deg(m) + f_deg <= deg) {
+                let mut row = vec![Rat::zero(); columns.len()];
+                for t in &f.terms {
+                    row[index[&monomial_mul(m, t).vars]] = t.val;
+                }
+
+                shifts.push((i, m.clone()));
+                rows.push(row);
+            }
+        }
+
+        MacaulayMatrix {
+            columns,
+            shifts,
+            rows,
+        }
+    }
+}
+
+impl MacaulayMatrix {
+    fn matrix(&self) -> Matrix<Rat> {
+        Matrix::new(self.columns.len(), self.rows.clone()).unwrap()
+    }
+
+    pub fn rank(&self) -> usize {
+        self.matrix().rank()
+    }
+
+    // a basis of the vectors the matrix takes to zero, each 1 at a different
+    // free column
+    pub fn nullspace(&self) -> Vec<Vec<Rat>> {
+        self.matrix().null_space()
+    }
+
+    fn svd(&self) -> (Vec<f64>, Vec<Vec<f64>>) {
+        let a = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(|c| f64::from(*c)).collect())
+            .collect();
+
+        jacobi_svd(a, self.columns.len())
+    }
+
+    // highest first, one per column
+    pub fn singular_values(&self) -> Vec<f64> {
+        let mut values = self.svd().0;
+        values.sort_by(|a, b| b.total_cmp(a));
+        values
+    }
+
+    // the singular values above tol times the largest
+    pub fn numeric_rank(&self, tol: f64) -> usize {
+        let values = self.singular_values();
+        let max = values.first().copied().unwrap_or(0.);
+        values.iter().filter(|s| **s > tol * max).count()
+    }
+
+    // orthonormal, spanning the right singular vectors of the singular
+    // values at or below tol times the largest
+    pub fn numeric_nullspace(&self, tol: f64) -> Vec<Vec<f64>> {
+        let (values, v) = self.svd();
+        let max = values.iter().copied().fold(0., f64::max);
+
+        (0..self.columns.len())
+            .filter(|j| values[*j] <= tol * max)
+            .map(|j| v.iter().map(|row| row[j]).collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::poly::mono::print_exps;
+    use crate::rational::Rat;
+    use crate::system;
+
+    #[test]
+    fn macaulay() {
+        let sys = system! {
+            x - 1,
+            y - 2
+        };
+
+        let m = sys.macaulay_matrix(2);
+        let columns = m
+            .columns
+            .iter()
+            .map(|c| print_exps(c, &sys.var_dict))
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["x^2", "xy", "y^2", "x", "y", ""], columns);
+        assert_eq!(6, m.rows.len());
+
+        // x (y - 2) - y (x - 1) = (y - 2) - 2 (x - 1) is the one relation,
+        // and the null space is the monomials at the solution (1, 2)
+        assert_eq!(5, m.rank());
+        let values = [1, 2, 4, 1, 2, 1].map(Rat::from).to_vec();
+        assert_eq!(vec![values], m.nullspace());
+
+        assert_eq!(5, m.numeric_rank(1e-9));
+        let null = &m.numeric_nullspace(1e-9)[0];
+        let scale = null[5];
+        assert!([1., 2., 4., 1., 2., 1.]
+            .iter()
+            .zip(null)
+            .all(|(a, b)| (a - b / scale).abs() < 1e-9));
+
+        // two nearly equal roots look like a double one numerically
+        let sys = system! {
+            x - 1,
+            1000*x - 1001
+        };
+
+        let m = sys.macaulay_matrix(1);
+        assert_eq!(2, m.rank());
+        assert_eq!(1, m.numeric_rank(1e-5));
+        assert_eq!(2, m.numeric_rank(1e-9));
+        assert!(m.nullspace().is_empty());
+    }
+}
@@ -1,3 +1,4 @@
+#[doc(hidden)]
 #[macro_export]
 macro_rules! poly_helper_b {
     () => { vec![] };
@@ -73,6 +74,7 @@ macro_rules! poly_helper_b {
     }};
 }
 
+#[doc(hidden)]
 #[macro_export]
 macro_rules! poly_helper_a {
     () => {{
@@ -115,22 +117,17 @@ macro_rules! poly_helper_a {
 #[macro_export]
 macro_rules! system {
     () => {{
-        use std::rc::Rc;
-        use $crate::poly::system::System;
+        use $crate::__private::System;
 
-        System {
-            members: vec![],
-            var_dict: Rc::new(vec![])
-        }
+        System::new(vec![], vec![]).unwrap()
     }};
 
     (@accumulate [ $($accumulated:tt)* ] [ ]) => {{
-        use std::rc::Rc;
         use std::collections::{HashSet, VecDeque};
-        use $crate::rational::Rat;
-        use $crate::poly::mono::Mono;
-        use $crate::poly::Poly;
-        use $crate::poly::system::System;
+        use $crate::__private::Rat;
+        use $crate::__private::Mono;
+        use $crate::__private::Poly;
+        use $crate::__private::System;
 
         let raw_polys = vec![$($accumulated)*];
 
@@ -148,33 +145,32 @@ macro_rules! system {
 
         let mut var_dict = var_dict.into_iter().collect::<Vec<_>>();
         var_dict.sort();
-        let var_dict = Rc::new(var_dict);
-
-        System {
-            var_dict: var_dict.clone(),
-            members: raw_polys
-                .into_iter()
-                .map(|monos| {
-                    let terms = monos.into_iter().map(|(coef, mut vars)| {
-                        vars.sort_by(|a, b| a.0.cmp(b.0));
-                        Mono {
-                            val: Rat::new(coef),
-                            vars: vars.into_iter()
-                                .map(|(var, pow)| (var_dict.iter().position(|v| v == var).unwrap(), pow))
-                                .collect()
-                        }}
-                    ).collect::<VecDeque<_>>();
-
-                    let mut acc = Poly::constant(Rat::from(0));
-
-                    for term in terms {
-                        acc = acc + Poly { terms: vec![term] };
-                    }
 
-                    acc
-                })
-                .collect::<Vec<_>>()
-        }
+        let members = raw_polys
+            .into_iter()
+            .map(|monos| {
+                let terms = monos.into_iter().map(|(coef, mut vars)| {
+                    vars.sort_by(|a, b| a.0.cmp(b.0));
+                    Mono {
+                        val: Rat::new(coef),
+                        vars: vars.into_iter()
+                            .map(|(var, pow)| (var_dict.iter().position(|v| v == var).unwrap(), pow))
+                            .collect()
+                    }}
+                ).collect::<VecDeque<_>>();
+
+                let mut acc = Poly::constant(Rat::from(0));
+
+                for term in terms {
+                    acc = acc + Poly { terms: vec![term] };
+                }
+
+                acc
+            })
+            .collect::<Vec<_>>();
+
+        // every var is named, so this cannot fail
+        System::new(var_dict, members).unwrap()
     }};
 
     (@accumulate [ $($accumulated:tt)* ] [ $($this_line:tt)* ]) => {
@@ -0,0 +1,190 @@
+// the size of polys and systems, and a simplification pass which reports it
+// before and after. simplify keeps the solutions: each member becomes the
+// integer primitive part of its squarefree part, and zero and repeated
+// members are dropped. splitting by factors goes further, turning each
+// reducible member into a case per distinct factor; the solutions are those
+// of any case, e.g. of their SystemUnion
+
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Metrics {
+    pub members: usize,
+    pub terms: usize,
+    // the highest total degree of a term
+    pub total_deg: u64,
+    // the most bits of a numerator or denominator
+    pub max_coef_bits: u32,
+    // of every numerator and denominator
+    pub coef_bits: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimplifyReport {
+    pub before: Metrics,
+    // one per system returned
+    pub after: Vec<Metrics>,
+}
+
+fn bits(n: i64) -> u32 {
+    64 - n.unsigned_abs().leading_zeros()
+}
+
+impl Metrics {
+    fn combine(self, other: Metrics) -> Metrics {
+        Metrics {
+            members: self.members + other.members,
+            terms: self.terms + other.terms,
+            total_deg: self.total_deg.max(other.total_deg),
+            max_coef_bits: self.max_coef_bits.max(other.max_coef_bits),
+            coef_bits: self.coef_bits + other.coef_bits,
+        }
+    }
+}
+
+impl Poly<Rat> {
+    pub fn metrics(&self) -> Metrics {
+        self.terms.iter().fold(
+            Metrics {
+                members: 1,
+                ..Metrics::default()
+            },
+            |m, t| {
+                let coef_bits = bits(t.val.num).max(bits(t.val.den));
+                Metrics {
+                    members: 1,
+                    terms: m.terms + 1,
+                    total_deg: m.total_deg.max(t.vars.iter().map(|(_, pow)| *pow).sum()),
+                    max_coef_bits: m.max_coef_bits.max(coef_bits),
+                    coef_bits: m.coef_bits + (bits(t.val.num) + bits(t.val.den)) as u64,
+                }
+            },
+        )
+    }
+}
+
+impl System<Rat> {
+    pub fn metrics(&self) -> Metrics {
+        self.members
+            .iter()
+            .fold(Metrics::default(), |m, p| m.combine(p.metrics()))
+    }
+
+    pub fn simplify(&self) -> (System<Rat>, SimplifyReport) {
+        let simplified = self.simplified();
+
+        let report = SimplifyReport {
+            before: self.metrics(),
+            after: vec![simplified.metrics()],
+        };
+
+        (simplified, report)
+    }
+
+    // simplify, then a case for each choice of a distinct irreducible factor
+    // of every member
+    pub fn split_factors(&self) -> (Vec<System<Rat>>, SimplifyReport) {
+        let simplified = self.simplified();
+        let mut cases = vec![vec![]];
+
+        for p in &simplified.members {
+            let (_, factors) = p.factor();
+            let factors = if factors.is_empty() {
+                vec![p.clone()]
+            } else {
+                factors.into_iter().map(|(f, _)| f).collect()
+            };
+
+            cases = cases
+                .into_iter()
+                .flat_map(|case: Vec<Poly<Rat>>| {
+                    factors.iter().map(move |f| {
+                        let mut case = case.clone();
+                        if !case.contains(f) {
+                            case.push(f.clone());
+                        }
+                        case
+                    })
+                })
+                .collect();
+        }
+
+        let cases = cases
+            .into_iter()
+            .map(|members| System {
+                var_dict: self.var_dict.clone(),
+                members,
+            })
+            .collect::<Vec<_>>();
+
+        let report = SimplifyReport {
+            before: self.metrics(),
+            after: cases.iter().map(|sys| sys.metrics()).collect(),
+        };
+
+        (cases, report)
+    }
+
+    fn simplified(&self) -> System<Rat> {
+        let mut members: Vec<Poly<Rat>> = vec![];
+
+        for p in self.members.iter().filter(|p| !p.is_zero()) {
+            let p = p.squarefree_part().norm();
+            if !members.contains(&p) {
+                members.push(p);
+            }
+        }
+
+        System {
+            var_dict: self.var_dict.clone(),
+            members,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Metrics;
+    use crate::system;
+
+    #[test]
+    fn simplify() {
+        let sys = system! {
+            2*x^2 - 4*x + 2,
+            6*x*y - 3*y,
+            12*x*y - 6*y
+        };
+
+        assert_eq!(
+            Metrics {
+                members: 1,
+                terms: 3,
+                total_deg: 2,
+                max_coef_bits: 3,
+                coef_bits: 10
+            },
+            sys.members[0].metrics()
+        );
+
+        // (x - 1)^2 is x - 1 on the solutions, and the last two agree
+        let (simplified, report) = sys.simplify();
+        assert_eq!("[x - 1, 2xy - y]", format!("{:?}", simplified));
+        assert_eq!(3, report.before.members);
+        assert_eq!(7, report.before.terms);
+        assert_eq!(4, report.before.max_coef_bits);
+        assert_eq!(2, report.after[0].members);
+        assert_eq!(4, report.after[0].terms);
+        assert_eq!(2, report.after[0].max_coef_bits);
+
+        // y (2x - 1) = 0 splits into y = 0 or x = 1/2
+        let (cases, report) = sys.split_factors();
+        let cases = cases
+            .iter()
+            .map(|sys| format!("{:?}", sys))
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["[x - 1, 2x - 1]", "[x - 1, y]"], cases);
+        assert_eq!(2, report.after.len());
+    }
+}
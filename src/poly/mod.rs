@@ -1,11 +1,45 @@
+pub mod approx_gb;
+pub mod bezout;
+pub mod bivariate;
+pub mod border;
+pub mod builder;
+pub mod certificate;
+pub mod diagnose;
+pub mod exactify;
+pub mod extended_gb;
+pub mod f4;
+#[cfg(feature = "gpu")]
+pub mod f4_gpu;
+pub mod fglm;
+pub mod gcd;
+pub mod groebner;
+pub mod hilbert;
+pub mod ideal;
+pub mod identity;
+pub mod json;
+pub mod macaulay;
 pub mod macros;
+pub mod metrics;
 pub mod mono;
+pub mod order;
+pub mod parse;
 pub mod poly_arithmetic;
+pub mod projective;
+pub mod quotient;
+pub mod quotient_ring;
+pub mod scaling;
+pub mod slp;
 pub mod system;
+pub mod template;
+pub mod triangular;
+pub mod univariate_system;
+pub mod var_order;
+pub mod var_store;
 
 use std::fmt::Write;
 
 use crate::poly::mono::*;
+use crate::poly::order::MonomialOrder;
 use crate::rational::{gcd, Rat};
 
 use crate::field::Field;
@@ -64,12 +98,18 @@ impl<T: Field> Poly<T> {
         self.terms.is_empty()
     }
 
+    // the leading term in grlex, the order the terms are stored in
     pub fn lt(&self) -> Poly<T> {
-        match self.terms.last() {
-            Some(m) => Poly {
-                terms: vec![m.clone()],
-            },
-            None => Poly { terms: vec![] },
+        self.lt_in(&MonomialOrder::Grlex)
+    }
+
+    // the leading term in order; zero for zero
+    pub fn lt_in(&self, order: &MonomialOrder) -> Poly<T> {
+        Poly {
+            terms: order
+                .lt_index(self)
+                .map(|i| vec![self.terms[i].clone()])
+                .unwrap_or_default(),
         }
     }
 
@@ -83,21 +123,25 @@ impl<T: Field> Poly<T> {
         }
     }
 
-    pub fn s_poly(p: Poly<T>, q: Poly<T>) -> Poly<T> {
-        let p_lt = p.lt();
-        let q_lt = q.lt();
+    // the leading term and the rest of the terms, both borrowed, so taking
+    // them apart in a reduction loop clones nothing. None for zero
+    pub fn split_lt(&self) -> Option<(&Mono<T>, &[Mono<T>])> {
+        self.terms.split_last()
+    }
 
-        let lcm_lmp_lmq = Poly {
-            terms: vec![monomial_lcm(p_lt.lt_mono(), q_lt.lt_mono())],
-        };
+    // the same, taking the poly apart without copying its terms
+    pub fn into_split_lt(mut self) -> Option<(Mono<T>, Poly<T>)> {
+        self.terms.pop().map(|lt| (lt, self))
+    }
 
-        if let (Some(coef_p), Some(coef_q)) =
-            (lcm_lmp_lmq.try_divide(&p_lt), lcm_lmp_lmq.try_divide(&q_lt))
-        {
-            coef_p * p - coef_q * q
-        } else {
-            unreachable!()
-        }
+    pub fn s_poly(p: Poly<T>, q: Poly<T>) -> Poly<T> {
+        Poly::s_poly_in(&p, &q, &MonomialOrder::Grlex)
+    }
+
+    // lcm / lt(p) p - lcm / lt(q) q for the lcm of the leading monomials in
+    // order, cancelling the leading terms; p and q must be nonzero
+    pub fn s_poly_in(p: &Poly<T>, q: &Poly<T>, order: &MonomialOrder) -> Poly<T> {
+        order.s_poly(p, q)
     }
 
     pub fn deg(&self, var: usize) -> usize {
@@ -109,9 +153,7 @@ impl<T: Field> Poly<T> {
 
     pub fn coefs(&self, var: usize) -> Vec<Poly<T>> {
         let deg = self.deg(var);
-        let mut coefs: Vec<_> = std::iter::repeat(Poly::constant(T::zero()))
-            .take(deg + 1)
-            .collect();
+        let mut coefs: Vec<_> = std::iter::repeat_n(Poly::constant(T::zero()), deg + 1).collect();
 
         for term in self.terms.iter().rev() {
             let (term_deg, term_coef) = term.coef(var);
@@ -133,7 +175,12 @@ impl<T: Field> Poly<T> {
             if i == deg {
                 new = new + term
             } else {
-                let var_pow = Poly { terms: vec![Mono { val: T::one(), vars: vec![(var, (deg - i) as u64)] }] };
+                let var_pow = Poly {
+                    terms: vec![Mono {
+                        val: T::one(),
+                        vars: vec![(var, (deg - i) as u64)],
+                    }],
+                };
 
                 new = new + term * var_pow;
             }
@@ -297,10 +344,7 @@ mod tests {
 
         assert_eq!(
             "20z^3 + 8y + z + 30",
-            format!(
-                "{}",
-                g.eval(0, Rat::from(2)).format(&var_dict)
-            )
+            format!("{}", g.eval(0, Rat::from(2)).format(&var_dict))
         );
     }
 }
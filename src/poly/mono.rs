@@ -12,7 +12,7 @@ impl<T: Field> Mono<T> {
     pub fn deg(&self, var: usize) -> usize {
         self.vars
             .iter()
-            .find_map(|(v, pow)| match var.cmp(v) {
+            .find_map(|(v, pow)| match v.cmp(&var) {
                 Ordering::Equal => Some(*pow as usize),
                 Ordering::Greater => Some(0),
                 Ordering::Less => None,
@@ -59,7 +59,7 @@ pub fn print_exps<T: Field>(term: &Mono<T>, var_dict: &[String]) -> String {
     res
 }
 
-pub fn grevlex<T: Field>(lhs: &Mono<T>, rhs: &Mono<T>) -> Ordering {
+pub fn grlex<T: Field>(lhs: &Mono<T>, rhs: &Mono<T>) -> Ordering {
     let lhs_total_degree = lhs.vars.iter().fold(0, |acc, (_, pow)| acc + pow);
     let rhs_total_degree = rhs.vars.iter().fold(0, |acc, (_, pow)| acc + pow);
 
@@ -84,6 +84,13 @@ pub fn grevlex<T: Field>(lhs: &Mono<T>, rhs: &Mono<T>) -> Ordering {
     }
 }
 
+// the old name of grlex, which it always computed; MonomialOrder::Grevlex is
+// the graded reverse lex order
+#[deprecated(note = "this is graded lex; use grlex, or MonomialOrder::Grevlex for grevlex")]
+pub fn grevlex<T: Field>(lhs: &Mono<T>, rhs: &Mono<T>) -> Ordering {
+    grlex(lhs, rhs)
+}
+
 pub fn monomial_div<T: Field>(lhs: &Mono<T>, rhs: &Mono<T>) -> Option<Mono<T>> {
     if rhs.val.is_zero() {
         None
@@ -329,13 +336,26 @@ z
         .split("\n")
         .collect::<Vec<_>>();
 
-        terms.sort_by(|a, b| grevlex(a, b));
+        terms.sort_by(grlex);
 
         for (i, term) in terms.iter().rev().enumerate() {
-            assert_eq!(expected_sort[i], print_exps(&term, &var_dict));
+            assert_eq!(expected_sort[i], print_exps(term, &var_dict));
         }
     }
 
+    #[test]
+    fn deg() {
+        let term = Mono {
+            val: Rat::new(1),
+            vars: vec![(0, 2), (2, 3)],
+        };
+
+        assert_eq!(
+            vec![2, 0, 3, 0],
+            (0..4).map(|var| term.deg(var)).collect::<Vec<_>>()
+        );
+    }
+
     #[test]
     fn div_mul_fuzz() {
         let mut rng = SmallRng::seed_from_u64(1);
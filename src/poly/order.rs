@@ -0,0 +1,466 @@
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+use crate::field::Field;
+use crate::poly::groebner::{GroebnerConfig, Normalization};
+use crate::poly::mono::{grlex, monomial_div, monomial_lcm, Mono};
+use crate::poly::system::System;
+use crate::poly::var_order::VarOrder;
+use crate::poly::Poly;
+use crate::profile::phase;
+use crate::rational::Rat;
+
+// polys always store their terms in grlex order; other orders only change
+// which term leads during Groebner basis computations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MonomialOrder {
+    // total degree, then lex
+    Grlex,
+    // total degree, then the smaller degree in the last var where they differ
+    Grevlex,
+    // var 0 > var 1 > ...
+    Lex,
+    // compare by grlex in the vars of the first block, then the next, ...;
+    // vars in no block form an implicit last block
+    Block(Vec<Vec<usize>>),
+}
+
+fn grevlex<T: Field>(lhs: &Mono<T>, rhs: &Mono<T>) -> Ordering {
+    let deg = |m: &Mono<T>| m.vars.iter().map(|(_, pow)| *pow).sum::<u64>();
+    let max_var = lhs.vars.iter().chain(&rhs.vars).map(|(v, _)| *v).max();
+
+    deg(lhs).cmp(&deg(rhs)).then_with(|| {
+        (0..max_var.map_or(0, |v| v + 1))
+            .rev()
+            .map(|v| rhs.deg(v).cmp(&lhs.deg(v)))
+            .find(|ord| ord.is_ne())
+            .unwrap_or(Ordering::Equal)
+    })
+}
+
+fn restrict<T: Field>(m: &Mono<T>, keep: impl Fn(usize) -> bool) -> Mono<T> {
+    Mono {
+        val: m.val.clone(),
+        vars: m.vars.iter().filter(|(v, _)| keep(*v)).cloned().collect(),
+    }
+}
+
+impl MonomialOrder {
+    // eliminate vars 0..k, keeping grlex within both blocks
+    pub fn eliminate_first_k(k: usize) -> MonomialOrder {
+        MonomialOrder::Block(vec![(0..k).collect()])
+    }
+
+    // eliminate vars, keeping grlex within both blocks
+    pub fn eliminate(vars: &[usize]) -> MonomialOrder {
+        let mut vars = vars.to_vec();
+        vars.sort();
+        vars.dedup();
+        MonomialOrder::Block(vec![vars])
+    }
+
+    pub fn cmp<T: Field>(&self, lhs: &Mono<T>, rhs: &Mono<T>) -> Ordering {
+        match self {
+            MonomialOrder::Grlex => grlex(lhs, rhs),
+            MonomialOrder::Grevlex => grevlex(lhs, rhs),
+            MonomialOrder::Lex => {
+                let max_var = lhs.vars.iter().chain(&rhs.vars).map(|(v, _)| *v).max();
+
+                (0..max_var.map_or(0, |v| v + 1))
+                    .map(|v| lhs.deg(v).cmp(&rhs.deg(v)))
+                    .find(|ord| ord.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            }
+            MonomialOrder::Block(blocks) => {
+                let in_block = blocks
+                    .iter()
+                    .map(|block| {
+                        grlex(
+                            &restrict(lhs, |v| block.contains(&v)),
+                            &restrict(rhs, |v| block.contains(&v)),
+                        )
+                    })
+                    .find(|ord| ord.is_ne());
+
+                in_block.unwrap_or_else(|| {
+                    let rest = |v: usize| blocks.iter().all(|block| !block.contains(&v));
+                    grlex(&restrict(lhs, rest), &restrict(rhs, rest))
+                })
+            }
+        }
+    }
+
+    // whether every monomial involving vars is above every monomial free of
+    // them, so that a Groebner basis for this order contains one for the
+    // elimination ideal
+    pub fn eliminates(&self, vars: &[usize]) -> bool {
+        let mut vars = vars.to_vec();
+        vars.sort();
+        vars.dedup();
+
+        match self {
+            MonomialOrder::Grlex | MonomialOrder::Grevlex => vars.is_empty(),
+            MonomialOrder::Lex => vars.iter().enumerate().all(|(i, v)| i == *v),
+            MonomialOrder::Block(blocks) => {
+                let mut covered: Vec<usize> = vec![];
+
+                if vars.is_empty() {
+                    return true;
+                }
+
+                for block in blocks {
+                    covered.extend(block);
+                    covered.sort();
+                    covered.dedup();
+
+                    if covered == vars {
+                        return true;
+                    }
+                }
+
+                false
+            }
+        }
+    }
+
+    // the position of the leading term in p.terms; None for zero
+    pub(crate) fn lt_index<T: Field>(&self, p: &Poly<T>) -> Option<usize> {
+        match self {
+            // the order the terms are stored in
+            MonomialOrder::Grlex => p.terms.len().checked_sub(1),
+            _ => (0..p.terms.len()).max_by(|a, b| self.cmp(&p.terms[*a], &p.terms[*b])),
+        }
+    }
+
+    pub(crate) fn lt<'a, T: Field>(&self, p: &'a Poly<T>) -> &'a Mono<T> {
+        &p.terms[self.lt_index(p).unwrap()]
+    }
+
+    pub(crate) fn s_poly<T: Field>(&self, p: &Poly<T>, q: &Poly<T>) -> Poly<T> {
+        let (p_lt, q_lt) = (self.lt(p), self.lt(q));
+        let lcm = monomial_lcm(p_lt.clone(), q_lt.clone());
+
+        let lcm = Mono {
+            val: T::one(),
+            vars: lcm.vars,
+        };
+
+        let coef = |lt: &Mono<T>| Poly {
+            terms: vec![monomial_div(&lcm, lt).unwrap()],
+        };
+
+        coef(p_lt).mul_ref(p) - coef(q_lt).mul_ref(q)
+    }
+
+    // normal form of p modulo divisors
+    pub(crate) fn reduce<T: Field>(&self, p: &Poly<T>, divisors: &[Poly<T>]) -> Poly<T> {
+        let mut p = p.clone();
+        let mut rem = Poly::constant(T::zero());
+
+        while !p.is_zero() {
+            let lt = self.lt(&p).clone();
+
+            let quot = divisors
+                .iter()
+                .filter(|d| !d.is_zero())
+                .find_map(|d| monomial_div(&lt, self.lt(d)).map(|m| (m, d)));
+
+            match quot {
+                Some((m, d)) => p = p - Poly { terms: vec![m] }.mul_ref(d),
+                None => {
+                    let lt = Poly { terms: vec![lt] };
+                    p = p - lt.clone();
+                    rem = rem + lt;
+                }
+            }
+        }
+
+        rem
+    }
+
+    // the reduced basis of a Groebner basis over any field: drop the members
+    // whose leading term is a multiple of another's, interreduce the rest and
+    // scale them by norm; by decreasing leading term
+    pub(crate) fn interreduce<T: Field>(
+        &self,
+        members: &[Poly<T>],
+        norm: impl Fn(Poly<T>) -> Poly<T>,
+    ) -> Vec<Poly<T>> {
+        let members = members.iter().filter(|p| !p.is_zero()).collect::<Vec<_>>();
+
+        let mut keep: Vec<Poly<T>> = vec![];
+
+        for (i, p) in members.iter().enumerate() {
+            let redundant = members.iter().enumerate().any(|(j, q)| {
+                i != j
+                    && monomial_div(self.lt(p), self.lt(q)).is_some()
+                    && (self.cmp(self.lt(p), self.lt(q)).is_ne() || j < i)
+            });
+
+            if !redundant {
+                keep.push((*p).clone());
+            }
+        }
+
+        let mut reduced = (0..keep.len())
+            .map(|i| {
+                let lt = Poly {
+                    terms: vec![self.lt(&keep[i]).clone()],
+                };
+                let others = keep
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, q)| q.clone())
+                    .collect::<Vec<_>>();
+
+                norm(lt.clone() + self.reduce(&(keep[i].clone() - lt), &others))
+            })
+            .collect::<Vec<_>>();
+
+        reduced.sort_by(|p, q| self.cmp(self.lt(p), self.lt(q)).reverse());
+        reduced
+    }
+}
+
+impl System<Rat> {
+    // reduced Groebner basis for order, by Buchberger's algorithm with the
+    // normal strategy and both criteria; see gb_with_config for others
+    pub fn gb_with_order(&self, order: &MonomialOrder) -> System<Rat> {
+        self.gb_with_config(&GroebnerConfig::new(order.clone())).0
+    }
+
+    // the reduced basis, unique for the ideal and order, of a Groebner basis
+    // for order, with the members normed
+    pub fn reduce_gb(&self, order: &MonomialOrder) -> System<Rat> {
+        self.reduce_gb_with(order, Normalization::Primitive)
+    }
+
+    // the same, with the members scaled by normalization
+    pub fn reduce_gb_with(
+        &self,
+        order: &MonomialOrder,
+        normalization: Normalization,
+    ) -> System<Rat> {
+        let _phase = phase("reduce gb");
+        System {
+            var_dict: self.var_dict.clone(),
+            members: order.interreduce(&self.members, |p| normalization.apply(p, order)),
+        }
+    }
+
+    // generators of the ideal intersected with the polys free of vars, read
+    // off a Groebner basis for order; None if order is not an elimination
+    // order for vars
+    pub fn eliminate_with_order(
+        &self,
+        vars: &[usize],
+        order: &MonomialOrder,
+    ) -> Option<System<Rat>> {
+        if !order.eliminates(vars) {
+            return None;
+        }
+
+        let mut gb = self.gb_with_order(order);
+        gb.members.retain(|p| vars.iter().all(|v| p.deg(*v) == 0));
+
+        Some(gb)
+    }
+
+    // the projection onto the other vars. a block order eliminates the same
+    // as lex, with a usually much smaller basis
+    pub fn eliminate(&self, vars: &[usize]) -> System<Rat> {
+        self.eliminate_with_order(vars, &MonomialOrder::eliminate(vars))
+            .unwrap()
+    }
+
+    // the projection onto the distinct vars keep, as a system in those vars
+    // alone: the rest are eliminated, and keep[i] becomes var i. None if keep
+    // repeats a var or has one past the system's
+    pub fn project(&self, keep: &[usize]) -> Option<System<Rat>> {
+        let n = self.var_dict.len();
+        if keep
+            .iter()
+            .enumerate()
+            .any(|(i, v)| *v >= n || keep[..i].contains(v))
+        {
+            return None;
+        }
+
+        let eliminated = (0..n).filter(|v| !keep.contains(v)).collect::<Vec<_>>();
+
+        let order = keep.iter().chain(&eliminated).copied().collect();
+        let mut projected = self
+            .eliminate(&eliminated)
+            .with_var_order(&VarOrder { order });
+        projected.var_dict = Rc::new(projected.var_dict[..keep.len()].to_vec());
+
+        Some(projected)
+    }
+
+    // the k'th elimination ideal, in the vars k, k + 1, ...
+    pub fn eliminate_first_k(&self, k: usize) -> System<Rat> {
+        let vars = (0..k).collect::<Vec<_>>();
+        self.eliminate_with_order(&vars, &MonomialOrder::eliminate_first_k(k))
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MonomialOrder;
+    use crate::poly::Poly;
+    use crate::rational::Rat;
+    use crate::system;
+
+    #[test]
+    fn orders() {
+        let sys = system! {
+            x^2*y,
+            x*y^3,
+            z^4,
+            x*z
+        };
+
+        let m = sys.members.iter().map(|p| p.lt_mono()).collect::<Vec<_>>();
+
+        let lex = MonomialOrder::Lex;
+        assert!(lex.cmp(&m[0], &m[1]).is_gt());
+        assert!(lex.cmp(&m[3], &m[2]).is_gt());
+
+        // {x} before {y, z}
+        let block = MonomialOrder::eliminate_first_k(1);
+        assert!(block.cmp(&m[3], &m[2]).is_gt());
+        assert!(block.cmp(&m[0], &m[1]).is_gt());
+        assert!(block.cmp(&m[1], &m[3]).is_gt());
+
+        assert!(lex.eliminates(&[0, 1]) && !lex.eliminates(&[1]));
+        assert!(block.eliminates(&[0]) && block.eliminates(&[]) && !block.eliminates(&[0, 1]));
+        assert!(!MonomialOrder::Grlex.eliminates(&[0]));
+
+        // equal degree, where grlex and grevlex differ
+        let sys = system! {
+            x*z^2,
+            y^3
+        };
+
+        let m = sys.members.iter().map(|p| p.lt_mono()).collect::<Vec<_>>();
+        assert!(MonomialOrder::Grlex.cmp(&m[0], &m[1]).is_gt());
+        assert!(MonomialOrder::Grevlex.cmp(&m[0], &m[1]).is_lt());
+        assert!(MonomialOrder::Grevlex.cmp(&m[1], &m[1]).is_eq());
+        assert!(MonomialOrder::eliminate(&[2, 0]).eliminates(&[0, 2]));
+
+        // the same through the poly methods
+        let sys = system! {
+            x*z^2 + y^3,
+            y^3 - z
+        };
+        let (p, q) = (&sys.members[0], &sys.members[1]);
+        let fmt = |p: Poly<Rat>| p.format(&sys.var_dict);
+        assert_eq!("xz^2", fmt(p.lt()));
+        assert_eq!("y^3", fmt(p.lt_in(&MonomialOrder::Grevlex)));
+        assert_eq!(
+            "0",
+            fmt(Poly::constant(Rat::from(0)).lt_in(&MonomialOrder::Lex))
+        );
+        assert_eq!(
+            fmt(Poly::s_poly(p.clone(), q.clone())),
+            fmt(Poly::s_poly_in(p, q, &MonomialOrder::Grlex))
+        );
+        assert_eq!(
+            "xz^2 + z",
+            fmt(Poly::s_poly_in(p, q, &MonomialOrder::Grevlex))
+        );
+    }
+
+    #[test]
+    fn eliminate() {
+        let sys = system! {
+            x^2*y + 1,
+            2*x + y*z - 1,
+            x - y^2*z^2 + 1
+        };
+
+        assert_eq!("[32z - 75]", format!("{:?}", sys.eliminate_first_k(2)));
+        assert_eq!(
+            "[4x - 5, 25y + 16, 32z - 75]",
+            format!("{:?}", sys.gb_with_order(&MonomialOrder::Lex))
+        );
+
+        // implicit equations of the twisted cubic
+        let sys = system! {
+            t - x,
+            t^2 - y,
+            t^3 - z
+        };
+
+        let implicit = sys.eliminate_first_k(1);
+        assert_eq!(
+            "[y^3 - z^2, x^2 - y, xy - z, xz - y^2]",
+            format!("{:?}", implicit)
+        );
+
+        assert!(sys
+            .eliminate_with_order(&[0], &MonomialOrder::Grlex)
+            .is_none());
+        // the same ideal from a lex basis
+        assert_eq!(
+            format!("{:?}", implicit),
+            format!(
+                "{:?}",
+                sys.eliminate_with_order(&[0], &MonomialOrder::Lex)
+                    .unwrap()
+                    .gb_with_order(&MonomialOrder::Grlex)
+            )
+        );
+    }
+
+    #[test]
+    fn project() {
+        let sys = system! {
+            t - x,
+            t^2 - y,
+            t^3 - z
+        };
+
+        // the block order basis generates the same ideal as the lex one
+        let lex = sys.eliminate_with_order(&[0], &MonomialOrder::Lex).unwrap();
+        let block = sys.eliminate(&[0]);
+        assert!(lex.iter().all(|p| block.contains(p)));
+        assert!(block.iter().all(|p| lex.contains(p)));
+
+        // the twisted cubic seen from above: a cusp in the yz plane
+        let cusp = sys.project(&[2, 3]).unwrap();
+        assert_eq!(vec!["y", "z"], *cusp.var_dict);
+        assert_eq!("[y^3 - z^2]", format!("{:?}", cusp));
+        let cusp = sys.project(&[3, 2]).unwrap();
+        assert_eq!(vec!["z", "y"], *cusp.var_dict);
+        assert_eq!("[y^3 - z^2]", format!("{:?}", cusp));
+
+        assert!(sys.project(&[7]).is_none());
+        assert!(sys.project(&[2, 2]).is_none());
+    }
+
+    #[test]
+    fn reduce_gb() {
+        let sys = system! {
+            x*y - 1,
+            x - y
+        };
+
+        for order in [MonomialOrder::Grlex, MonomialOrder::Lex] {
+            let gb = sys.gb_with_order(&order);
+
+            // a Groebner basis, but neither minimal nor reduced
+            let mut basis = gb.clone();
+            basis.members.push(gb.members[0].clone() * sys.var("y", 2));
+            basis
+                .members
+                .push(gb.members[1].clone() + gb.members[0].clone());
+            basis.members.push(sys.constant(0));
+            basis.members.reverse();
+
+            assert_eq!(gb.members, basis.reduce_gb(&order).members);
+        }
+    }
+}
@@ -0,0 +1,372 @@
+// polys from strings at runtime, for what system! cannot take: constraints
+// typed by a user or read from a file. the grammar is
+//
+//   sum     = ["+" | "-"] product {("+" | "-") product}
+//   product = power {["*" | "/"] power}
+//   power   = "-" power | atom ["^" integer]
+//   atom    = integer | name | "(" sum ")"
+//
+// where a missing operator between two powers multiplies them, so 3x^2y is
+// 3 * x^2 * y and (x + 1)(x - 1) is a product. a name is a letter followed
+// by letters, digits and underscores, so xy is one var; x y or x*y is two.
+// division is only by constants, which covers rational literals like 1/2.
+// names not yet in the var dict are appended to it in order of appearance.
+// coefs are computed exactly: one that does not fit in a Rat is an error
+// rather than rounded as Poly arithmetic would
+
+use std::fmt;
+
+use crate::poly::mono::{grlex, monomial_mul, Mono};
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+// positions are byte offsets into the source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    Unexpected { pos: usize, found: char },
+    UnexpectedEnd,
+    // an exponent which is not a nonnegative integer
+    Exponent { pos: usize },
+    // a coef past i64, in a literal or computed from them; pos is the start
+    // of the expression
+    Overflow { pos: usize },
+    DivisionByNonConstant { pos: usize },
+    DivisionByZero { pos: usize },
+    // parens or minus signs nested past MAX_DEPTH
+    TooDeep { pos: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Unexpected { pos, found } => write!(f, "unexpected '{found}' at {pos}"),
+            ParseError::UnexpectedEnd => write!(f, "unexpected end of input"),
+            ParseError::Exponent { pos } => {
+                write!(f, "expected a nonnegative integer exponent at {pos}")
+            }
+            ParseError::Overflow { pos } => write!(f, "integer too large at {pos}"),
+            ParseError::DivisionByNonConstant { pos } => {
+                write!(f, "division by a nonconstant at {pos}")
+            }
+            ParseError::DivisionByZero { pos } => write!(f, "division by zero at {pos}"),
+            ParseError::TooDeep { pos } => write!(f, "nested too deeply at {pos}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// sorted, with equal monomials combined; None if a coef does not fit
+fn combine(mut terms: Vec<Mono<Rat>>) -> Option<Poly<Rat>> {
+    terms.sort_by(grlex);
+
+    let mut combined: Vec<Mono<Rat>> = Vec::with_capacity(terms.len());
+    for term in terms {
+        match combined.last_mut() {
+            Some(last) if last.vars == term.vars => last.val = last.val.checked_add(term.val)?,
+            _ => combined.push(term),
+        }
+    }
+
+    combined.retain(|t| !t.val.is_zero());
+    Some(Poly { terms: combined })
+}
+
+fn checked_add(p: &Poly<Rat>, q: &Poly<Rat>) -> Option<Poly<Rat>> {
+    combine(p.terms.iter().chain(&q.terms).cloned().collect())
+}
+
+// None also if the degree does not fit in a u64
+fn checked_mul(p: &Poly<Rat>, q: &Poly<Rat>) -> Option<Poly<Rat>> {
+    p.total_deg().checked_add(q.total_deg())?;
+
+    let mut terms = Vec::with_capacity(p.terms.len() * q.terms.len());
+    for s in &p.terms {
+        for t in &q.terms {
+            let val = s.val.checked_mul(t.val)?;
+            terms.push(Mono {
+                val,
+                ..monomial_mul(s, t)
+            });
+        }
+    }
+
+    combine(terms)
+}
+
+// what may begin an atom, and so a factor of an implicit product
+fn starts_atom(c: char) -> bool {
+    c == '(' || c.is_ascii_digit() || c.is_alphabetic()
+}
+
+// each level of nesting is a few frames of recursion, so the source may not
+// overflow the stack however it is written
+const MAX_DEPTH: usize = 256;
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    depth: usize,
+    var_dict: &'a mut Vec<String>,
+}
+
+type Parsed = Result<Poly<Rat>, ParseError>;
+
+impl Parser<'_> {
+    fn peek(&mut self) -> Option<char> {
+        let rest = &self.src[self.pos..];
+        let trimmed = rest.trim_start();
+        self.pos += rest.len() - trimmed.len();
+        trimmed.chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(c);
+        if found {
+            self.pos += c.len_utf8();
+        }
+        found
+    }
+
+    fn unexpected(&mut self) -> ParseError {
+        match self.peek() {
+            Some(found) => ParseError::Unexpected {
+                pos: self.pos,
+                found,
+            },
+            None => ParseError::UnexpectedEnd,
+        }
+    }
+
+    // the longest run of chars matching at the current position
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &str {
+        let start = self.pos;
+        let len = self.src[start..]
+            .find(|c: char| !f(c))
+            .unwrap_or(self.src.len() - start);
+        self.pos += len;
+        &self.src[start..start + len]
+    }
+
+    // parse one level deeper
+    fn nested(&mut self, parse: impl FnOnce(&mut Self) -> Parsed) -> Parsed {
+        if self.depth == MAX_DEPTH {
+            // at the '(' or '-' just eaten
+            return Err(ParseError::TooDeep { pos: self.pos - 1 });
+        }
+
+        self.depth += 1;
+        let parsed = parse(self);
+        self.depth -= 1;
+        parsed
+    }
+
+    fn sum(&mut self) -> Parsed {
+        self.peek();
+        let overflow = ParseError::Overflow { pos: self.pos };
+        let negate = !self.eat('+') && self.eat('-');
+        let mut acc = self.product()?;
+        if negate {
+            acc = acc * Poly::constant(Rat::from(-1));
+        }
+
+        loop {
+            let rhs = if self.eat('+') {
+                self.product()?
+            } else if self.eat('-') {
+                self.product()? * Poly::constant(Rat::from(-1))
+            } else {
+                return Ok(acc);
+            };
+            acc = checked_add(&acc, &rhs).ok_or(overflow.clone())?;
+        }
+    }
+
+    fn product(&mut self) -> Parsed {
+        self.peek();
+        let overflow = ParseError::Overflow { pos: self.pos };
+        let mut acc = self.power()?;
+
+        loop {
+            let rhs = if self.eat('*') {
+                self.power()?
+            } else if self.eat('/') {
+                self.peek();
+                let pos = self.pos;
+                let divisor = self.power()?;
+                let c = match &divisor.terms[..] {
+                    [] => return Err(ParseError::DivisionByZero { pos }),
+                    [t] if t.vars.is_empty() => t.val,
+                    _ => return Err(ParseError::DivisionByNonConstant { pos }),
+                };
+                Poly::constant(Rat::from(1) / c)
+            } else if self.peek().is_some_and(starts_atom) {
+                self.power()?
+            } else {
+                return Ok(acc);
+            };
+            acc = checked_mul(&acc, &rhs).ok_or(overflow.clone())?;
+        }
+    }
+
+    fn power(&mut self) -> Parsed {
+        if self.eat('-') {
+            return Ok(self.nested(Self::power)? * Poly::constant(Rat::from(-1)));
+        }
+
+        self.peek();
+        let overflow = ParseError::Overflow { pos: self.pos };
+        let mut base = self.atom()?;
+        if !self.eat('^') {
+            return Ok(base);
+        }
+
+        self.peek();
+        let pos = self.pos;
+        let mut exp = self
+            .take_while(|c| c.is_ascii_digit())
+            .parse::<u64>()
+            .map_err(|_| ParseError::Exponent { pos })?;
+
+        // by squaring, so a large exponent overflows in at most 64 steps
+        let mut acc = Poly::constant(Rat::from(1));
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = checked_mul(&acc, &base).ok_or(overflow.clone())?;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = checked_mul(&base, &base).ok_or(overflow.clone())?;
+            }
+        }
+
+        Ok(acc)
+    }
+
+    fn atom(&mut self) -> Parsed {
+        match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let inner = self.nested(Self::sum)?;
+                if !self.eat(')') {
+                    return Err(self.unexpected());
+                }
+                Ok(inner)
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let pos = self.pos;
+                let val = self
+                    .take_while(|c| c.is_ascii_digit())
+                    .parse::<i64>()
+                    .map_err(|_| ParseError::Overflow { pos })?;
+                Ok(Poly::constant(Rat::from(val)))
+            }
+            Some(c) if c.is_alphabetic() => {
+                let name = self
+                    .take_while(|c| c.is_alphanumeric() || c == '_')
+                    .to_string();
+                let var = match self.var_dict.iter().position(|v| *v == name) {
+                    Some(var) => var,
+                    None => {
+                        self.var_dict.push(name);
+                        self.var_dict.len() - 1
+                    }
+                };
+                Ok(Poly::var(var, 1))
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+}
+
+impl Poly<Rat> {
+    pub fn parse(src: &str, var_dict: &mut Vec<String>) -> Result<Poly<Rat>, ParseError> {
+        let mut parser = Parser {
+            src,
+            pos: 0,
+            depth: 0,
+            var_dict,
+        };
+
+        let p = parser.sum()?;
+        match parser.peek() {
+            None => Ok(p),
+            Some(_) => Err(parser.unexpected()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParseError;
+    use crate::poly::Poly;
+    use crate::rational::Rat;
+
+    #[test]
+    fn parse() {
+        let mut var_dict = vec!["y".to_string()];
+
+        let p = Poly::parse("3x^2y - (x + 1/2)(y - 2) + -x*y", &mut var_dict).unwrap();
+        assert_eq!(vec!["y".to_string(), "x".to_string()], var_dict);
+        assert_eq!("3yx^2 - 2yx - 0.5y + 2x + 1", p.format(&var_dict));
+
+        // the same vars again, and a multi-letter name
+        let q = Poly::parse("  -(y^2 - x_1) / 4 + 2 * y ^ 2 ", &mut var_dict).unwrap();
+        assert_eq!(
+            vec!["y".to_string(), "x".to_string(), "x_1".to_string()],
+            var_dict
+        );
+        assert_eq!("1.75y^2 + 0.25x_1", q.format(&var_dict));
+
+        assert_eq!(
+            Ok(Poly::constant(Rat::from(3) / Rat::from(4))),
+            Poly::parse("3/4", &mut vec![])
+        );
+
+        let errors = [
+            ("x +", ParseError::UnexpectedEnd),
+            ("x + )", ParseError::Unexpected { pos: 4, found: ')' }),
+            ("(x", ParseError::UnexpectedEnd),
+            ("x^y", ParseError::Exponent { pos: 2 }),
+            ("x / y", ParseError::DivisionByNonConstant { pos: 4 }),
+            ("x / (1 - 1)", ParseError::DivisionByZero { pos: 4 }),
+            ("99999999999999999999x", ParseError::Overflow { pos: 0 }),
+            ("2^100", ParseError::Overflow { pos: 0 }),
+            ("3037000500*3037000500", ParseError::Overflow { pos: 0 }),
+            ("9223372036854775807 + 1", ParseError::Overflow { pos: 0 }),
+            ("y + (x + 1)^100", ParseError::Overflow { pos: 4 }),
+            ("x = 1", ParseError::Unexpected { pos: 2, found: '=' }),
+        ];
+        for (src, error) in errors {
+            assert_eq!(Err(error), Poly::parse(src, &mut vec![]));
+        }
+
+        // an error rather than a stack overflow
+        let deep = "(".repeat(5000) + "x" + &")".repeat(5000);
+        assert_eq!(
+            Err(ParseError::TooDeep { pos: 256 }),
+            Poly::parse(&deep, &mut vec![])
+        );
+        assert_eq!(
+            Err(ParseError::TooDeep { pos: 257 }),
+            Poly::parse(&("-".repeat(5000) + "x"), &mut vec![])
+        );
+        let shallow = "(".repeat(100) + "x" + &")".repeat(100);
+        assert!(Poly::parse(&shallow, &mut vec![]).is_ok());
+
+        // by squaring, and exact up to the edge of i64
+        let mut var_dict = vec![];
+        let p = Poly::parse("x^18446744073709551615", &mut var_dict).unwrap();
+        assert_eq!(u64::MAX, p.total_deg());
+        assert_eq!(
+            Ok(Poly::constant(Rat::from(i64::MAX))),
+            Poly::parse("2^62 - 1 + 2^62", &mut vec![])
+        );
+
+        // implicit products of names which are not ascii
+        let mut var_dict = vec![];
+        let p = Poly::parse("2θ(φ + 1)", &mut var_dict).unwrap();
+        assert_eq!(vec!["θ".to_string(), "φ".to_string()], var_dict);
+        assert_eq!("2θφ + 2θ", p.format(&var_dict));
+    }
+}
@@ -1,8 +1,9 @@
 use std::cmp::Ordering;
-use std::collections::VecDeque;
 use std::ops;
 
+use crate::poly::builder::PolyBuilder;
 use crate::poly::mono::*;
+use crate::poly::order::MonomialOrder;
 use crate::poly::*;
 
 impl<T: Field> ops::Add<Poly<T>> for Poly<T> {
@@ -25,7 +26,7 @@ impl<T: Field> ops::Add<Poly<T>> for Poly<T> {
         loop {
             if let Some(lhs_term) = lhs_term_iter.peek() {
                 if let Some(rhs_term) = rhs_term_iter.peek() {
-                    match grevlex(lhs_term, rhs_term) {
+                    match grlex(lhs_term, rhs_term) {
                         Ordering::Equal => {
                             let new_val = lhs_term.val.clone() + rhs_term.val.clone();
                             if !new_val.is_zero() {
@@ -99,66 +100,57 @@ impl<T: Field> Poly<T> {
         new
     }
 
-    pub fn compound_divide(&self, divisors: &Vec<Poly<T>>) -> (Vec<Poly<T>>, Poly<T>) {
-        if divisors.is_empty() {
-            return (vec![], self.clone());
-        }
-
-        let mut dividend = self.clone();
-
-        let mut rem = Poly::constant(T::zero());
-        let mut quotients: Vec<VecDeque<Mono<T>>> = std::iter::repeat(VecDeque::from(vec![]))
-            .take(divisors.len())
-            .collect();
-
-        let mut curr_divisor = 0;
-
-        while !dividend.is_zero() {
-            let self_lt = dividend.lt_mono();
-            if !divisors[curr_divisor].terms.is_empty() {
-                let div_lt = &divisors[curr_divisor].lt_mono();
-                let self_over_div_lt = monomial_div(&self_lt, div_lt);
-
-                if let Some(self_over_div_lt) = self_over_div_lt {
-                    quotients[curr_divisor].push_front(self_over_div_lt.clone());
+    // multivariate division in grlex, see div_rem_in
+    pub fn div_rem(&self, divisors: &[Poly<T>]) -> (Vec<Poly<T>>, Poly<T>) {
+        self.div_rem_in(divisors, &MonomialOrder::Grlex)
+    }
 
-                    let self_over_div_lt = Poly {
-                        terms: vec![self_over_div_lt],
-                    };
+    #[deprecated(note = "renamed to div_rem")]
+    pub fn compound_divide(&self, divisors: &[Poly<T>]) -> (Vec<Poly<T>>, Poly<T>) {
+        self.div_rem(divisors)
+    }
 
-                    dividend = dividend - (self_over_div_lt.mul_ref(&divisors[curr_divisor]));
-                    curr_divisor = 0;
-                } else {
-                    curr_divisor += 1;
+    // multivariate division in order, trying the divisors in order:
+    // self = sum quotients[i] divisors[i] + rem, and no term of rem is
+    // divisible by the leading term of any divisor
+    pub fn div_rem_in(
+        &self,
+        divisors: &[Poly<T>],
+        order: &MonomialOrder,
+    ) -> (Vec<Poly<T>>, Poly<T>) {
+        let mut dividend = self.clone();
+        let mut quotients = vec![PolyBuilder::new(); divisors.len()];
+        let mut rem = PolyBuilder::new();
+
+        while let Some(i) = order.lt_index(&dividend) {
+            let lt = &dividend.terms[i];
+            let quot = divisors
+                .iter()
+                .enumerate()
+                .filter(|(_, d)| !d.is_zero())
+                .find_map(|(k, d)| monomial_div(lt, order.lt(d)).map(|m| (k, m)));
+
+            match quot {
+                Some((k, m)) => {
+                    quotients[k].push(m.val.clone(), &m.vars);
+                    dividend = dividend - Poly { terms: vec![m] }.mul_ref(&divisors[k]);
+                }
+                // in grlex the last term, so popped in constant time
+                None => {
+                    let lt = dividend.terms.remove(i);
+                    rem.push(lt.val, &lt.vars);
                 }
-            } else {
-                curr_divisor += 1;
-            }
-
-            if curr_divisor == divisors.len() {
-                let self_lt = Poly {
-                    terms: vec![self_lt.clone()],
-                };
-
-                dividend.terms.pop();
-
-                rem = rem + self_lt;
-                curr_divisor = 0;
             }
         }
 
-        let quotients = quotients
-            .into_iter()
-            .map(|v| Poly {
-                terms: Vec::from(v),
-            })
-            .collect();
-
-        (quotients, rem)
+        (
+            quotients.into_iter().map(PolyBuilder::build).collect(),
+            rem.build(),
+        )
     }
 
     pub fn try_divide(&self, divisor: &Poly<T>) -> Option<Poly<T>> {
-        let (quots, rem) = self.compound_divide(&vec![divisor.clone()]);
+        let (quots, rem) = self.div_rem(std::slice::from_ref(divisor));
 
         if rem.is_zero() {
             Some(quots[0].clone())
@@ -200,6 +192,7 @@ impl<T: Field> Poly<T> {
 mod tests {
     use super::Poly;
     use crate::field::Zero;
+    use crate::poly::order::MonomialOrder;
     use crate::rational::Rat;
     use rand::prelude::*;
     use std::rc::Rc;
@@ -259,7 +252,7 @@ mod tests {
                 .take(n_divs)
                 .collect();
 
-            let (quotients, rem) = dividend.clone().compound_divide(&divisors);
+            let (quotients, rem) = dividend.clone().div_rem(&divisors);
 
             let calculated_dividend = quotients
                 .clone()
@@ -272,6 +265,77 @@ mod tests {
         }
     }
 
+    #[test]
+    fn div_rem() {
+        let sys = crate::system! {
+            x^2*y + x*y^2 + y^2,
+            x*y - 1,
+            y^2 - 1
+        };
+
+        let (quotients, rem) = sys.members[0].div_rem(&sys.members[1..]);
+        let quotients: Vec<_> = quotients.iter().map(|q| q.format(&sys.var_dict)).collect();
+
+        assert_eq!(vec!["x + y", "1"], quotients);
+        assert_eq!("x + y + 1", rem.format(&sys.var_dict));
+
+        // the remainder depends on the order of the divisors
+        let divisors = [sys.members[2].clone(), sys.members[1].clone()];
+        assert_eq!(
+            "2x + 1",
+            sys.members[0].div_rem(&divisors).1.format(&sys.var_dict)
+        );
+        #[allow(deprecated)]
+        let old = sys.members[0].compound_divide(&divisors);
+        assert_eq!(sys.members[0].div_rem(&divisors), old);
+
+        // and on the order: y^2 leads y^2 - x in grlex, x in lex
+        let sys = crate::system! { x + y^2, y^2 - x };
+        let divide = |order| {
+            let (quotients, rem) = sys.members[0].div_rem_in(&sys.members[1..], &order);
+            (
+                quotients[0].format(&sys.var_dict),
+                rem.format(&sys.var_dict),
+            )
+        };
+        assert_eq!(
+            ("1".to_string(), "2x".to_string()),
+            divide(MonomialOrder::Grlex)
+        );
+        assert_eq!(
+            ("-1".to_string(), "2y^2".to_string()),
+            divide(MonomialOrder::Lex)
+        );
+    }
+
+    #[test]
+    fn split_lt() {
+        let sys = crate::system! { x^2 + 2*y - 1 };
+        let p = &sys.members[0];
+
+        let (lt, tail) = p.split_lt().unwrap();
+        assert_eq!(
+            "x^2",
+            Poly {
+                terms: vec![lt.clone()]
+            }
+            .format(&sys.var_dict)
+        );
+        assert_eq!(
+            "2y - 1",
+            Poly {
+                terms: tail.to_vec()
+            }
+            .format(&sys.var_dict)
+        );
+
+        let (lt, tail) = p.clone().into_split_lt().unwrap();
+        assert_eq!(p.lt_mono(), lt);
+        assert_eq!("2y - 1", tail.format(&sys.var_dict));
+
+        assert!(Poly::<Rat>::constant(Rat::zero()).split_lt().is_none());
+    }
+
     #[test]
     fn tricky_order() {
         let sys = crate::system! {
@@ -0,0 +1,193 @@
+use std::rc::Rc;
+
+use crate::field::Field;
+use crate::poly::ideal::Ideal;
+use crate::poly::quotient::Quotient;
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+// one standard affine chart of a homogenized system
+#[derive(Debug)]
+pub struct Chart {
+    // the coordinate fixed to 1; the homogenizing coordinate gives the
+    // original affine space
+    pub var: usize,
+    // the homogenized system plus var - 1
+    pub sys: System<Rat>,
+    // complex solutions in the chart counted with multiplicity, None if there
+    // are infinitely many
+    pub solutions: Option<usize>,
+    // how many of those lie at infinity, where the homogenizing coordinate
+    // vanishes
+    pub at_infinity: Option<usize>,
+}
+
+impl<T: Field> Poly<T> {
+    pub fn total_deg(&self) -> u64 {
+        self.terms
+            .iter()
+            .map(|term| term.vars.iter().map(|(_, pow)| pow).sum())
+            .max()
+            .unwrap_or(0)
+    }
+
+    // multiply each term by the power of var which brings it to the total
+    // degree; var must not occur in self
+    pub fn homogenize(&self, var: usize) -> Poly<T> {
+        let deg = self.total_deg();
+
+        self.terms
+            .iter()
+            .fold(Poly::constant(T::zero()), |acc, term| {
+                let term_deg: u64 = term.vars.iter().map(|(_, pow)| pow).sum();
+                let term = Poly {
+                    terms: vec![term.clone()],
+                };
+
+                acc + term.mul_ref(&Poly::var(var, deg - term_deg))
+            })
+    }
+}
+
+impl System<Rat> {
+    // the homogenizing coordinate of homogenize
+    pub fn homogenizing_var(&self) -> usize {
+        self.var_dict.len()
+    }
+
+    // the members homogenized by one more coordinate "_h". this is the system
+    // itself read projectively, which unlike the closure of its solution set
+    // can have extra solutions at infinity: those are what make up the
+    // difference between affine solution counts and Bezout numbers
+    pub fn homogenize(&self) -> System<Rat> {
+        let h = self.homogenizing_var();
+        let mut var_dict = self.var_dict.as_ref().clone();
+        var_dict.push("_h".to_string());
+
+        System {
+            var_dict: Rc::new(var_dict),
+            members: self.members.iter().map(|p| p.homogenize(h)).collect(),
+        }
+    }
+
+    // the solutions of homogenize with "_h" = 0, as a homogeneous system in the
+    // original coordinates: the top degree forms of the members
+    pub fn at_infinity(&self) -> System<Rat> {
+        let members = self
+            .members
+            .iter()
+            .map(|p| {
+                let deg = p.total_deg();
+                Poly {
+                    terms: p
+                        .terms
+                        .iter()
+                        .filter(|t| t.vars.iter().map(|(_, pow)| pow).sum::<u64>() == deg)
+                        .cloned()
+                        .collect(),
+                }
+            })
+            .collect();
+
+        System {
+            var_dict: self.var_dict.clone(),
+            members,
+        }
+    }
+
+    // the homogenized system seen through every standard chart, the original
+    // affine one first
+    pub fn solve_projective(&self) -> Vec<Chart> {
+        let homogeneous = self.homogenize();
+        let h = self.homogenizing_var();
+
+        let dim = |sys: &System<Rat>| Quotient::new(sys).map(|q| q.dim());
+
+        std::iter::once(h)
+            .chain(0..h)
+            .map(|var| {
+                let mut sys = homogeneous.clone();
+                sys.members
+                    .push(Poly::var(var, 1) - Poly::constant(Rat::from(1)));
+
+                // saturating by "_h" leaves just the finite solutions
+                let solutions = dim(&sys);
+                let finite = dim(&Ideal::new(sys.clone()).saturate_by(&Poly::var(h, 1)).gens);
+
+                Chart {
+                    var,
+                    sys,
+                    solutions,
+                    at_infinity: solutions.zip(finite).map(|(all, finite)| all - finite),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::system;
+
+    #[test]
+    fn homogenize() {
+        let sys = system! {
+            x^2*y + 3*x - 1
+        };
+
+        assert_eq!("[x^2y + 3x_h^2 - _h^3]", format!("{:?}", sys.homogenize()));
+
+        // no affine solutions, but y = 0 meets xy = 0 at infinity
+        let sys = system! {
+            x*y - 1,
+            y
+        };
+        assert_eq!("[1]", format!("{:?}", sys.gb()));
+        assert_eq!("[xy, y]", format!("{:?}", sys.at_infinity()));
+    }
+
+    #[test]
+    fn charts() {
+        // parallel lines meet once, at infinity
+        let sys = system! {
+            x + y - 1,
+            x + y - 2
+        };
+
+        let charts = sys.solve_projective();
+        let counts = charts
+            .iter()
+            .map(|c| (c.var, c.solutions, c.at_infinity))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (2, Some(0), Some(0)),
+                (0, Some(1), Some(1)),
+                (1, Some(1), Some(1))
+            ],
+            counts
+        );
+
+        // of the 4 Bezout solutions, one is finite and the other 3 coincide
+        // at the point at infinity of the x axis
+        let sys = system! {
+            x*y - 1,
+            y^2 + y
+        };
+
+        let charts = sys.solve_projective();
+        let counts = charts
+            .iter()
+            .map(|c| (c.var, c.solutions, c.at_infinity))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (2, Some(1), Some(0)),
+                (0, Some(4), Some(3)),
+                (1, Some(1), Some(0))
+            ],
+            counts
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use std::collections::HashMap;
+
+use crate::field::{One, Zero};
+use crate::poly::mono::{monomial_div, monomial_mul, Mono};
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+use crate::univariate::UPoly;
+
+// zero-dimensional iff every variable has a pure power leading term
+fn bounded(lts: &[Mono<Rat>], n_vars: usize) -> bool {
+    (0..n_vars).all(|var| {
+        lts.iter()
+            .any(|lt| lt.vars.is_empty() || lt.vars.len() == 1 && lt.vars[0].0 == var)
+    })
+}
+
+// the fewest vars such that each support has one of them; by branching on the
+// vars of the smallest support, so the depth is at most the answer
+fn min_cover(supports: &[Vec<usize>]) -> usize {
+    let Some(smallest) = supports.iter().min_by_key(|s| s.len()) else {
+        return 0;
+    };
+
+    smallest
+        .iter()
+        .map(|v| {
+            let rest = supports
+                .iter()
+                .filter(|s| !s.contains(v))
+                .cloned()
+                .collect::<Vec<_>>();
+            1 + min_cover(&rest)
+        })
+        .min()
+        .unwrap()
+}
+
+// Q[x]/I for a zero-dimensional ideal I, as a vector space over Q spanned by
+// the monomials not divisible by any leading term of the Groebner basis
+pub struct Quotient {
+    pub gb: System<Rat>,
+    pub basis: Vec<Mono<Rat>>,
+    index: HashMap<Vec<(usize, u64)>, usize>,
+}
+
+impl Quotient {
+    // None unless sys has finitely many (complex) solutions
+    pub fn new(sys: &System<Rat>) -> Option<Quotient> {
+        Quotient::from_gb(sys.gb())
+    }
+
+    // the same from a reduced grlex basis computed some other way, e.g. by
+    // modular methods or FGLM
+    pub fn from_gb(gb: System<Rat>) -> Option<Quotient> {
+        let lts = gb.members.iter().map(|p| p.lt_mono()).collect::<Vec<_>>();
+        let n_vars = gb.var_dict.len();
+
+        if !bounded(&lts, n_vars) {
+            return None;
+        }
+
+        let standard = |m: &Mono<Rat>| lts.iter().all(|lt| monomial_div(m, lt).is_none());
+
+        let one = Mono {
+            val: Rat::one(),
+            vars: vec![],
+        };
+
+        // the standard monomials are closed under division, so they are all
+        // reachable from 1 one variable at a time
+        let mut basis = vec![];
+        let mut index = HashMap::new();
+        let mut frontier = if standard(&one) { vec![one] } else { vec![] };
+
+        while let Some(m) = frontier.pop() {
+            if index.contains_key(&m.vars) {
+                continue;
+            }
+
+            index.insert(m.vars.clone(), basis.len());
+
+            for var in 0..n_vars {
+                let next = monomial_mul(
+                    &m,
+                    &Mono {
+                        val: Rat::one(),
+                        vars: vec![(var, 1)],
+                    },
+                );
+
+                if standard(&next) && !index.contains_key(&next.vars) {
+                    frontier.push(next);
+                }
+            }
+
+            basis.push(m);
+        }
+
+        Some(Quotient { gb, basis, index })
+    }
+
+    pub fn dim(&self) -> usize {
+        self.basis.len()
+    }
+
+    // coordinates of the normal form of p in the standard monomial basis
+    pub fn reduce(&self, p: &Poly<Rat>) -> Vec<Rat> {
+        let (_, rem) = p.div_rem(&self.gb.members);
+        let mut coords = vec![Rat::zero(); self.dim()];
+
+        for term in rem.terms {
+            coords[self.index[&term.vars]] = term.val;
+        }
+
+        coords
+    }
+
+    // matrix of multiplication by g; column j is g times the jth basis monomial
+    pub fn mult_matrix(&self, g: &Poly<Rat>) -> Vec<Vec<Rat>> {
+        let mut mat = vec![vec![Rat::zero(); self.dim()]; self.dim()];
+
+        for (j, b) in self.basis.iter().enumerate() {
+            let gb = g.mul_ref(&Poly {
+                terms: vec![b.clone()],
+            });
+
+            for (i, c) in self.reduce(&gb).into_iter().enumerate() {
+                mat[i][j] = c;
+            }
+        }
+
+        mat
+    }
+
+    // sum of g over the solutions, counted with multiplicity
+    pub fn trace(&self, g: &Poly<Rat>) -> Rat {
+        self.basis
+            .iter()
+            .enumerate()
+            .map(|(i, b)| {
+                let gb = g.mul_ref(&Poly {
+                    terms: vec![b.clone()],
+                });
+                self.reduce(&gb)[i]
+            })
+            .fold(Rat::zero(), |acc, c| acc + c)
+    }
+
+    // the eigenvalues of multiplication by g are its values at the solutions,
+    // repeated by multiplicity (Stickelberger)
+    pub fn char_poly(&self, g: &Poly<Rat>) -> UPoly<Rat> {
+        char_poly(self.mult_matrix(g))
+    }
+
+    // monic, squarefree, and vanishing exactly at the values of g over the
+    // solutions
+    pub fn min_poly(&self, g: &Poly<Rat>) -> UPoly<Rat> {
+        self.char_poly(g).squarefree_part()
+    }
+}
+
+impl System<Rat> {
+    // exact values g can take over the solutions; None unless there are
+    // finitely many
+    pub fn min_poly(&self, g: &Poly<Rat>) -> Option<UPoly<Rat>> {
+        Quotient::new(self).map(|q| q.min_poly(g))
+    }
+
+    // finitely many complex solutions, possibly none
+    pub fn is_zero_dimensional(&self) -> bool {
+        let lts = self
+            .gb()
+            .members
+            .iter()
+            .map(|p| p.lt_mono())
+            .collect::<Vec<_>>();
+        bounded(&lts, self.var_dict.len())
+    }
+
+    // Krull dimension: the size of the largest set of vars with no leading
+    // term of the basis in those vars alone, such as the free vars of a
+    // parametrization. its complement is a smallest set of vars meeting the
+    // support of every leading term. None if there are no solutions
+    pub fn dimension(&self) -> Option<usize> {
+        let mut supports = self
+            .gb()
+            .members
+            .iter()
+            .map(|p| p.lt_mono().vars.iter().map(|(v, _)| *v).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        if supports.iter().any(|s| s.is_empty()) {
+            return None;
+        }
+
+        supports.sort();
+        supports.dedup();
+
+        Some(self.var_dict.len() - min_cover(&supports))
+    }
+
+    // the complex solutions counted with multiplicity, which bounds the
+    // distinct (and real) ones; None unless there are finitely many
+    pub fn solution_count_bound(&self) -> Option<usize> {
+        Quotient::new(self).map(|q| q.dim())
+    }
+}
+
+// reduce to upper Hessenberg form by similarity, then expand det(xI - H) along
+// the subdiagonal
+pub(crate) fn char_poly(mut a: Vec<Vec<Rat>>) -> UPoly<Rat> {
+    let n = a.len();
+
+    for m in 1..n.max(1) - 1 {
+        let pivot = match (m..n).find(|i| !a[*i][m - 1].is_zero()) {
+            Some(pivot) => pivot,
+            None => continue,
+        };
+
+        if pivot != m {
+            a.swap(pivot, m);
+            for row in a.iter_mut() {
+                row.swap(pivot, m);
+            }
+        }
+
+        for i in m + 1..n {
+            let u = a[i][m - 1] / a[m][m - 1];
+
+            if !u.is_zero() {
+                let row_m = a[m].clone();
+                for (x, y) in a[i].iter_mut().zip(row_m) {
+                    *x = *x - u * y;
+                }
+                for row in a.iter_mut() {
+                    row[m] = row[m] + u * row[i];
+                }
+            }
+        }
+    }
+
+    let mut p = vec![UPoly(vec![Rat::one()])];
+
+    for m in 1..=n {
+        let mut next = UPoly(vec![Rat::one(), a[m - 1][m - 1] * -1]) * p[m - 1].clone();
+        let mut subdiag = Rat::one();
+
+        for i in 1..m {
+            subdiag = subdiag * a[m - i][m - i - 1];
+            next = next - p[m - i - 1].scale(&(a[m - i - 1][m - 1] * subdiag));
+        }
+
+        p.push(next);
+    }
+
+    p.pop().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::Quotient;
+    use crate::poly::system::System;
+    use crate::poly::Poly;
+    use crate::system;
+    use crate::univariate::upoly;
+
+    #[test]
+    fn min_poly() {
+        // solutions (1, 2), (2, 1), (-1, -2), (-2, -1)
+        let sys = system! {
+            x^2 + y^2 - 5,
+            x*y - 2
+        };
+
+        let q = Quotient::new(&sys).unwrap();
+        assert_eq!(4, q.dim());
+
+        let x = sys.var("x", 1);
+        let y = sys.var("y", 1);
+
+        assert_eq!(upoly(&[1, 0, -5, 0, 4]), q.char_poly(&x));
+        // x + y takes the values 3, -3, each twice
+        assert_eq!(upoly(&[1, 0, -9]), q.min_poly(&(x.clone() + y.clone())));
+        assert_eq!(upoly(&[1, -2]), q.min_poly(&x.mul_ref(&y)));
+        assert_eq!(Some(upoly(&[1, 0, -1])), sys.min_poly(&(x - y)));
+    }
+
+    #[test]
+    fn multiplicity() {
+        // a double root at the origin and a simple one at (1, 1)
+        let sys = system! {
+            x^3 - x^2,
+            y - x
+        };
+
+        let q = Quotient::new(&sys).unwrap();
+        let x = sys.var("x", 1);
+
+        assert_eq!(upoly(&[1, -1, 0, 0]), q.char_poly(&x));
+        assert_eq!(upoly(&[1, -1, 0]), q.min_poly(&x));
+
+        assert!(sys.is_zero_dimensional());
+        assert_eq!(Some(3), sys.solution_count_bound());
+        assert_eq!(Some(0), sys.dimension());
+    }
+
+    #[test]
+    fn positive_dimensional() {
+        let sys = system! {
+            x^2 + y^2 - 1
+        };
+
+        assert!(Quotient::new(&sys).is_none());
+        assert!(sys.min_poly(&sys.var("x", 1)).is_none());
+        assert!(!sys.is_zero_dimensional());
+        assert!(sys.solution_count_bound().is_none());
+        assert_eq!(Some(1), sys.dimension());
+
+        // the plane x = 0 and the line y = z = 0
+        let sys = system! { x*y, x*z };
+        assert_eq!(Some(2), sys.dimension());
+
+        // x_0 = ... = x_9 = 0 in 80 vars
+        let sys = System {
+            var_dict: Rc::new((0..80).map(|v| format!("x{v}")).collect()),
+            members: (0..10).map(|v| Poly::var(v, 1)).collect(),
+        };
+        assert_eq!(Some(70), sys.dimension());
+
+        // no solutions at all is finitely many
+        let sys = system! { x - 1, x - 2 };
+        assert!(sys.is_zero_dimensional());
+        assert_eq!(Some(0), sys.solution_count_bound());
+        assert_eq!(None, sys.dimension());
+    }
+}
@@ -0,0 +1,98 @@
+// arithmetic in Q[x]/I for any ideal I, e.g. the coordinate ring of a
+// variety: a residue class is kept as its normal form by the reduced basis,
+// which is the same for every poly in the class, so classes are equal
+// exactly when their representatives are. the basis is computed once, on
+// first use
+
+use crate::poly::ideal::Ideal;
+use crate::poly::mono::Mono;
+use crate::poly::quotient::Quotient;
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+pub struct QuotientRing {
+    pub ideal: Ideal,
+}
+
+// the normal form of the class
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Residue(pub Poly<Rat>);
+
+impl QuotientRing {
+    pub fn new(sys: System<Rat>) -> QuotientRing {
+        QuotientRing {
+            ideal: Ideal::new(sys),
+        }
+    }
+
+    pub fn class(&self, p: &Poly<Rat>) -> Residue {
+        Residue(self.ideal.normal_form(p))
+    }
+
+    pub fn add(&self, a: &Residue, b: &Residue) -> Residue {
+        // sums of normal forms are normal forms
+        Residue(a.0.clone() + b.0.clone())
+    }
+
+    pub fn sub(&self, a: &Residue, b: &Residue) -> Residue {
+        Residue(a.0.clone() - b.0.clone())
+    }
+
+    pub fn mul(&self, a: &Residue, b: &Residue) -> Residue {
+        self.class(&a.0.mul_ref(&b.0))
+    }
+
+    pub fn pow(&self, a: &Residue, exp: u64) -> Residue {
+        (0..exp).fold(self.class(&Poly::constant(Rat::from(1))), |acc, _| {
+            self.mul(&acc, a)
+        })
+    }
+
+    pub fn is_zero(&self, a: &Residue) -> bool {
+        a.0.is_zero()
+    }
+
+    // the standard monomials, a basis of the ring over Q; None unless the
+    // ideal is zero-dimensional, which makes the ring finite-dimensional
+    pub fn basis(&self) -> Option<Vec<Mono<Rat>>> {
+        Quotient::new(self.ideal.gb()).map(|q| q.basis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QuotientRing;
+    use crate::poly::mono::print_exps;
+    use crate::poly::Poly;
+    use crate::rational::Rat;
+    use crate::system;
+
+    #[test]
+    fn quotient_ring() {
+        let sys = system! {
+            x^2 + y^2 - 1,
+            x - y
+        };
+
+        let ring = QuotientRing::new(sys.clone());
+        let (x, y) = (ring.class(&sys.var("x", 1)), ring.class(&sys.var("y", 1)));
+
+        // x = y, so x^2 = 1/2 and (x + y)^2 = 2
+        assert_eq!(x, y);
+        let half = Poly::constant(Rat::from(1) / Rat::from(2));
+        assert_eq!(ring.class(&half), ring.mul(&x, &x));
+        assert_eq!(ring.class(&sys.constant(2)), ring.pow(&ring.add(&x, &y), 2));
+        assert!(ring.is_zero(&ring.sub(&x, &y)));
+
+        let basis = ring.basis().unwrap();
+        let basis = basis
+            .iter()
+            .map(|m| print_exps(m, &sys.var_dict))
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["", "y"], basis);
+
+        let circle = QuotientRing::new(system! { x^2 + y^2 - 1 });
+        assert!(circle.basis().is_none());
+    }
+}
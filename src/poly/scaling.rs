@@ -0,0 +1,232 @@
+use crate::branch_and_prune::RealSolutions;
+use crate::interval::Interval;
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+// scaling exponents are searched in -MAX_EXPONENT..=MAX_EXPONENT, which keeps
+// the scaled coefs well inside i64
+const MAX_EXPONENT: i32 = 6;
+
+// the substitution x_v = 10^exponents[v] x'_v; solutions of the scaled system
+// are in the primed coordinates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scaling {
+    pub exponents: Vec<i32>,
+}
+
+fn pow10(k: i32) -> Rat {
+    let p = Rat::from(10i64.pow(k.unsigned_abs()));
+    if k < 0 {
+        Rat::from(1) / p
+    } else {
+        p
+    }
+}
+
+// orders of magnitude between the largest and smallest coef of p, were its
+// vars scaled by exponents
+fn spread(p: &Poly<Rat>, exponents: &[i32]) -> f64 {
+    let logs = p.terms.iter().map(|t| {
+        let shift: i64 = t
+            .vars
+            .iter()
+            .map(|(v, pow)| exponents[*v] as i64 * *pow as i64)
+            .sum();
+        f64::from(t.val).abs().log10() + shift as f64
+    });
+
+    let (lo, hi) = logs.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), l| {
+        (lo.min(l), hi.max(l))
+    });
+
+    if p.terms.is_empty() {
+        0.
+    } else {
+        hi - lo
+    }
+}
+
+impl Scaling {
+    pub fn identity(vars: usize) -> Scaling {
+        Scaling {
+            exponents: vec![0; vars],
+        }
+    }
+
+    // from the primed coordinates back to the original ones
+    pub fn unscale(&self, point: &[f64]) -> Vec<f64> {
+        point
+            .iter()
+            .zip(&self.exponents)
+            .map(|(x, k)| x * 10f64.powi(*k))
+            .collect()
+    }
+
+    // enclosures stay enclosures
+    pub fn unscale_box(&self, bx: &[Interval]) -> Vec<Interval> {
+        bx.iter()
+            .zip(&self.exponents)
+            .map(|(x, k)| x.mul(&Interval::point(pow10(*k))))
+            .collect()
+    }
+
+    // from the original coordinates to the primed ones, e.g. for search bounds
+    pub fn scale_box(&self, bx: &[Interval]) -> Vec<Interval> {
+        bx.iter()
+            .zip(&self.exponents)
+            .map(|(x, k)| x.mul(&Interval::point(pow10(-k))))
+            .collect()
+    }
+
+    pub fn unscale_solutions(&self, solutions: &RealSolutions) -> RealSolutions {
+        let unscale_all = |boxes: &[Vec<Interval>]| {
+            boxes
+                .iter()
+                .map(|bx| self.unscale_box(bx))
+                .collect::<Vec<_>>()
+        };
+
+        RealSolutions {
+            verified: unscale_all(&solutions.verified),
+            candidates: unscale_all(&solutions.candidates),
+            undecided: unscale_all(&solutions.undecided),
+            excluded: solutions.excluded,
+        }
+    }
+}
+
+impl System<Rat> {
+    // smallest and largest coef magnitude of each member; (0, 0) for zero
+    pub fn coefficient_ranges(&self) -> Vec<(f64, f64)> {
+        self.members
+            .iter()
+            .map(|p| {
+                let magnitudes = p.terms.iter().map(|t| f64::from(t.val).abs());
+                let (lo, hi) =
+                    magnitudes.fold((f64::INFINITY, 0f64), |(lo, hi), m| (lo.min(m), hi.max(m)));
+
+                if p.terms.is_empty() {
+                    (0., 0.)
+                } else {
+                    (lo, hi)
+                }
+            })
+            .collect()
+    }
+
+    // power of ten scaling of each var which greedily lowers the total spread
+    // of coef magnitudes over the members, one var at a time until no change
+    // helps
+    pub fn suggest_scaling(&self) -> Scaling {
+        let mut exponents = vec![0; self.var_dict.len()];
+        let cost = |exponents: &[i32]| {
+            self.members
+                .iter()
+                .map(|p| spread(p, exponents))
+                .sum::<f64>()
+        };
+
+        let mut best = cost(&exponents);
+
+        loop {
+            let mut improved = false;
+
+            for v in 0..exponents.len() {
+                for k in -MAX_EXPONENT..=MAX_EXPONENT {
+                    let mut next = exponents.clone();
+                    next[v] = k;
+
+                    let c = cost(&next);
+                    // ignore rounding noise in the logs
+                    if c < best - 1e-9 {
+                        best = c;
+                        exponents = next;
+                        improved = true;
+                    }
+                }
+            }
+
+            if !improved {
+                return Scaling { exponents };
+            }
+        }
+    }
+
+    // substitute x_v = 10^k_v x'_v
+    pub fn scale_vars(&self, scaling: &Scaling) -> System<Rat> {
+        let members = self
+            .members
+            .iter()
+            .map(|p| {
+                let mut p = p.clone();
+                for term in &mut p.terms {
+                    for (v, pow) in &term.vars {
+                        for _ in 0..*pow {
+                            term.val = term.val * pow10(scaling.exponents[*v]);
+                        }
+                    }
+                }
+                p
+            })
+            .collect();
+
+        System {
+            var_dict: self.var_dict.clone(),
+            members,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Scaling;
+    use crate::interval::Interval;
+    use crate::system;
+
+    #[test]
+    fn scaling() {
+        // x in the thousands, y in the thousandths
+        let sys = system! {
+            x^2 - 4000000,
+            1000*y - 2,
+            x*y - 4
+        };
+
+        assert_eq!(
+            vec![(1., 4e6), (2., 1000.), (1., 4.)],
+            sys.coefficient_ranges()
+        );
+
+        let scaling = sys.suggest_scaling();
+        assert_eq!(vec![3, -3], scaling.exponents);
+
+        let scaled = sys.scale_vars(&scaling);
+        assert_eq!(
+            "[1000000x^2 - 4000000, y - 2, xy - 4]",
+            format!("{:?}", scaled)
+        );
+
+        assert_eq!(vec![2000., 0.002], scaling.unscale(&[2., 2.]));
+
+        let bx = vec![Interval::new(1000., 3000.), Interval::new(0., 0.01)];
+        let scaled_box = scaling.scale_box(&bx);
+        assert!(scaled_box[0].contains(1.) && scaled_box[0].contains(3.));
+        assert!(scaled_box[1].contains(10.));
+
+        // solve in the primed coordinates and map back; the Krawczyk test
+        // needs a square system
+        let mut square = scaled.clone();
+        square.members.remove(1);
+        let solutions = square.solve_real_in_box(&scaled_box, 1e-6);
+        let solutions = scaling.unscale_solutions(&solutions);
+        assert_eq!(1, solutions.verified.len());
+        assert!(solutions.verified[0][0].contains(2000.));
+        assert!(solutions.verified[0][1].contains(0.002));
+
+        assert_eq!(
+            Scaling::identity(2),
+            system! { x + y - 1 }.suggest_scaling()
+        );
+    }
+}
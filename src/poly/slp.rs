@@ -0,0 +1,285 @@
+// straight-line programs for fast repeated evaluation: each poly in Horner form,
+// one var at a time, with equal subexpressions computed once, and code
+// generation of the program as a Rust or C function
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::ops::{Add, Mul};
+
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+// operands are earlier instructions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Instr {
+    Const(Rat),
+    Var(usize),
+    Add(usize, usize),
+    Mul(usize, usize),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Slp {
+    pub instrs: Vec<Instr>,
+    // the instruction holding each poly
+    pub outputs: Vec<usize>,
+    seen: HashMap<Instr, usize>,
+}
+
+impl Slp {
+    // polys share their common subexpressions
+    pub fn new(polys: &[Poly<Rat>]) -> Slp {
+        let mut slp = Slp::default();
+
+        for p in polys {
+            let vars = p
+                .terms
+                .iter()
+                .flat_map(|t| t.vars.iter().map(|(v, _)| v + 1))
+                .max()
+                .unwrap_or(0);
+
+            let out = slp.horner(p, 0, vars);
+            slp.outputs.push(out);
+        }
+
+        slp.prune();
+        slp
+    }
+
+    // drop instructions no output depends on, e.g. constants folded away
+    fn prune(&mut self) {
+        let mut live = vec![false; self.instrs.len()];
+        for i in &self.outputs {
+            live[*i] = true;
+        }
+
+        for i in (0..self.instrs.len()).rev() {
+            if let (true, Instr::Add(a, b) | Instr::Mul(a, b)) = (live[i], self.instrs[i]) {
+                live[a] = true;
+                live[b] = true;
+            }
+        }
+
+        let mut renamed = vec![0; self.instrs.len()];
+        let instrs = std::mem::take(&mut self.instrs);
+        self.seen.clear();
+
+        for (i, instr) in instrs.into_iter().enumerate().filter(|(i, _)| live[*i]) {
+            renamed[i] = self.instrs.len();
+            self.push(match instr {
+                Instr::Add(a, b) => Instr::Add(renamed[a], renamed[b]),
+                Instr::Mul(a, b) => Instr::Mul(renamed[a], renamed[b]),
+                instr => instr,
+            });
+        }
+
+        for i in &mut self.outputs {
+            *i = renamed[*i];
+        }
+    }
+
+    fn push(&mut self, instr: Instr) -> usize {
+        // order the operands, so that a + b and b + a are shared
+        let instr = match instr {
+            Instr::Add(a, b) => Instr::Add(a.min(b), a.max(b)),
+            Instr::Mul(a, b) => Instr::Mul(a.min(b), a.max(b)),
+            instr => instr,
+        };
+
+        if let Some(i) = self.seen.get(&instr) {
+            return *i;
+        }
+
+        self.instrs.push(instr);
+        self.seen.insert(instr, self.instrs.len() - 1);
+        self.instrs.len() - 1
+    }
+
+    fn constant(&self, i: usize) -> Option<Rat> {
+        match self.instrs[i] {
+            Instr::Const(c) => Some(c),
+            _ => None,
+        }
+    }
+
+    fn add(&mut self, a: usize, b: usize) -> usize {
+        match (self.constant(a), self.constant(b)) {
+            (Some(x), Some(y)) => self.push(Instr::Const(x + y)),
+            (Some(x), _) if x.is_zero() => b,
+            (_, Some(y)) if y.is_zero() => a,
+            _ => self.push(Instr::Add(a, b)),
+        }
+    }
+
+    fn mul(&mut self, a: usize, b: usize) -> usize {
+        let one = Rat::from(1);
+        match (self.constant(a), self.constant(b)) {
+            (Some(x), Some(y)) => self.push(Instr::Const(x * y)),
+            (Some(x), _) if x == one => b,
+            (_, Some(y)) if y == one => a,
+            _ => self.push(Instr::Mul(a, b)),
+        }
+    }
+
+    // p in vars var.., as a poly in var with coefs in the later vars
+    fn horner(&mut self, p: &Poly<Rat>, var: usize, vars: usize) -> usize {
+        if p.is_constant() || var == vars {
+            let c = p.terms.first().map_or(Rat::from(0), |t| t.val);
+            return self.push(Instr::Const(c));
+        }
+
+        let coefs = p.coefs(var);
+        let x = self.push(Instr::Var(var));
+        let mut acc = self.horner(&coefs[0], var + 1, vars);
+
+        for c in &coefs[1..] {
+            acc = self.mul(acc, x);
+            if !c.is_zero() {
+                let c = self.horner(c, var + 1, vars);
+                acc = self.add(acc, c);
+            }
+        }
+
+        acc
+    }
+
+    // additions and multiplications
+    pub fn ops(&self) -> usize {
+        self.instrs
+            .iter()
+            .filter(|i| matches!(i, Instr::Add(..) | Instr::Mul(..)))
+            .count()
+    }
+
+    pub fn eval<T>(&self, point: &[T]) -> Vec<T>
+    where
+        T: Copy + From<Rat> + Add<Output = T> + Mul<Output = T>,
+    {
+        let mut values: Vec<T> = Vec::with_capacity(self.instrs.len());
+
+        for instr in &self.instrs {
+            let value = match *instr {
+                Instr::Const(c) => T::from(c),
+                Instr::Var(v) => point[v],
+                Instr::Add(a, b) => values[a] + values[b],
+                Instr::Mul(a, b) => values[a] * values[b],
+            };
+            values.push(value);
+        }
+
+        self.outputs.iter().map(|i| values[*i]).collect()
+    }
+
+    fn operation(&self, instr: &Instr, var: impl Fn(usize) -> String) -> String {
+        match *instr {
+            Instr::Const(c) => format!("{:?}", f64::from(c)),
+            Instr::Var(v) => var(v),
+            Instr::Add(a, b) => format!("t{a} + t{b}"),
+            Instr::Mul(a, b) => format!("t{a} * t{b}"),
+        }
+    }
+
+    // fn name(x: &[f64]) -> [f64; outputs]
+    pub fn to_rust(&self, name: &str) -> String {
+        let mut s = format!(
+            "pub fn {name}(x: &[f64]) -> [f64; {}] {{\n",
+            self.outputs.len()
+        );
+
+        for (i, instr) in self.instrs.iter().enumerate() {
+            let op = self.operation(instr, |v| format!("x[{v}]"));
+            writeln!(s, "    let t{i} = {op};").unwrap();
+        }
+
+        let outputs = self.outputs.iter().map(|i| format!("t{i}"));
+        writeln!(s, "    [{}]\n}}", outputs.collect::<Vec<_>>().join(", ")).unwrap();
+
+        s
+    }
+
+    // void name(const double *x, double *out)
+    pub fn to_c(&self, name: &str) -> String {
+        let mut s = format!("void {name}(const double *x, double *out) {{\n");
+
+        for (i, instr) in self.instrs.iter().enumerate() {
+            let op = self.operation(instr, |v| format!("x[{v}]"));
+            writeln!(s, "    const double t{i} = {op};").unwrap();
+        }
+
+        for (j, i) in self.outputs.iter().enumerate() {
+            writeln!(s, "    out[{j}] = t{i};").unwrap();
+        }
+        s.push_str("}\n");
+
+        s
+    }
+}
+
+impl Poly<Rat> {
+    pub fn to_slp(&self) -> Slp {
+        Slp::new(std::slice::from_ref(self))
+    }
+}
+
+impl System<Rat> {
+    pub fn to_slp(&self) -> Slp {
+        Slp::new(&self.members)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rational::Rat;
+    use crate::system;
+
+    #[test]
+    fn slp() {
+        // (y x + y) x + y
+        let sys = system! {
+            x^2*y + x*y + y,
+            x*y + y + 3
+        };
+
+        let slp = sys.members[0].to_slp();
+        assert_eq!(4, slp.ops());
+        assert_eq!(
+            concat!(
+                "pub fn f(x: &[f64]) -> [f64; 1] {\n",
+                "    let t0 = x[0];\n",
+                "    let t1 = x[1];\n",
+                "    let t2 = t0 * t1;\n",
+                "    let t3 = t1 + t2;\n",
+                "    let t4 = t0 * t3;\n",
+                "    let t5 = t1 + t4;\n",
+                "    [t5]\n",
+                "}\n"
+            ),
+            slp.to_rust("f")
+        );
+
+        // y x is computed once
+        let slp = sys.to_slp();
+        assert_eq!(
+            4 + 3,
+            sys.members[0].to_slp().ops() + sys.members[1].to_slp().ops()
+        );
+        assert_eq!(6, slp.ops());
+        assert!(slp
+            .to_c("f")
+            .ends_with("    const double t8 = t2 + t7;\n    out[0] = t5;\n    out[1] = t8;\n}\n"));
+
+        let point = [Rat::from(2), Rat::from(-3)];
+        let exact = sys
+            .members
+            .iter()
+            .map(|p| p.eval(0, point[0]).eval(1, point[1]).terms[0].val)
+            .collect::<Vec<_>>();
+        assert_eq!(exact, slp.eval(&point));
+        assert_eq!(vec![-21., -6.], slp.eval(&[2., -3.]));
+
+        let zero = sys.members[0].clone() - sys.members[0].clone();
+        assert_eq!(vec![Rat::from(0)], zero.to_slp().eval(&point));
+    }
+}
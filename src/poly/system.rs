@@ -1,18 +1,61 @@
-use crate::poly::mono::{grevlex, monomial_div};
+use crate::matrix::Matrix;
+use crate::poly::mono::{grlex, monomial_div};
 use crate::poly::Poly;
 use std::fmt;
+use std::ops::Index;
 use std::rc::Rc;
 
 use super::Field;
 use crate::rational::Rat;
 
+// the members only use vars named in var_dict, which push and new check;
+// removing members leaves var_dict as it is, so var indices stay valid
 #[derive(Clone)]
 pub struct System<T: Field> {
-    pub var_dict: Rc<Vec<String>>,
-    pub members: Vec<Poly<T>>,
+    pub(crate) var_dict: Rc<Vec<String>>,
+    pub(crate) members: Vec<Poly<T>>,
+}
+
+// a member with a var past the end of the var dict
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownVar(pub usize);
+
+impl fmt::Display for UnknownVar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "variable {} not in system variable dict", self.0)
+    }
+}
+
+impl std::error::Error for UnknownVar {}
+
+pub(crate) fn unknown_var<T: Field>(p: &Poly<T>, n_vars: usize) -> Option<usize> {
+    p.terms
+        .iter()
+        .flat_map(|t| t.vars.iter().map(|(v, _)| *v))
+        .find(|v| *v >= n_vars)
 }
 
 impl<T: Field> System<T> {
+    pub fn new(var_dict: Vec<String>, members: Vec<Poly<T>>) -> Result<System<T>, UnknownVar> {
+        let mut sys = System {
+            var_dict: Rc::new(var_dict),
+            members: Vec::with_capacity(members.len()),
+        };
+        for p in members {
+            sys.push(p)?;
+        }
+
+        Ok(sys)
+    }
+
+    pub fn var_dict(&self) -> &[String] {
+        &self.var_dict
+    }
+
+    pub fn members(&self) -> &[Poly<T>] {
+        &self.members
+    }
+
     pub fn var(&self, var: &str, pow: u64) -> Poly<T> {
         match self.var_dict.iter().position(|v| v == var) {
             Some(i) => Poly::var(i, pow),
@@ -23,6 +66,45 @@ impl<T: Field> System<T> {
     pub fn get(&self, i: usize) -> Poly<T> {
         self.members[i].clone()
     }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Poly<T>> {
+        self.members.iter()
+    }
+
+    pub fn push(&mut self, p: Poly<T>) -> Result<(), UnknownVar> {
+        match unknown_var(&p, self.var_dict.len()) {
+            Some(var) => Err(UnknownVar(var)),
+            None => {
+                self.members.push(p);
+                Ok(())
+            }
+        }
+    }
+
+    // None past the end
+    pub fn remove(&mut self, i: usize) -> Option<Poly<T>> {
+        (i < self.members.len()).then(|| self.members.remove(i))
+    }
+
+    pub fn retain(&mut self, keep: impl FnMut(&Poly<T>) -> bool) {
+        self.members.retain(keep);
+    }
+
+    // a row of partials per member, a column per var
+    pub fn jacobian(&self) -> Vec<Vec<Poly<T>>> {
+        self.members
+            .iter()
+            .map(|f| (0..self.var_dict.len()).map(|v| f.derivative(v)).collect())
+            .collect()
+    }
 }
 
 impl System<Rat> {
@@ -30,6 +112,34 @@ impl System<Rat> {
         Poly::constant(Rat::from(val))
     }
 
+    // whether the Jacobian at point has rank below the number of vars, so a
+    // solution there is singular; None if point has the wrong length
+    pub fn is_singular_at(&self, point: &[Rat]) -> Option<bool> {
+        if point.len() != self.var_dict.len() {
+            return None;
+        }
+
+        let jac = self
+            .jacobian()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|p| {
+                        let p = point
+                            .iter()
+                            .enumerate()
+                            .fold(p.clone(), |p, (v, x)| p.eval(v, *x));
+                        p.terms.first().map_or(Rat::from(0), |t| t.val)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Some(Matrix::new(point.len(), jac).unwrap().rank() < point.len())
+    }
+
+    // reduced Groebner basis in grlex, by Buchberger's algorithm. see
+    // gb_with_order for other orders, and Ideal::contains for membership
     pub fn gb(&self) -> System<Rat> {
         let mut sys = self.clone();
 
@@ -50,7 +160,7 @@ impl System<Rat> {
 
         while let Some((a, b)) = combs.pop() {
             let s = Poly::s_poly(a, b);
-            let (_, rem) = s.compound_divide(&sys.members);
+            let (_, rem) = s.div_rem(&sys.members);
 
             if !rem.is_zero() {
                 for member in &sys.members {
@@ -93,22 +203,73 @@ impl System<Rat> {
         let mut keep2 = vec![];
 
         for (i, k) in keep.iter().enumerate() {
-            let (_, rem) = k.compound_divide(
+            let (_, rem) = k.div_rem(
                 &keep
                     .iter()
                     .enumerate()
                     .filter_map(|(j, p)| if j != i { Some(p.clone()) } else { None })
-                    .collect(),
+                    .collect::<Vec<_>>(),
             );
             keep2.push(rem);
         }
 
-        keep2.sort_by(|p, q| grevlex(&p.lt_mono(), &q.lt_mono()).reverse());
+        keep2.sort_by(|p, q| grlex(&p.lt_mono(), &q.lt_mono()).reverse());
 
         sys.members = keep2.iter().map(|p| p.norm()).collect();
 
         sys
     }
+
+    // gb, under the name it has elsewhere
+    pub fn groebner_basis(&self) -> System<Rat> {
+        self.gb()
+    }
+
+    // Buchberger's criterion in grlex: the members are a Groebner basis iff
+    // the S-poly of every pair reduces to zero by them
+    pub fn is_groebner_basis(&self) -> bool {
+        let members = self
+            .members
+            .iter()
+            .filter(|p| !p.is_zero())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        members.iter().enumerate().all(|(i, p)| {
+            members[i + 1..].iter().all(|q| {
+                Poly::s_poly(p.clone(), q.clone())
+                    .div_rem(&members)
+                    .1
+                    .is_zero()
+            })
+        })
+    }
+}
+
+impl<T: Field> Index<usize> for System<T> {
+    type Output = Poly<T>;
+
+    fn index(&self, i: usize) -> &Poly<T> {
+        &self.members[i]
+    }
+}
+
+impl<T: Field> IntoIterator for System<T> {
+    type Item = Poly<T>;
+    type IntoIter = std::vec::IntoIter<Poly<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.members.into_iter()
+    }
+}
+
+impl<'a, T: Field> IntoIterator for &'a System<T> {
+    type Item = &'a Poly<T>;
+    type IntoIter = std::slice::Iter<'a, Poly<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.members.iter()
+    }
 }
 
 impl fmt::Debug for System<Rat> {
@@ -127,6 +288,9 @@ impl fmt::Debug for System<Rat> {
 
 #[cfg(test)]
 mod tests {
+    use super::{System, UnknownVar};
+    use crate::poly::mono::monomial_div;
+
     #[test]
     fn gb() {
         let sys = crate::system! {
@@ -139,5 +303,85 @@ mod tests {
             "[9z^2 + 7z - 3, x + 6z + 7, y + 3z + 2]",
             format!("{:?}", sys.gb())
         );
+
+        let gb = sys.groebner_basis();
+        assert!(!sys.is_groebner_basis() && gb.is_groebner_basis());
+
+        // reduced: no term of a member is divisible by another's leading term
+        for (i, p) in gb.iter().enumerate() {
+            for (j, q) in gb.iter().enumerate() {
+                assert!(
+                    i == j
+                        || p.terms
+                            .iter()
+                            .all(|t| monomial_div(t, &q.lt_mono()).is_none())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn jacobian() {
+        let sys = crate::system! {
+            x^2 - y,
+            y + x*y
+        };
+
+        let jac = sys
+            .jacobian()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|p| p.format(&sys.var_dict))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(vec![vec!["2x", "-1"], vec!["y", "x + 1"]], jac);
+
+        // the parabola touches y = 0 at the origin
+        let rat = crate::rational::Rat::from;
+        assert_eq!(Some(true), sys.is_singular_at(&[rat(0), rat(0)]));
+        assert_eq!(Some(false), sys.is_singular_at(&[rat(1), rat(1)]));
+        assert_eq!(None, sys.is_singular_at(&[rat(0)]));
+    }
+
+    #[test]
+    fn collection() {
+        let mut sys = crate::system! {
+            x^2 - y,
+            y - 1
+        };
+
+        assert_eq!(2, sys.len());
+        assert_eq!("x^2 - y", sys[0].format(sys.var_dict()));
+
+        let x = sys.var("x", 1);
+        sys.push(x).unwrap();
+        sys.retain(|p| p.terms.len() > 1);
+        assert_eq!("[x^2 - y, y - 1]", format!("{:?}", sys));
+
+        let removed = sys.remove(0).unwrap();
+        assert_eq!("x^2 - y", removed.format(sys.var_dict()));
+        assert!(sys.remove(1).is_none());
+
+        // x is no longer used, but keeps its name and index
+        assert_eq!(vec!["x", "y"], sys.var_dict());
+        assert_eq!("[y - 1]", format!("{:?}", sys));
+
+        let terms = (&sys).into_iter().map(|p| p.terms.len()).sum::<usize>();
+        assert_eq!(2, terms);
+        assert_eq!(1, sys.into_iter().count());
+    }
+
+    #[test]
+    fn unknown_var() {
+        let mut sys = crate::system! { x - 1 };
+        let y = crate::poly::Poly::var(1, 1);
+        assert_eq!(Err(UnknownVar(1)), sys.push(y.clone()));
+        assert_eq!(1, sys.len());
+
+        let names = vec!["x".to_string()];
+        assert!(System::new(names.clone(), vec![y]).is_err());
+        assert_eq!(sys.members(), System::new(names, sys.members().to_vec()).unwrap().members());
     }
 }
@@ -0,0 +1,127 @@
+// a reusable block of constraints, e.g. a linkage or a circuit element, with
+// some vars marked as parameters. each instance binds the parameters to
+// constants or to expressions in the other vars, and drops them from the var
+// dict; prefix_vars keeps several instances of one template apart
+
+use std::rc::Rc;
+
+use crate::poly::system::System;
+use crate::poly::var_store::reindex;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+#[derive(Debug, Clone)]
+pub enum Binding {
+    Const(Rat),
+    // over the var dict of the template, without parameters
+    Expr(Poly<Rat>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Template {
+    pub sys: System<Rat>,
+    pub params: Vec<usize>,
+}
+
+// p with var replaced by e, by Horner
+fn substitute(p: &Poly<Rat>, var: usize, e: &Poly<Rat>) -> Poly<Rat> {
+    p.coefs(var)
+        .into_iter()
+        .fold(Poly::constant(Rat::from(0)), |acc, c| acc.mul_ref(e) + c)
+}
+
+impl Template {
+    // None if a parameter is not a var of sys
+    pub fn new(sys: System<Rat>, params: &[&str]) -> Option<Template> {
+        let params = params
+            .iter()
+            .map(|p| sys.var_dict.iter().position(|v| v == p))
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Template { sys, params })
+    }
+
+    // the var dict of the instances
+    pub fn vars(&self) -> Vec<String> {
+        (0..self.sys.var_dict.len())
+            .filter(|v| !self.params.contains(v))
+            .map(|v| self.sys.var_dict[v].clone())
+            .collect()
+    }
+
+    // one binding per parameter; None if an expression contains a parameter
+    pub fn instantiate(&self, bindings: &[Binding]) -> Option<System<Rat>> {
+        if bindings.len() != self.params.len() {
+            return None;
+        }
+
+        let mut members = self.sys.members.clone();
+
+        for (param, binding) in self.params.iter().zip(bindings) {
+            let e = match binding {
+                Binding::Const(c) => Poly::constant(*c),
+                Binding::Expr(e) => e.clone(),
+            };
+
+            if self.params.iter().any(|p| e.deg(*p) > 0) {
+                return None;
+            }
+
+            members = members.iter().map(|p| substitute(p, *param, &e)).collect();
+        }
+
+        // parameters no longer occur, so their index is never read
+        let mut map = vec![0; self.sys.var_dict.len()];
+        let vars = (0..map.len()).filter(|v| !self.params.contains(v));
+        for (i, v) in vars.enumerate() {
+            map[v] = i;
+        }
+
+        Some(System {
+            var_dict: Rc::new(self.vars()),
+            members: members.iter().map(|p| reindex(p, &map)).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Binding, Template};
+    use crate::rational::Rat;
+    use crate::system;
+
+    #[test]
+    fn template() {
+        // a point on a circle of radius r around (c, 0)
+        let circle = system! {
+            x^2 - 2*c*x + c^2 + y^2 - r^2
+        };
+
+        let template = Template::new(circle, &["c", "r"]).unwrap();
+        assert_eq!(vec!["x", "y"], template.vars());
+        assert!(Template::new(template.sys.clone(), &["s"]).is_none());
+
+        let unit = template
+            .instantiate(&[Binding::Const(Rat::from(0)), Binding::Const(Rat::from(1))])
+            .unwrap();
+        assert_eq!(vec!["x", "y"], *unit.var_dict);
+        assert_eq!("[x^2 + y^2 - 1]", format!("{:?}", unit));
+
+        // radius equal to the abscissa of the point
+        let x = template.sys.var("x", 1);
+        let tangent = template
+            .instantiate(&[Binding::Const(Rat::from(2)), Binding::Expr(x)])
+            .unwrap();
+        assert_eq!("[y^2 - 4x + 4]", format!("{:?}", tangent));
+
+        // two instances, sharing nothing
+        let two = unit.disjoint_union(&tangent, "a_", "b_");
+        assert_eq!(vec!["a_x", "a_y", "b_x", "b_y"], *two.var_dict);
+
+        let r = template.sys.var("r", 1);
+        assert!(template
+            .instantiate(&[Binding::Expr(r), Binding::Const(Rat::from(1))])
+            .is_none());
+        assert!(template.instantiate(&[]).is_none());
+    }
+}
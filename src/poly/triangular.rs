@@ -0,0 +1,194 @@
+// real solutions by back substitution through the lex basis of a
+// zero-dimensional system. sorted by their smallest var, its members form
+// levels: the last var alone, then one more var each. while the coordinates
+// found so far are rational, a level specializes exactly to polys in its var
+// alone, whose gcd has the next coordinates as roots. past an irrational
+// coordinate that would need arithmetic with algebraic numbers, so the next
+// coordinates are taken from the real roots of the minimal poly of their var
+// instead, keeping those at which every member of the level can vanish over
+// the enclosures of the coordinates so far. rational coordinates are found
+// exactly, from the linear factors of the polys whose roots they are
+
+use crate::interval::{eval, Interval};
+use crate::poly::bivariate::enclosure;
+use crate::poly::order::MonomialOrder;
+use crate::poly::quotient::Quotient;
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+use crate::roots::coeffs_to_roots;
+use crate::univariate::{Root, UPoly};
+
+fn lowest_var(p: &Poly<Rat>) -> Option<usize> {
+    p.terms
+        .iter()
+        .flat_map(|t| t.vars.iter().map(|(v, _)| *v))
+        .min()
+}
+
+fn roots(p: &UPoly<Rat>, tolerance: Rat) -> Vec<Root<Rat>> {
+    coeffs_to_roots(p, tolerance)
+        .into_iter()
+        .map(|(root, _)| root)
+        .collect()
+}
+
+impl System<Rat> {
+    // one vector of coordinates per real solution; Root::Point where the
+    // coordinate is exact. None unless the system is zero-dimensional
+    pub fn solve_triangular(&self, tolerance: Rat) -> Option<Vec<Vec<Root<Rat>>>> {
+        self.gb_with_order(&MonomialOrder::Lex)
+            .back_substitute(tolerance)
+    }
+
+    // the same, with self the reduced lex basis, e.g. from convert_order
+    pub fn back_substitute(&self, tolerance: Rat) -> Option<Vec<Vec<Root<Rat>>>> {
+        let n = self.var_dict.len();
+        let gb = self;
+
+        if gb.members.iter().any(|p| p.is_constant() && !p.is_zero()) {
+            return Some(vec![]);
+        }
+
+        let bounded = (0..n).all(|v| {
+            gb.members.iter().any(|p| {
+                let lt = MonomialOrder::Lex.lt(p);
+                lt.vars.len() == 1 && lt.vars[0].0 == v
+            })
+        });
+        if !bounded {
+            return None;
+        }
+
+        let mut levels = vec![vec![]; n];
+        for p in &gb.members {
+            if let Some(v) = lowest_var(p) {
+                levels[v].push(p);
+            }
+        }
+
+        // only needed past an irrational coordinate
+        let mut quotient = None;
+
+        // partial solutions, each holding the coordinates of vars k + 1..n
+        let mut partials: Vec<Vec<Root<Rat>>> = vec![vec![]];
+
+        for k in (0..n).rev() {
+            let mut next = vec![];
+
+            for partial in partials {
+                let exact = partial
+                    .iter()
+                    .map(|root| match root {
+                        Root::Point(x) => Some(*x),
+                        Root::Interval(..) => None,
+                    })
+                    .collect::<Option<Vec<_>>>();
+
+                let roots = match exact {
+                    Some(xs) => {
+                        let gcd = levels[k].iter().fold(UPoly(vec![]), |acc, p| {
+                            let p = xs
+                                .iter()
+                                .enumerate()
+                                .fold((*p).clone(), |p, (i, x)| p.eval(k + 1 + i, *x));
+                            acc.gcd(&p.to_upoly(k).unwrap())
+                        });
+
+                        roots(&gcd, tolerance)
+                    }
+                    None => {
+                        if quotient.is_none() {
+                            let grlex =
+                                gb.convert_order(&MonomialOrder::Lex, &MonomialOrder::Grlex)?;
+                            quotient = Some(Quotient::from_gb(grlex)?);
+                        }
+                        let min_poly = quotient.as_ref()?.min_poly(&Poly::var(k, 1));
+
+                        let mut bx = vec![Interval::new(0., 0.); k + 1];
+                        bx.extend(partial.iter().map(enclosure));
+
+                        roots(&min_poly, tolerance)
+                            .into_iter()
+                            .filter(|root| {
+                                bx[k] = enclosure(root);
+                                levels[k].iter().all(|p| eval(p, &bx).contains(0.))
+                            })
+                            .collect()
+                    }
+                };
+
+                for root in roots {
+                    let mut extended = vec![root];
+                    extended.extend(partial.iter().cloned());
+                    next.push(extended);
+                }
+            }
+
+            partials = next;
+        }
+
+        Some(partials)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rational::Rat;
+    use crate::system;
+    use crate::univariate::Root;
+
+    #[test]
+    fn triangular() {
+        let tolerance = Rat::from(1) / Rat::from(1_000_000_000);
+
+        // rational throughout: (1, 2), (2, 1), (-1, -2), (-2, -1)
+        let sys = system! {
+            x^2 + y^2 - 5,
+            x*y - 2
+        };
+
+        let mut solutions = sys.solve_triangular(tolerance).unwrap();
+        solutions.sort_by_key(|s| s[0].approx());
+        let points = solutions
+            .iter()
+            .map(|s| match s[..] {
+                [Root::Point(x), Root::Point(y)] => (x, y),
+                _ => panic!("not exact"),
+            })
+            .collect::<Vec<_>>();
+        let rat = Rat::from;
+        assert_eq!(
+            vec![
+                (rat(-2), rat(-1)),
+                (rat(-1), rat(-2)),
+                (rat(1), rat(2)),
+                (rat(2), rat(1))
+            ],
+            points
+        );
+
+        // y = (sqrt(5) - 1) / 2 on the parabola and circle, x = +-sqrt(y)
+        let sys = system! {
+            x^2 + y^2 - 1,
+            x^2 - y
+        };
+
+        let solutions = sys.solve_triangular(tolerance).unwrap();
+        assert_eq!(2, solutions.len());
+        for s in &solutions {
+            let (x, y) = (f64::from(s[0].approx()), f64::from(s[1].approx()));
+            assert!((x * x - y).abs() < 1e-6 && (x * x + y * y - 1.).abs() < 1e-6);
+        }
+
+        assert!(system! { x^2 + 1, y - x }
+            .solve_triangular(tolerance)
+            .unwrap()
+            .is_empty());
+        assert!(system! { x - 1, x - 2 }
+            .solve_triangular(tolerance)
+            .unwrap()
+            .is_empty());
+        assert!(system! { x*y - 1 }.solve_triangular(tolerance).is_none());
+    }
+}
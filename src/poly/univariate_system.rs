@@ -0,0 +1,196 @@
+// systems which are really univariate: some members in a single var x, and
+// every other var given by a member c y - q(x) with c constant. these are
+// solved from the gcd of the univariate members alone, by a squarefree
+// decomposition and root isolation: polynomial in the degrees, where a
+// Groebner basis can take time doubly exponential in the number of vars
+
+use crate::cad::sturm::isolating_intervals;
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+use crate::univariate::{Root, UPoly};
+
+#[derive(Debug, Clone)]
+pub struct UnivariateSolutions {
+    pub var: usize,
+    // squarefree decomposition of the gcd of the members in var alone
+    pub factors: Vec<(UPoly<Rat>, usize)>,
+    // every var as a poly in var, var itself included
+    pub coords: Vec<UPoly<Rat>>,
+    // real roots in var, sorted, with the multiplicity of their factor
+    pub roots: Vec<(Root<Rat>, usize)>,
+}
+
+impl UnivariateSolutions {
+    // approximate real solutions, in the order of roots
+    pub fn points(&self) -> Vec<Vec<f64>> {
+        self.roots
+            .iter()
+            .map(|(root, _)| {
+                let x = match root {
+                    Root::Point(x) => *x,
+                    Root::Interval(..) => root.approx(),
+                };
+                self.coords.iter().map(|q| f64::from(q.eval(&x))).collect()
+            })
+            .collect()
+    }
+}
+
+fn vars_of(p: &Poly<Rat>) -> Vec<usize> {
+    let mut vars = p
+        .terms
+        .iter()
+        .flat_map(|term| term.vars.iter().map(|(v, _)| *v))
+        .collect::<Vec<_>>();
+    vars.sort();
+    vars.dedup();
+    vars
+}
+
+// (y, q) for p = c y - c q(x) with c constant and q univariate in x
+fn substitution(p: &Poly<Rat>, x: usize) -> Option<(usize, UPoly<Rat>)> {
+    let vars = vars_of(p);
+    let y = *vars.iter().find(|v| **v != x)?;
+
+    if vars.len() > 2 || p.deg(y) != 1 {
+        return None;
+    }
+
+    let coefs = p.coefs(y);
+    if !coefs[0].is_constant() {
+        return None;
+    }
+
+    let c = coefs[0].terms[0].val;
+    let q = coefs[1].to_upoly(x)?.scale(&(Rat::from(-1) / c));
+
+    Some((y, q))
+}
+
+// p squarefree
+pub(crate) fn real_roots(p: &UPoly<Rat>, tolerance: Rat) -> Vec<Root<Rat>> {
+    if p.deg() == 1 {
+        return vec![Root::Point(p.0[1] / p.0[0] * -1)];
+    }
+
+    let derivative = p.derivative();
+    isolating_intervals(p)
+        .into_iter()
+        .map(|(lo, hi)| p.refine_root_interval(&derivative, lo, hi, tolerance))
+        .collect()
+}
+
+impl System<Rat> {
+    // None unless the system has the shape above
+    pub fn solve_univariate(&self, tolerance: Rat) -> Option<UnivariateSolutions> {
+        let n = self.var_dict.len();
+        let (uni, rest): (Vec<_>, Vec<_>) =
+            self.members.iter().partition(|p| vars_of(p).len() <= 1);
+
+        let mut var = None;
+        for p in &uni {
+            if let Some(v) = vars_of(p).first() {
+                if var.is_some_and(|var| var != *v) {
+                    return None;
+                }
+                var = Some(*v);
+            }
+        }
+        let var = var?;
+
+        let mut coords = vec![None; n];
+        coords[var] = Some(UPoly(vec![Rat::from(1), Rat::from(0)]));
+
+        for p in rest {
+            let (y, q) = substitution(p, var)?;
+            if coords[y].is_some() {
+                return None;
+            }
+            coords[y] = Some(q);
+        }
+
+        let coords = coords.into_iter().collect::<Option<Vec<_>>>()?;
+
+        let gcd = uni
+            .iter()
+            .fold(UPoly(vec![]), |acc, p| acc.gcd(&p.to_upoly(var).unwrap()));
+        let factors = gcd.squarefree_decomposition();
+
+        let mut roots = factors
+            .iter()
+            .flat_map(|(f, mult)| {
+                real_roots(f, tolerance)
+                    .into_iter()
+                    .map(move |root| (root, *mult))
+            })
+            .collect::<Vec<_>>();
+        roots.sort_by_key(|(root, _)| root.approx());
+
+        Some(UnivariateSolutions {
+            var,
+            factors,
+            coords,
+            roots,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rational::Rat;
+    use crate::system;
+
+    #[test]
+    fn univariate() {
+        let tolerance = Rat::from(1) / Rat::from(1000000);
+
+        // gcd (x - 1)^2 (x + 2)
+        let sys = system! {
+            x^3 - 3*x + 2,
+            y - x^2,
+            2*z + 4*x - 2,
+            x^4 - 5*x^3 - 3*x^2 + 17*x - 10
+        };
+
+        let solutions = sys.solve_univariate(tolerance).unwrap();
+        assert_eq!(0, solutions.var);
+        assert_eq!(
+            vec![(1, 1), (1, 2)],
+            solutions
+                .factors
+                .iter()
+                .map(|(f, mult)| (f.deg(), *mult))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![1, 2],
+            solutions.roots.iter().map(|(_, m)| *m).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![vec![-2., 4., 5.], vec![1., 1., -1.]],
+            solutions.points()
+        );
+
+        // irrational roots
+        let sys = system! {
+            x^2 - 2,
+            y - x
+        };
+        let points = sys.solve_univariate(tolerance).unwrap().points();
+        assert_eq!(2, points.len());
+        assert!((points[1][0] - 2f64.sqrt()).abs() < 1e-5 && points[1][1] == points[1][0]);
+
+        // not of the shape: nonlinear in y, two univariate vars, y undetermined
+        assert!(system! { x^2 - 1, y^2 - x }
+            .solve_univariate(tolerance)
+            .is_none());
+        assert!(system! { x^2 - 1, y^2 - 4 }
+            .solve_univariate(tolerance)
+            .is_none());
+        assert!(system! { x^2 - 1, x*y - 1, y }
+            .solve_univariate(tolerance)
+            .is_none());
+        assert!(system! { x*y - 1 }.solve_univariate(tolerance).is_none());
+    }
+}
@@ -0,0 +1,207 @@
+// which var to eliminate or project first. the choice can change the size of
+// CAD projections and elimination bases by orders of magnitude, so it is made
+// from cheap statistics of the members, and can be inspected and overridden
+// before use: sys.with_var_order(&order).eliminate_first_k(k) eliminates the
+// first k vars of the order, and weak_projection(constraints, &order.order)
+// projects them in that order
+
+use std::rc::Rc;
+
+use crate::poly::mono::Mono;
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VarStats {
+    pub var: usize,
+    // highest degree of var in any member
+    pub deg: usize,
+    // highest total degree of a term containing var
+    pub term_deg: usize,
+    // terms containing var, over all members
+    pub terms: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarOrderHeuristic {
+    // Brown's: lowest degree first, then lowest total degree of the terms
+    // containing the var, then fewest such terms
+    Brown,
+    // fewest terms containing the var first, then lowest degree
+    Occurrences,
+    // the order of a min fill tree decomposition of the vars sharing members,
+    // which keeps eliminants of sparse systems in few vars
+    MinFill,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VarOrder {
+    // vars in the order they are eliminated or projected
+    pub order: Vec<usize>,
+}
+
+impl VarOrder {
+    // a manual order; None unless it is a permutation of the vars of sys
+    pub fn manual(sys: &System<Rat>, order: &[usize]) -> Option<VarOrder> {
+        let mut sorted = order.to_vec();
+        sorted.sort();
+
+        if sorted != (0..sys.var_dict.len()).collect::<Vec<_>>() {
+            return None;
+        }
+
+        Some(VarOrder {
+            order: order.to_vec(),
+        })
+    }
+
+    // a point of sys.with_var_order(self) in the original coordinates
+    pub fn restore<T: Clone>(&self, point: &[T]) -> Vec<T> {
+        let mut restored = point.to_vec();
+        for (i, v) in self.order.iter().enumerate() {
+            restored[*v] = point[i].clone();
+        }
+        restored
+    }
+}
+
+impl System<Rat> {
+    pub fn var_stats(&self) -> Vec<VarStats> {
+        (0..self.var_dict.len())
+            .map(|var| {
+                let terms = self
+                    .members
+                    .iter()
+                    .flat_map(|p| &p.terms)
+                    .filter(|t| t.deg(var) > 0);
+
+                let (deg, term_deg, terms) = terms.fold((0, 0, 0), |(deg, term_deg, n), t| {
+                    let total = t.vars.iter().map(|(_, pow)| *pow as usize).sum();
+                    (deg.max(t.deg(var)), term_deg.max(total), n + 1)
+                });
+
+                VarStats {
+                    var,
+                    deg,
+                    term_deg,
+                    terms,
+                }
+            })
+            .collect()
+    }
+
+    // ties keep the original order of the vars
+    pub fn var_order(&self, heuristic: VarOrderHeuristic) -> VarOrder {
+        let mut stats = self.var_stats();
+
+        match heuristic {
+            VarOrderHeuristic::Brown => stats.sort_by_key(|s| (s.deg, s.term_deg, s.terms)),
+            VarOrderHeuristic::Occurrences => stats.sort_by_key(|s| (s.terms, s.deg)),
+            VarOrderHeuristic::MinFill => {
+                return VarOrder {
+                    order: self.tree_decomposition().order,
+                }
+            }
+        }
+
+        VarOrder {
+            order: stats.into_iter().map(|s| s.var).collect(),
+        }
+    }
+
+    // the same system with var order[i] renamed to var i
+    pub fn with_var_order(&self, order: &VarOrder) -> System<Rat> {
+        let mut renamed = vec![0; order.order.len()];
+        for (i, v) in order.order.iter().enumerate() {
+            renamed[*v] = i;
+        }
+
+        let members = self
+            .members
+            .iter()
+            .map(|p| {
+                p.terms.iter().fold(Poly::constant(Rat::from(0)), |acc, t| {
+                    let mut vars = t
+                        .vars
+                        .iter()
+                        .map(|(v, pow)| (renamed[*v], *pow))
+                        .collect::<Vec<_>>();
+                    vars.sort();
+
+                    acc + Poly {
+                        terms: vec![Mono { val: t.val, vars }],
+                    }
+                })
+            })
+            .collect();
+
+        System {
+            var_dict: Rc::new(
+                order
+                    .order
+                    .iter()
+                    .map(|v| self.var_dict[*v].clone())
+                    .collect(),
+            ),
+            members,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{VarOrder, VarOrderHeuristic, VarStats};
+    use crate::poly::order::MonomialOrder;
+    use crate::system;
+
+    #[test]
+    fn var_order() {
+        let sys = system! {
+            x^3*y + z,
+            y^2 - z,
+            z - 1
+        };
+
+        assert_eq!(
+            VarStats {
+                var: 1,
+                deg: 2,
+                term_deg: 4,
+                terms: 2
+            },
+            sys.var_stats()[1]
+        );
+
+        let brown = sys.var_order(VarOrderHeuristic::Brown);
+        assert_eq!(vec![2, 1, 0], brown.order);
+        assert_eq!(
+            vec![0, 1, 2],
+            sys.var_order(VarOrderHeuristic::Occurrences).order
+        );
+        // every pair of vars shares a member, so nothing needs filling
+        assert_eq!(
+            vec![0, 1, 2],
+            sys.var_order(VarOrderHeuristic::MinFill).order
+        );
+
+        let reordered = sys.with_var_order(&brown);
+        assert_eq!(vec!["z", "y", "x"], *reordered.var_dict);
+        assert_eq!("[yx^3 + z, y^2 - z, z - 1]", format!("{:?}", reordered));
+        assert_eq!(vec![1, 2, 3], brown.restore(&[3, 2, 1]));
+
+        // a lex basis is triangular, eliminating in the chosen order
+        assert_eq!(
+            "[z - 1, x^3 + y, x^6 - 1]",
+            format!("{:?}", reordered.gb_with_order(&MonomialOrder::Lex))
+        );
+
+        let manual = VarOrder::manual(&sys, &[1, 0, 2]).unwrap();
+        assert_eq!(
+            "[yx^3 + z, y^2 - z, z - 1]",
+            format!("{:?}", sys.with_var_order(&manual))
+        );
+        assert!(VarOrder::manual(&sys, &[0, 0, 1]).is_none());
+        assert!(VarOrder::manual(&sys, &[0, 1]).is_none());
+    }
+}
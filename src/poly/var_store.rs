@@ -0,0 +1,205 @@
+// a namespace of vars which only grows, so the index of a var never changes.
+// systems adopted into one store agree on their var indices, and can be mixed
+// freely; combining systems otherwise matches their vars by name
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::field::Field;
+use crate::poly::mono::Mono;
+use crate::poly::system::System;
+use crate::poly::Poly;
+
+// clones share the same namespace
+#[derive(Debug, Clone, Default)]
+pub struct VarStore(Rc<RefCell<Vec<String>>>);
+
+impl VarStore {
+    pub fn new() -> VarStore {
+        VarStore::default()
+    }
+
+    pub fn from_var_dict(var_dict: &[String]) -> VarStore {
+        let store = VarStore::new();
+        for v in var_dict {
+            store.intern(v);
+        }
+        store
+    }
+
+    // the index of var, added if it is new
+    pub fn intern(&self, var: &str) -> usize {
+        let mut vars = self.0.borrow_mut();
+
+        match vars.iter().position(|v| v == var) {
+            Some(i) => i,
+            None => {
+                vars.push(var.to_string());
+                vars.len() - 1
+            }
+        }
+    }
+
+    pub fn get(&self, var: &str) -> Option<usize> {
+        self.0.borrow().iter().position(|v| v == var)
+    }
+
+    // the vars so far; earlier var dicts of the store are prefixes of it
+    pub fn var_dict(&self) -> Rc<Vec<String>> {
+        Rc::new(self.0.borrow().clone())
+    }
+
+    // sys with its vars moved to their index in the store
+    pub fn adopt<T: Field>(&self, sys: &System<T>) -> System<T> {
+        let map = sys
+            .var_dict
+            .iter()
+            .map(|v| self.intern(v))
+            .collect::<Vec<_>>();
+
+        System {
+            var_dict: self.var_dict(),
+            members: sys.members.iter().map(|p| reindex(p, &map)).collect(),
+        }
+    }
+}
+
+// var v of p becomes var map[v]; vars mapped to the same index are multiplied
+pub(crate) fn reindex<T: Field>(p: &Poly<T>, map: &[usize]) -> Poly<T> {
+    p.terms.iter().fold(Poly::constant(T::zero()), |acc, t| {
+        let mut vars: Vec<(usize, u64)> = vec![];
+
+        for (v, pow) in &t.vars {
+            match vars.iter_mut().find(|(w, _)| *w == map[*v]) {
+                Some((_, existing)) => *existing += pow,
+                None => vars.push((map[*v], *pow)),
+            }
+        }
+        vars.sort();
+
+        acc + Poly {
+            terms: vec![Mono {
+                val: t.val.clone(),
+                vars,
+            }],
+        }
+    })
+}
+
+impl<T: Field> System<T> {
+    // the members of both; vars with the same name are the same var, and the
+    // new vars of other come after those of self
+    pub fn union(&self, other: &System<T>) -> System<T> {
+        let store = VarStore::from_var_dict(&self.var_dict);
+        let other = store.adopt(other);
+
+        let mut members = self.members.clone();
+        members.extend(other.members);
+
+        System {
+            var_dict: other.var_dict,
+            members,
+        }
+    }
+
+    // each var renamed by name; vars given the same name are identified, and
+    // keep the place of the first
+    fn renamed(&self, name: impl Fn(&str) -> String) -> System<T> {
+        let mut renamed = vec![];
+        let map = self
+            .var_dict
+            .iter()
+            .map(|v| {
+                let v = name(v);
+                match renamed.iter().position(|w| *w == v) {
+                    Some(j) => j,
+                    None => {
+                        renamed.push(v);
+                        renamed.len() - 1
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        System {
+            var_dict: Rc::new(renamed),
+            members: self.members.iter().map(|p| reindex(p, &map)).collect(),
+        }
+    }
+
+    // None if from is not a var. renaming to an existing var identifies the
+    // two, and drops from
+    pub fn rename(&self, from: &str, to: &str) -> Option<System<T>> {
+        self.var_dict.iter().position(|v| v == from)?;
+        Some(self.renamed(|v| if v == from { to } else { v }.to_string()))
+    }
+
+    // all at once, so vars can be swapped; vars not in mapping are kept
+    pub fn rename_vars(&self, mapping: &HashMap<String, String>) -> System<T> {
+        self.renamed(|v| mapping.get(v).map_or(v, |w| w).to_string())
+    }
+
+    pub fn prefix_vars(&self, prefix: &str) -> System<T> {
+        self.renamed(|v| format!("{prefix}{v}"))
+    }
+
+    // the union of two systems sharing no vars, e.g. two copies of one block
+    // of constraints; the prefixes must differ
+    pub fn disjoint_union(&self, other: &System<T>, prefix: &str, other_prefix: &str) -> System<T> {
+        self.prefix_vars(prefix)
+            .union(&other.prefix_vars(other_prefix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::VarStore;
+    use crate::system;
+
+    #[test]
+    fn var_store() {
+        let a = system! {
+            x^2 + y - 1
+        };
+        let b = system! {
+            z - y,
+            y*z + 2
+        };
+
+        let store = VarStore::new();
+        let b = store.adopt(&b);
+        let a = store.adopt(&a);
+        assert_eq!(vec!["y", "z", "x"], *store.var_dict());
+        assert_eq!(vec!["y", "z"], *b.var_dict);
+        assert_eq!(Some(2), store.get("x"));
+        assert_eq!("[x^2 + y - 1]", format!("{:?}", a));
+
+        let union = a.union(&b);
+        assert_eq!("[x^2 + y - 1, -y + z, yz + 2]", format!("{:?}", union));
+
+        let union = system! { x^2 + y - 1 }.union(&system! { z - y });
+        assert_eq!(vec!["x", "y", "z"], *union.var_dict);
+        assert_eq!("[x^2 + y - 1, -y + z]", format!("{:?}", union));
+
+        let renamed = union.rename("z", "x").unwrap();
+        assert_eq!(vec!["x", "y"], *renamed.var_dict);
+        assert_eq!("[x^2 + y - 1, x - y]", format!("{:?}", renamed));
+        assert!(union.rename("w", "x").is_none());
+
+        let block = system! { x*y - 1 };
+        let two = block.disjoint_union(&block, "a_", "b_");
+        assert_eq!(vec!["a_x", "a_y", "b_x", "b_y"], *two.var_dict);
+        assert_eq!("[a_xa_y - 1, b_xb_y - 1]", format!("{:?}", two));
+
+        let mapping = HashMap::from([
+            ("x".to_string(), "y".to_string()),
+            ("y".to_string(), "x".to_string()),
+        ]);
+        let swapped = system! { x^2 + y - 1 }.rename_vars(&mapping);
+        assert_eq!(vec!["y", "x"], *swapped.var_dict);
+        assert_eq!("[y^2 + x - 1]", format!("{:?}", swapped));
+    }
+}
@@ -0,0 +1,404 @@
+// race several strategies on threads and keep the first complete, verified
+// answer. the exact strategies only apply to some shapes of system and a
+// Groebner basis may take very long, so which one wins depends on the system.
+// once the race is decided the others give up at their next check of the
+// stop flag: between pairs in Buchberger's algorithm, between primes in the
+// modular one, between boxes in subdivision and the Krawczyk test, and
+// between the steps of each strategy. a step already running (a resultant,
+// an FGLM conversion, F4 mod one prime) finishes first
+
+use std::rc::Rc;
+use std::sync::atomic::{self, AtomicBool};
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+use crate::branch_and_prune::Budget;
+use crate::distributed::{worker, PRIMES};
+use crate::interval::{eval, Interval};
+use crate::poly::bivariate::enclosure;
+use crate::poly::groebner::GroebnerConfig;
+use crate::poly::order::MonomialOrder;
+use crate::poly::quotient::Quotient;
+use crate::poly::system::System;
+use crate::poly::univariate_system::real_roots;
+use crate::poly::Poly;
+use crate::rational::Rat;
+use crate::univariate::Root;
+
+// for the Krawczyk test around each candidate of an exact strategy
+const VERIFY_BUDGET: Budget = Budget {
+    max_boxes: 100,
+    max_depth: 8,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    // one var plus substitutions for the others, see solve_univariate
+    Univariate,
+    // two equations in two unknowns, see solve_bivariate
+    Resultant,
+    // real roots of the minimal poly of each var in the quotient ring
+    Groebner,
+    // back substitution through the lex basis, see solve_triangular
+    Triangular,
+    // the same, with the lex basis converted from grevlex by FGLM
+    GrevlexFglm,
+    // the grlex basis from F4 mod several primes, combined as in
+    // combine_modular, then as Groebner
+    F4Modular,
+    // branch and prune over the whole box
+    Subdivision,
+}
+
+#[derive(Debug, Clone)]
+pub struct PortfolioConfig {
+    pub strategies: Vec<Strategy>,
+    // solutions are only reported inside this box
+    pub bx: Vec<Interval>,
+    pub tolerance: Rat,
+    // for subdivision
+    pub budget: Budget,
+}
+
+impl PortfolioConfig {
+    // every strategy
+    pub fn new(bx: Vec<Interval>) -> PortfolioConfig {
+        PortfolioConfig {
+            strategies: vec![
+                Strategy::Univariate,
+                Strategy::Resultant,
+                Strategy::Groebner,
+                Strategy::Triangular,
+                Strategy::GrevlexFglm,
+                Strategy::F4Modular,
+                Strategy::Subdivision,
+            ],
+            bx,
+            tolerance: Rat::from(1) / Rat::from(1_000_000_000),
+            budget: Budget::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PortfolioSolution {
+    // the strategy which won
+    pub strategy: Strategy,
+    // one box per real solution in config.bx, each proven to hold exactly one
+    pub solutions: Vec<Vec<Interval>>,
+}
+
+fn overlaps(a: &[Interval], b: &[Interval]) -> bool {
+    a.iter().zip(b).all(|(x, y)| x.intersect(y).is_some())
+}
+
+// the candidates of an exact strategy hold every solution; keep the solutions
+// the Krawczyk test can prove near them, and give up on any it cannot decide
+fn verify(
+    sys: &System<Rat>,
+    candidates: Vec<Vec<Interval>>,
+    config: &PortfolioConfig,
+    stop: &AtomicBool,
+) -> Option<Vec<Vec<Interval>>> {
+    let tol = f64::from(config.tolerance);
+    let mut verified: Vec<Vec<Interval>> = vec![];
+
+    for bx in candidates {
+        // the test needs the solution in the interior
+        let inflated = bx
+            .iter()
+            .map(|x| Interval::new(x.lo - tol, x.hi + tol))
+            .collect::<Vec<_>>();
+
+        let res = sys.solve_real_in_box_until(&inflated, tol, VERIFY_BUDGET, stop);
+        if !res.candidates.is_empty() || !res.undecided.is_empty() {
+            return None;
+        }
+
+        for bx in res.verified {
+            if !verified.iter().any(|v| overlaps(v, &bx)) {
+                verified.push(bx);
+            }
+        }
+    }
+
+    Some(verified)
+}
+
+// every combination of coordinates the members can vanish on, from the real
+// roots of the minimal poly of each var
+fn quotient_candidates(
+    sys: &System<Rat>,
+    quotient: &Quotient,
+    config: &PortfolioConfig,
+    stop: &AtomicBool,
+) -> Option<Vec<Vec<Interval>>> {
+    let mut candidates = vec![vec![]];
+    for v in 0..sys.var_dict.len() {
+        if stop.load(atomic::Ordering::Relaxed) {
+            return None;
+        }
+
+        let roots = real_roots(&quotient.min_poly(&Poly::var(v, 1)), config.tolerance);
+        candidates = candidates
+            .into_iter()
+            .flat_map(|bx: Vec<Interval>| {
+                roots.iter().map(move |root| {
+                    let mut bx = bx.clone();
+                    bx.push(enclosure(root));
+                    bx
+                })
+            })
+            .filter(|bx| {
+                bx.len() < sys.var_dict.len()
+                    || sys.members.iter().all(|p| eval(p, bx).contains(0.))
+            })
+            .collect();
+    }
+
+    Some(candidates)
+}
+
+// the solutions from back substitution: exact points need no test, and may
+// well be multiple roots
+fn verify_triangular(
+    sys: &System<Rat>,
+    solutions: Vec<Vec<Root<Rat>>>,
+    config: &PortfolioConfig,
+    stop: &AtomicBool,
+) -> Option<Vec<Vec<Interval>>> {
+    let (exact, candidates): (Vec<_>, Vec<_>) = solutions
+        .iter()
+        .partition(|s| s.iter().all(|x| matches!(x, Root::Point(_))));
+    let boxes = |solutions: Vec<&Vec<Root<Rat>>>| {
+        solutions
+            .into_iter()
+            .map(|s| s.iter().map(enclosure).collect())
+            .collect::<Vec<_>>()
+    };
+
+    let mut solutions = verify(sys, boxes(candidates), config, stop)?;
+    solutions.extend(boxes(exact));
+    Some(solutions)
+}
+
+fn run(
+    strategy: Strategy,
+    sys: &System<Rat>,
+    config: &PortfolioConfig,
+    stop: &AtomicBool,
+) -> Option<Vec<Vec<Interval>>> {
+    let solutions = match strategy {
+        Strategy::Univariate => {
+            let solutions = sys.solve_univariate(config.tolerance)?;
+            let coords = solutions
+                .coords
+                .iter()
+                .map(|q| q.to_poly(0))
+                .collect::<Vec<_>>();
+
+            // exact, and the other coordinates are determined by the root
+            solutions
+                .roots
+                .iter()
+                .map(|(root, _)| {
+                    let x = [enclosure(root)];
+                    coords.iter().map(|q| eval(q, &x)).collect()
+                })
+                .collect()
+        }
+        Strategy::Resultant => {
+            let pairs = sys.solve_bivariate(config.tolerance)?;
+            let candidates = pairs
+                .iter()
+                .map(|(x, y)| vec![enclosure(x), enclosure(y)])
+                .collect();
+
+            verify(sys, candidates, config, stop)?
+        }
+        Strategy::Groebner => {
+            let config_grlex = GroebnerConfig::new(MonomialOrder::Grlex);
+            let (gb, _) = sys.gb_with_config_until(&config_grlex, stop)?;
+            let quotient = Quotient::from_gb(gb)?;
+            let candidates = quotient_candidates(sys, &quotient, config, stop)?;
+
+            verify(sys, candidates, config, stop)?
+        }
+        Strategy::Triangular => {
+            let config_lex = GroebnerConfig::new(MonomialOrder::Lex);
+            let (gb, _) = sys.gb_with_config_until(&config_lex, stop)?;
+            let solutions = gb.back_substitute(config.tolerance)?;
+
+            verify_triangular(sys, solutions, config, stop)?
+        }
+        Strategy::GrevlexFglm => {
+            let config_grevlex = GroebnerConfig::new(MonomialOrder::Grevlex);
+            let (gb, _) = sys.gb_with_config_until(&config_grevlex, stop)?;
+            if stop.load(atomic::Ordering::Relaxed) {
+                return None;
+            }
+            let lex = gb.convert_order(&MonomialOrder::Grevlex, &MonomialOrder::Lex)?;
+            let solutions = lex.back_substitute(config.tolerance)?;
+
+            verify_triangular(sys, solutions, config, stop)?
+        }
+        Strategy::F4Modular => {
+            let mut answers = vec![];
+            for job in sys.modular_jobs(PRIMES.len()) {
+                if stop.load(atomic::Ordering::Relaxed) {
+                    return None;
+                }
+                answers.extend(worker(&job));
+            }
+
+            let quotient = Quotient::from_gb(sys.combine_modular(&answers)?)?;
+            let candidates = quotient_candidates(sys, &quotient, config, stop)?;
+
+            verify(sys, candidates, config, stop)?
+        }
+        Strategy::Subdivision => {
+            let tol = f64::from(config.tolerance);
+            let res = sys.solve_real_in_box_until(&config.bx, tol, config.budget, stop);
+
+            if !res.candidates.is_empty() || !res.undecided.is_empty() {
+                return None;
+            }
+
+            res.verified
+        }
+    };
+
+    Some(
+        solutions
+            .into_iter()
+            .filter(|bx: &Vec<Interval>| overlaps(bx, &config.bx))
+            .collect(),
+    )
+}
+
+impl System<Rat> {
+    // None if no strategy gives a complete answer
+    pub fn solve_portfolio(&self, config: &PortfolioConfig) -> Option<PortfolioSolution> {
+        let (send, recv) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+
+        for strategy in &config.strategies {
+            // var names are shared by Rc, so each thread gets its own copy
+            let var_dict = self.var_dict.to_vec();
+            let members = self.members.clone();
+            let (strategy, config, stop, send) =
+                (*strategy, config.clone(), stop.clone(), send.clone());
+
+            thread::spawn(move || {
+                let sys = System {
+                    var_dict: Rc::new(var_dict),
+                    members,
+                };
+
+                // the race may be over already, with no one listening
+                let _ = send.send((strategy, run(strategy, &sys, &config, &stop)));
+            });
+        }
+
+        drop(send);
+
+        for (strategy, solutions) in recv {
+            if let Some(solutions) = solutions {
+                stop.store(true, atomic::Ordering::Relaxed);
+                return Some(PortfolioSolution {
+                    strategy,
+                    solutions,
+                });
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+
+    use super::{run, PortfolioConfig, Strategy};
+    use crate::interval::Interval;
+    use crate::system;
+
+    #[test]
+    fn strategies() {
+        let sys = system! {
+            x^2 + y^2 - 5,
+            x*y - 2
+        };
+
+        let config = PortfolioConfig::new(vec![Interval::new(-3., 3.), Interval::new(0., 3.)]);
+        let stop = AtomicBool::new(false);
+
+        // every complete strategy finds the two solutions with y > 0
+        for strategy in [
+            Strategy::Resultant,
+            Strategy::Groebner,
+            Strategy::Triangular,
+            Strategy::GrevlexFglm,
+            Strategy::F4Modular,
+            Strategy::Subdivision,
+        ] {
+            let solutions = run(strategy, &sys, &config, &stop).unwrap();
+            assert_eq!(2, solutions.len());
+            assert!(solutions
+                .iter()
+                .all(|bx| bx[0].lo > 0. && (bx[0].mid() * bx[1].mid() - 2.).abs() < 1e-6));
+        }
+        assert!(run(Strategy::Univariate, &sys, &config, &stop).is_none());
+
+        // a double root: exact, but not for the Krawczyk test
+        let sys = system! {
+            x^2 - 2*x + 1,
+            y - x
+        };
+        let solutions = run(Strategy::Univariate, &sys, &config, &stop).unwrap();
+        assert_eq!(1, solutions.len());
+        assert!(solutions[0][0].contains(1.) && solutions[0][1].contains(1.));
+        assert!(run(Strategy::Groebner, &sys, &config, &stop).is_none());
+        for strategy in [Strategy::Triangular, Strategy::GrevlexFglm] {
+            let solutions = run(strategy, &sys, &config, &stop).unwrap();
+            assert_eq!(1, solutions.len());
+        }
+
+        // stopped before starting
+        let sys = system! {
+            x^2 + y^2 - 5,
+            x*y - 2
+        };
+        let stopped = AtomicBool::new(true);
+        for strategy in [
+            Strategy::Groebner,
+            Strategy::Triangular,
+            Strategy::GrevlexFglm,
+            Strategy::F4Modular,
+            Strategy::Subdivision,
+        ] {
+            assert!(run(strategy, &sys, &config, &stopped).is_none());
+        }
+    }
+
+    #[test]
+    fn portfolio() {
+        let sys = system! {
+            x^2 + y^2 - 1,
+            x^2 - y
+        };
+
+        let bx = vec![Interval::new(-2., 2.); 2];
+        let solution = sys
+            .solve_portfolio(&PortfolioConfig::new(bx.clone()))
+            .unwrap();
+        assert_eq!(2, solution.solutions.len());
+        assert_ne!(Strategy::Univariate, solution.strategy);
+
+        // only a strategy which does not apply
+        let mut config = PortfolioConfig::new(bx);
+        config.strategies = vec![Strategy::Univariate];
+        assert!(sys.solve_portfolio(&config).is_none());
+    }
+}
@@ -0,0 +1,29 @@
+// the stable surface of the crate: use srs_solver::prelude::* brings in the
+// types and macros needed to build systems, compute bases and solve them.
+//
+// stability: the crate is pre-1.0, so by Cargo's rules a 0.x minor release
+// may break anything. the names re-exported here, and the signatures of
+// their pub methods, only break in such a release, and are deprecated for
+// one release first where possible. every other path, e.g.
+// srs_solver::poly::f4, is hidden from the docs and public only for
+// experimentation: it may move or change in any release, so code that should
+// survive upgrades goes through the prelude. that includes the types the
+// fields of the names here refer to, such as Strategy and Budget
+
+pub use crate::branch_and_prune::Budget;
+pub use crate::display::SolutionTable;
+pub use crate::expr::{Expr, Rewriter};
+pub use crate::field::{Field, One, Zero};
+pub use crate::interval::Interval;
+pub use crate::linear::LinearSolution;
+pub use crate::poly::groebner::{GroebnerConfig, GroebnerStats, Normalization, Selection};
+pub use crate::poly::order::MonomialOrder;
+pub use crate::poly::parse::ParseError;
+pub use crate::poly::system::{System, UnknownVar};
+pub use crate::poly::Poly;
+pub use crate::portfolio::Strategy;
+pub use crate::rational::Rat;
+pub use crate::solve::{Decision, Shape, Solution, SolveOptions, SolveReport};
+pub use crate::trace::Trace;
+pub use crate::univariate::UPoly;
+pub use crate::{system, univariate};
@@ -0,0 +1,111 @@
+// phase markers for profiling the Groebner basis engines. with the profile
+// feature, each phase calls the hook set on its thread on entry and exit,
+// e.g. to open and close tracing spans or count coz progress points, and
+// runs inside its own non-inlined frame, so sampling profilers and flame
+// graphs attribute time to it by name. without the feature a phase is an
+// empty struct and the markers compile away
+
+#[cfg(feature = "profile")]
+use std::cell::Cell;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Enter,
+    Exit,
+}
+
+pub type Hook = fn(&'static str, Event);
+
+#[cfg(feature = "profile")]
+thread_local! {
+    static HOOK: Cell<Option<Hook>> = const { Cell::new(None) };
+}
+
+// replaces the hook for this thread; None removes it
+#[cfg(feature = "profile")]
+pub fn set_hook(hook: Option<Hook>) {
+    HOOK.with(|h| h.set(hook));
+}
+
+#[cfg(feature = "profile")]
+fn call(name: &'static str, event: Event) {
+    if let Some(hook) = HOOK.with(|h| h.get()) {
+        hook(name, event);
+    }
+}
+
+// ends the phase when dropped
+pub struct Phase {
+    #[cfg(feature = "profile")]
+    name: &'static str,
+}
+
+#[cfg(feature = "profile")]
+pub fn phase(name: &'static str) -> Phase {
+    call(name, Event::Enter);
+    Phase { name }
+}
+
+#[cfg(not(feature = "profile"))]
+#[inline(always)]
+pub fn phase(_name: &'static str) -> Phase {
+    Phase {}
+}
+
+#[cfg(feature = "profile")]
+impl Drop for Phase {
+    fn drop(&mut self) {
+        call(self.name, Event::Exit);
+    }
+}
+
+// runs f as the named phase, in a frame of its own when profiling
+#[cfg(feature = "profile")]
+#[inline(never)]
+pub fn in_phase<R>(name: &'static str, f: impl FnOnce() -> R) -> R {
+    let _phase = phase(name);
+    f()
+}
+
+#[cfg(not(feature = "profile"))]
+#[inline(always)]
+pub fn in_phase<R>(_name: &'static str, f: impl FnOnce() -> R) -> R {
+    f()
+}
+
+#[cfg(all(test, feature = "profile"))]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::{in_phase, set_hook, Event};
+    use crate::poly::order::MonomialOrder;
+    use crate::system;
+
+    thread_local! {
+        static EVENTS: RefCell<Vec<(&'static str, Event)>> = const { RefCell::new(vec![]) };
+    }
+
+    #[test]
+    fn phases() {
+        set_hook(Some(|name, event| {
+            EVENTS.with(|e| e.borrow_mut().push((name, event)))
+        }));
+
+        in_phase("outer", || in_phase("inner", || ()));
+        system! { x^2 - 2, x*y - 1 }.gb_f4(&MonomialOrder::Lex);
+        set_hook(None);
+
+        let events = EVENTS.with(|e| e.take());
+        assert_eq!(
+            vec![
+                ("outer", Event::Enter),
+                ("inner", Event::Enter),
+                ("inner", Event::Exit),
+                ("outer", Event::Exit)
+            ],
+            events[..4]
+        );
+        assert!(events.contains(&("f4 reduction", Event::Enter)));
+        assert_eq!(Some(&("reduce gb", Event::Exit)), events.last());
+    }
+}
@@ -0,0 +1,222 @@
+// exists params, for all x in a box: every member >= 0, as in robust design.
+// the params are searched by branch and bound: at the midpoint of a param
+// box, the box of x is bisected until interval evaluation proves each piece
+// nonnegative, which makes the midpoint a witness, or a sample point makes
+// some member negative. such a counterexample x rules out the whole param
+// box if the member stays negative over it; otherwise the param box is
+// bisected. the witness is only proven at the f64 midpoint itself
+
+use crate::branch_and_prune::{bisect, Budget};
+use crate::interval::{eval, Interval};
+use crate::poly::system::System;
+use crate::rational::Rat;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Quantified {
+    // params at which every member is nonnegative over the whole box
+    Witness(Vec<f64>),
+    // every param box was ruled out by a counterexample
+    Refuted,
+    // the budget ran out, or some box reached the max depth undecided
+    Unknown,
+}
+
+enum Forall {
+    Holds,
+    Counterexample(Vec<f64>),
+    Unknown,
+}
+
+struct Problem<'a> {
+    sys: &'a System<Rat>,
+    params: Vec<usize>,
+    vars: Vec<usize>,
+    budget: Budget,
+    boxes: usize,
+}
+
+fn point(x: f64) -> Interval {
+    Interval::new(x, x)
+}
+
+impl Problem<'_> {
+    // the box over all the vars of sys
+    fn join(&self, param_box: &[Interval], x_box: &[Interval]) -> Vec<Interval> {
+        let mut bx = vec![Interval::whole(); self.sys.var_dict.len()];
+
+        for (v, x) in self.params.iter().zip(param_box) {
+            bx[*v] = *x;
+        }
+        for (v, x) in self.vars.iter().zip(x_box) {
+            bx[*v] = *x;
+        }
+
+        bx
+    }
+
+    fn nonneg(&self, bx: &[Interval]) -> bool {
+        self.sys.members.iter().all(|f| eval(f, bx).lo >= 0.)
+    }
+
+    fn negative(&self, bx: &[Interval]) -> bool {
+        self.sys.members.iter().any(|f| eval(f, bx).hi < 0.)
+    }
+
+    fn forall(&mut self, params: &[Interval], x_box: &[Interval]) -> Forall {
+        let mut work = vec![(x_box.to_vec(), 0)];
+        let mut undecided = false;
+
+        while let Some((bx, depth)) = work.pop() {
+            if self.boxes >= self.budget.max_boxes {
+                return Forall::Unknown;
+            }
+            self.boxes += 1;
+
+            if self.nonneg(&self.join(params, &bx)) {
+                continue;
+            }
+
+            let mid = bx.iter().map(|x| x.mid()).collect::<Vec<_>>();
+            let sample = mid.iter().map(|x| point(*x)).collect::<Vec<_>>();
+            if self.negative(&self.join(params, &sample)) {
+                return Forall::Counterexample(mid);
+            }
+
+            if depth == self.budget.max_depth {
+                undecided = true;
+                continue;
+            }
+
+            let (left, right) = bisect(&bx);
+            work.push((left, depth + 1));
+            work.push((right, depth + 1));
+        }
+
+        if undecided {
+            Forall::Unknown
+        } else {
+            Forall::Holds
+        }
+    }
+}
+
+impl System<Rat> {
+    // the vars not in params range over forall_box, in the order of the var
+    // dict. None if a param is not a var, or either box has the wrong length
+    pub fn exists_forall(
+        &self,
+        params: &[&str],
+        param_box: &[Interval],
+        forall_box: &[Interval],
+        budget: Budget,
+    ) -> Option<Quantified> {
+        let params = params
+            .iter()
+            .map(|p| self.var_dict.iter().position(|v| v == p))
+            .collect::<Option<Vec<_>>>()?;
+        let vars = (0..self.var_dict.len())
+            .filter(|v| !params.contains(v))
+            .collect::<Vec<_>>();
+
+        if params.is_empty()
+            || vars.is_empty()
+            || params.len() != param_box.len()
+            || vars.len() != forall_box.len()
+        {
+            return None;
+        }
+
+        let mut problem = Problem {
+            sys: self,
+            params,
+            vars,
+            budget,
+            boxes: 0,
+        };
+        let mut work = vec![(param_box.to_vec(), 0)];
+        let mut undecided = false;
+
+        while let Some((bx, depth)) = work.pop() {
+            let mid = bx.iter().map(|x| x.mid()).collect::<Vec<_>>();
+            let at = mid.iter().map(|x| point(*x)).collect::<Vec<_>>();
+
+            match problem.forall(&at, forall_box) {
+                Forall::Holds => return Some(Quantified::Witness(mid)),
+                Forall::Counterexample(x) => {
+                    let x = x.into_iter().map(point).collect::<Vec<_>>();
+                    if problem.negative(&problem.join(&bx, &x)) {
+                        continue;
+                    }
+                }
+                Forall::Unknown => (),
+            }
+
+            if problem.boxes >= budget.max_boxes {
+                return Some(Quantified::Unknown);
+            }
+
+            if depth == budget.max_depth {
+                undecided = true;
+                continue;
+            }
+
+            let (left, right) = bisect(&bx);
+            work.push((left, depth + 1));
+            work.push((right, depth + 1));
+        }
+
+        Some(if undecided {
+            Quantified::Unknown
+        } else {
+            Quantified::Refuted
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Quantified;
+    use crate::branch_and_prune::Budget;
+    use crate::interval::Interval;
+    use crate::system;
+
+    #[test]
+    fn exists_forall() {
+        let budget = Budget::default();
+
+        // a such that x^2 - a x + 1 >= 0 on [-1, 1]: any a in [-2, 2]; the
+        // first midpoint, a = 5/2, fails near x = 1 but a = 5/4 holds
+        let sys = system! { x^2 - a*x + 1 };
+        let q = sys
+            .exists_forall(
+                &["a"],
+                &[Interval::new(0., 5.)],
+                &[Interval::new(-1., 1.)],
+                budget,
+            )
+            .unwrap();
+        assert_eq!(Quantified::Witness(vec![1.25]), q);
+
+        // x - a >= 0 fails at x = 1/2 for every a in [1, 3], which rules out
+        // the whole param box at once
+        let sys = system! { a - x^2 - 1, x - a };
+        let q = sys
+            .exists_forall(
+                &["a"],
+                &[Interval::new(1., 3.)],
+                &[Interval::new(0., 1.)],
+                budget,
+            )
+            .unwrap();
+        assert_eq!(Quantified::Refuted, q);
+
+        assert!(sys
+            .exists_forall(
+                &["b"],
+                &[Interval::new(0., 1.)],
+                &[Interval::new(0., 1.)],
+                budget
+            )
+            .is_none());
+    }
+}
@@ -1,11 +1,13 @@
 use std::{
     cmp::{Ord, Ordering, PartialOrd},
-    ops,
+    fmt, ops,
 };
 
 use crate::field;
 
 // overflow-safe 127 bit rational type
+// operations are carried out exactly in 128 bits; if the reduced result does
+// not fit in 64 bits, low bits of numerator and denominator are dropped
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Rat {
     pub num: i64,
@@ -28,60 +30,92 @@ impl Rat {
     pub fn is_zero(&self) -> bool {
         self.num == 0
     }
+
+    // canonical form: reduced, positive denominator and 0/1 for zero,
+    // so the derived Eq and Hash are exact
+    fn from_wide(mut num: i128, mut den: i128) -> Rat {
+        if den == 0 {
+            panic!("attempt to divide by zero")
+        }
+
+        if num == 0 {
+            return Rat { num: 0, den: 1 };
+        }
+
+        if den < 0 {
+            num = -num;
+            den = -den;
+        }
+
+        let g = gcd_wide(num.abs(), den);
+        num /= g;
+        den /= g;
+
+        let bits = (128 - num.unsigned_abs().leading_zeros()).max(128 - den.leading_zeros());
+
+        if bits > 63 {
+            let shift = bits - 63;
+            num >>= shift;
+            den = (den >> shift).max(1);
+
+            if num == 0 {
+                return Rat { num: 0, den: 1 };
+            }
+
+            // the truncated pair may share factors again
+            let g = gcd_wide(num.abs(), den);
+            num /= g;
+            den /= g;
+        }
+
+        Rat {
+            num: num as i64,
+            den: den as i64,
+        }
+    }
+
+    // from_wide without dropping bits: None unless the reduced fraction fits
+    fn exact_wide(mut num: i128, mut den: i128) -> Option<Rat> {
+        if den == 0 {
+            return None;
+        }
+
+        if den < 0 {
+            num = -num;
+            den = -den;
+        }
+
+        let g = gcd_wide(num, den);
+        let num = i64::try_from(num / g).ok()?;
+        let den = i64::try_from(den / g).ok()?;
+        (num != i64::MIN).then_some(Rat { num, den })
+    }
+
+    // exact sum and product, None where + or * would drop bits
+    pub(crate) fn checked_add(self, rhs: Rat) -> Option<Rat> {
+        Rat::exact_wide(
+            self.num as i128 * rhs.den as i128 + rhs.num as i128 * self.den as i128,
+            self.den as i128 * rhs.den as i128,
+        )
+    }
+
+    pub(crate) fn checked_mul(self, rhs: Rat) -> Option<Rat> {
+        Rat::exact_wide(
+            self.num as i128 * rhs.num as i128,
+            self.den as i128 * rhs.den as i128,
+        )
+    }
 }
 
 impl PartialOrd<Rat> for Rat {
-    // Required method
     fn partial_cmp(&self, other: &Rat) -> Option<Ordering> {
-        if self == other {
-            Some(Ordering::Equal)
-        } else if (self.num < 0 && other.num >= 0) || (self.num == 0 && other.num > 0) {
-            Some(Ordering::Less)
-        } else if (self.num > 0 && other.num <= 0) || (self.num == 0 && other.num < 0) {
-            Some(Ordering::Greater)
-        } else {
-            let mut self_clone = *self;
-            let mut other_clone = *other;
-            loop {
-                let lhs = match self_clone.num.checked_mul(other_clone.den) {
-                    Some(v) => v,
-                    None => {
-                        if self_clone.num > other_clone.den {
-                            self_clone.num >>= 1;
-                            self_clone.den >>= 1;
-                        } else {
-                            other_clone.num >>= 1;
-                            other_clone.den >>= 1;
-                        }
-
-                        continue;
-                    }
-                };
-
-                let rhs = match self_clone.den.checked_mul(other_clone.num) {
-                    Some(v) => v,
-                    None => {
-                        if self_clone.den > other_clone.num {
-                            self_clone.num >>= 1;
-                            self_clone.den >>= 1;
-                        } else {
-                            other_clone.num >>= 1;
-                            other_clone.den >>= 1;
-                        }
-
-                        continue;
-                    }
-                };
-
-                return Some(lhs.cmp(&rhs));
-            }
-        }
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for Rat {
     fn cmp(&self, other: &Rat) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        (self.num as i128 * other.den as i128).cmp(&(other.num as i128 * self.den as i128))
     }
 }
 
@@ -128,199 +162,67 @@ impl field::One for Rat {
 impl ops::Add<Rat> for Rat {
     type Output = Self;
 
-    fn add(mut self, mut rhs: Self) -> Self {
-        loop {
-            let den_gcd = gcd(self.den, rhs.den);
-
-            let lhs_num = match (rhs.den / den_gcd).checked_mul(self.num) {
-                Some(v) => v,
-                None => {
-                    if self.num == i64::MIN
-                        || (rhs.den != i64::MIN && self.num.abs() > rhs.den.abs())
-                    {
-                        self.num >>= 1;
-                        self.den >>= 1;
-                    } else {
-                        rhs.num >>= 1;
-                        rhs.den >>= 1;
-                    }
-                    continue;
-                }
-            };
-
-            let rhs_num = match (self.den / den_gcd).checked_mul(rhs.num) {
-                Some(v) => v,
-                None => {
-                    if self.den == i64::MIN
-                        || (rhs.num != i64::MIN && self.den.abs() > rhs.num.abs())
-                    {
-                        self.num >>= 1;
-                        self.den >>= 1;
-                    } else {
-                        rhs.num >>= 1;
-                        rhs.den >>= 1;
-                    }
-
-                    continue;
-                }
-            };
-
-            let num = match lhs_num.checked_add(rhs_num) {
-                Some(v) => v,
-                None => {
-                    if self.num == i64::MIN
-                        || (rhs.num != i64::MIN && self.num.abs() > rhs.num.abs())
-                    {
-                        self.num >>= 1;
-                        self.den >>= 1;
-                    } else {
-                        rhs.num >>= 1;
-                        rhs.den >>= 1;
-                    }
-
-                    continue;
-                }
-            };
-
-            let den = match (self.den / den_gcd).checked_mul(rhs.den) {
-                Some(v) => v,
-                None => {
-                    if self.den == i64::MIN
-                        || (rhs.den != i64::MIN && self.den.abs() > rhs.den.abs())
-                    {
-                        self.num >>= 1;
-                        self.den >>= 1;
-                    } else {
-                        rhs.num >>= 1;
-                        rhs.den >>= 1;
-                    }
-
-                    continue;
-                }
-            };
-
-            let new_gcd = gcd(num, den).abs();
-
-            if den > 0 {
-                return Self {
-                    num: num / new_gcd,
-                    den: den / new_gcd,
-                };
-            } else {
-                return Self {
-                    num: -num / new_gcd,
-                    den: -den / new_gcd,
-                };
-            }
-        }
+    fn add(self, rhs: Self) -> Self {
+        Rat::from_wide(
+            self.num as i128 * rhs.den as i128 + rhs.num as i128 * self.den as i128,
+            self.den as i128 * rhs.den as i128,
+        )
     }
 }
 
 impl ops::Sub<Rat> for Rat {
     type Output = Self;
 
-    fn sub(self, mut rhs: Self) -> Self {
-        if rhs.num == i64::MIN {
-            rhs.num >>= 1;
-            rhs.den >>= 1;
-        }
-
-        rhs.num *= -1;
-
-        self + rhs
+    fn sub(self, rhs: Self) -> Self {
+        Rat::from_wide(
+            self.num as i128 * rhs.den as i128 - rhs.num as i128 * self.den as i128,
+            self.den as i128 * rhs.den as i128,
+        )
     }
 }
 
 impl ops::Mul<Rat> for Rat {
     type Output = Self;
 
-    fn mul(mut self, mut rhs: Self) -> Self {
-        loop {
-            if self.num == i64::MIN || self.den == i64::MIN {
-                self.num >>= 1;
-                self.den >>= 1;
-            }
-
-            if rhs.num == i64::MIN || rhs.den == i64::MIN {
-                rhs.num >>= 1;
-                rhs.den >>= 1;
-            }
-
-            let lhs_gcd = gcd(self.num, rhs.den);
-            let rhs_gcd = gcd(rhs.num, self.den);
-
-            let num = (self.num / lhs_gcd).checked_mul(rhs.num / rhs_gcd);
-            let den = (self.den / rhs_gcd).checked_mul(rhs.den / lhs_gcd);
-
-            if let (Some(num), Some(den)) = (num, den) {
-                return Self { num, den };
-            } else if self.num == i64::MIN
-                || (rhs.num != i64::MIN && self.num.abs() > rhs.num.abs())
-            {
-                self.num >>= 1;
-                self.den >>= 1;
-            } else {
-                rhs.num >>= 1;
-                rhs.den >>= 1;
-            }
-        }
+    fn mul(self, rhs: Self) -> Self {
+        Rat::from_wide(
+            self.num as i128 * rhs.num as i128,
+            self.den as i128 * rhs.den as i128,
+        )
     }
 }
 
 impl ops::Mul<i64> for Rat {
     type Output = Self;
 
-    fn mul(mut self, rhs: i64) -> Self {
-        if self.den % rhs == 0 {
-            self.den /= rhs;
-        } else {
-            self.num *= rhs;
-        }
-
-        self
+    fn mul(self, rhs: i64) -> Self {
+        Rat::from_wide(self.num as i128 * rhs as i128, self.den as i128)
     }
 }
 
 impl ops::Div<Rat> for Rat {
     type Output = Self;
 
-    fn div(mut self, mut rhs: Self) -> Self {
-        loop {
-            if self.num == i64::MIN || self.den == i64::MIN {
-                self.num >>= 1;
-                self.den >>= 1;
-            }
-
-            if rhs.num == i64::MIN || rhs.den == i64::MIN {
-                rhs.num >>= 1;
-                rhs.den >>= 1;
-            }
+    fn div(self, rhs: Self) -> Self {
+        Rat::from_wide(
+            self.num as i128 * rhs.den as i128,
+            self.den as i128 * rhs.num as i128,
+        )
+    }
+}
 
-            let num_gcd = gcd(self.num, rhs.num);
-            let den_gcd = gcd(rhs.den, self.den);
-
-            let num = (self.num / num_gcd).checked_mul(rhs.den / den_gcd);
-            let den = (self.den / den_gcd).checked_mul(rhs.num / num_gcd);
-
-            if let (Some(num), Some(den)) = (num, den) {
-                return Self { num, den };
-            } else if self.num == i64::MIN
-                || (rhs.den != i64::MIN && self.num.abs() > rhs.den.abs())
-            {
-                self.num >>= 1;
-                self.den >>= 1;
-            } else {
-                rhs.num >>= 1;
-                rhs.den >>= 1;
-            }
-        }
+impl fmt::Display for Rat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", f64::from(*self))
     }
 }
 
-impl std::string::ToString for Rat {
-    fn to_string(&self) -> String {
-        f64::from(*self).to_string()
+fn gcd_wide(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
     }
+
+    a.abs()
 }
 
 // Euclid's algorithm
@@ -394,6 +296,25 @@ mod tests {
         assert_eq!(f64::from(a) + f64::from(b), f64::from(c));
     }
 
+    #[test]
+    fn canonical() {
+        let third = Rat::from(1) / Rat::from(3);
+
+        assert_eq!(Rat::from(0), third - third);
+        assert_eq!(Rat::from(-1) / Rat::from(2), Rat::from(1) / Rat::from(-2));
+        assert_eq!(Rat::from(-2), Rat::from(1) * -2);
+        assert!(Rat::from(1) / Rat::from(-2) < Rat::from(0));
+
+        // results which lose low bits are reduced again, so that equal
+        // values are equal as structs
+        let x = Rat::from(3037000499) / Rat::from(3037000493);
+        let y = Rat::from((1 << 62) - 1) / Rat::from(3);
+        for k in 1..1000 {
+            let z = x * k + y;
+            assert_eq!(1, gcd(z.num, z.den));
+        }
+    }
+
     #[test]
     fn gcd_shifts() {
         let a = 16 * 74;
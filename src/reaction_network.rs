@@ -0,0 +1,206 @@
+use std::rc::Rc;
+
+use crate::field::Field;
+use crate::matrix::Matrix;
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+// a rate constant or conserved total, either known or a symbolic parameter;
+// parameters become variables after the species
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Num(Rat),
+    Param(String),
+}
+
+#[derive(Debug, Clone)]
+struct Reaction {
+    reactants: Vec<u64>,
+    products: Vec<u64>,
+    rate: Value,
+}
+
+// mass-action kinetics: reaction r proceeds at rate k_r prod_i x_i^a_ri for
+// reactant coefs a_ri
+#[derive(Debug, Clone)]
+pub struct ReactionNetwork {
+    species: Vec<String>,
+    reactions: Vec<Reaction>,
+}
+
+// basis of {w : w^T m = 0}, each vector with its free coordinate, where it is
+// 1 and the others are 0
+fn left_kernel(m: &[Vec<i64>]) -> Vec<(usize, Vec<Rat>)> {
+    let rows = m.len();
+    let cols = m.first().map_or(0, |row| row.len());
+
+    // the null space of the transpose, whose free columns are those without
+    // a pivot
+    let t = (0..cols)
+        .map(|j| (0..rows).map(|i| Rat::from(m[i][j])).collect())
+        .collect();
+    let t = Matrix::new(rows, t).unwrap();
+    let pivots = t.echelon().1;
+
+    (0..rows)
+        .filter(|col| !pivots.contains(col))
+        .zip(t.null_space())
+        .collect()
+}
+
+impl ReactionNetwork {
+    pub fn new(species: &[&str]) -> Self {
+        Self {
+            species: species.iter().map(|s| s.to_string()).collect(),
+            reactions: vec![],
+        }
+    }
+
+    // stoichiometric coefs of the reactants and products, one per species;
+    // panics on the wrong number
+    pub fn reaction(mut self, reactants: &[u64], products: &[u64], rate: Value) -> Self {
+        assert_eq!(reactants.len(), self.species.len());
+        assert_eq!(products.len(), self.species.len());
+
+        self.reactions.push(Reaction {
+            reactants: reactants.to_vec(),
+            products: products.to_vec(),
+            rate,
+        });
+        self
+    }
+
+    // entry (i, r) is the net change of species i in reaction r
+    pub fn stoichiometric_matrix(&self) -> Vec<Vec<i64>> {
+        (0..self.species.len())
+            .map(|i| {
+                self.reactions
+                    .iter()
+                    .map(|r| r.products[i] as i64 - r.reactants[i] as i64)
+                    .collect()
+            })
+            .collect()
+    }
+
+    // weights w with w . x constant along every trajectory
+    pub fn conservation_laws(&self) -> Vec<Vec<Rat>> {
+        left_kernel(&self.stoichiometric_matrix())
+            .into_iter()
+            .map(|(_, w)| w)
+            .collect()
+    }
+
+    // the rate equations dx_i/dt = 0, with the equation of each conservation
+    // law's free species (redundant given the others) replaced by w . x minus
+    // that law's total; None unless there is one total per law
+    pub fn steady_state(&self, totals: &[Value]) -> Option<System<Rat>> {
+        let laws = left_kernel(&self.stoichiometric_matrix());
+
+        if laws.len() != totals.len() {
+            return None;
+        }
+
+        let mut var_dict = self.species.clone();
+        for value in self.reactions.iter().map(|r| &r.rate).chain(totals) {
+            if let Value::Param(name) = value {
+                if !var_dict.contains(name) {
+                    var_dict.push(name.clone());
+                }
+            }
+        }
+
+        let to_poly = |value: &Value| match value {
+            Value::Num(val) => Poly::constant(*val),
+            Value::Param(name) => Poly::var(var_dict.iter().position(|v| v == name).unwrap(), 1),
+        };
+
+        let fluxes = self
+            .reactions
+            .iter()
+            .map(|r| {
+                r.reactants
+                    .iter()
+                    .enumerate()
+                    .fold(to_poly(&r.rate), |acc, (i, pow)| {
+                        acc.mul_ref(&Poly::var(i, *pow))
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        let mut members = self
+            .stoichiometric_matrix()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&fluxes)
+                    .fold(Poly::constant(Rat::zero()), |acc, (c, flux)| {
+                        acc + flux.mul_ref(&Poly::constant(Rat::from(*c)))
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        for ((free, w), total) in laws.iter().zip(totals) {
+            members[*free] = w.iter().enumerate().fold(
+                to_poly(total) * Poly::constant(Rat::from(-1)),
+                |acc, (i, c)| acc + Poly::var(i, 1).mul_ref(&Poly::constant(*c)),
+            );
+        }
+
+        members.retain(|p| !p.is_zero());
+
+        Some(System {
+            var_dict: Rc::new(var_dict),
+            members,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ReactionNetwork, Value};
+    use crate::poly::quotient::Quotient;
+    use crate::rational::Rat;
+
+    fn num(val: i64) -> Value {
+        Value::Num(Rat::from(val))
+    }
+
+    fn param(name: &str) -> Value {
+        Value::Param(name.to_string())
+    }
+
+    #[test]
+    fn binding() {
+        // A + B <-> C
+        let network = ReactionNetwork::new(&["a", "b", "c"])
+            .reaction(&[1, 1, 0], &[0, 0, 1], param("k"))
+            .reaction(&[0, 0, 1], &[1, 1, 0], param("l"));
+
+        assert_eq!(
+            vec![vec![-1, 1], vec![-1, 1], vec![1, -1]],
+            network.stoichiometric_matrix()
+        );
+        // b - a and a + c
+        let laws = network.conservation_laws();
+        assert_eq!(2, laws.len());
+        assert!(laws.contains(&vec![Rat::from(-1), Rat::from(1), Rat::from(0)]));
+        assert!(laws.contains(&vec![Rat::from(1), Rat::from(0), Rat::from(1)]));
+
+        assert!(network.steady_state(&[num(0)]).is_none());
+
+        let sys = network.steady_state(&[param("s"), param("t")]).unwrap();
+        assert_eq!(vec!["a", "b", "c", "k", "l", "s", "t"], *sys.var_dict);
+        // the rate equations of b and c are replaced by the laws
+        assert_eq!("[-abk + cl, -a + b - s, a + c - t]", format!("{:?}", sys));
+
+        // with k = 1, l = 2, b = a and a + c = 3: a^2 = 2(3 - a), two states
+        let network = ReactionNetwork::new(&["a", "b", "c"])
+            .reaction(&[1, 1, 0], &[0, 0, 1], num(1))
+            .reaction(&[0, 0, 1], &[1, 1, 0], num(2));
+
+        let sys = network.steady_state(&[num(0), num(3)]).unwrap();
+        assert_eq!(2, Quotient::new(&sys).unwrap().dim());
+        assert_eq!("[a^2 + 2a - 6]", format!("{:?}", sys.eliminate(&[1, 2])));
+    }
+}
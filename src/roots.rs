@@ -0,0 +1,259 @@
+// real root isolation by Descartes' rule of signs (Vincent-Collins-Akritas):
+// the sign variations of the coefs of (x + 1)^n p((lo x + hi) / (x + 1))
+// bound the roots of p in (lo, hi) and have the same parity, so 0 and 1 are
+// exact. bisect until every interval has 0 or 1. unlike
+// cad::sturm::isolating_intervals this needs no remainder sequence, only
+// Taylor shifts of p
+
+use crate::cad::sturm::split_point;
+use crate::poly::univariate_system::real_roots;
+use crate::poly::Poly;
+use crate::rational::Rat;
+use crate::univariate::{Root, UPoly};
+
+// p(x + c), coefs lowest first
+fn shift(p: &[Rat], c: Rat) -> Vec<Rat> {
+    let mut a = p.to_vec();
+
+    for i in 0..a.len() {
+        for j in (i..a.len() - 1).rev() {
+            a[j] = a[j] + c * a[j + 1];
+        }
+    }
+
+    a
+}
+
+// a bound on the roots of p in (lo, hi), with coefs lowest first
+fn variations(p: &[Rat], lo: Rat, hi: Rat) -> usize {
+    let mut scale = Rat::from(1);
+    let mut q = shift(p, lo);
+    for c in &mut q {
+        *c = *c * scale;
+        scale = scale * (hi - lo);
+    }
+
+    q.reverse();
+    let signs = shift(&q, Rat::from(1))
+        .into_iter()
+        .filter(|c| !c.is_zero())
+        .map(|c| c > Rat::from(0))
+        .collect::<Vec<_>>();
+
+    signs.windows(2).filter(|w| w[0] != w[1]).count()
+}
+
+// sorted disjoint open intervals, each containing exactly one real root of p;
+// no endpoint is a root. None if a var other than var occurs, or p is zero,
+// when every real is a root
+pub fn isolate_real_roots(p: &Poly<Rat>, var: usize) -> Option<Vec<(Rat, Rat)>> {
+    if p.is_zero() {
+        return None;
+    }
+
+    let p = p.to_upoly(var)?.squarefree_part();
+    if p.deg() == 0 {
+        return Some(vec![]);
+    }
+
+    let coefs = p.0.iter().rev().copied().collect::<Vec<_>>();
+    let bound = p.root_bound();
+
+    let mut intervals = vec![];
+    let mut stack = vec![(bound * -1, bound)];
+
+    while let Some((lo, hi)) = stack.pop() {
+        match variations(&coefs, lo, hi) {
+            0 => {}
+            1 => intervals.push((lo, hi)),
+            _ => {
+                let mid = split_point(&lo, &hi, &[&p]);
+
+                // upper half first so that intervals come out sorted
+                stack.push((mid, hi));
+                stack.push((lo, mid));
+            }
+        }
+    }
+
+    Some(intervals)
+}
+
+// a point within tolerance of the root in interval, by bisection on the sign
+// of the squarefree part; exact if a midpoint is the root. unlike Newton
+// steps, each step only doubles the denominators. a tolerance finer than a
+// Rat resolves near the root gives the last midpoint, where bisection stops
+// moving. None if a var other than var occurs, tolerance is not positive,
+// interval is empty, or the signs at its ends do not differ, as they do for
+// isolate_real_roots
+pub fn refine_root(p: &Poly<Rat>, var: usize, interval: (Rat, Rat), tolerance: Rat) -> Option<Rat> {
+    let p = p.to_upoly(var)?.squarefree_part();
+    let (mut lo, mut hi) = interval;
+    let zero = Rat::from(0);
+
+    if tolerance <= zero || lo >= hi {
+        return None;
+    }
+
+    let lo_sign = p.eval(&lo) > zero;
+    if p.eval(&lo).is_zero() || p.eval(&hi).is_zero() || lo_sign == (p.eval(&hi) > zero) {
+        return None;
+    }
+
+    while hi - lo > tolerance {
+        let mid = (lo + hi) / Rat::from(2);
+        if mid <= lo || mid >= hi {
+            return Some(mid);
+        }
+
+        let val = p.eval(&mid);
+        if val.is_zero() {
+            return Some(mid);
+        } else if (val > zero) == lo_sign {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    Some((lo + hi) / Rat::from(2))
+}
+
+// real roots of p with multiplicity, sorted; Root::Point exactly for the
+// rational ones, from the linear factors of p, and intervals within
+// tolerance for the others
+pub fn coeffs_to_roots(p: &UPoly<Rat>, tolerance: Rat) -> Vec<(Root<Rat>, usize)> {
+    let mut roots = p
+        .squarefree_decomposition()
+        .into_iter()
+        .flat_map(|(part, mult)| {
+            part.to_poly(0)
+                .factor()
+                .1
+                .into_iter()
+                .flat_map(move |(f, _)| {
+                    let f = f.to_upoly(0).unwrap();
+                    let roots = if f.deg() == 1 {
+                        vec![Root::Point(f.0[1] / f.0[0] * -1)]
+                    } else {
+                        real_roots(&f, tolerance)
+                    };
+
+                    roots.into_iter().map(move |root| (root, mult))
+                })
+        })
+        .collect::<Vec<_>>();
+
+    roots.sort_by_key(|(root, _)| root.approx());
+    roots
+}
+
+// coefs of prod (x - r), highest first; see UPoly::from_roots for exact roots
+pub fn roots_to_coeffs(roots: &[f64]) -> Vec<f64> {
+    roots.iter().fold(vec![1.], |acc, r| {
+        let mut next = acc.clone();
+        next.push(0.);
+        for (i, c) in acc.iter().enumerate() {
+            next[i + 1] -= r * c;
+        }
+        next
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{coeffs_to_roots, isolate_real_roots, refine_root, roots_to_coeffs};
+    use crate::cad::sturm::isolating_intervals;
+    use crate::rational::Rat;
+    use crate::system;
+    use crate::univariate::{Root, UPoly};
+
+    #[test]
+    fn isolation() {
+        let sys = system! {
+            x^3 - 2*x,
+            x^3 - 3*x + 2,
+            x^2 + 1,
+            x^5 - 10*x^4 + 35*x^3 - 50*x^2 + 24*x,
+            x*y
+        };
+
+        for (p, roots) in sys.members.iter().zip([3, 2, 0, 5]) {
+            let intervals = isolate_real_roots(p, 0).unwrap();
+            assert_eq!(roots, intervals.len());
+
+            let up = p.to_upoly(0).unwrap().squarefree_part();
+            assert_eq!(isolating_intervals(&up).len(), intervals.len());
+
+            // simple roots, so a sign change across each, and sorted
+            let zero = Rat::from(0);
+            assert!(intervals
+                .iter()
+                .all(|(lo, hi)| lo < hi && (up.eval(lo) < zero) != (up.eval(hi) < zero)));
+            assert!(intervals.windows(2).all(|w| w[0].1 <= w[1].0));
+        }
+
+        assert!(isolate_real_roots(&sys.members[4], 0).is_none());
+        assert_eq!(Some(vec![]), isolate_real_roots(&sys.constant(3), 0));
+        assert!(isolate_real_roots(&sys.constant(0), 0).is_none());
+    }
+
+    #[test]
+    fn refinement() {
+        let sys = system! {
+            x^2 - 2,
+            4*x^2 - 1
+        };
+
+        let tolerance = Rat::from(1) / Rat::from(1_000_000_000_000);
+        let intervals = isolate_real_roots(&sys.members[0], 0).unwrap();
+        let root = refine_root(&sys.members[0], 0, intervals[1], tolerance).unwrap();
+        assert!((f64::from(root) - 2f64.sqrt()).abs() < 1e-12);
+
+        // found exactly on the way
+        let (lo, hi) = (Rat::from(0), Rat::from(1));
+        assert_eq!(
+            Some(Rat::from(1) / Rat::from(2)),
+            refine_root(&sys.members[1], 0, (lo, hi), tolerance)
+        );
+
+        // not isolating
+        let (lo, hi) = (Rat::from(-1), Rat::from(1));
+        assert!(refine_root(&sys.members[1], 0, (lo, hi), tolerance).is_none());
+
+        // reversed, or no tolerance at all
+        let (lo, hi) = (Rat::from(1), Rat::from(2));
+        assert!(refine_root(&sys.members[0], 0, (hi, lo), tolerance).is_none());
+        assert!(refine_root(&sys.members[0], 0, (lo, hi), Rat::from(0)).is_none());
+
+        // finer than a Rat resolves: stops where the midpoint does
+        let tolerance = Rat::from(1) / Rat::from(i64::MAX);
+        let root = refine_root(&sys.members[0], 0, (lo, hi), tolerance).unwrap();
+        assert!((f64::from(root) - 2f64.sqrt()).abs() < 1e-15);
+    }
+
+    #[test]
+    fn round_trip() {
+        let rat = |num: i64, den: i64| Rat::from(num) / Rat::from(den);
+        let tolerance = rat(1, 1_000_000_000);
+
+        // a double root at 1/2, and -3
+        let p = UPoly::from_roots(&[rat(1, 2), rat(-3, 1), rat(1, 2)]);
+        assert_eq!(
+            vec![(Root::Point(rat(-3, 1)), 1), (Root::Point(rat(1, 2)), 2)],
+            coeffs_to_roots(&p, tolerance)
+        );
+
+        // x^3 - 2x, with an irrational pair
+        let p = UPoly(vec![1, 0, -2, 0].into_iter().map(Rat::from).collect());
+        let roots = coeffs_to_roots(&p, tolerance);
+        assert_eq!(3, roots.len());
+        assert_eq!((Root::Point(rat(0, 1)), 1), roots[1]);
+        assert!(matches!(roots[2].0, Root::Interval(..)));
+        let sqrt2 = f64::from(roots[2].0.approx());
+        assert!((sqrt2 - 2f64.sqrt()).abs() < 1e-9);
+
+        assert_eq!(vec![1., -1., -6.], roots_to_coeffs(&[3., -2.]));
+        assert_eq!(vec![1.], roots_to_coeffs(&[]));
+    }
+}
@@ -0,0 +1,140 @@
+// first order sensitivity of an isolated solution x of a square system
+// F(x; c) = 0 to its coefs c: by the implicit function theorem,
+// dx/dc = -J^-1 dF/dc, where dF_i/dc is the monomial of c when c is a coef
+// of member i and 0 otherwise
+
+use crate::branch_and_prune::inverse;
+use crate::interval::{eval, Interval};
+use crate::poly::mono::Mono;
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::rational::Rat;
+
+#[derive(Debug, Clone)]
+pub struct Sensitivity {
+    // each coef as (member, index into its terms)
+    pub coefs: Vec<(usize, usize)>,
+    // dx_v/dc for var v and the cth coef
+    pub derivatives: Vec<Vec<f64>>,
+    // of the Jacobian at the solution, in the infinity norm; large means the
+    // solution is barely isolated
+    pub condition_number: f64,
+    values: Vec<f64>,
+    solution: Vec<f64>,
+}
+
+fn norm(m: &[Vec<f64>]) -> f64 {
+    m.iter()
+        .map(|row| row.iter().map(|x| x.abs()).sum::<f64>())
+        .fold(0., f64::max)
+}
+
+impl Sensitivity {
+    // |c / x_v| |dx_v/dc|: the relative change in x_v per relative change in
+    // the coef; infinite when x_v = 0
+    pub fn relative(&self, var: usize, coef: usize) -> f64 {
+        (self.values[coef] / self.solution[var]).abs() * self.derivatives[var][coef].abs()
+    }
+
+    // the coef x_v is most sensitive to, relatively
+    pub fn dominant(&self, var: usize) -> Option<(usize, usize)> {
+        (0..self.coefs.len())
+            .max_by(|a, b| self.relative(var, *a).total_cmp(&self.relative(var, *b)))
+            .map(|c| self.coefs[c])
+    }
+}
+
+impl System<Rat> {
+    // None unless the system is square with an invertible Jacobian at solution
+    pub fn sensitivity(&self, solution: &[f64]) -> Option<Sensitivity> {
+        let n = self.var_dict.len();
+
+        if self.members.len() != n || solution.len() != n {
+            return None;
+        }
+
+        let at = solution
+            .iter()
+            .map(|x| Interval::new(*x, *x))
+            .collect::<Vec<_>>();
+        let value = |p: &Poly<Rat>| eval(p, &at).mid();
+
+        let jac = self
+            .jacobian()
+            .iter()
+            .map(|row| row.iter().map(value).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let inv = inverse(jac.clone())?;
+
+        let mut coefs = vec![];
+        let mut values = vec![];
+        let mut derivatives = vec![vec![]; n];
+
+        for (i, f) in self.members.iter().enumerate() {
+            for (t, term) in f.terms.iter().enumerate() {
+                let monomial = value(&Poly {
+                    terms: vec![Mono {
+                        val: Rat::from(1),
+                        vars: term.vars.clone(),
+                    }],
+                });
+
+                coefs.push((i, t));
+                values.push(f64::from(term.val));
+
+                for (v, row) in derivatives.iter_mut().enumerate() {
+                    row.push(-inv[v][i] * monomial);
+                }
+            }
+        }
+
+        Some(Sensitivity {
+            coefs,
+            derivatives,
+            condition_number: norm(&jac) * norm(&inv),
+            values,
+            solution: solution.to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::system;
+
+    // evaluation goes through outward rounded intervals
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn sensitivity() {
+        // x = sqrt(4): terms are stored lowest first, so coef 0 is the -4
+        let sys = system! {
+            x^2 - 4
+        };
+
+        let s = sys.sensitivity(&[2.]).unwrap();
+        assert_eq!(vec![(0, 0), (0, 1)], s.coefs);
+        assert!(close(-0.25, s.derivatives[0][0]) && close(-1., s.derivatives[0][1]));
+        // sqrt halves relative errors
+        assert!(close(0.5, s.relative(0, 0)));
+
+        let sys = system! {
+            x + y - 3,
+            x - y - 1
+        };
+
+        let s = sys.sensitivity(&[2., 1.]).unwrap();
+        assert!(close(2., s.condition_number));
+        // x = (3 + 1) / 2 depends most on the 3
+        assert_eq!(Some((0, 0)), s.dominant(0));
+
+        // a double root is not isolated to first order
+        let sys = system! {
+            x^2
+        };
+        assert!(sys.sensitivity(&[0.]).is_none());
+        assert!(system! { x*y - 1 }.sensitivity(&[1., 1.]).is_none());
+    }
+}
@@ -0,0 +1,335 @@
+// one entry point for the solvers: look at the shape of the system and pick
+// the pipeline. linear systems are solved exactly; otherwise the quotient ring
+// tells inconsistent, zero-dimensional and positive-dimensional systems apart,
+// and the real solutions of zero-dimensional ones are raced for by the
+// portfolio. the report says which way it went and why, with the time each
+// stage took, and the options can override the strategies
+
+use std::time::{Duration, Instant};
+
+use crate::branch_and_prune::Budget;
+use crate::interval::Interval;
+use crate::linear::LinearSolution;
+use crate::poly::quotient::Quotient;
+use crate::poly::system::System;
+use crate::portfolio::{PortfolioConfig, Strategy};
+use crate::rational::Rat;
+use crate::trace::Trace;
+
+#[derive(Debug, Clone)]
+pub struct SolveOptions {
+    // real solutions are only reported inside this box; subdivision only
+    // runs with one
+    pub bx: Option<Vec<Interval>>,
+    pub tolerance: Rat,
+    // for subdivision
+    pub budget: Budget,
+    // the portfolio strategies to race, instead of all that apply
+    pub strategies: Option<Vec<Strategy>>,
+}
+
+impl Default for SolveOptions {
+    fn default() -> Self {
+        SolveOptions {
+            bx: None,
+            tolerance: Rat::from(1) / Rat::from(1_000_000_000),
+            budget: Budget::default(),
+            strategies: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    // every member of degree at most 1, with exactly one solution
+    Linear,
+    // no complex solutions
+    Inconsistent,
+    ZeroDimensional,
+    PositiveDimensional,
+}
+
+#[derive(Debug, Clone)]
+pub enum Solution {
+    Empty,
+    // the one solution of a linear system
+    Exact(Vec<Rat>),
+    // one box per real solution, each proven to hold exactly one
+    Boxes(Vec<Vec<Interval>>),
+    // infinitely many complex solutions, given by the reduced basis
+    Variety(System<Rat>),
+    // zero-dimensional, but no strategy gave a complete answer
+    Unknown,
+}
+
+#[derive(Debug, Clone)]
+pub struct Decision {
+    pub reason: String,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct SolveReport {
+    pub shape: Shape,
+    // complex solutions counted with multiplicity, if zero-dimensional
+    pub degree: Option<usize>,
+    // the portfolio strategy which found the boxes
+    pub strategy: Option<Strategy>,
+    // labelled by stage, in order
+    pub trace: Trace<Decision>,
+}
+
+impl SolveReport {
+    // one "stage: reason (time)" line per stage
+    pub fn format(&self) -> String {
+        self.trace
+            .steps
+            .iter()
+            .map(|(stage, d)| format!("{stage}: {} ({:?})", d.reason, d.elapsed))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn decide(trace: &mut Trace<Decision>, stage: &str, start: Instant, reason: &str) {
+    trace.record(
+        stage,
+        &Decision {
+            reason: reason.to_string(),
+            elapsed: start.elapsed(),
+        },
+    );
+}
+
+impl System<Rat> {
+    pub fn solve(&self, options: &SolveOptions) -> (Solution, SolveReport) {
+        let mut trace = Trace::new();
+        let done = |shape, trace| SolveReport {
+            shape,
+            degree: None,
+            strategy: None,
+            trace,
+        };
+        let zero_dimensional = |degree, strategy, trace| SolveReport {
+            shape: Shape::ZeroDimensional,
+            degree: Some(degree),
+            strategy,
+            trace,
+        };
+
+        let start = Instant::now();
+        match self.solve_linear() {
+            Some(LinearSolution::Affine {
+                particular,
+                null_space,
+            }) if null_space.is_empty() => {
+                let inside = options.bx.as_ref().is_none_or(|bx| {
+                    bx.iter()
+                        .zip(&particular)
+                        .all(|(x, p)| x.contains(f64::from(*p)))
+                });
+
+                if !inside {
+                    let reason = "one solution, by elimination, outside the box";
+                    decide(&mut trace, "linear", start, reason);
+                    return (Solution::Empty, done(Shape::Linear, trace));
+                }
+
+                decide(&mut trace, "linear", start, "one solution, by elimination");
+                return (Solution::Exact(particular), done(Shape::Linear, trace));
+            }
+            Some(LinearSolution::Affine { null_space, .. }) => {
+                let reason = format!(
+                    "linear, with a solution set of dimension {}",
+                    null_space.len()
+                );
+                decide(&mut trace, "linear", start, &reason);
+                return (
+                    Solution::Variety(self.gb()),
+                    done(Shape::PositiveDimensional, trace),
+                );
+            }
+            Some(LinearSolution::Inconsistent) => {
+                let reason = "no solution: elimination leaves a nonzero constant";
+                decide(&mut trace, "linear", start, reason);
+                return (Solution::Empty, done(Shape::Inconsistent, trace));
+            }
+            None => {
+                let reason = "not linear, so computing a basis";
+                decide(&mut trace, "linear", start, reason);
+            }
+        }
+
+        let start = Instant::now();
+        let Some(quotient) = Quotient::new(self) else {
+            let reason = "some var has no pure power leading term in the grlex basis, so \
+                          positive-dimensional";
+            decide(&mut trace, "basis", start, reason);
+            return (
+                Solution::Variety(self.gb()),
+                done(Shape::PositiveDimensional, trace),
+            );
+        };
+
+        if quotient.dim() == 0 {
+            decide(
+                &mut trace,
+                "basis",
+                start,
+                "the grlex basis is 1, so no solutions",
+            );
+            return (Solution::Empty, done(Shape::Inconsistent, trace));
+        }
+
+        let reason = format!(
+            "zero-dimensional, {} complex solutions with multiplicity",
+            quotient.dim()
+        );
+        decide(&mut trace, "basis", start, &reason);
+
+        let whole = vec![Interval::whole(); self.var_dict.len()];
+        let mut config = PortfolioConfig::new(options.bx.clone().unwrap_or(whole));
+        config.tolerance = options.tolerance;
+        config.budget = options.budget;
+
+        let reason = match (&options.strategies, &options.bx) {
+            (Some(strategies), _) => {
+                config.strategies = strategies.clone();
+                format!("{:?}, as given", config.strategies)
+            }
+            (None, Some(_)) => format!("{:?}", config.strategies),
+            (None, None) => {
+                config.strategies.retain(|s| *s != Strategy::Subdivision);
+                format!("{:?}; subdivision needs a box", config.strategies)
+            }
+        };
+        decide(&mut trace, "strategies", Instant::now(), &reason);
+
+        let start = Instant::now();
+        let Some(solution) = self.solve_portfolio(&config) else {
+            decide(
+                &mut trace,
+                "portfolio",
+                start,
+                "no strategy gave a complete answer",
+            );
+            return (
+                Solution::Unknown,
+                zero_dimensional(quotient.dim(), None, trace),
+            );
+        };
+
+        let reason = format!(
+            "{:?} found {} real solutions",
+            solution.strategy,
+            solution.solutions.len()
+        );
+        decide(&mut trace, "portfolio", start, &reason);
+
+        (
+            Solution::Boxes(solution.solutions),
+            zero_dimensional(quotient.dim(), Some(solution.strategy), trace),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Shape, Solution, SolveOptions};
+    use crate::interval::Interval;
+    use crate::portfolio::Strategy;
+    use crate::rational::Rat;
+    use crate::system;
+
+    #[test]
+    fn solve() {
+        let options = SolveOptions::default();
+
+        let (solution, report) = system! { x + y - 3, x - y - 1 }.solve(&options);
+        assert_eq!(Shape::Linear, report.shape);
+        assert!(matches!(solution, Solution::Exact(p) if p == vec![Rat::from(2), Rat::from(1)]));
+
+        // the same point, outside a box
+        let boxed = SolveOptions {
+            bx: Some(vec![Interval::new(-1., 1.), Interval::new(-1., 1.)]),
+            ..options.clone()
+        };
+        let (solution, report) = system! { x + y - 3, x - y - 1 }.solve(&boxed);
+        assert_eq!(Shape::Linear, report.shape);
+        assert!(matches!(solution, Solution::Empty));
+
+        for sys in [
+            system! { x - 1, x - 2 },
+            system! { x^2 + y^2 - 1, x^2 + y^2 - 2 },
+        ] {
+            let (solution, report) = sys.solve(&options);
+            assert_eq!(Shape::Inconsistent, report.shape);
+            assert!(matches!(solution, Solution::Empty));
+        }
+
+        let (solution, report) = system! { x + y - 1, 2*x + 2*y - 2 }.solve(&options);
+        assert_eq!(Shape::PositiveDimensional, report.shape);
+        assert!(matches!(solution, Solution::Variety(gb) if gb.members.len() == 1));
+
+        let (solution, report) = system! { x*y - 1 }.solve(&options);
+        assert_eq!(Shape::PositiveDimensional, report.shape);
+        assert!(matches!(solution, Solution::Variety(gb) if gb.members.len() == 1));
+
+        // four real solutions, two of them with y > 0
+        let sys = system! {
+            x^2 + y^2 - 5,
+            x*y - 2
+        };
+
+        let (solution, report) = sys.solve(&options);
+        assert_eq!(Shape::ZeroDimensional, report.shape);
+        assert_eq!(Some(4), report.degree);
+        assert!(report.strategy.is_some());
+        assert!(matches!(solution, Solution::Boxes(boxes) if boxes.len() == 4));
+
+        let options = SolveOptions {
+            bx: Some(vec![Interval::new(-3., 3.), Interval::new(0., 3.)]),
+            ..options
+        };
+        let (solution, _) = sys.solve(&options);
+        assert!(matches!(solution, Solution::Boxes(boxes) if boxes.len() == 2));
+    }
+
+    #[test]
+    fn explain() {
+        let sys = system! {
+            x^2 + y^2 - 5,
+            x*y - 2
+        };
+
+        let (_, report) = sys.solve(&SolveOptions::default());
+        let stages = report
+            .trace
+            .steps
+            .iter()
+            .map(|(stage, _)| stage.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["linear", "basis", "strategies", "portfolio"], stages);
+
+        let lines = report.format();
+        let lines = lines.lines().collect::<Vec<_>>();
+        assert!(lines[1].starts_with("basis: zero-dimensional, 4 complex solutions"));
+        assert!(lines[2].starts_with(
+            "strategies: [Univariate, Resultant, Groebner, Triangular, GrevlexFglm, F4Modular]; \
+             subdivision"
+        ));
+
+        // overridden with a strategy which does not apply
+        let options = SolveOptions {
+            strategies: Some(vec![Strategy::Univariate]),
+            ..SolveOptions::default()
+        };
+        let (solution, report) = sys.solve(&options);
+        assert!(matches!(solution, Solution::Unknown));
+        assert_eq!(None, report.strategy);
+        assert!(report.format().ends_with(&format!(
+            "portfolio: no strategy gave a complete answer ({:?})",
+            report.trace.steps[3].1.elapsed
+        )));
+    }
+}
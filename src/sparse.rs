@@ -0,0 +1,230 @@
+// sparse matrices in compressed sparse row form: the nonzero entries of all
+// rows in one array, by row and then column, with the offset of each row's
+// first entry. the matrices from F4 and Macaulay constructions have a few
+// entries per row out of thousands of columns, which a dense matrix spends
+// almost all its space and time on zeros for.
+//
+// the elimination is structured: rows are sorted by leading column and then
+// length, and the shortest row with each leading column becomes its pivot
+// without any work. only the rows left over are reduced, one at a time in a
+// dense accumulator which is cleared by the columns it touched, and those
+// which do not vanish become pivots for the columns nothing else leads
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::field::Field;
+use crate::matrix::Matrix;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Csr<T: Field> {
+    cols: usize,
+    // row i is at starts[i]..starts[i + 1]
+    starts: Vec<usize>,
+    columns: Vec<usize>,
+    vals: Vec<T>,
+}
+
+impl<T: Field> Csr<T> {
+    // None unless each row has increasing columns below cols; zero entries
+    // are dropped
+    pub fn from_rows(cols: usize, rows: Vec<Vec<(usize, T)>>) -> Option<Csr<T>> {
+        let mut m = Csr {
+            cols,
+            starts: vec![0],
+            columns: vec![],
+            vals: vec![],
+        };
+
+        for row in rows {
+            if row.windows(2).any(|w| w[0].0 >= w[1].0)
+                || row.last().is_some_and(|(c, _)| *c >= cols)
+            {
+                return None;
+            }
+            m.push(row.into_iter().filter(|(_, v)| !v.is_zero()));
+        }
+
+        Some(m)
+    }
+
+    fn push(&mut self, row: impl Iterator<Item = (usize, T)>) {
+        for (c, v) in row {
+            self.columns.push(c);
+            self.vals.push(v);
+        }
+        self.starts.push(self.columns.len());
+    }
+
+    pub fn rows(&self) -> usize {
+        self.starts.len() - 1
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    // the number of nonzero entries
+    pub fn nnz(&self) -> usize {
+        self.vals.len()
+    }
+
+    // the columns and values of the nonzero entries of row i
+    pub fn row(&self, i: usize) -> (&[usize], &[T]) {
+        let range = self.starts[i]..self.starts[i + 1];
+        (&self.columns[range.clone()], &self.vals[range])
+    }
+
+    pub fn to_dense(&self) -> Matrix<T> {
+        let rows = (0..self.rows())
+            .map(|i| {
+                let mut row = vec![T::zero(); self.cols];
+                let (columns, vals) = self.row(i);
+                for (c, v) in columns.iter().zip(vals) {
+                    row[*c] = v.clone();
+                }
+                row
+            })
+            .collect();
+
+        Matrix::new(self.cols, rows).unwrap()
+    }
+
+    // rows spanning the same space with distinct leading columns, each with
+    // leading coef 1, sorted by leading column
+    pub fn echelon(&self) -> Csr<T> {
+        let mut order = (0..self.rows())
+            .filter(|i| self.starts[*i] < self.starts[i + 1])
+            .collect::<Vec<_>>();
+        order.sort_by_key(|i| {
+            (
+                self.columns[self.starts[*i]],
+                self.starts[i + 1] - self.starts[*i],
+            )
+        });
+
+        // pivot rows by leading column, as (columns, values) with values
+        // scaled to leading coef 1
+        let mut pivots: Vec<Option<(Vec<usize>, Vec<T>)>> = vec![None; self.cols];
+        let mut rest = vec![];
+
+        let monic = |columns: &[usize], vals: &[T]| {
+            let inv = T::one() / vals[0].clone();
+            (
+                columns.to_vec(),
+                vals.iter().map(|v| v.clone() * inv.clone()).collect(),
+            )
+        };
+
+        for i in order {
+            let (columns, vals) = self.row(i);
+            match &pivots[columns[0]] {
+                None => pivots[columns[0]] = Some(monic(columns, vals)),
+                Some(_) => rest.push(i),
+            }
+        }
+
+        let mut acc = vec![T::zero(); self.cols];
+        let mut touched = vec![false; self.cols];
+        for i in rest {
+            let (columns, vals) = self.row(i);
+            let mut queue = BinaryHeap::new();
+            for (c, v) in columns.iter().zip(vals) {
+                acc[*c] = v.clone();
+                touched[*c] = true;
+                queue.push(Reverse(*c));
+            }
+
+            // by increasing column, so each pivot only adds entries to the
+            // right of the one it clears
+            let mut reduced = (vec![], vec![]);
+            while let Some(Reverse(c)) = queue.pop() {
+                touched[c] = false;
+                let f = std::mem::replace(&mut acc[c], T::zero());
+                if f.is_zero() {
+                    continue;
+                }
+
+                let Some((p_columns, p_vals)) = &pivots[c] else {
+                    reduced.0.push(c);
+                    reduced.1.push(f);
+                    continue;
+                };
+
+                for (pc, pv) in p_columns.iter().zip(p_vals).skip(1) {
+                    acc[*pc] = acc[*pc].clone() - f.clone() * pv.clone();
+                    if !touched[*pc] {
+                        touched[*pc] = true;
+                        queue.push(Reverse(*pc));
+                    }
+                }
+            }
+
+            if let Some(lead) = reduced.0.first() {
+                pivots[*lead] = Some(monic(&reduced.0, &reduced.1));
+            }
+        }
+
+        let mut echelon = Csr {
+            cols: self.cols,
+            starts: vec![0],
+            columns: vec![],
+            vals: vec![],
+        };
+        for (columns, vals) in pivots.into_iter().flatten() {
+            echelon.push(columns.into_iter().zip(vals));
+        }
+
+        echelon
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Csr;
+    use crate::rational::Rat;
+
+    #[test]
+    fn structured_elimination() {
+        let r = Rat::from;
+
+        // rows 1 and 2 lead with column 0, and row 3 is row 0 + row 1
+        let m = Csr::from_rows(
+            6,
+            vec![
+                vec![(1, r(2)), (4, r(1))],
+                vec![(0, r(1)), (3, r(-1)), (5, r(2))],
+                vec![(0, r(3)), (2, r(1)), (5, r(1))],
+                vec![(0, r(1)), (1, r(2)), (3, r(-1)), (4, r(1)), (5, r(2))],
+                vec![],
+            ],
+        )
+        .unwrap();
+        assert_eq!(5, m.rows());
+        assert_eq!(13, m.nnz());
+
+        let echelon = m.echelon();
+        assert_eq!(m.to_dense().rank(), echelon.rows());
+        assert_eq!(3, echelon.rows());
+
+        let leading = (0..echelon.rows())
+            .map(|i| {
+                let (columns, vals) = echelon.row(i);
+                assert_eq!(r(1), vals[0]);
+                columns[0]
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(vec![0, 1, 2], leading);
+
+        // the echelon rows span the same space as the original
+        let stacked = (0..m.rows())
+            .map(|i| m.row(i))
+            .chain((0..echelon.rows()).map(|i| echelon.row(i)))
+            .map(|(columns, vals)| columns.iter().copied().zip(vals.iter().copied()).collect())
+            .collect();
+        assert_eq!(3, Csr::from_rows(6, stacked).unwrap().to_dense().rank());
+
+        assert_eq!(None, Csr::from_rows(2, vec![vec![(1, r(1)), (0, r(1))]]));
+        assert_eq!(None, Csr::from_rows(2, vec![vec![(2, r(1))]]));
+    }
+}
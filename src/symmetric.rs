@@ -0,0 +1,233 @@
+use std::rc::Rc;
+
+use crate::field::Field;
+use crate::poly::system::System;
+use crate::poly::Poly;
+use crate::univariate::UPoly;
+
+// throughout, power sums p_k = sum_i x_i^k and elementary symmetric polys e_k
+// of n values are indexed by k, with p_0 = n and e_0 = 1. the conversions
+// divide by 1..n, as Newton's identities need characteristic 0
+
+// e_0, ..., e_n from p_0, ..., p_n, by k e_k = sum_(i = 1..k) (-1)^(i - 1) e_(k - i) p_i
+pub fn elementary_from_power_sums<T: Field>(p: &[T]) -> Vec<T> {
+    let mut e = vec![T::one()];
+
+    for k in 1..p.len() {
+        let sum = (1..=k).fold(T::zero(), |acc, i| {
+            let term = e[k - i].clone() * p[i].clone();
+            if !i.is_multiple_of(2) {
+                acc + term
+            } else {
+                acc - term
+            }
+        });
+
+        e.push(sum / T::from(k as i64));
+    }
+
+    e
+}
+
+// p_0, ..., p_m from e_0, ..., e_n, for any m; e_k = 0 past n
+pub fn power_sums_from_elementary<T: Field>(e: &[T], m: usize) -> Vec<T> {
+    let n = e.len().saturating_sub(1);
+    let e_at = |k: usize| e.get(k).cloned().unwrap_or(T::zero());
+    let mut p = vec![T::from(n as i64)];
+
+    for k in 1..=m {
+        // p_k = (-1)^(k - 1) k e_k + sum_(i = 1..k - 1) (-1)^(i - 1) e_i p_(k - i)
+        let mut sum = e_at(k) * T::from(k as i64);
+        if k.is_multiple_of(2) {
+            sum = sum * -1;
+        }
+
+        for i in 1..k {
+            let term = e_at(i) * p[k - i].clone();
+            sum = if !i.is_multiple_of(2) {
+                sum + term
+            } else {
+                sum - term
+            };
+        }
+
+        p.push(sum);
+    }
+
+    p
+}
+
+// monic prod (x - x_i) = x^n - e_1 x^(n - 1) + e_2 x^(n - 2) - ...
+pub fn poly_from_elementary<T: Field>(e: &[T]) -> UPoly<T> {
+    UPoly(
+        e.iter()
+            .enumerate()
+            .map(|(k, c)| {
+                if k.is_multiple_of(2) {
+                    c.clone()
+                } else {
+                    c.clone() * -1
+                }
+            })
+            .collect(),
+    )
+}
+
+// elementary symmetric polys of the roots of p, after making it monic; None
+// for the zero poly
+pub fn elementary_from_poly<T: Field>(p: &UPoly<T>) -> Option<Vec<T>> {
+    if p.is_zero() {
+        return None;
+    }
+
+    Some(poly_from_elementary(&p.monic().0).0)
+}
+
+impl<T: Field> UPoly<T> {
+    // prod (x - r), with a root repeated for each multiplicity
+    pub fn from_roots(roots: &[T]) -> UPoly<T> {
+        roots.iter().fold(UPoly(vec![T::one()]), |acc, r| {
+            acc * UPoly(vec![T::one(), r.clone() * -1])
+        })
+    }
+
+    // the monic poly whose roots have power sums p_0, ..., p_n
+    pub fn from_power_sums(p: &[T]) -> UPoly<T> {
+        poly_from_elementary(&elementary_from_power_sums(p))
+    }
+
+    // power sums p_0, ..., p_m of the roots, with multiplicity; None for the
+    // zero poly
+    pub fn power_sums(&self, m: usize) -> Option<Vec<T>> {
+        elementary_from_poly(self).map(|e| power_sums_from_elementary(&e, m))
+    }
+}
+
+// e_0, ..., e_n of vars 0..n, from prod (1 + x_i t) one var at a time
+fn elementary_polys<T: Field>(n: usize) -> Vec<Poly<T>> {
+    let mut e = vec![Poly::constant(T::one())];
+
+    for v in 0..n {
+        e.push(Poly::constant(T::zero()));
+        for k in (1..e.len()).rev() {
+            e[k] = e[k].clone() + Poly::var(v, 1).mul_ref(&e[k - 1]);
+        }
+    }
+
+    e
+}
+
+fn vieta<T: Field>(polys: Vec<Poly<T>>, values: &[T]) -> System<T> {
+    let n = values.len().saturating_sub(1);
+
+    System {
+        var_dict: Rc::new((1..=n).map(|i| format!("x{i}")).collect()),
+        members: polys
+            .into_iter()
+            .zip(values)
+            .skip(1)
+            .map(|(p, c)| p - Poly::constant(c.clone()))
+            .collect(),
+    }
+}
+
+impl<T: Field> System<T> {
+    // vars x1, ..., xn with elementary symmetric polys e_1, ..., e_n; the
+    // solutions are the orderings of the roots of poly_from_elementary(e)
+    pub fn from_elementary(e: &[T]) -> System<T> {
+        vieta(elementary_polys(e.len().saturating_sub(1)), e)
+    }
+
+    // the same with power sums p_1, ..., p_n
+    pub fn from_power_sums(p: &[T]) -> System<T> {
+        let n = p.len().saturating_sub(1);
+        let sums = (0..=n)
+            .map(|k| {
+                (0..n).fold(Poly::constant(T::zero()), |acc, v| {
+                    acc + Poly::var(v, k as u64)
+                })
+            })
+            .collect();
+
+        vieta(sums, p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{elementary_from_poly, elementary_from_power_sums, power_sums_from_elementary};
+    use crate::poly::quotient::Quotient;
+    use crate::poly::system::System;
+    use crate::rational::Rat;
+    use crate::system;
+    use crate::univariate;
+    use crate::univariate::UPoly;
+
+    fn rats(coefs: &[i64]) -> Vec<Rat> {
+        coefs.iter().map(|c| Rat::from(*c)).collect()
+    }
+
+    #[test]
+    fn newton() {
+        // roots 1, 2, 3
+        let e = rats(&[1, 6, 11, 6]);
+        let p = rats(&[3, 6, 14, 36, 98]);
+
+        assert_eq!(p, power_sums_from_elementary(&e, 4));
+        assert_eq!(e, elementary_from_power_sums(&p[..4]));
+
+        let cubic = univariate! { 2*x^3 - 12*x^2 + 22*x - 12 };
+        assert_eq!(Some(e), elementary_from_poly(&cubic));
+        assert_eq!(cubic.monic(), UPoly::from_power_sums(&p[..4]));
+        assert_eq!(cubic.monic(), UPoly::from_roots(&rats(&[1, 2, 3])));
+        assert_eq!(Some(p), cubic.power_sums(4));
+
+        assert_eq!(None, UPoly::<Rat>(vec![]).power_sums(2));
+        assert_eq!(rats(&[0, 0]), power_sums_from_elementary(&rats(&[1]), 1));
+    }
+
+    #[test]
+    fn traces() {
+        // solutions (1, 2), (2, 1), (-1, -2), (-2, -1): the char poly of x
+        // back from the traces of its powers
+        let sys = system! {
+            x^2 + y^2 - 5,
+            x*y - 2
+        };
+
+        let q = Quotient::new(&sys).unwrap();
+        let x = sys.var("x", 1);
+
+        let p = (0..=q.dim())
+            .map(|k| q.trace(&sys.var("x", k as u64)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(rats(&[4, 0, 10, 0, 34]), p);
+        assert_eq!(q.char_poly(&x), UPoly::from_power_sums(&p));
+    }
+
+    #[test]
+    fn vieta() {
+        let sys = System::from_elementary(&rats(&[1, 6, 11, 6]));
+        assert_eq!(vec!["x1", "x2", "x3"], *sys.var_dict);
+        assert_eq!(
+            "[x1 + x2 + x3 - 6, x1x2 + x1x3 + x2x3 - 11, x1x2x3 - 6]",
+            format!("{:?}", sys)
+        );
+        assert_eq!(
+            "[x1 + x2 + x3 - 6, x1^2 + x2^2 + x3^2 - 14, x1^3 + x2^3 + x3^3 - 36]",
+            format!("{:?}", System::from_power_sums(&rats(&[3, 6, 14, 36])))
+        );
+
+        // roots 1, 2 in either order
+        let sys = System::from_elementary(&rats(&[1, 3, 2]));
+        let q = Quotient::new(&sys).unwrap();
+        assert_eq!(2, q.dim());
+        assert_eq!(univariate! { x^2 - 3*x + 2 }, q.min_poly(&sys.var("x2", 1)));
+
+        let sums = System::from_power_sums(&rats(&[2, 3, 5]));
+        assert_eq!(format!("{:?}", sys.gb()), format!("{:?}", sums.gb()));
+
+        assert!(System::<Rat>::from_power_sums(&[]).members.is_empty());
+    }
+}
@@ -0,0 +1,123 @@
+use crate::poly::Poly;
+use crate::rational::Rat;
+use crate::univariate::UPoly;
+
+// the intermediate polys of a gcd, remainder sequence or subresultant
+// computation, in the order they were produced; pass one to the *_traced
+// variants to see how a result came about
+#[derive(Debug, Clone)]
+pub struct Trace<P> {
+    enabled: bool,
+    pub steps: Vec<(String, P)>,
+}
+
+impl<P: Clone> Default for Trace<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: Clone> Trace<P> {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            steps: vec![],
+        }
+    }
+
+    // records nothing; what the untraced variants pass
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            steps: vec![],
+        }
+    }
+
+    pub fn record(&mut self, label: &str, p: &P) {
+        if self.enabled {
+            self.steps.push((label.to_string(), p.clone()));
+        }
+    }
+
+    pub fn polys(&self) -> Vec<&P> {
+        self.steps.iter().map(|(_, p)| p).collect()
+    }
+}
+
+impl Trace<UPoly<Rat>> {
+    // one "label: poly" line per step
+    pub fn format(&self, var: &str) -> String {
+        let var_dict = [var.to_string()];
+
+        self.steps
+            .iter()
+            .map(|(label, p)| format!("{label}: {}", p.to_poly(0).format(&var_dict)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Trace<Poly<Rat>> {
+    pub fn format(&self, var_dict: &[String]) -> String {
+        self.steps
+            .iter()
+            .map(|(label, p)| format!("{label}: {}", p.format(var_dict)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Trace;
+    use crate::cad::sturm;
+    use crate::system;
+    use crate::univariate;
+
+    #[test]
+    fn univariate() {
+        let p = univariate! { x^3 - x };
+        let q = univariate! { x^2 - 3*x + 2 };
+
+        let mut trace = Trace::new();
+        let gcd = p.gcd_traced(&q, &mut trace);
+        assert_eq!(gcd, p.gcd(&q));
+        assert_eq!(
+            "a: x^3 - x\nb: x^2 - 3x + 2\nrem: 6x - 6\nrem: 0\ngcd: x - 1",
+            trace.format("x")
+        );
+
+        let mut trace = Trace::new();
+        let seq = sturm::sturm_sequence_traced(&p, &mut trace);
+        assert_eq!(seq, trace.polys().into_iter().cloned().collect::<Vec<_>>());
+
+        let mut trace = Trace::new();
+        sturm::signed_subresultants_traced(&p, &q, &mut trace);
+        // the common root shows up as sResP_1 = -6(x - 1), and sResP_0 = 0
+        assert_eq!(
+            "3: x^3 - x\n2: x^2 - 3x + 2\n1: -6x + 6\n0: 0",
+            trace.format("x")
+        );
+    }
+
+    #[test]
+    fn multivariate() {
+        let sys = system! {
+            x^2*y - y^3,
+            x*y^2 + y^3
+        };
+
+        let mut trace = Trace::new();
+        let gcd = sys.members[0].gcd_traced(&sys.members[1], &mut trace);
+        // the subresultant keeps its content x until the end
+        assert_eq!(
+            "content: 1\na: x^2y - y^3\nb: xy^2 + y^3\nsres: x^2y + xy^2\ngcd: xy + y^2",
+            trace.format(&sys.var_dict)
+        );
+        assert_eq!(gcd, sys.members[0].gcd(&sys.members[1]));
+
+        let mut disabled = Trace::disabled();
+        sys.members[0].gcd_traced(&sys.members[1], &mut disabled);
+        assert!(disabled.steps.is_empty());
+    }
+}
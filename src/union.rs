@@ -0,0 +1,260 @@
+// disjunctions of systems over the same vars, e.g. the sign cases of a
+// piecewise model: the solutions are those of any case. cases equal up to
+// the order, scaling and repetition of their members are solved once, and
+// if the members common to every case have no solutions then neither does
+// any case, which one basis shows without solving them. the real solutions
+// of the cases are merged, with overlapping boxes taken as one solution
+
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::rc::Rc;
+
+use crate::expr::{Expr, Rewriter};
+use crate::interval::Interval;
+use crate::poly::system::System;
+use crate::rational::Rat;
+use crate::solve::{Solution, SolveOptions, SolveReport};
+
+#[derive(Debug, Clone)]
+pub struct SystemUnion {
+    pub cases: Vec<System<Rat>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UnionSolution {
+    // one per real solution of the zero-dimensional cases, exact ones as
+    // point boxes
+    pub boxes: Vec<Vec<Interval>>,
+    // the reduced bases of the positive-dimensional cases
+    pub varieties: Vec<System<Rat>>,
+    // false if some case was zero-dimensional but got no complete answer
+    pub complete: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct UnionReport {
+    // (case, the earlier case it duplicates)
+    pub duplicates: Vec<(usize, usize)>,
+    // the members common to every case have no solutions, so none was solved
+    pub common_inconsistent: bool,
+    // for each case solved
+    pub cases: Vec<(usize, SolveReport)>,
+}
+
+// each box overlapping one found before narrows it instead of being added
+fn merge(boxes: &mut Vec<Vec<Interval>>, bx: Vec<Interval>) {
+    for other in boxes.iter_mut() {
+        let common = other
+            .iter()
+            .zip(&bx)
+            .map(|(a, b)| a.intersect(b))
+            .collect::<Option<Vec<_>>>();
+
+        if let Some(common) = common {
+            *other = common;
+            return;
+        }
+    }
+
+    boxes.push(bx);
+}
+
+impl SystemUnion {
+    // None if there are no cases, or their var dicts differ
+    pub fn new(cases: Vec<System<Rat>>) -> Option<SystemUnion> {
+        let first = cases.first()?;
+
+        if cases.iter().any(|sys| sys.var_dict != first.var_dict) {
+            return None;
+        }
+
+        Some(SystemUnion { cases })
+    }
+
+    // a case per assignment of the symbolic exponents of members, each taken
+    // from its range, and the assignments in the same order, by increasing
+    // exponents with the last changing fastest; None if an exponent has no
+    // range, or a member has a var not in vars
+    pub fn exponent_cases(
+        vars: &[&str],
+        members: &[Expr],
+        ranges: &[(&str, RangeInclusive<u64>)],
+    ) -> Option<(SystemUnion, Vec<Vec<u64>>)> {
+        let named = members.iter().flat_map(|e| e.exponent_vars());
+        if named
+            .into_iter()
+            .any(|name| ranges.iter().all(|(n, _)| *n != name))
+        {
+            return None;
+        }
+
+        let assignments = ranges.iter().fold(vec![vec![]], |acc, (_, range)| {
+            acc.into_iter()
+                .flat_map(|prefix: Vec<u64>| {
+                    range.clone().map(move |exp| [&prefix[..], &[exp]].concat())
+                })
+                .collect()
+        });
+
+        let var_dict = Rc::new(vars.iter().map(|v| v.to_string()).collect::<Vec<_>>());
+        let rewriter = Rewriter::new();
+        let cases = assignments
+            .iter()
+            .map(|exps| {
+                let exps = ranges
+                    .iter()
+                    .zip(exps)
+                    .map(|((name, _), exp)| (*name, *exp))
+                    .collect::<Vec<_>>();
+
+                let members = members
+                    .iter()
+                    .map(|e| rewriter.rewrite(&e.bind_exponents(&exps)).lower(&var_dict))
+                    .collect::<Option<Vec<_>>>()?;
+
+                Some(System {
+                    var_dict: var_dict.clone(),
+                    members,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some((SystemUnion::new(cases)?, assignments))
+    }
+
+    // the members in every case
+    pub fn common(&self) -> System<Rat> {
+        let first = &self.cases[0];
+
+        System {
+            var_dict: first.var_dict.clone(),
+            members: first
+                .members
+                .iter()
+                .filter(|p| self.cases.iter().all(|sys| sys.members.contains(p)))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    pub fn solve(&self, options: &SolveOptions) -> (UnionSolution, UnionReport) {
+        let mut solution = UnionSolution {
+            boxes: vec![],
+            varieties: vec![],
+            complete: true,
+        };
+        let mut report = UnionReport {
+            duplicates: vec![],
+            common_inconsistent: false,
+            cases: vec![],
+        };
+
+        let common = self.common();
+        if !common.members.is_empty() && common.solution_count_bound() == Some(0) {
+            report.common_inconsistent = true;
+            return (solution, report);
+        }
+
+        let mut seen = HashMap::new();
+
+        for (i, sys) in self.cases.iter().enumerate() {
+            if let Some(j) = seen.get(&sys.canonical_form()) {
+                report.duplicates.push((i, *j));
+                continue;
+            }
+            seen.insert(sys.canonical_form(), i);
+
+            let (case, case_report) = sys.solve(options);
+            report.cases.push((i, case_report));
+
+            match case {
+                Solution::Empty => (),
+                Solution::Exact(point) => merge(
+                    &mut solution.boxes,
+                    point.into_iter().map(Interval::point).collect(),
+                ),
+                Solution::Boxes(boxes) => {
+                    for bx in boxes {
+                        merge(&mut solution.boxes, bx);
+                    }
+                }
+                Solution::Variety(gb) => solution.varieties.push(gb),
+                Solution::Unknown => solution.complete = false,
+            }
+        }
+
+        (solution, report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SystemUnion;
+    use crate::expr::Expr;
+    use crate::piecewise::Piecewise;
+    use crate::poly::Poly;
+    use crate::rational::Rat;
+    use crate::solve::SolveOptions;
+    use crate::system;
+
+    #[test]
+    fn union() {
+        let options = SolveOptions::default();
+
+        // x^2 = 1 or x = 1, with x = 1 found by both
+        let cases = vec![system! { x^2 - 1 }, system! { x - 1 }, system! { 2*x - 2 }];
+
+        let (solution, report) = SystemUnion::new(cases).unwrap().solve(&options);
+        assert_eq!(vec![(2, 1)], report.duplicates);
+        assert_eq!(2, report.cases.len());
+        assert_eq!(2, solution.boxes.len());
+        assert!(solution.complete && solution.varieties.is_empty());
+
+        // |x - 1| = 2 by its sign cases; the slack var is +-sqrt(2) in each
+        let mut model = Piecewise::new(&["x"]);
+        let x = model.var("x");
+        let t = model.abs(&(x - Poly::constant(Rat::from(1))));
+        model.push(t - Poly::constant(Rat::from(2)));
+
+        let union = SystemUnion::new(model.cases()).unwrap();
+        assert_eq!(1, union.common().members.len());
+        let (solution, _) = union.solve(&options);
+        let mut xs = solution
+            .boxes
+            .iter()
+            .map(|bx| bx[0].mid().round())
+            .collect::<Vec<_>>();
+        xs.sort_by(f64::total_cmp);
+        assert_eq!(vec![-1., -1., 3., 3.], xs);
+
+        // the shared members are already inconsistent
+        let cases = vec![system! { x - 1, x - 2, y }, system! { x - 1, x - 2, y - 1 }];
+        let (solution, report) = SystemUnion::new(cases).unwrap().solve(&options);
+        assert!(report.common_inconsistent && report.cases.is_empty());
+        assert!(solution.boxes.is_empty());
+
+        assert!(SystemUnion::new(vec![]).is_none());
+        assert!(SystemUnion::new(vec![system! { x }, system! { y }]).is_none());
+    }
+
+    #[test]
+    fn exponent_cases() {
+        // x^n = 4 y^m, x = y with n in 1..=3 and m in 1..=2
+        let (x, y) = (Expr::var("x"), Expr::var("y"));
+        let members = [
+            x.clone().pow_var("n") - Expr::constant(4) * y.clone().pow_var("m"),
+            x - y,
+        ];
+        let ranges = [("n", 1..=3), ("m", 1..=2)];
+
+        let (union, assignments) =
+            SystemUnion::exponent_cases(&["x", "y"], &members, &ranges).unwrap();
+        assert_eq!(6, union.cases.len());
+        assert_eq!(vec![1, 1], assignments[0]);
+        assert_eq!(vec![3, 2], assignments[5]);
+        assert_eq!("[x^3 - 4y^2, x - y]", format!("{:?}", union.cases[5]));
+        assert_eq!(1, union.common().members.len());
+
+        assert!(SystemUnion::exponent_cases(&["x", "y"], &members, &ranges[..1]).is_none());
+    }
+}
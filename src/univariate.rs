@@ -1,9 +1,12 @@
 use std::cmp::Ordering;
+use std::ops;
 
 // thanks to Osvaldo Carvalho
 // https://www.researchgate.net/publication/320864673_A_simple_recursive_algorithm_to_find_all_real_roots_of_a_polynomial
 use crate::field::Field;
+use crate::poly::Poly;
 use crate::rational::Rat;
+use crate::trace::Trace;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct UPoly<T: Field>(pub Vec<T>);
@@ -32,6 +35,10 @@ impl<T: Field> UPoly<T> {
     }
 
     pub fn derivative(&self) -> Self {
+        if self.0.len() < 2 {
+            return Self(vec![]);
+        }
+
         let mut new = self.0.clone();
         new.pop();
         let deg = new.len() - 1;
@@ -43,6 +50,188 @@ impl<T: Field> UPoly<T> {
         Self(new)
     }
 
+    // drop leading zero coefs; the zero polynomial is UPoly(vec![])
+    pub fn trim(mut self) -> Self {
+        let first_nonzero = self
+            .0
+            .iter()
+            .position(|c| !c.is_zero())
+            .unwrap_or(self.0.len());
+        self.0.drain(..first_nonzero);
+        self
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.iter().all(|c| c.is_zero())
+    }
+
+    pub fn deg(&self) -> usize {
+        let leading_zeros = self.0.iter().take_while(|c| c.is_zero()).count();
+        (self.0.len() - leading_zeros).max(1) - 1
+    }
+
+    pub fn lc(&self) -> T {
+        self.0
+            .iter()
+            .find(|c| !c.is_zero())
+            .cloned()
+            .unwrap_or(T::zero())
+    }
+
+    pub fn scale(&self, by: &T) -> Self {
+        Self(self.0.iter().map(|c| c.clone() * by.clone()).collect()).trim()
+    }
+
+    pub fn monic(&self) -> Self {
+        if self.is_zero() {
+            Self(vec![])
+        } else {
+            self.scale(&(T::one() / self.lc()))
+        }
+    }
+
+    // long division; panics on a zero divisor
+    pub fn div_rem(&self, divisor: &UPoly<T>) -> (UPoly<T>, UPoly<T>) {
+        let divisor = divisor.clone().trim();
+        let mut rem = self.clone().trim();
+
+        if divisor.is_zero() {
+            panic!("division by zero polynomial");
+        }
+
+        if rem.0.len() < divisor.0.len() {
+            return (Self(vec![]), rem);
+        }
+
+        let mut quot = vec![];
+
+        for i in 0..rem.0.len() + 1 - divisor.0.len() {
+            let c = rem.0[i].clone() / divisor.0[0].clone();
+
+            if !c.is_zero() {
+                for (j, d) in divisor.0.iter().enumerate().skip(1) {
+                    rem.0[i + j] = rem.0[i + j].clone() - c.clone() * d.clone();
+                }
+            }
+
+            quot.push(c);
+        }
+
+        rem.0.drain(..quot.len());
+
+        (Self(quot).trim(), rem.trim())
+    }
+
+    pub fn rem(&self, divisor: &UPoly<T>) -> UPoly<T> {
+        self.div_rem(divisor).1
+    }
+
+    // self^exp mod m, by repeated squaring
+    pub fn pow_mod(&self, mut exp: u128, m: &UPoly<T>) -> UPoly<T> {
+        let mut base = self.rem(m);
+        let mut acc = UPoly(vec![T::one()]).rem(m);
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = (acc * base.clone()).rem(m);
+            }
+            base = (base.clone() * base).rem(m);
+            exp >>= 1;
+        }
+
+        acc
+    }
+
+    // monic gcd by Euclid's algorithm
+    pub fn gcd(&self, other: &UPoly<T>) -> UPoly<T> {
+        self.gcd_traced(other, &mut Trace::disabled())
+    }
+
+    // records the Euclidean remainder sequence, then the gcd
+    pub fn gcd_traced(&self, other: &UPoly<T>, trace: &mut Trace<UPoly<T>>) -> UPoly<T> {
+        let mut a = self.clone().trim();
+        let mut b = other.clone().trim();
+        trace.record("a", &a);
+        trace.record("b", &b);
+
+        while !b.is_zero() {
+            let r = a.rem(&b);
+            trace.record("rem", &r);
+            a = b;
+            b = r;
+        }
+
+        let gcd = a.monic();
+        trace.record("gcd", &gcd);
+        gcd
+    }
+
+    // same roots, all simple
+    pub fn squarefree_part(&self) -> UPoly<T> {
+        let p = self.clone().trim();
+        if p.deg() == 0 {
+            return p.monic();
+        }
+
+        p.div_rem(&p.gcd(&p.derivative())).0.monic()
+    }
+
+    // monic squarefree, pairwise coprime (factor, multiplicity) with self a
+    // constant times the product of factor^multiplicity (Yun)
+    pub fn squarefree_decomposition(&self) -> Vec<(UPoly<T>, usize)> {
+        let p = self.clone().trim();
+        if p.deg() == 0 {
+            return vec![];
+        }
+
+        let derivative = p.derivative();
+        let a = p.gcd(&derivative);
+        let mut b = p.div_rem(&a).0;
+        let mut d = derivative.div_rem(&a).0 - b.derivative();
+        let mut factors = vec![];
+        let mut mult = 1;
+
+        while b.deg() > 0 {
+            let a = b.gcd(&d);
+            b = b.div_rem(&a).0;
+            let c = d.div_rem(&a).0;
+            d = c - b.derivative();
+
+            if a.deg() > 0 {
+                factors.push((a, mult));
+            }
+            mult += 1;
+        }
+
+        factors
+    }
+
+    pub fn sign_at(&self, x: &T) -> Ordering {
+        self.eval(x).cmp(&T::zero())
+    }
+
+    pub fn sign_at_pos_inf(&self) -> Ordering {
+        self.lc().cmp(&T::zero())
+    }
+
+    pub fn sign_at_neg_inf(&self) -> Ordering {
+        if self.deg().is_multiple_of(2) {
+            self.sign_at_pos_inf()
+        } else {
+            self.sign_at_pos_inf().reverse()
+        }
+    }
+
+    // Cauchy's bound: every real root lies strictly inside (-bound, bound)
+    pub fn root_bound(&self) -> T {
+        let p = self.clone().trim();
+        let abs = |x: T| if x < T::zero() { x * -1 } else { x };
+
+        p.0.iter().skip(1).fold(T::zero(), |acc, c| {
+            acc.max(abs(c.clone() / p.0[0].clone()))
+        }) + T::one()
+    }
+
     pub fn real_root_intervals(&self, tolerance: T) -> Vec<Root<T>> {
         match self.0.len() {
             0 | 1 => vec![],
@@ -64,7 +253,7 @@ impl<T: Field> UPoly<T> {
 
                 match self.eval(&first_derivative_root).cmp(&T::zero()) {
                     Ordering::Less => {
-                        if (self.0[0] < T::zero()) ^ (self.0.len() % 2 == 0) {
+                        if (self.0[0] < T::zero()) ^ self.0.len().is_multiple_of(2) {
                             // value here is same sign as -inf; no root
                         } else {
                             // probe backwards until we have a finite interval
@@ -83,7 +272,7 @@ impl<T: Field> UPoly<T> {
                         }
                     }
                     Ordering::Greater => {
-                        if !((self.0[0] < T::zero()) ^ (self.0.len() % 2 == 0)) {
+                        if !((self.0[0] < T::zero()) ^ self.0.len().is_multiple_of(2)) {
                             // value here is same sign as -inf; no root
                         } else {
                             // probe backwards until we have a finite interval
@@ -201,14 +390,23 @@ impl<T: Field> UPoly<T> {
                 return Root::Point(mid);
             }
 
-            let candidate1 = mid.clone() - mid_eval.clone() / start_deriv;
+            // a vanishing derivative degrades the newton step to bisection
+            let candidate1 = if start_deriv.is_zero() {
+                mid.clone()
+            } else {
+                mid.clone() - mid_eval.clone() / start_deriv
+            };
             let candidate1_eval = self.eval(&candidate1);
 
             if candidate1_eval == T::zero() {
                 return Root::Point(candidate1);
             }
 
-            let candidate2 = mid - mid_eval / end_deriv;
+            let candidate2 = if end_deriv.is_zero() {
+                mid
+            } else {
+                mid - mid_eval / end_deriv
+            };
             let candidate2_eval = self.eval(&candidate2);
 
             if candidate2_eval == T::zero() {
@@ -264,7 +462,101 @@ impl<T: Field> UPoly<T> {
     }
 }
 
+impl<T: Field> ops::Add<UPoly<T>> for UPoly<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let (mut long, short) = if self.0.len() >= rhs.0.len() {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
+
+        let offset = long.0.len() - short.0.len();
+        for (i, c) in short.0.into_iter().enumerate() {
+            long.0[offset + i] = long.0[offset + i].clone() + c;
+        }
+
+        long.trim()
+    }
+}
+
+impl<T: Field> ops::Sub<UPoly<T>> for UPoly<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + rhs.scale(&T::from(-1))
+    }
+}
+
+impl<T: Field> ops::Mul<UPoly<T>> for UPoly<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        if self.is_zero() || rhs.is_zero() {
+            return Self(vec![]);
+        }
+
+        let mut new = vec![T::zero(); self.0.len() + rhs.0.len() - 1];
+
+        for (i, a) in self.0.iter().enumerate() {
+            for (j, b) in rhs.0.iter().enumerate() {
+                new[i + j] = new[i + j].clone() + a.clone() * b.clone();
+            }
+        }
+
+        Self(new).trim()
+    }
+}
+
+impl<T: Field> Poly<T> {
+    // dense coefs in var, highest first; None if another var occurs
+    pub fn to_upoly(&self, var: usize) -> Option<UPoly<T>> {
+        if self
+            .terms
+            .iter()
+            .any(|term| term.vars.iter().any(|(v, _)| *v != var))
+        {
+            return None;
+        }
+
+        let coefs = self
+            .coefs(var)
+            .into_iter()
+            .map(|c| c.terms.first().map(|t| t.val.clone()).unwrap_or(T::zero()))
+            .collect();
+
+        Some(UPoly(coefs).trim())
+    }
+}
+
+impl<T: Field> UPoly<T> {
+    pub fn to_poly(&self, var: usize) -> Poly<T> {
+        if self.is_zero() {
+            return Poly::constant(T::zero());
+        }
+
+        Poly::from_uni_fmt(
+            self.0.iter().map(|c| Poly::constant(c.clone())).collect(),
+            var,
+        )
+    }
+}
+
 impl UPoly<Rat> {
+    // irreducible factors over Q, through the multivariate factorization
+    pub fn factor(&self) -> (Rat, Vec<(UPoly<Rat>, usize)>) {
+        let (unit, factors) = self.to_poly(0).factor();
+
+        (
+            unit,
+            factors
+                .into_iter()
+                .map(|(f, mult)| (f.to_upoly(0).unwrap(), mult))
+                .collect(),
+        )
+    }
+
     pub fn real_roots(&self, tolerance: f64) -> Vec<f64> {
         let mut tolerance_rat = Rat::from(1);
         while f64::from(tolerance_rat) > tolerance {
@@ -281,45 +573,31 @@ impl UPoly<Rat> {
 #[macro_export]
 macro_rules! univariate {
     ( $($t:tt)* ) => ({
-        use $crate::univariate::UPoly;
-        use $crate::rational::Rat;
-        use $crate::field::Field;
-
         let system = $crate::system! { $($t)* };
 
-        if system.members.len() != 1 {
+        if system.len() != 1 {
             panic!("pass 1 polynomial to univariate macro")
         }
 
-        if system.var_dict.len() > 1 {
+        if system.var_dict().len() > 1 {
             panic!("use at most 1 variable in univariate macro")
         }
 
-        let mut coefs = vec![];
-
-        let mut curr_pow = 0;
-        for term in &system.members[0].terms {
-            if term.vars.is_empty() {
-                coefs.push(term.val);
-                curr_pow += 1;
-            } else {
-                while curr_pow < term.vars[0].1 {
-                    coefs.push(Rat::zero());
-                    curr_pow += 1
-                }
-                coefs.push(term.val);
-                curr_pow += 1;
-            }
-        }
-
-        UPoly(coefs.into_iter().rev().collect::<Vec<_>>())
+        system[0].to_upoly(0).unwrap()
     })
 }
 
+// integer coefs, highest first, for tests
+#[cfg(test)]
+pub(crate) fn upoly(coefs: &[i64]) -> UPoly<Rat> {
+    UPoly(coefs.iter().map(|c| Rat::from(*c)).collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::{Root, UPoly};
     use crate::rational::Rat;
+    use crate::system;
 
     #[test]
     fn eval() {
@@ -391,4 +669,57 @@ mod tests {
         assert!((roots_f[2] - 3.).abs() < f64::from(tol));
         assert!((roots_f[3] - 5.).abs() < f64::from(tol));
     }
+
+    #[test]
+    fn conversions() {
+        let sys = system! {
+            x^3 - 2*x + 5,
+            y*x^2 + 1,
+            0
+        };
+
+        let p = sys.members[0].to_upoly(0).unwrap();
+        assert_eq!(p.0, [1, 0, -2, 5].map(Rat::from));
+        assert_eq!(sys.members[0], p.to_poly(0));
+
+        assert!(sys.members[1].to_upoly(0).is_none());
+        assert_eq!(Some(UPoly(vec![])), sys.members[2].to_upoly(0));
+        assert!(UPoly::<Rat>(vec![]).to_poly(0).is_zero());
+
+        // (x - 1)^2 (x^2 + 1)
+        let (_, factors) = UPoly(vec![
+            Rat::from(1),
+            Rat::from(-2),
+            Rat::from(2),
+            Rat::from(-2),
+            Rat::from(1),
+        ])
+        .factor();
+        let mut degs = factors
+            .iter()
+            .map(|(f, mult)| (f.deg(), *mult))
+            .collect::<Vec<_>>();
+        degs.sort();
+        assert_eq!(vec![(1, 2), (2, 1)], degs);
+    }
+
+    #[test]
+    fn squarefree_decomposition() {
+        // (x - 1)^3 (x + 1)^2 x
+        let p = univariate! { x^6 - x^5 - 2*x^4 + 2*x^3 + x^2 - x };
+        let factors = p.squarefree_decomposition();
+
+        assert_eq!(
+            vec![(1, 1), (1, 2), (1, 3)],
+            factors
+                .iter()
+                .map(|(f, mult)| (f.deg(), *mult))
+                .collect::<Vec<_>>()
+        );
+
+        let (squared, _) = &factors[1];
+        let (cubed, _) = &factors[2];
+        assert_eq!(vec![Rat::from(1), Rat::from(1)], squared.0);
+        assert_eq!(vec![Rat::from(1), Rat::from(-1)], cubed.0);
+    }
 }
@@ -23,3 +23,36 @@ fn gb() {
 
     assert_eq!("[4x - 5, 25y + 16, 32z - 75]", format!("{:?}", sys.gb()));
 }
+
+#[test]
+fn prelude() {
+    use srs_solver::prelude::*;
+
+    let sys: System<Rat> = system! {
+        x + y - 3,
+        x - y - 1
+    };
+    let (solution, report) = sys.solve(&SolveOptions::default());
+    assert_eq!(Shape::Linear, report.shape);
+    assert!(matches!(solution, Solution::Exact(x) if x == vec![Rat::from(2), Rat::from(1)]));
+
+    let mut var_dict = vec![];
+    let p = Poly::parse("x^2 - 2", &mut var_dict).unwrap();
+    let config = GroebnerConfig::new(MonomialOrder::Lex);
+    let sys = System::new(var_dict, vec![p]).unwrap();
+    assert_eq!("[x^2 - 2]", format!("{:?}", sys.gb_with_config(&config).0));
+
+    // the types the options refer to come from the prelude too
+    let sys: System<Rat> = system! {
+        x^2 + y^2 - 5,
+        x*y - 2
+    };
+    let options = SolveOptions {
+        bx: Some(vec![Interval::new(-3., 3.); 2]),
+        strategies: Some(vec![Strategy::Groebner]),
+        ..SolveOptions::default()
+    };
+    let (solution, report) = sys.solve(&options);
+    assert_eq!(Some(Strategy::Groebner), report.strategy);
+    assert!(matches!(solution, Solution::Boxes(boxes) if boxes.len() == 4));
+}